@@ -6,43 +6,18 @@
 //! - Entire aggregate transactions can be referenced by a single CID
 //! - Event streams can be retrieved from JetStream with one CID request
 
-use cim_graph::graphs::{Cid, EventChainBuilder, CidGenerator};
+use cim_graph::graphs::{EventChainBuilder, DagCborCidGenerator};
 use cim_graph::core::{CimGraphEvent, EventData};
 use uuid::Uuid;
 use cim_domain::{Subject, SubjectSegment};
 use chrono::Utc;
 use std::collections::HashMap;
 
-/// Example CID generator that shows the concept
-/// (Real implementation would use cim-ipld)
-struct ExampleCidGenerator;
-
-impl CidGenerator for ExampleCidGenerator {
-    fn generate_cid(&self, data: &EventData) -> Cid {
-        // Simulate CID generation from event data
-        let json = serde_json::to_string(data).unwrap();
-        let hash = sha256_mock(&json);
-        Cid::new(format!("Qm{}", hash))
-    }
-    
-    fn verify_cid(&self, cid: &Cid, data: &EventData) -> bool {
-        self.generate_cid(data) == *cid
-    }
-}
-
-/// Mock SHA256 for demo (first 16 chars of a simple hash)
-fn sha256_mock(data: &str) -> String {
-    data.bytes()
-        .take(8)
-        .map(|b| format!("{:02x}", b))
-        .collect()
-}
-
 fn main() {
     println!("=== IPLD CID Chains - The Heart of CIM Storage ===\n");
     
-    // Create CID generator (would use cim-ipld in production)
-    let cid_gen = ExampleCidGenerator;
+    // CIDv1 over DAG-CBOR with a SHA2-256 multihash
+    let cid_gen = DagCborCidGenerator::default();
     let mut chain_builder = EventChainBuilder::new(cid_gen);
     
     // Simulate an aggregate's event stream
@@ -158,7 +133,7 @@ fn main() {
     
     // Build the CID chain
     println!("🔨 Building CID chain from {} events...\n", events.len());
-    let chain = chain_builder.build_chain(&events);
+    let chain = chain_builder.build_chain(&events).unwrap();
     
     // Display the Merkle DAG structure
     println!("📊 Merkle DAG Structure:");
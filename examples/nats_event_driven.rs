@@ -199,7 +199,9 @@ async fn main() -> Result<()> {
     // 9. Show IPLD chain
     println!("\n9. IPLD Chain Summary:");
     if let Some(chain) = ipld_chains.get(&workflow_id) {
-        println!("   - Root CID: {}", chain.root_cid);
+        if let Some(root) = &chain.root_cid {
+            println!("   - Root CID: {}", root);
+        }
         println!("   - Chain length: {} events", chain.metadata.length);
         
         // Verify chain integrity
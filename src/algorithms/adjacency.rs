@@ -0,0 +1,227 @@
+//! Position-indexed adjacency shared by the algorithm implementations
+//!
+//! The algorithms work on dense node positions rather than string IDs. An
+//! [`IndexedAdjacency`] fixes those positions once, with node IDs sorted, and
+//! holds each node's distinct successors. Weights and reverse edges are
+//! derived from it on demand. This way every algorithm orders nodes the same
+//! way and treats parallel edges and dangling targets the same way.
+
+use crate::core::{Edge, GraphProjection, Node};
+use crate::error::{GraphError, Result};
+
+/// Node IDs by position, with the distinct successors of each node
+#[derive(Debug, Clone)]
+pub(super) struct IndexedAdjacency {
+    /// Node IDs, sorted
+    pub(super) ids: Vec<String>,
+    /// Distinct successors of each node, sorted
+    pub(super) successors: Vec<Vec<usize>>,
+}
+
+impl IndexedAdjacency {
+    /// The projection's nodes, with no edges between them
+    pub(super) fn nodes<P>(projection: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+    {
+        let mut ids: Vec<String> = projection.nodes().iter().map(|node| node.id()).collect();
+        ids.sort();
+        ids.dedup();
+        let successors = vec![Vec::new(); ids.len()];
+        Self { ids, successors }
+    }
+
+    /// The projection's nodes and adjacency; neighbours that are not nodes are dropped
+    pub(super) fn build<P>(projection: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+    {
+        let mut graph = Self::nodes(projection);
+        for source in 0..graph.len() {
+            let targets: Vec<usize> = projection
+                .neighbors(&graph.ids[source])
+                .into_iter()
+                .filter_map(|target| graph.position(target))
+                .collect();
+            graph.successors[source] = targets;
+        }
+        graph.normalize();
+        graph
+    }
+
+    /// The projection's nodes and those of its edges that pass `include`
+    pub(super) fn from_edges<P, F>(projection: &P, include: F) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+        F: Fn(&P::Edge) -> bool,
+    {
+        let mut graph = Self::nodes(projection);
+        for edge in projection.edges().into_iter().filter(|edge| include(edge)) {
+            if let Some((source, target)) = graph.endpoints(edge) {
+                graph.successors[source].push(target);
+            }
+        }
+        graph.normalize();
+        graph
+    }
+
+    /// Nodes `ids` joined by `(source, target)` pairs; pairs naming other nodes are dropped
+    pub(super) fn from_pairs<'a, I, E>(ids: I, pairs: E) -> Self
+    where
+        I: IntoIterator<Item = String>,
+        E: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut ids: Vec<String> = ids.into_iter().collect();
+        ids.sort();
+        ids.dedup();
+        let mut graph = Self {
+            successors: vec![Vec::new(); ids.len()],
+            ids,
+        };
+        for (source, target) in pairs {
+            if let (Some(source), Some(target)) = (graph.position(source), graph.position(target)) {
+                graph.successors[source].push(target);
+            }
+        }
+        graph.normalize();
+        graph
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Position of a node ID
+    pub(super) fn position(&self, id: &str) -> Option<usize> {
+        self.ids.binary_search_by(|candidate| candidate.as_str().cmp(id)).ok()
+    }
+
+    /// Position of a node ID, failing for unknown nodes
+    pub(super) fn lookup(&self, id: &str) -> Result<usize> {
+        self.position(id).ok_or_else(|| GraphError::NodeNotFound(id.to_string()))
+    }
+
+    /// Positions of an edge's source and target, if both are nodes
+    pub(super) fn endpoints<E: Edge>(&self, edge: &E) -> Option<(usize, usize)> {
+        Some((self.position(&edge.source())?, self.position(&edge.target())?))
+    }
+
+    /// Node IDs of a list of positions, in the same order
+    pub(super) fn names(&self, nodes: &[usize]) -> Vec<String> {
+        nodes.iter().map(|&node| self.ids[node].clone()).collect()
+    }
+
+    /// Distinct predecessors of each node, sorted
+    pub(super) fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut predecessors = vec![Vec::new(); self.len()];
+        for (source, targets) in self.successors.iter().enumerate() {
+            for &target in targets {
+                predecessors[target].push(source);
+            }
+        }
+        predecessors
+    }
+
+    /// Successors paired with `edge_weight(source, target)`
+    pub(super) fn weighted<W, F>(&self, edge_weight: F) -> Vec<Vec<(usize, W)>>
+    where
+        F: Fn(&str, &str) -> W,
+    {
+        self.successors
+            .iter()
+            .enumerate()
+            .map(|(source, targets)| {
+                targets
+                    .iter()
+                    .map(|&target| (target, edge_weight(&self.ids[source], &self.ids[target])))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Successors paired with connection strengths, which must be finite and non-negative
+    pub(super) fn strengths<F>(&self, edge_weight: F) -> Result<Vec<Vec<(usize, f64)>>>
+    where
+        F: Fn(&str, &str) -> f64,
+    {
+        let weighted = self.weighted(edge_weight);
+        for (source, targets) in weighted.iter().enumerate() {
+            for &(target, weight) in targets {
+                if !weight.is_finite() || weight < 0.0 {
+                    return Err(GraphError::InvalidOperation(format!(
+                        "Edge {} -> {} has invalid weight {}",
+                        self.ids[source], self.ids[target], weight
+                    )));
+                }
+            }
+        }
+        Ok(weighted)
+    }
+
+    fn normalize(&mut self) {
+        for targets in &mut self.successors {
+            targets.sort_unstable();
+            targets.dedup();
+        }
+    }
+}
+
+/// Reverse of per-node `(neighbour, weight)` lists, each sorted by neighbour
+pub(super) fn reversed<W: Clone>(lists: &[Vec<(usize, W)>]) -> Vec<Vec<(usize, W)>> {
+    let mut reversed = vec![Vec::new(); lists.len()];
+    for (source, edges) in lists.iter().enumerate() {
+        for (target, weight) in edges {
+            reversed[*target].push((source, weight.clone()));
+        }
+    }
+    reversed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::fixtures::{create_graph, create_transitions, ids};
+
+    #[test]
+    fn test_build_sorts_nodes_and_deduplicates_successors() {
+        let mut projection = create_graph(&["C", "A", "B"], &[("A", "C"), ("A", "B"), ("A", "C"), ("B", "A")]);
+        projection.adjacency.get_mut("B").unwrap().push("missing".to_string());
+        let graph = IndexedAdjacency::build(&projection);
+
+        assert_eq!(graph.ids, ids(&["A", "B", "C"]));
+        assert_eq!(graph.successors, vec![vec![1, 2], vec![0], vec![]]);
+        assert_eq!(graph.predecessors(), vec![vec![1], vec![0], vec![0]]);
+        assert_eq!(graph.position("C"), Some(2));
+        assert!(matches!(graph.lookup("missing"), Err(GraphError::NodeNotFound(_))));
+        assert_eq!(graph.names(&[2, 0]), ids(&["C", "A"]));
+    }
+
+    #[test]
+    fn test_from_edges_and_pairs() {
+        let projection = create_transitions(&["A", "B", "C"], &[("e1", "A", "B"), ("e2", "B", "C"), ("e3", "A", "B")]);
+        let graph = IndexedAdjacency::from_edges(&projection, |edge| edge.id != "e2");
+        assert_eq!(graph.successors, vec![vec![1], vec![], vec![]]);
+
+        let pairs = IndexedAdjacency::from_pairs(ids(&["B", "A"]), [("A", "B"), ("B", "Z"), ("A", "B")]);
+        assert_eq!(pairs.ids, ids(&["A", "B"]));
+        assert_eq!(pairs.successors, vec![vec![1], vec![]]);
+    }
+
+    #[test]
+    fn test_weights_and_reverse() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "B"), ("A", "C"), ("B", "C")]);
+        let graph = IndexedAdjacency::build(&projection);
+
+        let weighted = graph.weighted(|from, to| if (from, to) == ("A", "C") { 5 } else { 1 });
+        assert_eq!(weighted, vec![vec![(1, 1), (2, 5)], vec![(2, 1)], vec![]]);
+        assert_eq!(reversed(&weighted), vec![vec![], vec![(0, 1)], vec![(0, 5), (1, 1)]]);
+
+        assert!(graph.strengths(|_, _| 0.5).is_ok());
+        assert!(matches!(graph.strengths(|_, _| -1.0), Err(GraphError::InvalidOperation(_))));
+        assert!(graph.strengths(|_, _| f64::NAN).is_err());
+    }
+}
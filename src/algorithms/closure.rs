@@ -0,0 +1,341 @@
+//! Transitive closure and transitive reduction
+//!
+//! Reachability is stored as one bitset row per strongly connected
+//! component, filled in reverse topological order so each row is the union
+//! of rows already computed. The `_by` variants close over only the edges a
+//! filter selects, so a projection can be closed over a single relation.
+
+use super::adjacency::IndexedAdjacency;
+use super::connectivity::DirectedIndex;
+use crate::core::{Edge, GraphProjection, Node};
+use crate::error::{GraphError, Result};
+use std::collections::{HashMap, HashSet};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Reachability between every pair of nodes of a projection
+#[derive(Debug, Clone)]
+pub struct TransitiveClosure {
+    /// Node IDs, sorted
+    ids: Vec<String>,
+    index: HashMap<String, usize>,
+    /// Strongly connected component of each node; members share a row
+    component: Vec<usize>,
+    /// Nodes reachable from each component over a non-empty path
+    rows: Vec<Vec<u64>>,
+}
+
+impl TransitiveClosure {
+    /// Whether a non-empty path leads from `from` to `to`
+    ///
+    /// A node reaches itself only when it lies on a cycle.
+    pub fn reaches(&self, from: &str, to: &str) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(&to)) => contains(self.row(from), to),
+            _ => false,
+        }
+    }
+
+    /// Nodes reachable from a node, sorted
+    pub fn reachable_from(&self, node: &str) -> Vec<&str> {
+        match self.index.get(node) {
+            Some(&node) => members(self.row(node)).map(|target| self.ids[target].as_str()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Every `(from, to)` pair joined by a non-empty path, sorted
+    pub fn pairs(&self) -> Vec<(&str, &str)> {
+        (0..self.ids.len())
+            .flat_map(|source| {
+                members(self.row(source)).map(move |target| (self.ids[source].as_str(), self.ids[target].as_str()))
+            })
+            .collect()
+    }
+
+    /// Number of `(from, to)` pairs joined by a non-empty path
+    pub fn pair_count(&self) -> usize {
+        (0..self.ids.len())
+            .map(|node| self.row(node).iter().map(|word| word.count_ones() as usize).sum::<usize>())
+            .sum()
+    }
+
+    /// Whether any node lies on a cycle
+    pub fn has_cycle(&self) -> bool {
+        (0..self.ids.len()).any(|node| contains(self.row(node), node))
+    }
+
+    fn row(&self, node: usize) -> &[u64] {
+        &self.rows[self.component[node]]
+    }
+}
+
+/// Transitive closure over the projection's adjacency
+pub fn transitive_closure<P: GraphProjection>(projection: &P) -> TransitiveClosure
+where
+    P::Node: Node,
+{
+    close(&DirectedIndex::build(projection))
+}
+
+/// Transitive closure over the edges selected by `include`
+///
+/// # Arguments
+/// * `projection` - The graph projection to analyse
+/// * `include` - Function deciding whether an edge takes part
+pub fn transitive_closure_by<P, F>(projection: &P, include: F) -> TransitiveClosure
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    F: Fn(&P::Edge) -> bool,
+{
+    close(&IndexedAdjacency::from_edges(projection, include).into())
+}
+
+/// Transitive reduction of a DAG over the edges selected by `include`
+///
+/// Returns the IDs of the edges to keep, sorted. An edge is dropped when its
+/// target is also reachable through another successor of its source, and of
+/// parallel edges only the one with the smallest ID is kept, so the result
+/// has the same closure as the selected edges and no redundant edge.
+///
+/// # Errors
+/// * `InvalidOperation` - If the selected edges contain a cycle
+pub fn transitive_reduction<P, F>(projection: &P, include: F) -> Result<Vec<String>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    F: Fn(&P::Edge) -> bool,
+{
+    let graph: DirectedIndex = IndexedAdjacency::from_edges(projection, &include).into();
+    let closure = close(&graph);
+    if closure.has_cycle() {
+        return Err(GraphError::InvalidOperation(
+            "Graph contains cycles - cannot compute transitive reduction".to_string(),
+        ));
+    }
+
+    let words = graph.ids.len().div_ceil(WORD_BITS);
+    let mut needed: HashSet<(usize, usize)> = HashSet::new();
+    for (source, successors) in graph.successors.iter().enumerate() {
+        let mut implied = vec![0u64; words];
+        for &next in successors {
+            union(&mut implied, closure.row(next));
+        }
+        for &next in successors {
+            if !contains(&implied, next) {
+                needed.insert((source, next));
+            }
+        }
+    }
+
+    let mut edges: Vec<(String, usize, usize)> = projection
+        .edges()
+        .into_iter()
+        .filter(|edge| include(edge))
+        .filter_map(|edge| {
+            let source = *closure.index.get(&edge.source())?;
+            let target = *closure.index.get(&edge.target())?;
+            Some((edge.id(), source, target))
+        })
+        .collect();
+    edges.sort();
+
+    let mut kept: Vec<String> = Vec::new();
+    for (id, source, target) in edges {
+        if needed.remove(&(source, target)) {
+            kept.push(id);
+        }
+    }
+    Ok(kept)
+}
+
+fn close(graph: &DirectedIndex) -> TransitiveClosure {
+    let count = graph.ids.len();
+    let words = count.div_ceil(WORD_BITS);
+
+    // Tarjan emits every component after all components it can reach
+    let components = graph.tarjan(&vec![true; count]);
+    let mut component = vec![0; count];
+    for (index, members) in components.iter().enumerate() {
+        for &node in members {
+            component[node] = index;
+        }
+    }
+
+    let mut rows: Vec<Vec<u64>> = Vec::with_capacity(components.len());
+    for (index, members) in components.iter().enumerate() {
+        let mut row = vec![0u64; words];
+        let cyclic = members.len() > 1 || graph.successors[members[0]].contains(&members[0]);
+        for &node in members {
+            if cyclic {
+                insert(&mut row, node);
+            }
+            for &next in &graph.successors[node] {
+                insert(&mut row, next);
+                if component[next] != index {
+                    union(&mut row, &rows[component[next]]);
+                }
+            }
+        }
+        rows.push(row);
+    }
+
+    TransitiveClosure {
+        index: graph.ids.iter().cloned().zip(0..).collect(),
+        ids: graph.ids.clone(),
+        component,
+        rows,
+    }
+}
+
+fn insert(row: &mut [u64], node: usize) {
+    row[node / WORD_BITS] |= 1 << (node % WORD_BITS);
+}
+
+fn contains(row: &[u64], node: usize) -> bool {
+    row[node / WORD_BITS] & (1 << (node % WORD_BITS)) != 0
+}
+
+fn union(row: &mut [u64], other: &[u64]) {
+    for (word, bits) in row.iter_mut().zip(other) {
+        *word |= bits;
+    }
+}
+
+/// Set positions of a bitset row, ascending
+fn members(row: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    row.iter().enumerate().flat_map(|(word, &bits)| {
+        (0..WORD_BITS)
+            .filter(move |bit| bits & (1 << bit) != 0)
+            .map(move |bit| word * WORD_BITS + bit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::fixtures::create_transitions;
+    use crate::graphs::workflow::{WorkflowEdge, WorkflowEdgeType};
+
+    // ========== Transitive Closure Tests ==========
+
+    #[test]
+    fn test_transitive_closure_chain() {
+        let projection = create_transitions(&["A", "B", "C", "D"], &[("e1", "A", "B"), ("e2", "B", "C"), ("e3", "C", "D")]);
+        let closure = transitive_closure(&projection);
+
+        assert_eq!(closure.reachable_from("A"), vec!["B", "C", "D"]);
+        assert_eq!(closure.reachable_from("D"), Vec::<&str>::new());
+        assert!(closure.reaches("A", "D"));
+        assert!(!closure.reaches("D", "A"));
+        assert!(!closure.reaches("A", "A"));
+        assert_eq!(closure.pair_count(), 6);
+        assert!(!closure.has_cycle());
+    }
+
+    #[test]
+    fn test_transitive_closure_with_cycle() {
+        // A -> {B <-> C} -> D
+        let projection = create_transitions(
+            &["A", "B", "C", "D"],
+            &[("e1", "A", "B"), ("e2", "B", "C"), ("e3", "C", "B"), ("e4", "C", "D")],
+        );
+        let closure = transitive_closure(&projection);
+
+        assert!(closure.reaches("B", "B"));
+        assert!(closure.reaches("C", "B"));
+        assert!(!closure.reaches("A", "A"));
+        assert_eq!(closure.reachable_from("C"), vec!["B", "C", "D"]);
+        assert!(closure.has_cycle());
+        assert_eq!(
+            closure.pairs(),
+            vec![
+                ("A", "B"), ("A", "C"), ("A", "D"),
+                ("B", "B"), ("B", "C"), ("B", "D"),
+                ("C", "B"), ("C", "C"), ("C", "D"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transitive_closure_by_edge_filter() {
+        let mut projection = create_transitions(&["A", "B", "C"], &[("e1", "A", "B")]);
+        let timeout = WorkflowEdge::new("e2", "B", "C", WorkflowEdgeType::TimeoutTransition { timeout_ms: 10 });
+        projection.edges.insert("e2".to_string(), timeout);
+        projection.adjacency.get_mut("B").unwrap().push("C".to_string());
+
+        let all = transitive_closure_by(&projection, |_| true);
+        assert!(all.reaches("A", "C"));
+
+        let transitions =
+            transitive_closure_by(&projection, |edge| matches!(edge.edge_type, WorkflowEdgeType::Transition));
+        assert!(transitions.reaches("A", "B"));
+        assert!(!transitions.reaches("A", "C"));
+    }
+
+    #[test]
+    fn test_transitive_closure_many_nodes() {
+        // A chain longer than one bitset word
+        let ids: Vec<String> = (0..150).map(|i| format!("n{:03}", i)).collect();
+        let edges: Vec<(String, String, String)> = ids
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| (format!("e{}", i), pair[0].clone(), pair[1].clone()))
+            .collect();
+        let id_refs: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let edge_refs: Vec<(&str, &str, &str)> =
+            edges.iter().map(|(id, from, to)| (id.as_str(), from.as_str(), to.as_str())).collect();
+        let projection = create_transitions(&id_refs, &edge_refs);
+
+        let closure = transitive_closure(&projection);
+        assert_eq!(closure.reachable_from("n000").len(), 149);
+        assert!(closure.reaches("n010", "n149"));
+        assert!(!closure.reaches("n149", "n010"));
+        assert_eq!(closure.pair_count(), 150 * 149 / 2);
+    }
+
+    // ========== Transitive Reduction Tests ==========
+
+    #[test]
+    fn test_transitive_reduction_drops_implied_edges() {
+        // A -> B -> C -> D with shortcuts A -> C, A -> D and a duplicate B -> C
+        let projection = create_transitions(
+            &["A", "B", "C", "D"],
+            &[
+                ("e1", "A", "B"),
+                ("e2", "B", "C"),
+                ("e3", "C", "D"),
+                ("e4", "A", "C"),
+                ("e5", "A", "D"),
+                ("e6", "B", "C"),
+            ],
+        );
+        let kept = transitive_reduction(&projection, |_| true).unwrap();
+        assert_eq!(kept, vec!["e1", "e2", "e3"]);
+    }
+
+    #[test]
+    fn test_transitive_reduction_keeps_diamond() {
+        let projection = create_transitions(
+            &["A", "B", "C", "D"],
+            &[("e1", "A", "B"), ("e2", "A", "C"), ("e3", "B", "D"), ("e4", "C", "D"), ("e5", "A", "D")],
+        );
+        let kept = transitive_reduction(&projection, |_| true).unwrap();
+        assert_eq!(kept, vec!["e1", "e2", "e3", "e4"]);
+    }
+
+    #[test]
+    fn test_transitive_reduction_rejects_cycles() {
+        let projection = create_transitions(&["A", "B"], &[("e1", "A", "B"), ("e2", "B", "A")]);
+        assert!(matches!(
+            transitive_reduction(&projection, |_| true),
+            Err(GraphError::InvalidOperation(_))
+        ));
+        // Leaving out the back edge makes it a DAG
+        let kept = transitive_reduction(&projection, |edge| edge.id != "e2").unwrap();
+        assert_eq!(kept, vec!["e1"]);
+    }
+}
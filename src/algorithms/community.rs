@@ -0,0 +1,581 @@
+//! Community detection for projections
+//!
+//! Groups densely connected nodes, for example to suggest bounded-context
+//! boundaries in large concept and context graphs. Edges are treated as
+//! undirected: reciprocal edges add their weights, and nodes in different
+//! weakly connected parts never share a community.
+
+use super::adjacency::IndexedAdjacency;
+use crate::core::{GraphProjection, Node};
+use crate::error::{GraphError, Result};
+use std::collections::{BTreeMap, HashMap};
+
+/// Parameters for [`louvain`] and [`label_propagation`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommunityConfig {
+    /// Modularity resolution; above 1 favours smaller communities, below 1 larger ones
+    pub resolution: f64,
+    /// Maximum sweeps over the nodes per level (Louvain) or in total (label propagation)
+    pub max_iterations: usize,
+    /// Seed for label propagation's visiting order and tie-breaking
+    pub seed: u64,
+}
+
+impl Default for CommunityConfig {
+    fn default() -> Self {
+        Self {
+            resolution: 1.0,
+            max_iterations: 100,
+            seed: 0,
+        }
+    }
+}
+
+impl CommunityConfig {
+    /// Set the modularity resolution
+    pub fn with_resolution(mut self, resolution: f64) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set the maximum number of sweeps
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the label propagation seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// A partition of a projection's nodes into communities
+#[derive(Debug, Clone, PartialEq)]
+pub struct Communities {
+    /// Members of each community, largest first, each sorted by node ID
+    pub communities: Vec<Vec<String>>,
+    /// Community index of every node
+    pub membership: HashMap<String, usize>,
+    /// Modularity of the partition (at resolution 1)
+    pub modularity: f64,
+}
+
+impl Communities {
+    /// Community index of a node
+    pub fn community_of(&self, node_id: &str) -> Option<usize> {
+        self.membership.get(node_id).copied()
+    }
+
+    /// Number of communities
+    pub fn len(&self) -> usize {
+        self.communities.len()
+    }
+
+    /// Whether there are no communities (the projection was empty)
+    pub fn is_empty(&self) -> bool {
+        self.communities.is_empty()
+    }
+}
+
+/// Detect communities with Louvain modularity optimization, every edge weighted 1
+pub fn louvain<P: GraphProjection>(projection: &P, config: &CommunityConfig) -> Result<Communities>
+where
+    P::Node: Node,
+{
+    weighted_louvain(projection, config, |_, _| 1.0)
+}
+
+/// Detect communities with Louvain modularity optimization over weighted edges
+///
+/// Weights must be finite and non-negative. Nodes are visited in ID order,
+/// so the result is deterministic.
+pub fn weighted_louvain<P, W>(projection: &P, config: &CommunityConfig, edge_weight: W) -> Result<Communities>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    W: Fn(&str, &str) -> f64,
+{
+    let graph = UndirectedIndex::build(projection, edge_weight)?;
+    let mut membership: Vec<usize> = (0..graph.len()).collect();
+    let mut level = graph.clone();
+
+    loop {
+        let (assignment, moved) = level.local_moving(config);
+        if !moved {
+            break;
+        }
+        let (renumbered, aggregated) = level.aggregate(&assignment);
+        membership.iter_mut().for_each(|community| *community = renumbered[*community]);
+        if aggregated.len() == level.len() {
+            break;
+        }
+        level = aggregated;
+    }
+
+    Ok(graph.communities(&membership))
+}
+
+/// Detect communities by label propagation, every edge weighted 1
+pub fn label_propagation<P: GraphProjection>(projection: &P, config: &CommunityConfig) -> Result<Communities>
+where
+    P::Node: Node,
+{
+    weighted_label_propagation(projection, config, |_, _| 1.0)
+}
+
+/// Detect communities by label propagation over weighted edges
+///
+/// Each node repeatedly adopts the label with the largest total edge weight
+/// among its neighbours, keeping its own label when it is among the best,
+/// until no label changes or `max_iterations` sweeps have run. Visiting order
+/// and remaining ties are drawn from `config.seed`, so a given seed always
+/// gives the same result.
+pub fn weighted_label_propagation<P, W>(
+    projection: &P,
+    config: &CommunityConfig,
+    edge_weight: W,
+) -> Result<Communities>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    W: Fn(&str, &str) -> f64,
+{
+    let graph = UndirectedIndex::build(projection, edge_weight)?;
+    let mut labels: Vec<usize> = (0..graph.len()).collect();
+    let mut order: Vec<usize> = (0..graph.len()).collect();
+    let mut rng = SplitMix64(config.seed);
+
+    for _ in 0..config.max_iterations {
+        rng.shuffle(&mut order);
+        let mut changed = false;
+        for &node in &order {
+            let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+            for (&neighbor, &weight) in &graph.adjacency[node] {
+                if neighbor != node {
+                    *weights.entry(labels[neighbor]).or_default() += weight;
+                }
+            }
+
+            let best = weights.values().copied().fold(0.0, f64::max);
+            let candidates: Vec<usize> = weights
+                .iter()
+                .filter(|(_, &weight)| weight == best)
+                .map(|(&label, _)| label)
+                .collect();
+            if best <= 0.0 || candidates.contains(&labels[node]) {
+                continue;
+            }
+            labels[node] = candidates[rng.below(candidates.len())];
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(graph.communities(&labels))
+}
+
+/// Modularity of a partition, every edge weighted 1
+///
+/// Nodes missing from `communities` count as singleton communities.
+pub fn modularity<P: GraphProjection>(projection: &P, communities: &[Vec<String>]) -> Result<f64>
+where
+    P::Node: Node,
+{
+    weighted_modularity(projection, communities, |_, _| 1.0)
+}
+
+/// Modularity of a partition over weighted edges
+///
+/// Fails with [`GraphError::NodeNotFound`] for unknown nodes and
+/// [`GraphError::InvalidOperation`] when a node is in two communities.
+pub fn weighted_modularity<P, W>(projection: &P, communities: &[Vec<String>], edge_weight: W) -> Result<f64>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    W: Fn(&str, &str) -> f64,
+{
+    let graph = UndirectedIndex::build(projection, edge_weight)?;
+    let mut membership: Vec<Option<usize>> = vec![None; graph.len()];
+    for (community, members) in communities.iter().enumerate() {
+        for id in members {
+            let node = graph
+                .ids
+                .binary_search(id)
+                .map_err(|_| GraphError::NodeNotFound(id.clone()))?;
+            if membership[node].is_some() {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Node {} is in more than one community",
+                    id
+                )));
+            }
+            membership[node] = Some(community);
+        }
+    }
+
+    let mut next = communities.len();
+    let membership: Vec<usize> = membership
+        .into_iter()
+        .map(|community| {
+            community.unwrap_or_else(|| {
+                next += 1;
+                next - 1
+            })
+        })
+        .collect();
+    Ok(graph.modularity(&membership, 1.0))
+}
+
+/// Small seeded generator for label propagation (SplitMix64)
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform-enough index below `bound`
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Symmetric weighted adjacency indexed by position, with node IDs sorted
+///
+/// A self-loop of weight `w` contributes `2w` to its node's degree, which is
+/// how Louvain's aggregated graphs keep each community's internal weight.
+#[derive(Clone)]
+struct UndirectedIndex {
+    /// Node IDs, sorted (community labels after aggregation)
+    ids: Vec<String>,
+    /// Neighbour weights per node, including self-loops
+    adjacency: Vec<BTreeMap<usize, f64>>,
+}
+
+impl UndirectedIndex {
+    fn build<P, W>(projection: &P, edge_weight: W) -> Result<Self>
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        W: Fn(&str, &str) -> f64,
+    {
+        let graph = IndexedAdjacency::build(projection);
+        let mut adjacency = vec![BTreeMap::new(); graph.len()];
+        for (source, targets) in graph.strengths(edge_weight)?.into_iter().enumerate() {
+            for (target, weight) in targets {
+                *adjacency[source].entry(target).or_insert(0.0) += weight;
+                if source != target {
+                    *adjacency[target].entry(source).or_insert(0.0) += weight;
+                }
+            }
+        }
+
+        Ok(Self {
+            ids: graph.ids,
+            adjacency,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Weighted degree, counting self-loops twice
+    fn degree(&self, node: usize) -> f64 {
+        self.adjacency[node]
+            .iter()
+            .map(|(&neighbor, &weight)| if neighbor == node { 2.0 * weight } else { weight })
+            .sum()
+    }
+
+    fn modularity(&self, membership: &[usize], resolution: f64) -> f64 {
+        let total: f64 = (0..self.len()).map(|node| self.degree(node)).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut degree: HashMap<usize, f64> = HashMap::new();
+        for node in 0..self.len() {
+            *degree.entry(membership[node]).or_default() += self.degree(node);
+            for (&neighbor, &weight) in &self.adjacency[node] {
+                if membership[neighbor] == membership[node] {
+                    let counted = if neighbor == node { 2.0 * weight } else { weight };
+                    *internal.entry(membership[node]).or_default() += counted;
+                }
+            }
+        }
+
+        degree
+            .iter()
+            .map(|(community, &tot)| {
+                internal.get(community).copied().unwrap_or(0.0) / total - resolution * (tot / total).powi(2)
+            })
+            .sum()
+    }
+
+    /// Louvain phase one: move single nodes while modularity improves
+    fn local_moving(&self, config: &CommunityConfig) -> (Vec<usize>, bool) {
+        let n = self.len();
+        let degrees: Vec<f64> = (0..n).map(|node| self.degree(node)).collect();
+        let total: f64 = degrees.iter().sum();
+        let mut assignment: Vec<usize> = (0..n).collect();
+        if total == 0.0 {
+            return (assignment, false);
+        }
+
+        let mut community_degree = degrees.clone();
+        let mut moved = false;
+
+        for _ in 0..config.max_iterations {
+            let mut improved = false;
+            for node in 0..n {
+                let current = assignment[node];
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                for (&neighbor, &weight) in &self.adjacency[node] {
+                    if neighbor != node {
+                        *links.entry(assignment[neighbor]).or_default() += weight;
+                    }
+                }
+
+                community_degree[current] -= degrees[node];
+                let gain = |community: usize, link: f64| {
+                    link - config.resolution * community_degree[community] * degrees[node] / total
+                };
+
+                let mut best = current;
+                let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+                for (&community, &link) in &links {
+                    let candidate = gain(community, link);
+                    if candidate > best_gain + 1e-12 {
+                        best = community;
+                        best_gain = candidate;
+                    }
+                }
+
+                community_degree[best] += degrees[node];
+                if best != current {
+                    assignment[node] = best;
+                    improved = true;
+                    moved = true;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        (assignment, moved)
+    }
+
+    /// Louvain phase two: collapse communities into nodes
+    ///
+    /// Returns the dense renumbering of the assignment and the aggregated graph.
+    fn aggregate(&self, assignment: &[usize]) -> (Vec<usize>, UndirectedIndex) {
+        let mut renumbered = vec![usize::MAX; self.len()];
+        let mut next = 0;
+        for &community in assignment {
+            if renumbered[community] == usize::MAX {
+                renumbered[community] = next;
+                next += 1;
+            }
+        }
+        let dense: Vec<usize> = assignment.iter().map(|&community| renumbered[community]).collect();
+
+        let mut adjacency = vec![BTreeMap::new(); next];
+        for (node, neighbors) in self.adjacency.iter().enumerate() {
+            for (&neighbor, &weight) in neighbors {
+                let (from, to) = (dense[node], dense[neighbor]);
+                // Each undirected edge appears twice; keep both halves apart
+                // from self-loops, which appear once
+                let share = if from == to && node != neighbor { weight / 2.0 } else { weight };
+                *adjacency[from].entry(to).or_insert(0.0) += share;
+            }
+        }
+
+        let ids = (0..next).map(|community| community.to_string()).collect();
+        (dense, UndirectedIndex { ids, adjacency })
+    }
+
+    /// Partition from a membership vector over this graph's nodes
+    fn communities(&self, membership: &[usize]) -> Communities {
+        let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (id, &community) in self.ids.iter().zip(membership) {
+            groups.entry(community).or_default().push(id.clone());
+        }
+
+        let mut communities: Vec<Vec<String>> = groups.into_values().collect();
+        communities.iter_mut().for_each(|members| members.sort());
+        communities.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let mut membership_by_id = HashMap::new();
+        let mut dense = vec![0; self.len()];
+        for (community, members) in communities.iter().enumerate() {
+            for id in members {
+                if let Ok(node) = self.ids.binary_search(id) {
+                    dense[node] = community;
+                }
+                membership_by_id.insert(id.clone(), community);
+            }
+        }
+
+        Communities {
+            modularity: self.modularity(&dense, 1.0),
+            communities,
+            membership: membership_by_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::fixtures::{create_graph, ids, TestProjection};
+
+    fn create_two_cliques() -> TestProjection {
+        // Two 4-cliques joined by the single edge D -> E
+        let mut edges = Vec::new();
+        for group in [["A", "B", "C", "D"], ["E", "F", "G", "H"]] {
+            for i in 0..4 {
+                for j in (i + 1)..4 {
+                    edges.push((group[i], group[j]));
+                }
+            }
+        }
+        edges.push(("D", "E"));
+        create_graph(&["A", "B", "C", "D", "E", "F", "G", "H"], &edges)
+    }
+
+    // ========== Louvain Tests ==========
+
+    #[test]
+    fn test_louvain_two_cliques() {
+        let projection = create_two_cliques();
+        let result = louvain(&projection, &CommunityConfig::default()).unwrap();
+
+        assert_eq!(result.communities, vec![ids(&["A", "B", "C", "D"]), ids(&["E", "F", "G", "H"])]);
+        assert_eq!(result.community_of("A"), Some(0));
+        assert_eq!(result.community_of("H"), Some(1));
+        // Each clique holds 6 of 13 edges and half of the total degree
+        assert!((result.modularity - (12.0 / 13.0 - 0.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_louvain_follows_weights() {
+        let projection = create_graph(
+            &["A", "B", "C", "D"],
+            &[("A", "B"), ("B", "C"), ("C", "D"), ("D", "A")],
+        );
+
+        // Edges between the given pairs weigh 10, the rest 1
+        let heavy = |pairs: [(&'static str, &'static str); 2]| {
+            move |from: &str, to: &str| -> f64 {
+                if pairs.iter().any(|&(a, b)| (a, b) == (from, to) || (b, a) == (from, to)) {
+                    10.0
+                } else {
+                    1.0
+                }
+            }
+        };
+        let ab_cd = heavy([("A", "B"), ("C", "D")]);
+        let bc_da = heavy([("B", "C"), ("D", "A")]);
+
+        let config = CommunityConfig::default();
+        let result = weighted_louvain(&projection, &config, ab_cd).unwrap();
+        assert_eq!(result.communities, vec![ids(&["A", "B"]), ids(&["C", "D"])]);
+
+        let result = weighted_louvain(&projection, &config, bc_da).unwrap();
+        assert_eq!(result.communities, vec![ids(&["A", "D"]), ids(&["B", "C"])]);
+    }
+
+    #[test]
+    fn test_louvain_resolution() {
+        let projection = create_two_cliques();
+
+        let coarse = louvain(&projection, &CommunityConfig::default().with_resolution(0.01)).unwrap();
+        assert_eq!(coarse.len(), 1);
+
+        let fine = louvain(&projection, &CommunityConfig::default().with_resolution(10.0)).unwrap();
+        assert!(fine.len() > 2);
+    }
+
+    #[test]
+    fn test_louvain_isolated_and_empty() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "B")]);
+        let result = louvain(&projection, &CommunityConfig::default()).unwrap();
+        assert_eq!(result.communities, vec![ids(&["A", "B"]), ids(&["C"])]);
+
+        let empty = create_graph(&[], &[]);
+        let result = louvain(&empty, &CommunityConfig::default()).unwrap();
+        assert!(result.is_empty());
+        assert_eq!(result.modularity, 0.0);
+    }
+
+    // ========== Label Propagation Tests ==========
+
+    #[test]
+    fn test_label_propagation_two_cliques() {
+        let projection = create_two_cliques();
+        let result = label_propagation(&projection, &CommunityConfig::default()).unwrap();
+
+        assert_eq!(result.communities, vec![ids(&["A", "B", "C", "D"]), ids(&["E", "F", "G", "H"])]);
+        assert!(result.modularity > 0.4);
+
+        let again = label_propagation(&projection, &CommunityConfig::default()).unwrap();
+        assert_eq!(again, result);
+    }
+
+    #[test]
+    fn test_label_propagation_disconnected_parts() {
+        let projection = create_graph(&["A", "B", "C", "D", "E"], &[("A", "B"), ("B", "C"), ("D", "E")]);
+        let result = label_propagation(&projection, &CommunityConfig::default()).unwrap();
+
+        assert_eq!(result.communities, vec![ids(&["A", "B", "C"]), ids(&["D", "E"])]);
+    }
+
+    // ========== Modularity Tests ==========
+
+    #[test]
+    fn test_modularity_of_partitions() {
+        let projection = create_two_cliques();
+
+        let split = modularity(&projection, &[ids(&["A", "B", "C", "D"]), ids(&["E", "F", "G", "H"])]).unwrap();
+        assert!((split - (12.0 / 13.0 - 0.5)).abs() < 1e-9);
+
+        let whole = modularity(&projection, &[ids(&["A", "B", "C", "D", "E", "F", "G", "H"])]).unwrap();
+        assert!(whole.abs() < 1e-9);
+
+        // Missing nodes are singletons
+        let singletons = modularity(&projection, &[]).unwrap();
+        assert!(singletons < 0.0);
+    }
+
+    #[test]
+    fn test_modularity_rejects_invalid_partitions() {
+        let projection = create_two_cliques();
+
+        assert!(matches!(
+            modularity(&projection, &[ids(&["A", "Z"])]),
+            Err(GraphError::NodeNotFound(_))
+        ));
+        assert!(matches!(
+            modularity(&projection, &[ids(&["A"]), ids(&["A", "B"])]),
+            Err(GraphError::InvalidOperation(_))
+        ));
+        assert!(weighted_modularity(&projection, &[], |_, _| f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,559 @@
+//! Directed connectivity: strongly connected components, condensation and cycles
+//!
+//! Unlike [`connected_components`](super::traversal::connected_components),
+//! everything here respects edge direction.
+
+use super::adjacency::IndexedAdjacency;
+use crate::core::graph::GraphType;
+use crate::core::projection_engine::GenericGraphProjection;
+use crate::core::{GenericEdge, GenericNode, GraphProjection, Node};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Node of a [`CondensationProjection`]; its data is the sorted member node IDs
+pub type CondensationNode = GenericNode<Vec<String>>;
+
+/// Edge of a [`CondensationProjection`]; its data is the number of original
+/// edges it stands for
+pub type CondensationEdge = GenericEdge<usize>;
+
+/// Projection in which every strongly connected component is a single node
+pub type CondensationProjection = GenericGraphProjection<CondensationNode, CondensationEdge>;
+
+/// Condensation DAG of a projection
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    /// The DAG, with component nodes named by [`Condensation::component_id`]
+    pub projection: CondensationProjection,
+    /// Members of each component, components in topological order
+    pub components: Vec<Vec<String>>,
+    /// Component index of every original node
+    pub component_of: HashMap<String, usize>,
+}
+
+impl Condensation {
+    /// Node ID of a component in the condensation projection
+    pub fn component_id(index: usize) -> String {
+        format!("scc-{}", index)
+    }
+
+    /// Components reachable from `index` over a single condensation edge
+    pub fn successors(&self, index: usize) -> Vec<usize> {
+        self.projection
+            .neighbors(&Self::component_id(index))
+            .into_iter()
+            .filter_map(|id| id.strip_prefix("scc-").and_then(|i| i.parse().ok()))
+            .collect()
+    }
+}
+
+/// Find strongly connected components with Tarjan's algorithm
+///
+/// Components are returned in topological order of the condensation (a
+/// component comes before every component it has edges to), each with its
+/// node IDs sorted.
+pub fn strongly_connected_components<P: GraphProjection>(projection: &P) -> Vec<Vec<String>>
+where
+    P::Node: Node,
+{
+    let graph = DirectedIndex::build(projection);
+    graph
+        .tarjan(&vec![true; graph.len()])
+        .into_iter()
+        .rev()
+        .map(|component| graph.names(&component))
+        .collect()
+}
+
+/// Collapse every strongly connected component into a single node
+///
+/// The condensation keeps the source projection's aggregate ID and version.
+pub fn condensation<P: GraphProjection>(projection: &P) -> Condensation
+where
+    P::Node: Node,
+{
+    let graph = DirectedIndex::build(projection);
+    let mut components: Vec<Vec<usize>> = graph.tarjan(&vec![true; graph.len()]);
+    components.reverse();
+
+    let mut component_index = vec![0; graph.len()];
+    for (index, component) in components.iter().enumerate() {
+        for &node in component {
+            component_index[node] = index;
+        }
+    }
+
+    let mut dag = CondensationProjection::new(projection.aggregate_id(), GraphType::Generic);
+    dag.version = projection.version();
+
+    let members: Vec<Vec<String>> = components.iter().map(|component| graph.names(component)).collect();
+    for (index, names) in members.iter().enumerate() {
+        let id = Condensation::component_id(index);
+        dag.nodes.insert(id.clone(), CondensationNode::new(id.clone(), names.clone()));
+        dag.adjacency.insert(id, Vec::new());
+    }
+
+    let mut collapsed: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (source, successors) in graph.successors.iter().enumerate() {
+        for &target in successors {
+            let (from, to) = (component_index[source], component_index[target]);
+            if from != to {
+                *collapsed.entry((from, to)).or_default() += 1;
+            }
+        }
+    }
+    for ((from, to), count) in collapsed {
+        let (source, target) = (Condensation::component_id(from), Condensation::component_id(to));
+        let id = format!("{}->{}", source, target);
+        dag.edges.insert(id.clone(), CondensationEdge::with_id(id, source.clone(), target.clone(), count));
+        dag.adjacency.entry(source).or_default().push(target);
+    }
+
+    let component_of = graph
+        .ids
+        .iter()
+        .cloned()
+        .zip(component_index)
+        .collect();
+
+    Condensation {
+        projection: dag,
+        components: members,
+        component_of,
+    }
+}
+
+/// List elementary cycles with Johnson's algorithm
+///
+/// Each cycle is listed once, as its nodes starting from the smallest ID and
+/// without repeating the first node; a self-loop is a cycle of one node.
+/// With `max_length`, only cycles of at most that many nodes are listed and
+/// the search uses the length-bounded blocking of Gupta and Suzumura, so
+/// long cycles are pruned rather than enumerated and filtered. Cycles are
+/// sorted by length, then by node IDs.
+pub fn elementary_cycles<P: GraphProjection>(projection: &P, max_length: Option<usize>) -> Vec<Vec<String>>
+where
+    P::Node: Node,
+{
+    let graph = DirectedIndex::build(projection);
+    let bound = max_length.unwrap_or(graph.len()).min(graph.len());
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+    if bound == 0 {
+        return Vec::new();
+    }
+
+    for (node, successors) in graph.successors.iter().enumerate() {
+        if successors.contains(&node) {
+            cycles.push(vec![node]);
+        }
+    }
+
+    if bound > 1 {
+        let mut pending: Vec<Vec<usize>> = graph
+            .tarjan(&vec![true; graph.len()])
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect();
+
+        while let Some(component) = pending.pop() {
+            let mut allowed = vec![false; graph.len()];
+            component.iter().for_each(|&node| allowed[node] = true);
+
+            // Ids are sorted, so the smallest index is the smallest ID
+            let start = component.iter().copied().min().unwrap_or_default();
+            graph.bounded_cycle_search(start, &allowed, bound, &mut cycles);
+
+            allowed[start] = false;
+            pending.extend(
+                graph
+                    .tarjan(&allowed)
+                    .into_iter()
+                    .filter(|component| component.len() > 1),
+            );
+        }
+    }
+
+    cycles.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    cycles.into_iter().map(|cycle| graph.names(&cycle)).collect()
+}
+
+/// Projection adjacency indexed by position, with node IDs sorted
+pub(super) struct DirectedIndex {
+    /// Node IDs, sorted
+    pub(super) ids: Vec<String>,
+    /// Distinct successors of each node
+    pub(super) successors: Vec<Vec<usize>>,
+}
+
+impl From<IndexedAdjacency> for DirectedIndex {
+    fn from(graph: IndexedAdjacency) -> Self {
+        Self {
+            ids: graph.ids,
+            successors: graph.successors,
+        }
+    }
+}
+
+impl DirectedIndex {
+    pub(super) fn build<P: GraphProjection>(projection: &P) -> Self
+    where
+        P::Node: Node,
+    {
+        IndexedAdjacency::build(projection).into()
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Node IDs of a list of indices, in the same order
+    fn names(&self, nodes: &[usize]) -> Vec<String> {
+        nodes.iter().map(|&node| self.ids[node].clone()).collect()
+    }
+
+    /// Iterative Tarjan over the allowed nodes; components in reverse topological order
+    pub(super) fn tarjan(&self, allowed: &[bool]) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..n {
+            if !allowed[root] || index[root] != usize::MAX {
+                continue;
+            }
+
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            let mut calls: Vec<(usize, usize)> = vec![(root, 0)];
+
+            while let Some(&(node, position)) = calls.last() {
+                match self.successors[node].get(position) {
+                    Some(&successor) => {
+                        if let Some(call) = calls.last_mut() {
+                            call.1 += 1;
+                        }
+                        if !allowed[successor] {
+                            continue;
+                        }
+                        if index[successor] == usize::MAX {
+                            index[successor] = next_index;
+                            low[successor] = next_index;
+                            next_index += 1;
+                            stack.push(successor);
+                            on_stack[successor] = true;
+                            calls.push((successor, 0));
+                        } else if on_stack[successor] {
+                            low[node] = low[node].min(index[successor]);
+                        }
+                    }
+                    None => {
+                        calls.pop();
+                        if let Some(&(parent, _)) = calls.last() {
+                            low[parent] = low[parent].min(low[node]);
+                        }
+                        if low[node] == index[node] {
+                            let mut component = Vec::new();
+                            while let Some(member) = stack.pop() {
+                                on_stack[member] = false;
+                                component.push(member);
+                                if member == node {
+                                    break;
+                                }
+                            }
+                            component.sort_unstable();
+                            components.push(component);
+                        }
+                    }
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Cycles of at most `bound` nodes through `start` within the allowed nodes
+    fn bounded_cycle_search(&self, start: usize, allowed: &[bool], bound: usize, cycles: &mut Vec<Vec<usize>>) {
+        let mut path = vec![start];
+        let mut on_path: HashSet<usize> = HashSet::from([start]);
+        // A node may only be entered at a path length below its lock
+        let mut lock: HashMap<usize, usize> = HashMap::from([(start, 0)]);
+        let mut blocked_by: HashMap<usize, HashSet<usize>> = HashMap::new();
+        // Shortest distance back to `start` found below each path position
+        let mut closing = vec![bound];
+        let mut stack = vec![0usize];
+
+        while let Some(&position) = stack.last() {
+            let node = path[path.len() - 1];
+            let successors = &self.successors[node];
+
+            let mut advanced = false;
+            let mut cursor = position;
+            while cursor < successors.len() {
+                let successor = successors[cursor];
+                cursor += 1;
+                if !allowed[successor] || successor == node {
+                    continue;
+                }
+                if successor == start {
+                    cycles.push(path.clone());
+                    if let Some(last) = closing.last_mut() {
+                        *last = 1;
+                    }
+                } else if !on_path.contains(&successor)
+                    && path.len() < bound
+                    && path.len() < *lock.get(&successor).unwrap_or(&bound)
+                {
+                    if let Some(top) = stack.last_mut() {
+                        *top = cursor;
+                    }
+                    lock.insert(successor, path.len());
+                    path.push(successor);
+                    on_path.insert(successor);
+                    closing.push(bound);
+                    stack.push(0);
+                    advanced = true;
+                    break;
+                }
+            }
+            if advanced {
+                continue;
+            }
+
+            stack.pop();
+            let finished = path.pop().unwrap_or(start);
+            on_path.remove(&finished);
+            let distance = closing.pop().unwrap_or(bound);
+            if let Some(last) = closing.last_mut() {
+                *last = (*last).min(distance.saturating_add(1));
+            }
+            if stack.is_empty() {
+                break;
+            }
+
+            if distance < bound {
+                // Unlock nodes that can now close a cycle within the bound
+                let mut relax = vec![(distance, finished)];
+                while let Some((distance, node)) = relax.pop() {
+                    let unlocked = bound - distance + 1;
+                    if *lock.get(&node).unwrap_or(&bound) < unlocked {
+                        lock.insert(node, unlocked);
+                        if let Some(blockers) = blocked_by.get(&node) {
+                            relax.extend(
+                                blockers
+                                    .iter()
+                                    .filter(|blocker| !on_path.contains(blocker))
+                                    .map(|&blocker| (distance + 1, blocker)),
+                            );
+                        }
+                    }
+                }
+            } else {
+                for &successor in &self.successors[finished] {
+                    if allowed[successor] {
+                        blocked_by.entry(successor).or_default().insert(finished);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::fixtures::{create_graph, ids, TestProjection};
+
+    fn create_two_loops() -> TestProjection {
+        // {A, B} -> {C, D, E} -> F
+        create_graph(
+            &["A", "B", "C", "D", "E", "F"],
+            &[
+                ("A", "B"),
+                ("B", "A"),
+                ("B", "C"),
+                ("C", "D"),
+                ("D", "E"),
+                ("E", "C"),
+                ("E", "F"),
+            ],
+        )
+    }
+
+    // ========== Strongly Connected Components Tests ==========
+
+    #[test]
+    fn test_scc_topological_order() {
+        let projection = create_two_loops();
+        let components = strongly_connected_components(&projection);
+
+        assert_eq!(components, vec![ids(&["A", "B"]), ids(&["C", "D", "E"]), ids(&["F"])]);
+    }
+
+    #[test]
+    fn test_scc_dag_and_empty() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "B"), ("B", "C")]);
+        assert_eq!(
+            strongly_connected_components(&projection),
+            vec![ids(&["A"]), ids(&["B"]), ids(&["C"])]
+        );
+
+        let empty = create_graph(&[], &[]);
+        assert!(strongly_connected_components(&empty).is_empty());
+    }
+
+    #[test]
+    fn test_scc_deep_chain_does_not_recurse() {
+        let names: Vec<String> = (0..20_000).map(|i| format!("n{:05}", i)).collect();
+        let refs: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut edges: Vec<(&str, &str)> = refs.windows(2).map(|pair| (pair[0], pair[1])).collect();
+        edges.push((refs[refs.len() - 1], refs[0]));
+        let projection = create_graph(&refs, &edges);
+
+        let components = strongly_connected_components(&projection);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 20_000);
+    }
+
+    // ========== Condensation Tests ==========
+
+    #[test]
+    fn test_condensation_projection() {
+        let projection = create_two_loops();
+        let condensed = condensation(&projection);
+
+        assert_eq!(condensed.projection.node_count(), 3);
+        assert_eq!(condensed.projection.edge_count(), 2);
+        assert_eq!(condensed.projection.aggregate_id(), projection.aggregate_id);
+        assert_eq!(condensed.component_of["D"], condensed.component_of["E"]);
+        assert_eq!(condensed.successors(0), vec![1]);
+        assert_eq!(condensed.successors(1), vec![2]);
+        assert!(condensed.successors(2).is_empty());
+
+        let loop_node = condensed.projection.get_node(&Condensation::component_id(1)).unwrap();
+        assert_eq!(loop_node.data(), &ids(&["C", "D", "E"]));
+    }
+
+    #[test]
+    fn test_condensation_counts_collapsed_edges() {
+        let projection = create_graph(
+            &["A", "B", "C"],
+            &[("A", "B"), ("B", "A"), ("A", "C"), ("B", "C")],
+        );
+        let condensed = condensation(&projection);
+
+        let edge = condensed.projection.get_edge("scc-0->scc-1").unwrap();
+        assert_eq!(edge.data(), &2);
+    }
+
+    // ========== Elementary Cycle Tests ==========
+
+    #[test]
+    fn test_elementary_cycles() {
+        let projection = create_two_loops();
+        let cycles = elementary_cycles(&projection, None);
+
+        assert_eq!(cycles, vec![ids(&["A", "B"]), ids(&["C", "D", "E"])]);
+    }
+
+    #[test]
+    fn test_elementary_cycles_complete_graph() {
+        // The complete directed graph on 4 nodes has 6 + 8 + 6 elementary cycles
+        let names = ["A", "B", "C", "D"];
+        let mut edges = Vec::new();
+        for from in names {
+            for to in names {
+                if from != to {
+                    edges.push((from, to));
+                }
+            }
+        }
+        let projection = create_graph(&names, &edges);
+
+        assert_eq!(elementary_cycles(&projection, None).len(), 20);
+        assert_eq!(elementary_cycles(&projection, Some(2)).len(), 6);
+        assert_eq!(elementary_cycles(&projection, Some(3)).len(), 14);
+
+        let cycles = elementary_cycles(&projection, None);
+        let unique: HashSet<_> = cycles.iter().collect();
+        assert_eq!(unique.len(), cycles.len());
+        assert!(cycles.iter().all(|cycle| cycle[0] == "A" || !cycle.contains(&"A".to_string())));
+    }
+
+    #[test]
+    fn test_elementary_cycles_keep_traversal_order() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "C"), ("C", "B"), ("B", "A")]);
+        assert_eq!(elementary_cycles(&projection, None), vec![ids(&["A", "C", "B"])]);
+    }
+
+    #[test]
+    fn test_elementary_cycles_self_loops_and_limits() {
+        let projection = create_graph(&["A", "B"], &[("A", "A"), ("A", "B"), ("B", "A")]);
+
+        assert_eq!(elementary_cycles(&projection, None), vec![ids(&["A"]), ids(&["A", "B"])]);
+        assert_eq!(elementary_cycles(&projection, Some(1)), vec![ids(&["A"])]);
+        assert!(elementary_cycles(&projection, Some(0)).is_empty());
+    }
+
+    /// Every simple cycle, found by extending paths from their smallest node
+    fn exhaustive_cycles(projection: &TestProjection) -> Vec<Vec<String>> {
+        fn extend(projection: &TestProjection, path: &mut Vec<String>, cycles: &mut Vec<Vec<String>>) {
+            let last = path[path.len() - 1].clone();
+            let mut targets: Vec<&String> = projection.adjacency[&last].iter().collect();
+            targets.sort();
+            targets.dedup();
+            for target in targets {
+                if *target == path[0] {
+                    cycles.push(path.clone());
+                } else if *target > path[0] && !path.contains(target) {
+                    path.push(target.clone());
+                    extend(projection, path, cycles);
+                    path.pop();
+                }
+            }
+        }
+
+        let mut cycles = Vec::new();
+        let mut starts: Vec<&String> = projection.nodes.keys().collect();
+        starts.sort();
+        for start in starts {
+            extend(projection, &mut vec![start.clone()], &mut cycles);
+        }
+        cycles.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        cycles
+    }
+
+    #[test]
+    fn test_cycles_match_exhaustive_search() {
+        let mut seed: u64 = 7;
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+
+        for _ in 0..30 {
+            let names: Vec<String> = (0..7).map(|i| format!("n{}", i)).collect();
+            let refs: Vec<&str> = names.iter().map(String::as_str).collect();
+            let edges: Vec<(&str, &str)> = (0..18).map(|_| (refs[next() % 7], refs[next() % 7])).collect();
+            let projection = create_graph(&refs, &edges);
+
+            let expected = exhaustive_cycles(&projection);
+            assert_eq!(elementary_cycles(&projection, None), expected);
+            for bound in 1..=7 {
+                let bounded: Vec<_> = expected.iter().filter(|cycle| cycle.len() <= bound).cloned().collect();
+                assert_eq!(elementary_cycles(&projection, Some(bound)), bounded);
+            }
+        }
+    }
+
+    #[test]
+    fn test_elementary_cycles_acyclic() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "B"), ("A", "C"), ("B", "C")]);
+        assert!(elementary_cycles(&projection, None).is_empty());
+    }
+}
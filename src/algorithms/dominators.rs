@@ -0,0 +1,378 @@
+//! Dominator and post-dominator trees
+//!
+//! Node `a` dominates node `b` when every path from the root to `b` passes
+//! through `a`; it post-dominates `b` when every path from `b` to an exit
+//! passes through `a`. Both trees are built with the Lengauer-Tarjan
+//! algorithm, respecting edge direction.
+
+use super::adjacency::IndexedAdjacency;
+use crate::core::{GraphProjection, Node};
+use crate::error::{GraphError, Result};
+use std::collections::HashMap;
+
+/// Marks an unset ancestor, parent or DFS number
+const NONE: usize = usize::MAX;
+
+/// Dominator (or post-dominator) tree of the nodes reachable from its roots
+#[derive(Debug, Clone)]
+pub struct DominatorTree {
+    /// Node IDs in the tree, sorted
+    ids: Vec<String>,
+    index: HashMap<String, usize>,
+    /// Immediate dominator of each node, `None` for tree roots
+    idom: Vec<Option<usize>>,
+    /// Children of each node, sorted by ID
+    children: Vec<Vec<usize>>,
+    /// Pre- and post-order numbers in the tree, for constant-time dominance checks
+    entry: Vec<usize>,
+    exit: Vec<usize>,
+}
+
+impl DominatorTree {
+    /// Nodes without an immediate dominator, sorted
+    ///
+    /// A dominator tree has just its root. A post-dominator tree over several
+    /// exits also has every node whose paths to different exits share no
+    /// other node, such as the last branch point before them.
+    pub fn roots(&self) -> Vec<&str> {
+        (0..self.ids.len())
+            .filter(|&node| self.idom[node].is_none())
+            .map(|node| self.ids[node].as_str())
+            .collect()
+    }
+
+    /// Whether a node is in the tree, i.e. connected to a root
+    pub fn contains(&self, node: &str) -> bool {
+        self.index.contains_key(node)
+    }
+
+    /// Number of nodes in the tree
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the tree has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The closest strict dominator of a node
+    pub fn immediate_dominator(&self, node: &str) -> Option<&str> {
+        let node = *self.index.get(node)?;
+        self.idom[node].map(|parent| self.ids[parent].as_str())
+    }
+
+    /// All dominators of a node, from the node itself up to its root
+    ///
+    /// Empty if the node is not in the tree.
+    pub fn dominators(&self, node: &str) -> Vec<&str> {
+        let mut chain = Vec::new();
+        let mut current = self.index.get(node).copied();
+        while let Some(node) = current {
+            chain.push(self.ids[node].as_str());
+            current = self.idom[node];
+        }
+        chain
+    }
+
+    /// Nodes immediately dominated by a node, sorted
+    pub fn children(&self, node: &str) -> Vec<&str> {
+        match self.index.get(node) {
+            Some(&node) => self.children[node].iter().map(|&child| self.ids[child].as_str()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Whether `a` dominates `b`; every node in the tree dominates itself
+    pub fn dominates(&self, a: &str, b: &str) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.entry[a] <= self.entry[b] && self.exit[b] <= self.exit[a],
+            _ => false,
+        }
+    }
+
+    /// Whether `a` dominates `b` and is a different node
+    pub fn strictly_dominates(&self, a: &str, b: &str) -> bool {
+        a != b && self.dominates(a, b)
+    }
+}
+
+/// Build the dominator tree of the nodes reachable from `root`
+pub fn dominator_tree<P: GraphProjection>(projection: &P, root: &str) -> Result<DominatorTree>
+where
+    P::Node: Node,
+{
+    let graph = FlowGraph::build(projection);
+    let root = graph.lookup(root)?;
+    Ok(graph.dominators(&[root], false))
+}
+
+/// Build the post-dominator tree of the nodes that can reach one of `exits`
+///
+/// With several exits, a node is post-dominated only by nodes that lie on
+/// its paths to every exit, as if all exits led to a single virtual exit.
+pub fn post_dominator_tree<P: GraphProjection>(projection: &P, exits: &[&str]) -> Result<DominatorTree>
+where
+    P::Node: Node,
+{
+    if exits.is_empty() {
+        return Err(GraphError::InvalidOperation(
+            "Post-dominators need at least one exit node".to_string(),
+        ));
+    }
+    let graph = FlowGraph::build(projection);
+    let exits = exits.iter().map(|exit| graph.lookup(exit)).collect::<Result<Vec<_>>>()?;
+    Ok(graph.dominators(&exits, true))
+}
+
+/// Projection nodes with distinct successors and predecessors
+struct FlowGraph {
+    graph: IndexedAdjacency,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl FlowGraph {
+    fn build<P: GraphProjection>(projection: &P) -> Self
+    where
+        P::Node: Node,
+    {
+        let graph = IndexedAdjacency::build(projection);
+        let predecessors = graph.predecessors();
+        Self { graph, predecessors }
+    }
+
+    fn lookup(&self, id: &str) -> Result<usize> {
+        self.graph.lookup(id)
+    }
+
+    /// Lengauer-Tarjan from a virtual root whose successors are `roots`,
+    /// over reversed edges when `reverse` is set
+    fn dominators(&self, roots: &[usize], reverse: bool) -> DominatorTree {
+        let (forward, backward) = if reverse {
+            (&self.predecessors, &self.graph.successors)
+        } else {
+            (&self.graph.successors, &self.predecessors)
+        };
+        let virtual_root = self.graph.ids.len();
+        let count = self.graph.ids.len() + 1;
+        let successors = |node: usize| -> &[usize] {
+            if node == virtual_root {
+                roots
+            } else {
+                &forward[node]
+            }
+        };
+
+        // Number nodes in DFS preorder from the virtual root
+        let mut number = vec![NONE; count];
+        let mut vertex = Vec::with_capacity(count);
+        let mut parent = vec![NONE; count];
+        let mut stack = vec![(virtual_root, NONE)];
+        while let Some((node, from)) = stack.pop() {
+            if number[node] != NONE {
+                continue;
+            }
+            number[node] = vertex.len();
+            vertex.push(node);
+            parent[node] = from;
+            for &next in successors(node).iter().rev() {
+                if number[next] == NONE {
+                    stack.push((next, node));
+                }
+            }
+        }
+
+        let mut semi: Vec<usize> = number.clone();
+        let mut label: Vec<usize> = (0..count).collect();
+        let mut ancestor = vec![NONE; count];
+        let mut idom = vec![NONE; count];
+        let mut bucket: Vec<Vec<usize>> = vec![Vec::new(); count];
+
+        for &node in vertex.iter().skip(1).rev() {
+            // The virtual root is a predecessor of every root
+            let is_root = roots.contains(&node);
+            let predecessors = backward[node].iter().copied().chain(is_root.then_some(virtual_root));
+            for predecessor in predecessors {
+                if number[predecessor] == NONE {
+                    continue;
+                }
+                let candidate = eval(predecessor, &mut ancestor, &mut label, &semi);
+                semi[node] = semi[node].min(semi[candidate]);
+            }
+            bucket[vertex[semi[node]]].push(node);
+            ancestor[node] = parent[node];
+
+            for waiting in std::mem::take(&mut bucket[parent[node]]) {
+                let candidate = eval(waiting, &mut ancestor, &mut label, &semi);
+                idom[waiting] = if semi[candidate] < semi[waiting] {
+                    candidate
+                } else {
+                    parent[node]
+                };
+            }
+        }
+        for &node in vertex.iter().skip(1) {
+            if idom[node] != vertex[semi[node]] {
+                idom[node] = idom[idom[node]];
+            }
+        }
+
+        // Keep the real nodes, in ID order
+        let mut members: Vec<usize> = vertex.iter().copied().filter(|&node| node != virtual_root).collect();
+        members.sort_unstable();
+        let position: HashMap<usize, usize> = members.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let ids: Vec<String> = members.iter().map(|&node| self.graph.ids[node].clone()).collect();
+        let tree_idom: Vec<Option<usize>> = members
+            .iter()
+            .map(|node| position.get(&idom[*node]).copied())
+            .collect();
+
+        let mut children = vec![Vec::new(); members.len()];
+        for (node, parent) in tree_idom.iter().enumerate() {
+            if let Some(parent) = parent {
+                children[*parent].push(node);
+            }
+        }
+
+        let mut entry = vec![0; members.len()];
+        let mut exit = vec![0; members.len()];
+        let mut clock = 0;
+        for root in (0..members.len()).filter(|&node| tree_idom[node].is_none()) {
+            let mut stack = vec![(root, 0)];
+            entry[root] = clock;
+            clock += 1;
+            while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+                if let Some(&child) = children[node].get(*next) {
+                    *next += 1;
+                    entry[child] = clock;
+                    clock += 1;
+                    stack.push((child, 0));
+                } else {
+                    exit[node] = clock;
+                    clock += 1;
+                    stack.pop();
+                }
+            }
+        }
+
+        DominatorTree {
+            index: ids.iter().cloned().zip(0..).collect(),
+            ids,
+            idom: tree_idom,
+            children,
+            entry,
+            exit,
+        }
+    }
+}
+
+/// Node with the smallest semidominator on the forest path above `node`,
+/// compressing the path as it goes
+fn eval(node: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[node] == NONE {
+        return node;
+    }
+    let mut path = Vec::new();
+    let mut current = node;
+    while ancestor[ancestor[current]] != NONE {
+        path.push(current);
+        current = ancestor[current];
+    }
+    while let Some(current) = path.pop() {
+        let above = ancestor[current];
+        if semi[label[above]] < semi[label[current]] {
+            label[current] = label[above];
+        }
+        ancestor[current] = ancestor[above];
+    }
+    label[node]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::fixtures::create_graph_from_edges;
+
+    // ========== Dominator Tree Tests ==========
+
+    #[test]
+    fn test_dominator_tree_textbook_graph() {
+        // The flow graph from Lengauer and Tarjan's paper
+        let projection = create_graph_from_edges(&[
+            ("R", "A"), ("R", "B"), ("R", "C"),
+            ("A", "D"),
+            ("B", "A"), ("B", "D"), ("B", "E"),
+            ("C", "F"), ("C", "G"),
+            ("D", "L"),
+            ("E", "H"),
+            ("F", "I"),
+            ("G", "I"), ("G", "J"),
+            ("H", "E"), ("H", "K"),
+            ("I", "K"),
+            ("J", "I"),
+            ("K", "I"), ("K", "R"),
+            ("L", "H"),
+        ]);
+        let tree = dominator_tree(&projection, "R").unwrap();
+
+        let expected = [
+            ("A", "R"), ("B", "R"), ("C", "R"), ("D", "R"), ("E", "R"), ("H", "R"), ("I", "R"), ("K", "R"),
+            ("F", "C"), ("G", "C"), ("J", "G"), ("L", "D"),
+        ];
+        for (node, idom) in expected {
+            assert_eq!(tree.immediate_dominator(node), Some(idom), "idom of {}", node);
+        }
+        assert_eq!(tree.roots(), vec!["R"]);
+        assert_eq!(tree.immediate_dominator("R"), None);
+        assert_eq!(tree.children("C"), vec!["F", "G"]);
+        assert_eq!(tree.dominators("J"), vec!["J", "G", "C", "R"]);
+        assert!(tree.dominates("C", "J"));
+        assert!(tree.dominates("J", "J"));
+        assert!(!tree.strictly_dominates("J", "J"));
+        assert!(!tree.dominates("B", "E"));
+        assert_eq!(tree.len(), 13);
+    }
+
+    #[test]
+    fn test_dominator_tree_skips_unreachable_nodes() {
+        let projection = create_graph_from_edges(&[("A", "B"), ("B", "C"), ("X", "B")]);
+        let tree = dominator_tree(&projection, "A").unwrap();
+
+        assert!(!tree.contains("X"));
+        assert!(tree.dominators("X").is_empty());
+        assert!(!tree.dominates("X", "B"));
+        assert_eq!(tree.dominators("C"), vec!["C", "B", "A"]);
+
+        assert!(matches!(dominator_tree(&projection, "missing"), Err(GraphError::NodeNotFound(_))));
+    }
+
+    #[test]
+    fn test_post_dominator_tree() {
+        // A branches to B or C, which rejoin at D; D can finish at E or F
+        let projection = create_graph_from_edges(&[("A", "B"), ("A", "C"), ("B", "D"), ("C", "D"), ("D", "E"), ("D", "F")]);
+
+        let single = post_dominator_tree(&projection, &["E"]).unwrap();
+        assert_eq!(single.dominators("A"), vec!["A", "D", "E"]);
+        assert!(!single.contains("F"));
+
+        let both = post_dominator_tree(&projection, &["E", "F"]).unwrap();
+        assert_eq!(both.roots(), vec!["D", "E", "F"]);
+        assert_eq!(both.immediate_dominator("D"), None);
+        assert_eq!(both.immediate_dominator("B"), Some("D"));
+        assert_eq!(both.immediate_dominator("A"), Some("D"));
+        assert!(!both.dominates("B", "A"));
+
+        assert!(matches!(post_dominator_tree(&projection, &[]), Err(GraphError::InvalidOperation(_))));
+    }
+
+    #[test]
+    fn test_dominator_tree_with_loops() {
+        // A loop B -> C -> B with an exit from C to D
+        let projection = create_graph_from_edges(&[("A", "B"), ("B", "C"), ("C", "B"), ("C", "D"), ("A", "D")]);
+        let tree = dominator_tree(&projection, "A").unwrap();
+
+        assert_eq!(tree.immediate_dominator("B"), Some("A"));
+        assert_eq!(tree.immediate_dominator("C"), Some("B"));
+        assert_eq!(tree.immediate_dominator("D"), Some("A"));
+    }
+}
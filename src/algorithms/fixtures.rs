@@ -0,0 +1,58 @@
+//! Projection fixtures shared by the algorithm tests
+
+use crate::core::projection_engine::GenericGraphProjection;
+use crate::core::GraphType;
+use crate::graphs::workflow::{WorkflowEdge, WorkflowNode, WorkflowNodeType};
+use uuid::Uuid;
+
+pub(super) type TestProjection = GenericGraphProjection<WorkflowNode, WorkflowEdge>;
+
+/// Projection over `ids` whose edges exist only as adjacency
+pub(super) fn create_graph(ids: &[&str], edges: &[(&str, &str)]) -> TestProjection {
+    let mut projection = TestProjection::new(Uuid::new_v4(), GraphType::Generic);
+    for id in ids {
+        projection.nodes.insert(id.to_string(), WorkflowNode::new(*id, WorkflowNodeType::Start));
+        projection.adjacency.insert(id.to_string(), vec![]);
+    }
+    for (from, to) in edges {
+        projection.adjacency.get_mut(*from).unwrap().push(to.to_string());
+    }
+    projection
+}
+
+/// Projection of state nodes over `ids` joined by named `(id, from, to)` transitions
+///
+/// Endpoints missing from `ids` are added as state nodes too.
+pub(super) fn create_transitions(ids: &[&str], edges: &[(&str, &str, &str)]) -> TestProjection {
+    let mut projection = TestProjection::new(Uuid::new_v4(), GraphType::Generic);
+    for id in ids {
+        add_state(&mut projection, id);
+    }
+    for &(id, from, to) in edges {
+        add_state(&mut projection, from);
+        add_state(&mut projection, to);
+        projection.edges.insert(id.to_string(), WorkflowEdge::transition(id, from, to));
+        projection.adjacency.get_mut(from).unwrap().push(to.to_string());
+    }
+    projection
+}
+
+/// Projection of state nodes joined by transitions named `e0`, `e1`, ... in order
+pub(super) fn create_graph_from_edges(edges: &[(&str, &str)]) -> TestProjection {
+    let ids: Vec<String> = (0..edges.len()).map(|index| format!("e{}", index)).collect();
+    let named: Vec<(&str, &str, &str)> =
+        ids.iter().zip(edges).map(|(id, &(from, to))| (id.as_str(), from, to)).collect();
+    create_transitions(&[], &named)
+}
+
+/// Owned IDs, for comparing against algorithm results
+pub(super) fn ids(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn add_state(projection: &mut TestProjection, id: &str) {
+    if !projection.nodes.contains_key(id) {
+        projection.nodes.insert(id.to_string(), WorkflowNode::state(id, id));
+        projection.adjacency.insert(id.to_string(), vec![]);
+    }
+}
@@ -0,0 +1,601 @@
+//! Network flow algorithms for projections
+//!
+//! Capacities and costs come from closures over the projection's edges, so
+//! the same projection can be analysed under different capacity models.
+//! Parallel edges are separate arcs and self-loops carry no flow.
+
+use super::adjacency::IndexedAdjacency;
+use crate::core::{Edge, GraphProjection, Node};
+use crate::error::{GraphError, Result};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Result of [`max_flow`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxFlow {
+    /// Total flow from source to sink
+    pub value: u64,
+    /// Flow on every edge carrying any, by edge ID
+    pub edge_flows: HashMap<String, u64>,
+}
+
+impl MaxFlow {
+    /// Flow on an edge, zero for edges carrying none
+    pub fn flow_on(&self, edge_id: &str) -> u64 {
+        self.edge_flows.get(edge_id).copied().unwrap_or(0)
+    }
+}
+
+/// Minimum s-t cut found by [`min_cut`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    /// Total capacity of the cut, equal to the maximum flow
+    pub value: u64,
+    /// Nodes still reachable from the source in the residual network, sorted
+    pub source_side: Vec<String>,
+    /// All other nodes, sorted
+    pub sink_side: Vec<String>,
+    /// IDs of the positive-capacity edges from the source side to the sink side, sorted
+    pub cut_edges: Vec<String>,
+}
+
+/// Result of [`min_cost_flow`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCostFlow {
+    /// Total flow sent, at most the requested limit
+    pub value: u64,
+    /// Total cost of the flow
+    pub cost: u64,
+    /// Flow on every edge carrying any, by edge ID
+    pub edge_flows: HashMap<String, u64>,
+}
+
+impl MinCostFlow {
+    /// Flow on an edge, zero for edges carrying none
+    pub fn flow_on(&self, edge_id: &str) -> u64 {
+        self.edge_flows.get(edge_id).copied().unwrap_or(0)
+    }
+}
+
+/// Maximum flow from `source` to `sink` using Dinic's algorithm
+///
+/// # Arguments
+/// * `projection` - The graph projection to analyse
+/// * `source` - Node the flow leaves
+/// * `sink` - Node the flow arrives at
+/// * `capacity` - Function returning the capacity of an edge
+pub fn max_flow<P, C>(projection: &P, source: &str, sink: &str, capacity: C) -> Result<MaxFlow>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    C: Fn(&P::Edge) -> u64,
+{
+    let mut network = FlowNetwork::build(projection, source, sink, capacity, |_| 0)?;
+    let value = network.dinic(u64::MAX);
+    Ok(MaxFlow {
+        value,
+        edge_flows: network.edge_flows(),
+    })
+}
+
+/// Minimum s-t cut, derived from a maximum flow
+///
+/// The source side is the set of nodes reachable from `source` through edges
+/// with spare capacity; every edge leaving it is saturated.
+pub fn min_cut<P, C>(projection: &P, source: &str, sink: &str, capacity: C) -> Result<MinCut>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    C: Fn(&P::Edge) -> u64,
+{
+    let mut network = FlowNetwork::build(projection, source, sink, capacity, |_| 0)?;
+    let value = network.dinic(u64::MAX);
+    let reachable = network.residual_reachable();
+
+    let (source_side, sink_side): (Vec<usize>, Vec<usize>) =
+        (0..network.ids.len()).partition(|&node| reachable[node]);
+    let mut cut_edges: Vec<String> = network
+        .arcs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, arc)| {
+            let edge = arc.edge?;
+            let from = network.arcs[arc.reverse].to;
+            (reachable[from] && !reachable[arc.to] && network.capacities[index] > 0)
+                .then(|| network.edge_ids[edge].clone())
+        })
+        .collect();
+    cut_edges.sort();
+
+    Ok(MinCut {
+        value,
+        source_side: source_side.into_iter().map(|node| network.ids[node].clone()).collect(),
+        sink_side: sink_side.into_iter().map(|node| network.ids[node].clone()).collect(),
+        cut_edges,
+    })
+}
+
+/// Cheapest flow of up to `limit` units from `source` to `sink`
+///
+/// Uses successive shortest paths with Dijkstra over reduced costs. With
+/// `limit` of `None` this is a minimum-cost maximum flow; otherwise the
+/// result carries less than `limit` only when no more flow fits. Costs must
+/// fit in 32 bits.
+///
+/// # Arguments
+/// * `projection` - The graph projection to analyse
+/// * `source` - Node the flow leaves
+/// * `sink` - Node the flow arrives at
+/// * `limit` - Maximum amount of flow to send
+/// * `capacity` - Function returning the capacity of an edge
+/// * `cost` - Function returning the cost per unit of flow on an edge
+pub fn min_cost_flow<P, C, K>(
+    projection: &P,
+    source: &str,
+    sink: &str,
+    limit: Option<u64>,
+    capacity: C,
+    cost: K,
+) -> Result<MinCostFlow>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    C: Fn(&P::Edge) -> u64,
+    K: Fn(&P::Edge) -> u64,
+{
+    let mut network = FlowNetwork::build(projection, source, sink, capacity, cost)?;
+    let (value, cost) = network.successive_shortest_paths(limit.unwrap_or(u64::MAX));
+    Ok(MinCostFlow {
+        value,
+        cost,
+        edge_flows: network.edge_flows(),
+    })
+}
+
+/// One direction of a residual edge
+struct ResidualArc {
+    /// Head node
+    to: usize,
+    /// Remaining capacity
+    residual: u64,
+    /// Cost per unit, negated on reverse arcs
+    cost: i64,
+    /// Index of the paired arc
+    reverse: usize,
+    /// Projection edge this forward arc stands for
+    edge: Option<usize>,
+}
+
+/// Residual network over a projection, nodes and edges sorted by ID
+struct FlowNetwork {
+    ids: Vec<String>,
+    edge_ids: Vec<String>,
+    arcs: Vec<ResidualArc>,
+    /// Original capacity of each arc (zero for reverse arcs)
+    capacities: Vec<u64>,
+    /// Outgoing arc indices per node
+    outgoing: Vec<Vec<usize>>,
+    source: usize,
+    sink: usize,
+}
+
+impl FlowNetwork {
+    fn build<P, C, K>(projection: &P, source: &str, sink: &str, capacity: C, cost: K) -> Result<Self>
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+        C: Fn(&P::Edge) -> u64,
+        K: Fn(&P::Edge) -> u64,
+    {
+        if projection.get_node(source).is_none() {
+            return Err(GraphError::NodeNotFound(source.to_string()));
+        }
+        if projection.get_node(sink).is_none() {
+            return Err(GraphError::NodeNotFound(sink.to_string()));
+        }
+        if source == sink {
+            return Err(GraphError::InvalidOperation(format!(
+                "Source and sink must differ, both are {}",
+                source
+            )));
+        }
+
+        let nodes = IndexedAdjacency::nodes(projection);
+        let mut edges = projection.edges();
+        edges.sort_by_key(|edge| edge.id());
+
+        let mut network = Self {
+            source: nodes.lookup(source)?,
+            sink: nodes.lookup(sink)?,
+            outgoing: vec![Vec::new(); nodes.len()],
+            edge_ids: Vec::with_capacity(edges.len()),
+            arcs: Vec::with_capacity(edges.len() * 2),
+            capacities: Vec::with_capacity(edges.len() * 2),
+            ids: Vec::new(),
+        };
+
+        for edge in edges {
+            let (from, to) = match nodes.endpoints(edge) {
+                Some((from, to)) if from != to => (from, to),
+                _ => continue,
+            };
+            let unit_cost = cost(edge);
+            if unit_cost > u64::from(u32::MAX) {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Edge {} has cost {} beyond the supported 32 bits",
+                    edge.id(),
+                    unit_cost
+                )));
+            }
+            let unit_cost = unit_cost as i64;
+            let forward = network.arcs.len();
+            let edge_capacity = capacity(edge);
+
+            network.arcs.push(ResidualArc {
+                to,
+                residual: edge_capacity,
+                cost: unit_cost,
+                reverse: forward + 1,
+                edge: Some(network.edge_ids.len()),
+            });
+            network.arcs.push(ResidualArc {
+                to: from,
+                residual: 0,
+                cost: -unit_cost,
+                reverse: forward,
+                edge: None,
+            });
+            network.capacities.extend([edge_capacity, 0]);
+            network.outgoing[from].push(forward);
+            network.outgoing[to].push(forward + 1);
+            network.edge_ids.push(edge.id());
+        }
+
+        network.ids = nodes.ids;
+        Ok(network)
+    }
+
+    /// Push flow along an arc
+    fn push(&mut self, arc: usize, amount: u64) {
+        self.arcs[arc].residual -= amount;
+        let reverse = self.arcs[arc].reverse;
+        self.arcs[reverse].residual += amount;
+    }
+
+    /// Send up to `limit` units with Dinic's blocking flows
+    fn dinic(&mut self, limit: u64) -> u64 {
+        let mut total = 0u64;
+        while total < limit {
+            let level = match self.levels() {
+                Some(level) => level,
+                None => break,
+            };
+            let mut next = vec![0; self.ids.len()];
+            loop {
+                let pushed = self.augment(&level, &mut next, limit - total);
+                if pushed == 0 {
+                    break;
+                }
+                total += pushed;
+            }
+        }
+        total
+    }
+
+    /// BFS levels from the source, or `None` once the sink is unreachable
+    fn levels(&self) -> Option<Vec<usize>> {
+        let mut level = vec![usize::MAX; self.ids.len()];
+        let mut queue = VecDeque::from([self.source]);
+        level[self.source] = 0;
+        while let Some(node) = queue.pop_front() {
+            for &arc in &self.outgoing[node] {
+                let to = self.arcs[arc].to;
+                if self.arcs[arc].residual > 0 && level[to] == usize::MAX {
+                    level[to] = level[node] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+        (level[self.sink] != usize::MAX).then_some(level)
+    }
+
+    /// Find one source-sink path in the level graph and saturate it
+    ///
+    /// `next` holds each node's first untried arc; dead ends advance their
+    /// parent's pointer so later calls skip them.
+    fn augment(&mut self, level: &[usize], next: &mut [usize], limit: u64) -> u64 {
+        let mut path: Vec<usize> = Vec::new();
+        let mut node = self.source;
+        loop {
+            if node == self.sink {
+                let amount = path
+                    .iter()
+                    .map(|&arc| self.arcs[arc].residual)
+                    .fold(limit, u64::min);
+                for &arc in &path {
+                    self.push(arc, amount);
+                }
+                return amount;
+            }
+
+            let advance = self.outgoing[node][next[node]..].iter().position(|&arc| {
+                let to = self.arcs[arc].to;
+                self.arcs[arc].residual > 0 && level[to] == level[node] + 1
+            });
+            match advance {
+                Some(offset) => {
+                    next[node] += offset;
+                    let arc = self.outgoing[node][next[node]];
+                    path.push(arc);
+                    node = self.arcs[arc].to;
+                }
+                None => {
+                    next[node] = self.outgoing[node].len();
+                    match path.pop() {
+                        Some(arc) => {
+                            node = self.arcs[self.arcs[arc].reverse].to;
+                            next[node] += 1;
+                        }
+                        None => return 0,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Successive shortest augmenting paths; returns flow and cost
+    fn successive_shortest_paths(&mut self, limit: u64) -> (u64, u64) {
+        let n = self.ids.len();
+        let mut potential = vec![0i64; n];
+        let (mut flow, mut cost) = (0u64, 0u64);
+
+        while flow < limit {
+            // Dijkstra over reduced costs, which stay non-negative
+            let mut distance = vec![i64::MAX; n];
+            let mut via: Vec<Option<usize>> = vec![None; n];
+            let mut heap = BinaryHeap::from([Reverse((0i64, self.source))]);
+            distance[self.source] = 0;
+            while let Some(Reverse((dist, node))) = heap.pop() {
+                if dist > distance[node] {
+                    continue;
+                }
+                for &arc in &self.outgoing[node] {
+                    let ResidualArc { to, residual, cost: arc_cost, .. } = self.arcs[arc];
+                    if residual == 0 {
+                        continue;
+                    }
+                    let candidate = dist + arc_cost + potential[node] - potential[to];
+                    if candidate < distance[to] {
+                        distance[to] = candidate;
+                        via[to] = Some(arc);
+                        heap.push(Reverse((candidate, to)));
+                    }
+                }
+            }
+            if distance[self.sink] == i64::MAX {
+                break;
+            }
+            for node in 0..n {
+                if distance[node] != i64::MAX {
+                    potential[node] += distance[node];
+                }
+            }
+
+            let mut path = Vec::new();
+            let mut node = self.sink;
+            while let Some(arc) = via[node] {
+                path.push(arc);
+                node = self.arcs[self.arcs[arc].reverse].to;
+            }
+            let amount = path
+                .iter()
+                .map(|&arc| self.arcs[arc].residual)
+                .fold(limit - flow, u64::min);
+            let unit_cost: i64 = path.iter().map(|&arc| self.arcs[arc].cost).sum();
+            for &arc in &path {
+                self.push(arc, amount);
+            }
+            flow += amount;
+            cost = cost.saturating_add(amount.saturating_mul(unit_cost as u64));
+        }
+
+        (flow, cost)
+    }
+
+    /// Nodes reachable from the source through arcs with spare capacity
+    fn residual_reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.ids.len()];
+        let mut queue = VecDeque::from([self.source]);
+        reachable[self.source] = true;
+        while let Some(node) = queue.pop_front() {
+            for &arc in &self.outgoing[node] {
+                let to = self.arcs[arc].to;
+                if self.arcs[arc].residual > 0 && !reachable[to] {
+                    reachable[to] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Flow on each projection edge carrying any
+    fn edge_flows(&self) -> HashMap<String, u64> {
+        self.arcs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, arc)| {
+                let edge = arc.edge?;
+                let flow = self.capacities[index] - arc.residual;
+                (flow > 0).then(|| (self.edge_ids[edge].clone(), flow))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GraphType;
+    use crate::graphs::composed::{
+        ComposedEdge, ComposedEdgeType, ComposedGraph, ComposedNode, ComposedNodeType,
+    };
+    use uuid::Uuid;
+
+    /// Data-flow network whose edges carry `capacity` and `cost` metadata
+    fn create_network(edges: &[(&str, &str, &str, u64, u64)]) -> ComposedGraph {
+        let mut projection = ComposedGraph::new(Uuid::new_v4(), GraphType::ComposedGraph);
+        for (id, from, to, capacity, cost) in edges {
+            for node in [from, to] {
+                projection.nodes.entry(node.to_string()).or_insert_with(|| {
+                    ComposedNode::new(*node, ComposedNodeType::Transform { operation: "stage".to_string() })
+                });
+                projection.adjacency.entry(node.to_string()).or_default();
+            }
+            let mut edge = ComposedEdge::new(
+                *id,
+                *from,
+                *to,
+                ComposedEdgeType::DataFlow { flow_type: "stream".to_string() },
+            );
+            edge.metadata.insert("capacity".to_string(), (*capacity).into());
+            edge.metadata.insert("cost".to_string(), (*cost).into());
+            projection.edges.insert(id.to_string(), edge);
+            projection.adjacency.get_mut(*from).unwrap().push(to.to_string());
+        }
+        projection
+    }
+
+    fn capacity(edge: &ComposedEdge) -> u64 {
+        edge.metadata["capacity"].as_u64().unwrap()
+    }
+
+    fn cost(edge: &ComposedEdge) -> u64 {
+        edge.metadata["cost"].as_u64().unwrap()
+    }
+
+    /// The textbook network with maximum flow 23
+    fn create_textbook_network() -> ComposedGraph {
+        create_network(&[
+            ("s-v1", "s", "v1", 16, 0),
+            ("s-v2", "s", "v2", 13, 0),
+            ("v2-v1", "v2", "v1", 4, 0),
+            ("v1-v3", "v1", "v3", 12, 0),
+            ("v3-v2", "v3", "v2", 9, 0),
+            ("v2-v4", "v2", "v4", 14, 0),
+            ("v4-v3", "v4", "v3", 7, 0),
+            ("v3-t", "v3", "t", 20, 0),
+            ("v4-t", "v4", "t", 4, 0),
+        ])
+    }
+
+    // ========== Max Flow Tests ==========
+
+    #[test]
+    fn test_max_flow_textbook_network() {
+        let projection = create_textbook_network();
+        let flow = max_flow(&projection, "s", "t", capacity).unwrap();
+
+        assert_eq!(flow.value, 23);
+        assert_eq!(flow.flow_on("v3-t") + flow.flow_on("v4-t"), 23);
+        for edge in projection.edges.values() {
+            assert!(flow.flow_on(&edge.id) <= capacity(edge));
+        }
+        // Conservation at every inner node
+        for node in ["v1", "v2", "v3", "v4"] {
+            let inflow: u64 = projection.edges.values().filter(|e| e.target == node).map(|e| flow.flow_on(&e.id)).sum();
+            let outflow: u64 = projection.edges.values().filter(|e| e.source == node).map(|e| flow.flow_on(&e.id)).sum();
+            assert_eq!(inflow, outflow, "flow not conserved at {}", node);
+        }
+    }
+
+    #[test]
+    fn test_max_flow_parallel_edges_and_no_path() {
+        let projection = create_network(&[("a", "s", "t", 3, 0), ("b", "s", "t", 4, 0), ("c", "t", "x", 9, 0)]);
+
+        let flow = max_flow(&projection, "s", "t", capacity).unwrap();
+        assert_eq!(flow.value, 7);
+        assert_eq!(flow.flow_on("a"), 3);
+        assert_eq!(flow.flow_on("b"), 4);
+
+        let flow = max_flow(&projection, "x", "s", capacity).unwrap();
+        assert_eq!(flow.value, 0);
+        assert!(flow.edge_flows.is_empty());
+    }
+
+    #[test]
+    fn test_max_flow_invalid_endpoints() {
+        let projection = create_textbook_network();
+
+        assert!(matches!(max_flow(&projection, "s", "missing", capacity), Err(GraphError::NodeNotFound(_))));
+        assert!(matches!(max_flow(&projection, "s", "s", capacity), Err(GraphError::InvalidOperation(_))));
+    }
+
+    // ========== Min Cut Tests ==========
+
+    #[test]
+    fn test_min_cut_textbook_network() {
+        let projection = create_textbook_network();
+        let cut = min_cut(&projection, "s", "t", capacity).unwrap();
+
+        assert_eq!(cut.value, 23);
+        assert_eq!(cut.source_side, vec!["s", "v1", "v2", "v4"]);
+        assert_eq!(cut.sink_side, vec!["t", "v3"]);
+        assert_eq!(cut.cut_edges, vec!["v1-v3", "v4-t", "v4-v3"]);
+
+        let total: u64 = cut.cut_edges.iter().map(|id| capacity(&projection.edges[id])).sum();
+        assert_eq!(total, cut.value);
+    }
+
+    #[test]
+    fn test_min_cut_bottleneck() {
+        let projection = create_network(&[
+            ("ingest", "source", "queue", 100, 0),
+            ("drain", "queue", "worker", 5, 0),
+            ("emit", "worker", "sink", 100, 0),
+        ]);
+        let cut = min_cut(&projection, "source", "sink", capacity).unwrap();
+
+        assert_eq!(cut.value, 5);
+        assert_eq!(cut.cut_edges, vec!["drain"]);
+        assert_eq!(cut.source_side, vec!["queue", "source"]);
+    }
+
+    // ========== Min Cost Flow Tests ==========
+
+    #[test]
+    fn test_min_cost_flow_prefers_cheap_routes() {
+        let projection = create_network(&[
+            ("s-a", "s", "a", 4, 1),
+            ("s-b", "s", "b", 2, 4),
+            ("a-b", "a", "b", 2, 2),
+            ("a-t", "a", "t", 2, 6),
+            ("b-t", "b", "t", 5, 1),
+        ]);
+
+        let flow = min_cost_flow(&projection, "s", "t", None, capacity, cost).unwrap();
+        assert_eq!(flow.value, 6);
+        assert_eq!(flow.cost, 32);
+        assert_eq!(flow.flow_on("a-t"), 2);
+
+        // Two units fit on the cheapest route s-a-b-t
+        let flow = min_cost_flow(&projection, "s", "t", Some(2), capacity, cost).unwrap();
+        assert_eq!(flow.value, 2);
+        assert_eq!(flow.cost, 8);
+        assert_eq!(flow.flow_on("a-b"), 2);
+    }
+
+    #[test]
+    fn test_min_cost_flow_matches_max_flow_value() {
+        let projection = create_textbook_network();
+        let flow = min_cost_flow(&projection, "s", "t", None, capacity, |_| 1).unwrap();
+        assert_eq!(flow.value, 23);
+
+        let flow = min_cost_flow(&projection, "s", "t", Some(100), capacity, |_| 1).unwrap();
+        assert_eq!(flow.value, 23);
+    }
+}
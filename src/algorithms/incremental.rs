@@ -0,0 +1,1031 @@
+//! Algorithm results maintained incrementally as events arrive
+//!
+//! Each maintainer is an [`EventHandler`] for one aggregate: it follows the
+//! same `EventData` stream a projection is built from and updates its result
+//! per event instead of recomputing over the whole graph. Additions are
+//! handled in time proportional to the part of the result they change;
+//! removals that may split a result fall back to a rebuild.
+//!
+//! Maintainers only see node and edge IDs, so every `NodeAdded` counts as a
+//! node and an edge counts once both of its endpoints exist when it is added.
+
+use super::adjacency::IndexedAdjacency;
+use crate::core::cim_graph::{EventData, EventHandler, GraphEvent};
+use crate::core::{Edge, GraphProjection, Node};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+/// Connected components (ignoring edge direction), kept with union-find
+#[derive(Debug, Clone)]
+pub struct IncrementalComponents {
+    graph: Topology,
+    /// Node IDs by union-find index
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    count: usize,
+}
+
+impl IncrementalComponents {
+    /// Start from an empty graph for `aggregate_id`
+    pub fn new(aggregate_id: Uuid) -> Self {
+        Self::from_topology(Topology::new(aggregate_id))
+    }
+
+    /// Start from the current state of a projection
+    pub fn from_projection<P>(projection: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+    {
+        Self::from_topology(Topology::from_projection(projection))
+    }
+
+    /// Sequence number of the last event applied
+    pub fn version(&self) -> u64 {
+        self.graph.version
+    }
+
+    /// Number of connected components
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+
+    /// Whether two nodes are in the same component
+    pub fn connected(&self, a: &str, b: &str) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.root(a) == self.root(b),
+            _ => false,
+        }
+    }
+
+    /// Every component with its node IDs sorted, components sorted by their first ID
+    pub fn components(&self) -> Vec<Vec<String>> {
+        let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (node, name) in self.names.iter().enumerate() {
+            groups.entry(self.root(node)).or_default().push(name.clone());
+        }
+        let mut components: Vec<Vec<String>> = groups.into_values().collect();
+        for component in components.iter_mut() {
+            component.sort();
+        }
+        components.sort();
+        components
+    }
+
+    fn from_topology(graph: Topology) -> Self {
+        let mut components = Self {
+            graph,
+            names: Vec::new(),
+            index: HashMap::new(),
+            parent: Vec::new(),
+            rank: Vec::new(),
+            count: 0,
+        };
+        components.rebuild();
+        components
+    }
+
+    fn rebuild(&mut self) {
+        let graph = self.graph.indexed();
+        self.index = graph.ids.iter().cloned().zip(0..).collect();
+        self.parent = (0..graph.len()).collect();
+        self.rank = vec![0; graph.len()];
+        self.count = graph.len();
+        for (source, targets) in graph.successors.iter().enumerate() {
+            for &target in targets {
+                self.union(source, target);
+            }
+        }
+        self.names = graph.ids;
+    }
+
+    fn add_node(&mut self, id: String) {
+        self.index.insert(id.clone(), self.names.len());
+        self.parent.push(self.names.len());
+        self.rank.push(0);
+        self.names.push(id);
+        self.count += 1;
+    }
+
+    fn root(&self, mut node: usize) -> usize {
+        while self.parent[node] != node {
+            node = self.parent[node];
+        }
+        node
+    }
+
+    fn find(&mut self, node: usize) -> usize {
+        let root = self.root(node);
+        let mut current = node;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        let (low, high) = if self.rank[a] < self.rank[b] { (a, b) } else { (b, a) };
+        self.parent[low] = high;
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+        self.count -= 1;
+    }
+}
+
+impl EventHandler for IncrementalComponents {
+    fn handle(&mut self, event: &GraphEvent) {
+        let mut stale = false;
+        for change in self.graph.apply(event) {
+            match change {
+                Change::NodeAdded(id) => self.add_node(id),
+                Change::EdgeAdded(source, target) => {
+                    let (source, target) = (self.index[&source], self.index[&target]);
+                    self.union(source, target);
+                }
+                // Still joined by a parallel or reverse edge
+                Change::EdgeRemoved(source, target) if self.graph.adjacent(&source, &target) => {}
+                Change::EdgeRemoved(..) | Change::NodeRemoved(_) => stale = true,
+            }
+        }
+        if stale {
+            self.rebuild();
+        }
+    }
+}
+
+/// Degree centrality, matching [`centrality`](super::metrics::centrality)
+#[derive(Debug, Clone)]
+pub struct IncrementalDegree {
+    graph: Topology,
+    out_degree: HashMap<String, usize>,
+    in_degree: HashMap<String, usize>,
+    /// Number of nodes with each out-degree, for the maximum
+    histogram: BTreeMap<usize, usize>,
+}
+
+impl IncrementalDegree {
+    /// Start from an empty graph for `aggregate_id`
+    pub fn new(aggregate_id: Uuid) -> Self {
+        Self::from_topology(Topology::new(aggregate_id))
+    }
+
+    /// Start from the current state of a projection
+    pub fn from_projection<P>(projection: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+    {
+        Self::from_topology(Topology::from_projection(projection))
+    }
+
+    /// Sequence number of the last event applied
+    pub fn version(&self) -> u64 {
+        self.graph.version
+    }
+
+    /// Number of edges leaving a node, counting parallel edges
+    pub fn out_degree(&self, node: &str) -> Option<usize> {
+        self.out_degree.get(node).copied()
+    }
+
+    /// Number of edges entering a node, counting parallel edges
+    pub fn in_degree(&self, node: &str) -> Option<usize> {
+        self.in_degree.get(node).copied()
+    }
+
+    /// Largest out-degree of any node
+    pub fn max_out_degree(&self) -> usize {
+        self.histogram.keys().next_back().copied().unwrap_or(0)
+    }
+
+    /// Out-degree of a node normalized by the largest out-degree
+    pub fn centrality(&self, node: &str) -> Option<f64> {
+        let degree = self.out_degree(node)?;
+        Some(match self.max_out_degree() {
+            0 => 0.0,
+            max => degree as f64 / max as f64,
+        })
+    }
+
+    /// Centrality of every node
+    pub fn centralities(&self) -> HashMap<String, f64> {
+        let max = self.max_out_degree().max(1) as f64;
+        self.out_degree
+            .iter()
+            .map(|(node, &degree)| (node.clone(), degree as f64 / max))
+            .collect()
+    }
+
+    fn from_topology(graph: Topology) -> Self {
+        let mut degree = Self {
+            graph,
+            out_degree: HashMap::new(),
+            in_degree: HashMap::new(),
+            histogram: BTreeMap::new(),
+        };
+        let nodes: Vec<String> = degree.graph.nodes.iter().cloned().collect();
+        for node in nodes {
+            degree.add_node(node);
+        }
+        let edges: Vec<(String, String)> = degree.graph.edges.values().cloned().collect();
+        for (source, target) in edges {
+            degree.shift(&source, &target, true);
+        }
+        degree
+    }
+
+    fn add_node(&mut self, id: String) {
+        self.out_degree.insert(id.clone(), 0);
+        self.in_degree.insert(id, 0);
+        *self.histogram.entry(0).or_default() += 1;
+    }
+
+    fn remove_node(&mut self, id: &str) {
+        self.in_degree.remove(id);
+        if let Some(degree) = self.out_degree.remove(id) {
+            self.forget(degree);
+        }
+    }
+
+    /// Count an edge in or out of both endpoints' degrees
+    fn shift(&mut self, source: &str, target: &str, added: bool) {
+        if let Some(degree) = self.out_degree.get_mut(source) {
+            let old = *degree;
+            *degree = if added { old + 1 } else { old - 1 };
+            let new = *degree;
+            self.forget(old);
+            *self.histogram.entry(new).or_default() += 1;
+        }
+        if let Some(degree) = self.in_degree.get_mut(target) {
+            *degree = if added { *degree + 1 } else { *degree - 1 };
+        }
+    }
+
+    fn forget(&mut self, degree: usize) {
+        if let Some(count) = self.histogram.get_mut(&degree) {
+            *count -= 1;
+            if *count == 0 {
+                self.histogram.remove(&degree);
+            }
+        }
+    }
+}
+
+impl EventHandler for IncrementalDegree {
+    fn handle(&mut self, event: &GraphEvent) {
+        for change in self.graph.apply(event) {
+            match change {
+                Change::NodeAdded(id) => self.add_node(id),
+                Change::NodeRemoved(id) => self.remove_node(&id),
+                Change::EdgeAdded(source, target) => self.shift(&source, &target, true),
+                Change::EdgeRemoved(source, target) => self.shift(&source, &target, false),
+            }
+        }
+    }
+}
+
+/// Nodes reachable from a root over directed edges
+#[derive(Debug, Clone)]
+pub struct IncrementalReachability {
+    graph: Topology,
+    root: String,
+    reachable: HashSet<String>,
+}
+
+impl IncrementalReachability {
+    /// Start from an empty graph for `aggregate_id`
+    pub fn new(aggregate_id: Uuid, root: impl Into<String>) -> Self {
+        Self::from_topology(Topology::new(aggregate_id), root.into())
+    }
+
+    /// Start from the current state of a projection
+    pub fn from_projection<P>(projection: &P, root: impl Into<String>) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+    {
+        Self::from_topology(Topology::from_projection(projection), root.into())
+    }
+
+    /// Sequence number of the last event applied
+    pub fn version(&self) -> u64 {
+        self.graph.version
+    }
+
+    /// The node reachability is measured from
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Whether a node can be reached from the root; the root reaches itself
+    /// once it exists
+    pub fn is_reachable(&self, node: &str) -> bool {
+        self.reachable.contains(node)
+    }
+
+    /// Number of reachable nodes, including the root
+    pub fn len(&self) -> usize {
+        self.reachable.len()
+    }
+
+    /// Whether nothing is reachable, i.e. the root does not exist
+    pub fn is_empty(&self) -> bool {
+        self.reachable.is_empty()
+    }
+
+    /// Reachable node IDs, sorted
+    pub fn reachable(&self) -> Vec<&str> {
+        let mut nodes: Vec<&str> = self.reachable.iter().map(String::as_str).collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    fn from_topology(graph: Topology, root: String) -> Self {
+        let mut reachability = Self {
+            graph,
+            root,
+            reachable: HashSet::new(),
+        };
+        reachability.rebuild();
+        reachability
+    }
+
+    fn rebuild(&mut self) {
+        self.reachable.clear();
+        if self.graph.nodes.contains(&self.root) {
+            self.extend_from(self.root.clone());
+        }
+    }
+
+    /// Mark `start` and everything newly reachable from it
+    fn extend_from(&mut self, start: String) {
+        let mut queue = VecDeque::new();
+        if self.reachable.insert(start.clone()) {
+            queue.push_back(start);
+        }
+        while let Some(node) = queue.pop_front() {
+            for next in self.graph.successors(&node) {
+                if self.reachable.insert(next.to_string()) {
+                    queue.push_back(next.to_string());
+                }
+            }
+        }
+    }
+}
+
+impl EventHandler for IncrementalReachability {
+    fn handle(&mut self, event: &GraphEvent) {
+        let mut stale = false;
+        for change in self.graph.apply(event) {
+            match change {
+                Change::NodeAdded(id) if id == self.root => self.extend_from(id),
+                Change::NodeAdded(_) => {}
+                Change::EdgeAdded(source, target) => {
+                    if self.reachable.contains(&source) && !self.reachable.contains(&target) {
+                        self.extend_from(target);
+                    }
+                }
+                Change::EdgeRemoved(source, target) => {
+                    stale |= self.reachable.contains(&source) && !self.graph.adjacent_to(&source, &target);
+                }
+                Change::NodeRemoved(id) => stale |= self.reachable.contains(&id),
+            }
+        }
+        if stale {
+            self.rebuild();
+        }
+    }
+}
+
+/// Topological order kept with the Pearce-Kelly algorithm
+///
+/// An edge that closes a cycle is still recorded; the order is unavailable
+/// until removals make the graph acyclic again.
+#[derive(Debug, Clone)]
+pub struct IncrementalTopologicalOrder {
+    graph: Topology,
+    /// Position of every node; only relative order is meaningful
+    position: HashMap<String, usize>,
+    next_position: usize,
+    acyclic: bool,
+}
+
+impl IncrementalTopologicalOrder {
+    /// Start from an empty graph for `aggregate_id`
+    pub fn new(aggregate_id: Uuid) -> Self {
+        Self::from_topology(Topology::new(aggregate_id))
+    }
+
+    /// Start from the current state of a projection
+    pub fn from_projection<P>(projection: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+    {
+        Self::from_topology(Topology::from_projection(projection))
+    }
+
+    /// Sequence number of the last event applied
+    pub fn version(&self) -> u64 {
+        self.graph.version
+    }
+
+    /// Whether the graph currently has no cycle
+    pub fn is_acyclic(&self) -> bool {
+        self.acyclic
+    }
+
+    /// Node IDs in topological order, or `None` while the graph has a cycle
+    pub fn order(&self) -> Option<Vec<&str>> {
+        if !self.acyclic {
+            return None;
+        }
+        let mut nodes: Vec<(&str, usize)> = self.position.iter().map(|(id, &at)| (id.as_str(), at)).collect();
+        nodes.sort_unstable_by_key(|&(_, at)| at);
+        Some(nodes.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// Whether `a` comes before `b`, or `None` while the graph has a cycle
+    /// or either node is missing
+    pub fn precedes(&self, a: &str, b: &str) -> Option<bool> {
+        if !self.acyclic {
+            return None;
+        }
+        Some(self.position.get(a)? < self.position.get(b)?)
+    }
+
+    fn from_topology(graph: Topology) -> Self {
+        let mut order = Self {
+            graph,
+            position: HashMap::new(),
+            next_position: 0,
+            acyclic: true,
+        };
+        order.rebuild();
+        order
+    }
+
+    /// Kahn's algorithm over the whole graph, smallest ID first
+    fn rebuild(&mut self) {
+        let graph = self.graph.indexed();
+        let mut in_degree = vec![0usize; graph.len()];
+        for &next in graph.successors.iter().flatten() {
+            in_degree[next] += 1;
+        }
+        // Positions follow sorted IDs, so the smallest ready ID comes first
+        let mut ready: BTreeSet<usize> = (0..graph.len()).filter(|&node| in_degree[node] == 0).collect();
+
+        let mut position = HashMap::new();
+        while let Some(node) = ready.pop_first() {
+            position.insert(graph.ids[node].clone(), position.len());
+            for &next in &graph.successors[node] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        self.acyclic = position.len() == self.graph.nodes.len();
+        self.next_position = position.len();
+        self.position = position;
+    }
+
+    fn add_edge(&mut self, source: &str, target: &str) {
+        let (lower, upper) = (self.position[target], self.position[source]);
+        if lower > upper {
+            return;
+        }
+        if lower == upper {
+            self.acyclic = false;
+            return;
+        }
+
+        // Nodes after `target` but not after `source` that must move behind `source`
+        let Some(forward) = self.affected(target, upper, true) else {
+            self.acyclic = false;
+            return;
+        };
+        // Nodes before `source` but not before `target` that must move ahead of `target`
+        let backward = self.affected(source, lower, false).unwrap_or_default();
+
+        // Reuse the same positions: the backward set first, then the forward set
+        let (mut moved, mut forward) = (backward, forward);
+        moved.sort_unstable_by_key(|id| self.position[id]);
+        forward.sort_unstable_by_key(|id| self.position[id]);
+        moved.append(&mut forward);
+        let mut slots: Vec<usize> = moved.iter().map(|id| self.position[id]).collect();
+        slots.sort_unstable();
+        for (id, slot) in moved.into_iter().zip(slots) {
+            self.position.insert(id, slot);
+        }
+    }
+
+    /// Nodes reachable from `start` (forwards or backwards) whose positions
+    /// lie strictly within `bound`; `None` if the search hits `bound` itself,
+    /// which means the new edge closes a cycle
+    fn affected(&self, start: &str, bound: usize, forwards: bool) -> Option<Vec<String>> {
+        let mut seen: HashSet<&str> = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            let links = if forwards { &self.graph.successors } else { &self.graph.predecessors };
+            for next in links.get(node).into_iter().flat_map(|neighbors| neighbors.keys()) {
+                let next = next.as_str();
+                let at = self.position[next];
+                if at == bound {
+                    return None;
+                }
+                let inside = if forwards { at < bound } else { at > bound };
+                if inside && seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        Some(seen.into_iter().map(str::to_string).collect())
+    }
+}
+
+impl EventHandler for IncrementalTopologicalOrder {
+    fn handle(&mut self, event: &GraphEvent) {
+        let mut removed = false;
+        for change in self.graph.apply(event) {
+            match change {
+                Change::NodeAdded(id) => {
+                    self.position.insert(id, self.next_position);
+                    self.next_position += 1;
+                }
+                Change::NodeRemoved(id) => {
+                    self.position.remove(&id);
+                    removed = true;
+                }
+                Change::EdgeAdded(source, target) if self.acyclic => self.add_edge(&source, &target),
+                Change::EdgeAdded(..) => {}
+                // Removing edges never invalidates an order
+                Change::EdgeRemoved(..) => removed = true,
+            }
+        }
+        // A removal may have broken the last cycle
+        if removed && !self.acyclic {
+            self.rebuild();
+        }
+    }
+}
+
+/// Structural effect of one event
+#[derive(Debug, Clone)]
+enum Change {
+    NodeAdded(String),
+    NodeRemoved(String),
+    EdgeAdded(String, String),
+    EdgeRemoved(String, String),
+}
+
+/// The nodes and edges of one aggregate, as far as maintainers need them
+#[derive(Debug, Clone)]
+struct Topology {
+    aggregate_id: Uuid,
+    version: u64,
+    nodes: HashSet<String>,
+    /// Source and target of every edge, by edge ID
+    edges: HashMap<String, (String, String)>,
+    /// IDs of the edges touching each node
+    incident: HashMap<String, HashSet<String>>,
+    /// Number of parallel edges to each successor and from each predecessor
+    successors: HashMap<String, HashMap<String, usize>>,
+    predecessors: HashMap<String, HashMap<String, usize>>,
+}
+
+impl Topology {
+    fn new(aggregate_id: Uuid) -> Self {
+        Self {
+            aggregate_id,
+            version: 0,
+            nodes: HashSet::new(),
+            edges: HashMap::new(),
+            incident: HashMap::new(),
+            successors: HashMap::new(),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    fn from_projection<P>(projection: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        P::Edge: Edge,
+    {
+        let mut topology = Self::new(projection.aggregate_id());
+        topology.version = projection.version();
+        for node in projection.nodes() {
+            topology.add_node(node.id());
+        }
+        for edge in projection.edges() {
+            topology.add_edge(edge.id(), edge.source(), edge.target());
+        }
+        topology
+    }
+
+    /// Position-indexed snapshot, for rebuilding a maintainer from scratch
+    fn indexed(&self) -> IndexedAdjacency {
+        IndexedAdjacency::from_pairs(
+            self.nodes.iter().cloned(),
+            self.edges.values().map(|(source, target)| (source.as_str(), target.as_str())),
+        )
+    }
+
+    /// Apply an event, returning what it changed; removing a node first
+    /// removes its edges
+    fn apply(&mut self, event: &GraphEvent) -> Vec<Change> {
+        if event.aggregate_id != self.aggregate_id {
+            return Vec::new();
+        }
+        self.version = event.sequence;
+
+        let mut changes = Vec::new();
+        match &event.data {
+            EventData::NodeAdded { node_id, .. } => {
+                if self.add_node(node_id.clone()) {
+                    changes.push(Change::NodeAdded(node_id.clone()));
+                }
+            }
+            EventData::EdgeAdded {
+                edge_id,
+                source_id,
+                target_id,
+                ..
+            } => {
+                // Re-adding an edge ID replaces the edge
+                if let Some((source, target)) = self.remove_edge(edge_id) {
+                    changes.push(Change::EdgeRemoved(source, target));
+                }
+                if self.add_edge(edge_id.clone(), source_id.clone(), target_id.clone()) {
+                    changes.push(Change::EdgeAdded(source_id.clone(), target_id.clone()));
+                }
+            }
+            EventData::EdgeRemoved { edge_id } => {
+                if let Some((source, target)) = self.remove_edge(edge_id) {
+                    changes.push(Change::EdgeRemoved(source, target));
+                }
+            }
+            EventData::NodeRemoved { node_id } => {
+                if self.nodes.contains(node_id) {
+                    let mut edge_ids: Vec<String> =
+                        self.incident.remove(node_id).unwrap_or_default().into_iter().collect();
+                    edge_ids.sort();
+                    for edge_id in edge_ids {
+                        if let Some((source, target)) = self.remove_edge(&edge_id) {
+                            changes.push(Change::EdgeRemoved(source, target));
+                        }
+                    }
+                    self.nodes.remove(node_id);
+                    self.successors.remove(node_id);
+                    self.predecessors.remove(node_id);
+                    changes.push(Change::NodeRemoved(node_id.clone()));
+                }
+            }
+            EventData::GraphInitialized { .. } | EventData::NodeUpdated { .. } | EventData::EdgeUpdated { .. } => {}
+        }
+        changes
+    }
+
+    fn add_node(&mut self, id: String) -> bool {
+        self.nodes.insert(id)
+    }
+
+    fn add_edge(&mut self, id: String, source: String, target: String) -> bool {
+        if !self.nodes.contains(&source) || !self.nodes.contains(&target) {
+            return false;
+        }
+        self.incident.entry(source.clone()).or_default().insert(id.clone());
+        self.incident.entry(target.clone()).or_default().insert(id.clone());
+        *self.successors.entry(source.clone()).or_default().entry(target.clone()).or_default() += 1;
+        *self.predecessors.entry(target.clone()).or_default().entry(source.clone()).or_default() += 1;
+        self.edges.insert(id, (source, target));
+        true
+    }
+
+    fn remove_edge(&mut self, id: &str) -> Option<(String, String)> {
+        let (source, target) = self.edges.remove(id)?;
+        for end in [&source, &target] {
+            if let Some(edges) = self.incident.get_mut(end) {
+                edges.remove(id);
+            }
+        }
+        Self::decrement(&mut self.successors, &source, &target);
+        Self::decrement(&mut self.predecessors, &target, &source);
+        Some((source, target))
+    }
+
+    fn decrement(counts: &mut HashMap<String, HashMap<String, usize>>, from: &str, to: &str) {
+        if let Some(neighbors) = counts.get_mut(from) {
+            if let Some(count) = neighbors.get_mut(to) {
+                *count -= 1;
+                if *count == 0 {
+                    neighbors.remove(to);
+                }
+            }
+        }
+    }
+
+    fn successors(&self, node: &str) -> impl Iterator<Item = &str> {
+        self.successors.get(node).into_iter().flat_map(|targets| targets.keys().map(String::as_str))
+    }
+
+    /// Whether an edge still leads from `source` to `target`
+    fn adjacent_to(&self, source: &str, target: &str) -> bool {
+        self.successors.get(source).is_some_and(|targets| targets.contains_key(target))
+    }
+
+    /// Whether an edge still joins two nodes in either direction
+    fn adjacent(&self, a: &str, b: &str) -> bool {
+        self.adjacent_to(a, b) || self.adjacent_to(b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::projection_engine::{GenericGraphProjection, ProjectionEngine};
+    use crate::graphs::workflow::{WorkflowEdge, WorkflowNode};
+    use chrono::Utc;
+
+    type TestProjection = GenericGraphProjection<WorkflowNode, WorkflowEdge>;
+
+    /// Events for one aggregate with increasing sequence numbers
+    struct Stream {
+        aggregate_id: Uuid,
+        sequence: u64,
+    }
+
+    impl Stream {
+        fn new() -> Self {
+            Self {
+                aggregate_id: Uuid::new_v4(),
+                sequence: 0,
+            }
+        }
+
+        fn event(&mut self, data: EventData) -> GraphEvent {
+            self.sequence += 1;
+            GraphEvent {
+                event_id: Uuid::new_v4(),
+                aggregate_id: self.aggregate_id,
+                sequence: self.sequence,
+                subject: format!("cim.graph.evt.{}", self.aggregate_id),
+                timestamp: Utc::now(),
+                correlation_id: Uuid::new_v4(),
+                causation_id: None,
+                data,
+            }
+        }
+
+        fn node(&mut self, id: &str) -> GraphEvent {
+            self.event(EventData::NodeAdded {
+                node_id: id.to_string(),
+                node_type: "state".to_string(),
+                data: serde_json::json!({ "name": id }),
+            })
+        }
+
+        fn edge(&mut self, id: &str, source: &str, target: &str) -> GraphEvent {
+            self.event(EventData::EdgeAdded {
+                edge_id: id.to_string(),
+                source_id: source.to_string(),
+                target_id: target.to_string(),
+                edge_type: "transition".to_string(),
+                data: serde_json::json!({}),
+            })
+        }
+
+        fn remove_edge(&mut self, id: &str) -> GraphEvent {
+            self.event(EventData::EdgeRemoved { edge_id: id.to_string() })
+        }
+
+        fn remove_node(&mut self, id: &str) -> GraphEvent {
+            self.event(EventData::NodeRemoved { node_id: id.to_string() })
+        }
+    }
+
+    fn feed(handler: &mut impl EventHandler, events: &[GraphEvent]) {
+        for event in events {
+            handler.handle(event);
+        }
+    }
+
+    // ========== Components Tests ==========
+
+    #[test]
+    fn test_incremental_components() {
+        let mut stream = Stream::new();
+        let mut components = IncrementalComponents::new(stream.aggregate_id);
+        let events = [
+            stream.node("a"),
+            stream.node("b"),
+            stream.node("c"),
+            stream.node("d"),
+            stream.edge("e1", "a", "b"),
+            stream.edge("e2", "c", "b"),
+        ];
+        feed(&mut components, &events);
+
+        assert_eq!(components.component_count(), 2);
+        assert!(components.connected("a", "c"));
+        assert!(!components.connected("a", "d"));
+        assert_eq!(components.components(), vec![vec!["a", "b", "c"], vec!["d"]]);
+        assert_eq!(components.version(), 6);
+
+        // A reverse edge keeps a and b joined when e1 goes
+        let events = [stream.edge("e3", "b", "a"), stream.remove_edge("e1")];
+        feed(&mut components, &events);
+        assert_eq!(components.component_count(), 2);
+
+        let events = [stream.remove_node("b")];
+        feed(&mut components, &events);
+        assert_eq!(components.components(), vec![vec!["a"], vec!["c"], vec!["d"]]);
+    }
+
+    #[test]
+    fn test_incremental_components_ignores_other_aggregates() {
+        let mut stream = Stream::new();
+        let mut other = Stream::new();
+        let mut components = IncrementalComponents::new(stream.aggregate_id);
+
+        feed(&mut components, &[stream.node("a"), other.node("b")]);
+        assert_eq!(components.component_count(), 1);
+        assert_eq!(components.version(), 1);
+    }
+
+    // ========== Degree Tests ==========
+
+    #[test]
+    fn test_incremental_degree_matches_centrality() {
+        let mut stream = Stream::new();
+        let events = vec![
+            stream.node("a"),
+            stream.node("b"),
+            stream.node("c"),
+            stream.edge("e1", "a", "b"),
+            stream.edge("e2", "a", "c"),
+            stream.edge("e3", "b", "c"),
+            stream.edge("e4", "a", "b"),
+            stream.remove_edge("e2"),
+        ];
+        let mut degree = IncrementalDegree::new(stream.aggregate_id);
+        feed(&mut degree, &events);
+
+        let projection = ProjectionEngine::<TestProjection>::new().project(events);
+        let expected = crate::algorithms::centrality(&projection).unwrap();
+
+        assert_eq!(degree.centralities(), expected);
+        assert_eq!(degree.out_degree("a"), Some(2));
+        assert_eq!(degree.in_degree("b"), Some(2));
+        assert_eq!(degree.max_out_degree(), 2);
+        assert_eq!(degree.centrality("b"), Some(0.5));
+
+        feed(&mut degree, &[stream.remove_node("a")]);
+        assert_eq!(degree.out_degree("a"), None);
+        assert_eq!(degree.in_degree("b"), Some(0));
+        assert_eq!(degree.max_out_degree(), 1);
+    }
+
+    // ========== Reachability Tests ==========
+
+    #[test]
+    fn test_incremental_reachability() {
+        let mut stream = Stream::new();
+        let mut reachability = IncrementalReachability::new(stream.aggregate_id, "a");
+        assert!(reachability.is_empty());
+
+        let events = [
+            stream.node("a"),
+            stream.node("b"),
+            stream.node("c"),
+            stream.node("d"),
+            stream.edge("e1", "b", "c"),
+            stream.edge("e2", "a", "b"),
+        ];
+        feed(&mut reachability, &events);
+        assert_eq!(reachability.reachable(), vec!["a", "b", "c"]);
+        assert!(!reachability.is_reachable("d"));
+
+        feed(&mut reachability, &[stream.edge("e3", "d", "a")]);
+        assert!(!reachability.is_reachable("d"));
+
+        feed(&mut reachability, &[stream.remove_edge("e2")]);
+        assert_eq!(reachability.reachable(), vec!["a"]);
+
+        feed(&mut reachability, &[stream.edge("e4", "a", "c"), stream.remove_node("a")]);
+        assert!(reachability.is_empty());
+    }
+
+    #[test]
+    fn test_incremental_reachability_from_projection() {
+        let mut stream = Stream::new();
+        let events = vec![stream.node("a"), stream.node("b"), stream.edge("e1", "a", "b")];
+        let projection = ProjectionEngine::<TestProjection>::new().project(events);
+
+        let mut reachability = IncrementalReachability::from_projection(&projection, "a");
+        assert_eq!(reachability.reachable(), vec!["a", "b"]);
+        assert_eq!(reachability.version(), 3);
+
+        feed(&mut reachability, &[stream.node("c"), stream.edge("e2", "b", "c")]);
+        assert_eq!(reachability.len(), 3);
+    }
+
+    // ========== Topological Order Tests ==========
+
+    fn assert_respects_edges(order: &IncrementalTopologicalOrder, edges: &[(&str, &str)]) {
+        for &(source, target) in edges {
+            assert_eq!(order.precedes(source, target), Some(true), "{} before {}", source, target);
+        }
+    }
+
+    #[test]
+    fn test_incremental_topological_order_reorders() {
+        let mut stream = Stream::new();
+        let mut order = IncrementalTopologicalOrder::new(stream.aggregate_id);
+        let events = [
+            stream.node("a"),
+            stream.node("b"),
+            stream.node("c"),
+            stream.node("d"),
+            stream.edge("e1", "a", "b"),
+            // Both go against the insertion order
+            stream.edge("e2", "d", "a"),
+            stream.edge("e3", "c", "d"),
+        ];
+        feed(&mut order, &events);
+
+        assert!(order.is_acyclic());
+        assert_eq!(order.order(), Some(vec!["c", "d", "a", "b"]));
+        assert_respects_edges(&order, &[("a", "b"), ("d", "a"), ("c", "d")]);
+    }
+
+    #[test]
+    fn test_incremental_topological_order_cycles() {
+        let mut stream = Stream::new();
+        let mut order = IncrementalTopologicalOrder::new(stream.aggregate_id);
+        let events = [
+            stream.node("a"),
+            stream.node("b"),
+            stream.node("c"),
+            stream.edge("e1", "a", "b"),
+            stream.edge("e2", "b", "c"),
+            stream.edge("e3", "c", "a"),
+        ];
+        feed(&mut order, &events);
+
+        assert!(!order.is_acyclic());
+        assert_eq!(order.order(), None);
+        assert_eq!(order.precedes("a", "b"), None);
+
+        feed(&mut order, &[stream.remove_edge("e1")]);
+        assert!(order.is_acyclic());
+        assert_respects_edges(&order, &[("b", "c"), ("c", "a")]);
+
+        feed(&mut order, &[stream.edge("e4", "a", "a")]);
+        assert!(!order.is_acyclic());
+        feed(&mut order, &[stream.remove_node("a")]);
+        assert_eq!(order.order(), Some(vec!["b", "c"]));
+    }
+
+    #[test]
+    fn test_incremental_topological_order_matches_random_dag() {
+        // Edges always run from a smaller to a larger number, added in a scrambled order
+        let mut stream = Stream::new();
+        let mut order = IncrementalTopologicalOrder::new(stream.aggregate_id);
+        let count = 30;
+        let mut events: Vec<GraphEvent> = (0..count).rev().map(|i| stream.node(&format!("n{}", i))).collect();
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for i in 0..count {
+            for j in (i + 1)..count {
+                if (i * 7 + j * 13) % 5 == 0 {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs.sort_by_key(|&(i, j)| (i * 17 + j * 31) % 23);
+        let edges: Vec<(String, String)> = pairs.iter().map(|(i, j)| (format!("n{}", i), format!("n{}", j))).collect();
+        for (index, (source, target)) in edges.iter().enumerate() {
+            events.push(stream.edge(&format!("e{}", index), source, target));
+        }
+        feed(&mut order, &events);
+
+        assert!(order.is_acyclic());
+        let edge_refs: Vec<(&str, &str)> = edges.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+        assert_respects_edges(&order, &edge_refs);
+    }
+}
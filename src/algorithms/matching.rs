@@ -0,0 +1,552 @@
+//! Subgraph pattern matching for projections
+//!
+//! A [`Pattern`] is a small directed graph whose nodes and edges carry
+//! predicates over the projection's node and edge types. [`find_embeddings`]
+//! returns every injective mapping of pattern nodes onto projection nodes
+//! such that each node satisfies its predicate and each pattern edge is
+//! matched by at least one projection edge satisfying its predicate. Extra
+//! projection edges between matched nodes are allowed.
+//!
+//! ```rust,ignore
+//! use cim_graph::algorithms::{find_embeddings, Pattern};
+//!
+//! // Every aggregate with two entities that reference one another
+//! let pattern = Pattern::new()
+//!     .node("aggregate", |n: &ContextNode| matches!(n.node_type, ContextNodeType::Aggregate))
+//!     .node("left", |n: &ContextNode| matches!(n.node_type, ContextNodeType::Entity))
+//!     .node("right", |n: &ContextNode| matches!(n.node_type, ContextNodeType::Entity))
+//!     .edge("aggregate", "left", |_| true)
+//!     .edge("aggregate", "right", |_| true)
+//!     .edge("left", "right", |e: &ContextEdge| e.relationship == "references");
+//!
+//! for embedding in find_embeddings(&projection, &pattern)? {
+//!     println!("{} -> {}", embedding["left"], embedding["right"]);
+//! }
+//! ```
+
+use super::adjacency::IndexedAdjacency;
+use crate::core::{Edge, GraphProjection, Node};
+use crate::error::{GraphError, Result};
+use std::collections::{HashMap, HashSet};
+
+/// Mapping from pattern node names to projection node IDs
+pub type Embedding = HashMap<String, String>;
+
+type NodePredicate<N> = Box<dyn Fn(&N) -> bool + Send + Sync>;
+type EdgePredicate<E> = Box<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// A pattern graph with node and edge predicates
+pub struct Pattern<N, E> {
+    nodes: Vec<(String, NodePredicate<N>)>,
+    edges: Vec<(String, String, EdgePredicate<E>)>,
+}
+
+impl<N, E> Default for Pattern<N, E> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N, E> std::fmt::Debug for Pattern<N, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pattern")
+            .field("nodes", &self.nodes.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .field("edges", &self.edges.iter().map(|(from, to, _)| (from, to)).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<N, E> Pattern<N, E> {
+    /// Create an empty pattern
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a pattern node matching projection nodes that satisfy `predicate`
+    pub fn node<F>(mut self, name: impl Into<String>, predicate: F) -> Self
+    where
+        F: Fn(&N) -> bool + Send + Sync + 'static,
+    {
+        self.nodes.push((name.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Add a directed pattern edge matching projection edges that satisfy `predicate`
+    pub fn edge<F>(mut self, from: impl Into<String>, to: impl Into<String>, predicate: F) -> Self
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.edges.push((from.into(), to.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Number of pattern nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of pattern edges
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Pattern edges as index pairs, failing on unknown or repeated names
+    fn resolve(&self) -> Result<Vec<(usize, usize)>> {
+        let mut index = HashMap::new();
+        for (i, (name, _)) in self.nodes.iter().enumerate() {
+            if index.insert(name.as_str(), i).is_some() {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Pattern node {} is declared twice",
+                    name
+                )));
+            }
+        }
+
+        self.edges
+            .iter()
+            .map(|(from, to, _)| {
+                let lookup = |name: &String| {
+                    index.get(name.as_str()).copied().ok_or_else(|| {
+                        GraphError::InvalidOperation(format!("Pattern edge refers to undeclared node {}", name))
+                    })
+                };
+                Ok((lookup(from)?, lookup(to)?))
+            })
+            .collect()
+    }
+}
+
+/// Find every embedding of `pattern` in `projection`
+///
+/// Symmetric patterns yield one embedding per symmetry. An empty pattern has
+/// exactly one, empty, embedding. Results are ordered by the projection node
+/// IDs chosen during the search, so they are stable between calls.
+pub fn find_embeddings<P>(projection: &P, pattern: &Pattern<P::Node, P::Edge>) -> Result<Vec<Embedding>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+{
+    Matcher::new(projection, pattern).map(|matcher| matcher.run(usize::MAX))
+}
+
+/// Whether `pattern` occurs anywhere in `projection`, stopping at the first embedding
+pub fn contains_pattern<P>(projection: &P, pattern: &Pattern<P::Node, P::Edge>) -> Result<bool>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+{
+    Matcher::new(projection, pattern).map(|matcher| !matcher.run(1).is_empty())
+}
+
+/// Backtracking state for one pattern against one projection
+struct Matcher<'a, N, E> {
+    pattern: &'a Pattern<N, E>,
+    /// Projection node IDs, sorted
+    ids: Vec<String>,
+    /// Distinct successors and predecessors per projection node
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    /// Projection edges by (source, target)
+    edges: HashMap<(usize, usize), Vec<&'a E>>,
+    /// Pattern edges as index pairs
+    pattern_edges: Vec<(usize, usize)>,
+    /// Candidate projection nodes per pattern node
+    domains: Vec<HashSet<usize>>,
+    /// Pattern nodes in search order
+    order: Vec<usize>,
+}
+
+impl<'a, N, E> Matcher<'a, N, E> {
+    fn new<P>(projection: &'a P, pattern: &'a Pattern<N, E>) -> Result<Self>
+    where
+        P: GraphProjection<Node = N, Edge = E>,
+        N: Node,
+        E: Edge,
+    {
+        let pattern_edges = pattern.resolve()?;
+
+        // Nodes in the same sorted order as the graph's positions
+        let mut nodes = projection.nodes();
+        nodes.sort_by_key(|node| node.id());
+        nodes.dedup_by_key(|node| node.id());
+        let graph = IndexedAdjacency::from_edges(projection, |_| true);
+        let predecessors = graph.predecessors();
+
+        let mut edges: HashMap<(usize, usize), Vec<&E>> = HashMap::new();
+        for edge in projection.edges() {
+            if let Some(pair) = graph.endpoints(edge) {
+                edges.entry(pair).or_default().push(edge);
+            }
+        }
+        let IndexedAdjacency { ids, successors } = graph;
+
+        // A pattern node needs at least as many distinct neighbours as it has in the pattern
+        let pattern_degree = |select: fn(&(usize, usize)) -> (usize, usize), var: usize| {
+            pattern_edges
+                .iter()
+                .map(select)
+                .filter(|&(own, _)| own == var)
+                .map(|(_, other)| other)
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        let domains: Vec<HashSet<usize>> = pattern
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(var, (_, predicate))| {
+                let out_degree = pattern_degree(|&(from, to)| (from, to), var);
+                let in_degree = pattern_degree(|&(from, to)| (to, from), var);
+                (0..ids.len())
+                    .filter(|&node| {
+                        successors[node].len() >= out_degree
+                            && predecessors[node].len() >= in_degree
+                            && predicate(nodes[node])
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let order = search_order(pattern.nodes.len(), &pattern_edges, &domains);
+
+        Ok(Self {
+            pattern,
+            ids,
+            successors,
+            predecessors,
+            edges,
+            pattern_edges,
+            domains,
+            order,
+        })
+    }
+
+    /// Collect up to `limit` embeddings
+    fn run(&self, limit: usize) -> Vec<Embedding> {
+        let mut results = Vec::new();
+        if self.domains.iter().any(HashSet::is_empty) {
+            return results;
+        }
+
+        let mut assignment = vec![usize::MAX; self.pattern.nodes.len()];
+        let mut used = vec![false; self.ids.len()];
+        self.extend(0, &mut assignment, &mut used, &mut results, limit);
+        results
+    }
+
+    fn extend(
+        &self,
+        depth: usize,
+        assignment: &mut [usize],
+        used: &mut [bool],
+        results: &mut Vec<Embedding>,
+        limit: usize,
+    ) {
+        if results.len() >= limit {
+            return;
+        }
+        if depth == self.order.len() {
+            results.push(
+                assignment
+                    .iter()
+                    .enumerate()
+                    .map(|(var, &node)| (self.pattern.nodes[var].0.clone(), self.ids[node].clone()))
+                    .collect(),
+            );
+            return;
+        }
+
+        let var = self.order[depth];
+        for node in self.candidates(var, assignment) {
+            if used[node] || !self.domains[var].contains(&node) {
+                continue;
+            }
+            assignment[var] = node;
+            if self.edges_hold(var, assignment) {
+                used[node] = true;
+                self.extend(depth + 1, assignment, used, results, limit);
+                used[node] = false;
+            }
+            assignment[var] = usize::MAX;
+        }
+    }
+
+    /// Projection nodes worth trying for `var`
+    ///
+    /// When a pattern edge links `var` to an assigned node, only that node's
+    /// neighbours can match; otherwise the whole domain is tried.
+    fn candidates(&self, var: usize, assignment: &[usize]) -> Vec<usize> {
+        for &(from, to) in &self.pattern_edges {
+            if to == var && from != var && assignment[from] != usize::MAX {
+                return self.successors[assignment[from]].clone();
+            }
+            if from == var && to != var && assignment[to] != usize::MAX {
+                return self.predecessors[assignment[to]].clone();
+            }
+        }
+        let mut domain: Vec<usize> = self.domains[var].iter().copied().collect();
+        domain.sort_unstable();
+        domain
+    }
+
+    /// Check the pattern edges between `var` and already assigned nodes
+    fn edges_hold(&self, var: usize, assignment: &[usize]) -> bool {
+        self.pattern_edges
+            .iter()
+            .zip(&self.pattern.edges)
+            .filter(|((from, to), _)| {
+                (*from == var || *to == var) && assignment[*from] != usize::MAX && assignment[*to] != usize::MAX
+            })
+            .all(|(&(from, to), (_, _, predicate))| {
+                self.edges
+                    .get(&(assignment[from], assignment[to]))
+                    .is_some_and(|parallel| parallel.iter().any(|edge| predicate(edge)))
+            })
+    }
+}
+
+/// Order pattern nodes so each one is as constrained as possible when reached
+///
+/// Starts from the node with the smallest domain, then repeatedly takes the
+/// node with the most pattern edges to already ordered nodes.
+fn search_order(count: usize, edges: &[(usize, usize)], domains: &[HashSet<usize>]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(count);
+    let mut placed = vec![false; count];
+    while order.len() < count {
+        let next = (0..count)
+            .filter(|&var| !placed[var])
+            .max_by_key(|&var| {
+                let links = edges
+                    .iter()
+                    .filter(|&&(from, to)| (from == var && placed[to]) || (to == var && placed[from]))
+                    .count();
+                (links, std::cmp::Reverse(domains[var].len()), std::cmp::Reverse(var))
+            })
+            .unwrap_or(0);
+        placed[next] = true;
+        order.push(next);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GraphType;
+    use crate::graphs::context_projection::{ContextEdge, ContextNode, ContextNodeType, ContextProjection};
+    use crate::graphs::workflow::{WorkflowEdge, WorkflowNode, WorkflowNodeType, WorkflowProjection};
+    use uuid::Uuid;
+
+    fn context_node(id: &str, node_type: ContextNodeType) -> ContextNode {
+        ContextNode {
+            id: id.to_string(),
+            node_type,
+            name: id.to_string(),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    fn create_context(nodes: &[(&str, ContextNodeType)], edges: &[(&str, &str, &str)]) -> ContextProjection {
+        let mut projection = ContextProjection::new(Uuid::new_v4(), GraphType::ContextGraph);
+        for (id, node_type) in nodes {
+            projection.nodes.insert(id.to_string(), context_node(id, node_type.clone()));
+            projection.adjacency.insert(id.to_string(), vec![]);
+        }
+        for (from, to, relationship) in edges {
+            let id = format!("{}-{}-{}", from, relationship, to);
+            projection.edges.insert(
+                id.clone(),
+                ContextEdge {
+                    id,
+                    source: from.to_string(),
+                    target: to.to_string(),
+                    relationship: relationship.to_string(),
+                },
+            );
+            projection.adjacency.get_mut(*from).unwrap().push(to.to_string());
+        }
+        projection
+    }
+
+    fn is_aggregate(node: &ContextNode) -> bool {
+        matches!(node.node_type, ContextNodeType::Aggregate)
+    }
+
+    fn is_entity(node: &ContextNode) -> bool {
+        matches!(node.node_type, ContextNodeType::Entity)
+    }
+
+    /// An aggregate containing two entities, the first referencing the second
+    fn aggregate_pattern() -> Pattern<ContextNode, ContextEdge> {
+        Pattern::new()
+            .node("aggregate", is_aggregate)
+            .node("left", is_entity)
+            .node("right", is_entity)
+            .edge("aggregate", "left", |e: &ContextEdge| e.relationship == "contains")
+            .edge("aggregate", "right", |e: &ContextEdge| e.relationship == "contains")
+            .edge("left", "right", |e: &ContextEdge| e.relationship == "references")
+    }
+
+    fn create_orders_context() -> ContextProjection {
+        create_context(
+            &[
+                ("order", ContextNodeType::Aggregate),
+                ("line", ContextNodeType::Entity),
+                ("product", ContextNodeType::Entity),
+                ("customer", ContextNodeType::Entity),
+                ("money", ContextNodeType::ValueObject),
+            ],
+            &[
+                ("order", "line", "contains"),
+                ("order", "product", "contains"),
+                ("order", "money", "contains"),
+                ("line", "product", "references"),
+                ("customer", "order", "places"),
+            ],
+        )
+    }
+
+    // ========== Pattern Matching Tests ==========
+
+    #[test]
+    fn test_find_embeddings_context_pattern() {
+        let projection = create_orders_context();
+        let embeddings = find_embeddings(&projection, &aggregate_pattern()).unwrap();
+
+        assert_eq!(embeddings.len(), 1);
+        assert_eq!(embeddings[0]["aggregate"], "order");
+        assert_eq!(embeddings[0]["left"], "line");
+        assert_eq!(embeddings[0]["right"], "product");
+    }
+
+    #[test]
+    fn test_edge_predicates_filter_matches() {
+        let projection = create_orders_context();
+        let pattern = Pattern::new()
+            .node("aggregate", is_aggregate)
+            .node("left", is_entity)
+            .node("right", is_entity)
+            .edge("left", "right", |e: &ContextEdge| e.relationship == "owns")
+            .edge("aggregate", "left", |_| true);
+
+        assert!(find_embeddings(&projection, &pattern).unwrap().is_empty());
+        assert!(!contains_pattern(&projection, &pattern).unwrap());
+        assert!(contains_pattern(&projection, &aggregate_pattern()).unwrap());
+    }
+
+    #[test]
+    fn test_symmetric_pattern_yields_each_symmetry() {
+        let projection = create_orders_context();
+        // Two entities contained by the same aggregate, in either order
+        let pattern = Pattern::new()
+            .node("aggregate", is_aggregate)
+            .node("first", is_entity)
+            .node("second", is_entity)
+            .edge("aggregate", "first", |_| true)
+            .edge("aggregate", "second", |_| true);
+
+        let mut pairs: Vec<(String, String)> = find_embeddings(&projection, &pattern)
+            .unwrap()
+            .into_iter()
+            .map(|embedding| (embedding["first"].clone(), embedding["second"].clone()))
+            .collect();
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                ("line".to_string(), "product".to_string()),
+                ("product".to_string(), "line".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matching_is_injective() {
+        let mut projection = create_context(&[("a", ContextNodeType::Entity)], &[("a", "a", "self")]);
+
+        let pair = Pattern::new()
+            .node("x", |_: &ContextNode| true)
+            .node("y", |_: &ContextNode| true)
+            .edge("x", "y", |_: &ContextEdge| true);
+        assert!(find_embeddings(&projection, &pair).unwrap().is_empty());
+
+        let self_loop = Pattern::new()
+            .node("x", |_: &ContextNode| true)
+            .edge("x", "x", |_: &ContextEdge| true);
+        assert_eq!(find_embeddings(&projection, &self_loop).unwrap().len(), 1);
+
+        projection.nodes.insert("b".to_string(), context_node("b", ContextNodeType::Entity));
+        assert!(find_embeddings(&projection, &pair).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_disconnected_pattern_and_empty_pattern() {
+        let projection = create_orders_context();
+        let pattern = Pattern::new()
+            .node("aggregate", is_aggregate)
+            .node("value", |n: &ContextNode| matches!(n.node_type, ContextNodeType::ValueObject))
+            .node("entity", is_entity);
+
+        // 1 aggregate x 1 value object x 3 entities
+        assert_eq!(find_embeddings(&projection, &pattern).unwrap().len(), 3);
+        assert_eq!(find_embeddings(&projection, &Pattern::new()).unwrap(), vec![Embedding::new()]);
+    }
+
+    #[test]
+    fn test_workflow_anti_pattern() {
+        // Two states bouncing back and forth without an exit
+        let mut projection = WorkflowProjection::new(Uuid::new_v4(), GraphType::WorkflowGraph);
+        for (id, node_type) in [
+            ("start", WorkflowNodeType::Start),
+            ("review", WorkflowNodeType::State { name: "review".to_string() }),
+            ("rework", WorkflowNodeType::State { name: "rework".to_string() }),
+            ("done", WorkflowNodeType::End),
+        ] {
+            projection.nodes.insert(id.to_string(), WorkflowNode::new(id, node_type));
+        }
+        for (id, from, to) in [
+            ("t1", "start", "review"),
+            ("t2", "review", "rework"),
+            ("t3", "rework", "review"),
+            ("t4", "review", "done"),
+        ] {
+            projection.edges.insert(id.to_string(), WorkflowEdge::transition(id, from, to));
+        }
+
+        let is_state = |n: &WorkflowNode| matches!(n.node_type, WorkflowNodeType::State { .. });
+        let ping_pong = Pattern::new()
+            .node("a", is_state)
+            .node("b", is_state)
+            .edge("a", "b", |_: &WorkflowEdge| true)
+            .edge("b", "a", |_: &WorkflowEdge| true);
+
+        let embeddings = find_embeddings(&projection, &ping_pong).unwrap();
+        assert_eq!(embeddings.len(), 2);
+        assert_eq!(embeddings[0]["a"], "review");
+        assert_eq!(embeddings[0]["b"], "rework");
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let projection = create_orders_context();
+
+        let undeclared = Pattern::new()
+            .node("a", is_aggregate)
+            .edge("a", "missing", |_: &ContextEdge| true);
+        assert!(matches!(
+            find_embeddings(&projection, &undeclared),
+            Err(GraphError::InvalidOperation(_))
+        ));
+
+        let duplicate = Pattern::new().node("a", is_aggregate).node("a", is_entity);
+        assert!(matches!(
+            find_embeddings(&projection, &duplicate),
+            Err(GraphError::InvalidOperation(_))
+        ));
+    }
+}
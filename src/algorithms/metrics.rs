@@ -1,10 +1,18 @@
 //! Graph metrics algorithms for projections
 
+use super::adjacency::{reversed, IndexedAdjacency};
 use crate::core::GraphProjection;
-use crate::{Node, error::Result};
-use std::collections::HashMap;
-
-/// Calculate centrality measures for nodes
+use crate::{Node, error::{GraphError, Result}};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Calculate degree centrality for nodes (out-degree normalized by the maximum)
+///
+/// Parallel edges each count, as in
+/// [`IncrementalDegree`](super::incremental::IncrementalDegree). See
+/// [`centrality_with`] for degree over distinct successors and for
+/// betweenness, closeness, harmonic, eigenvector and PageRank centrality.
 pub fn centrality<P: GraphProjection>(projection: &P) -> Result<HashMap<String, f64>> 
 where
     P::Node: Node,
@@ -77,9 +85,492 @@ where
     }
 }
 
+// ============================================================================
+// Centrality Measures
+// ============================================================================
+
+/// Node count from which centrality measures run on the rayon thread pool
+const PARALLEL_THRESHOLD: usize = 128;
+
+/// Centrality measure computed by [`centrality_with`] and [`weighted_centrality_with`]
+///
+/// Edges are directed: path-based measures follow outgoing edges, eigenvector
+/// and PageRank centrality accumulate score along incoming edges.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CentralityKind {
+    /// Out-degree over distinct successors, normalized by the maximum out-degree
+    Degree,
+    /// Brandes betweenness, normalized by `(n - 1)(n - 2)`
+    Betweenness,
+    /// Closeness over reachable nodes, scaled by the reachable fraction (Wasserman–Faust)
+    Closeness,
+    /// Sum of inverse distances to other nodes, normalized by `n - 1`
+    Harmonic,
+    /// Power-iteration eigenvector centrality with unit Euclidean norm
+    Eigenvector {
+        /// Maximum number of power iterations
+        max_iterations: usize,
+        /// Per-node convergence tolerance
+        tolerance: f64,
+    },
+    /// PageRank with damping and an optional personalization vector
+    PageRank(PageRankConfig),
+}
+
+impl CentralityKind {
+    /// Eigenvector centrality with 100 iterations and a tolerance of `1e-6`
+    pub fn eigenvector() -> Self {
+        CentralityKind::Eigenvector {
+            max_iterations: 100,
+            tolerance: 1e-6,
+        }
+    }
+
+    /// PageRank with the default [`PageRankConfig`]
+    pub fn page_rank() -> Self {
+        CentralityKind::PageRank(PageRankConfig::default())
+    }
+}
+
+/// PageRank parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRankConfig {
+    /// Probability of following an edge rather than teleporting
+    pub damping: f64,
+    /// Teleport weights by node ID; uniform when `None`
+    pub personalization: Option<HashMap<String, f64>>,
+    /// Maximum number of iterations
+    pub max_iterations: usize,
+    /// Per-node convergence tolerance
+    pub tolerance: f64,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            personalization: None,
+            max_iterations: 100,
+            tolerance: 1e-6,
+        }
+    }
+}
+
+impl PageRankConfig {
+    /// Set the damping factor
+    pub fn with_damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Teleport (and redistribute dangling score) according to these weights
+    ///
+    /// Nodes missing from the map get weight zero; weights are normalized.
+    pub fn with_personalization(mut self, personalization: HashMap<String, f64>) -> Self {
+        self.personalization = Some(personalization);
+        self
+    }
+
+    /// Set the maximum number of iterations
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the per-node convergence tolerance
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Calculate a centrality measure with every edge weighted 1
+///
+/// Runs in parallel for projections with many nodes. Fails with
+/// [`GraphError::InvalidOperation`] when an iterative measure does not
+/// converge (eigenvector centrality on most acyclic graphs, for example) or
+/// its parameters are invalid, and with [`GraphError::NodeNotFound`] when a
+/// PageRank personalization names an unknown node.
+pub fn centrality_with<P: GraphProjection>(projection: &P, kind: &CentralityKind) -> Result<HashMap<String, f64>>
+where
+    P::Node: Node,
+{
+    let graph = IndexedGraph::build(projection, false, |_, _| 1.0)?;
+    graph.centrality(kind)
+}
+
+/// Calculate a centrality measure with weighted edges
+///
+/// `edge_weight(source, target)` is a distance for betweenness, closeness
+/// and harmonic centrality, and a connection strength for degree,
+/// eigenvector and PageRank centrality. Weights must be finite and
+/// non-negative.
+pub fn weighted_centrality_with<P, W>(
+    projection: &P,
+    kind: &CentralityKind,
+    edge_weight: W,
+) -> Result<HashMap<String, f64>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    W: Fn(&str, &str) -> f64,
+{
+    let graph = IndexedGraph::build(projection, true, edge_weight)?;
+    graph.centrality(kind)
+}
+
+/// Projection adjacency indexed by position, so measures can run without
+/// borrowing the projection across threads
+struct IndexedGraph {
+    /// Node IDs, sorted
+    ids: Vec<String>,
+    /// Outgoing `(target, weight)` pairs per node
+    outgoing: Vec<Vec<(usize, f64)>>,
+    /// Whether weights matter for shortest paths
+    weighted: bool,
+}
+
+/// Single-source shortest path DAG used by the path-based measures
+struct ShortestPaths {
+    /// Nodes in order of non-decreasing distance
+    order: Vec<usize>,
+    /// Number of shortest paths from the source
+    sigma: Vec<f64>,
+    /// Predecessors on shortest paths
+    predecessors: Vec<Vec<usize>>,
+    /// Distance from the source (infinite when unreachable)
+    distance: Vec<f64>,
+}
+
+/// Min-heap entry for weighted shortest paths
+#[derive(PartialEq)]
+struct HeapEntry {
+    distance: f64,
+    node: usize,
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl IndexedGraph {
+    fn build<P, W>(projection: &P, weighted: bool, edge_weight: W) -> Result<Self>
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        W: Fn(&str, &str) -> f64,
+    {
+        let graph = IndexedAdjacency::build(projection);
+        let outgoing = graph.strengths(edge_weight)?;
+        Ok(Self {
+            ids: graph.ids,
+            outgoing,
+            weighted,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn centrality(self, kind: &CentralityKind) -> Result<HashMap<String, f64>> {
+        let scores = match kind {
+            CentralityKind::Degree => self.degree(),
+            CentralityKind::Betweenness => self.betweenness(),
+            CentralityKind::Closeness => self.closeness(),
+            CentralityKind::Harmonic => self.harmonic(),
+            CentralityKind::Eigenvector { max_iterations, tolerance } => {
+                self.eigenvector(*max_iterations, *tolerance)?
+            }
+            CentralityKind::PageRank(config) => self.page_rank(config)?,
+        };
+        Ok(self.ids.into_iter().zip(scores).collect())
+    }
+
+    /// Incoming `(source, weight)` pairs per node
+    fn incoming(&self) -> Vec<Vec<(usize, f64)>> {
+        reversed(&self.outgoing)
+    }
+
+    fn shortest_paths(&self, source: usize) -> ShortestPaths {
+        let n = self.len();
+        let mut paths = ShortestPaths {
+            order: Vec::with_capacity(n),
+            sigma: vec![0.0; n],
+            predecessors: vec![Vec::new(); n],
+            distance: vec![f64::INFINITY; n],
+        };
+        paths.sigma[source] = 1.0;
+        paths.distance[source] = 0.0;
+
+        if self.weighted {
+            let mut settled = vec![false; n];
+            let mut heap = BinaryHeap::new();
+            heap.push(HeapEntry { distance: 0.0, node: source });
+
+            while let Some(HeapEntry { distance, node }) = heap.pop() {
+                if settled[node] {
+                    continue;
+                }
+                settled[node] = true;
+                paths.order.push(node);
+
+                for &(target, weight) in &self.outgoing[node] {
+                    let next = distance + weight;
+                    if next < paths.distance[target] {
+                        paths.distance[target] = next;
+                        paths.sigma[target] = paths.sigma[node];
+                        paths.predecessors[target] = vec![node];
+                        heap.push(HeapEntry { distance: next, node: target });
+                    } else if next == paths.distance[target] && !settled[target] {
+                        paths.sigma[target] += paths.sigma[node];
+                        paths.predecessors[target].push(node);
+                    }
+                }
+            }
+        } else {
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                paths.order.push(node);
+                let next = paths.distance[node] + 1.0;
+
+                for &(target, _) in &self.outgoing[node] {
+                    if paths.distance[target].is_infinite() {
+                        paths.distance[target] = next;
+                        queue.push_back(target);
+                    }
+                    if paths.distance[target] == next {
+                        paths.sigma[target] += paths.sigma[node];
+                        paths.predecessors[target].push(node);
+                    }
+                }
+            }
+        }
+
+        paths
+    }
+
+    fn degree(&self) -> Vec<f64> {
+        let strengths: Vec<f64> = self
+            .outgoing
+            .iter()
+            .map(|edges| edges.iter().map(|(_, weight)| weight).sum())
+            .collect();
+        let max = strengths.iter().copied().fold(0.0, f64::max);
+        if max > 0.0 {
+            strengths.into_iter().map(|strength| strength / max).collect()
+        } else {
+            strengths
+        }
+    }
+
+    fn betweenness(&self) -> Vec<f64> {
+        let n = self.len();
+        let dependencies = |source: usize| {
+            let paths = self.shortest_paths(source);
+            let mut delta = vec![0.0; n];
+            let mut scores = vec![0.0; n];
+            for &node in paths.order.iter().rev() {
+                for &predecessor in &paths.predecessors[node] {
+                    delta[predecessor] += paths.sigma[predecessor] / paths.sigma[node] * (1.0 + delta[node]);
+                }
+                if node != source {
+                    scores[node] += delta[node];
+                }
+            }
+            scores
+        };
+
+        let add = |mut total: Vec<f64>, scores: Vec<f64>| {
+            total.iter_mut().zip(scores).for_each(|(t, s)| *t += s);
+            total
+        };
+        let mut scores = if n >= PARALLEL_THRESHOLD {
+            (0..n).into_par_iter().map(dependencies).reduce(|| vec![0.0; n], add)
+        } else {
+            (0..n).map(dependencies).fold(vec![0.0; n], add)
+        };
+
+        if n > 2 {
+            let scale = 1.0 / ((n - 1) * (n - 2)) as f64;
+            scores.iter_mut().for_each(|score| *score *= scale);
+        }
+        scores
+    }
+
+    fn closeness(&self) -> Vec<f64> {
+        let n = self.len();
+        self.per_node(|source| {
+            let paths = self.shortest_paths(source);
+            let reachable = paths.order.len() - 1;
+            let total: f64 = paths.order.iter().map(|&node| paths.distance[node]).sum();
+            if reachable == 0 || total <= 0.0 {
+                return 0.0;
+            }
+            let reachable = reachable as f64;
+            (reachable / total) * (reachable / (n - 1) as f64)
+        })
+    }
+
+    fn harmonic(&self) -> Vec<f64> {
+        let n = self.len();
+        self.per_node(|source| {
+            if n < 2 {
+                return 0.0;
+            }
+            let paths = self.shortest_paths(source);
+            let total: f64 = paths
+                .order
+                .iter()
+                .map(|&node| paths.distance[node])
+                .filter(|&distance| distance > 0.0)
+                .map(|distance| 1.0 / distance)
+                .sum();
+            total / (n - 1) as f64
+        })
+    }
+
+    fn eigenvector(&self, max_iterations: usize, tolerance: f64) -> Result<Vec<f64>> {
+        let n = self.len();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let incoming = self.incoming();
+        let mut scores = vec![1.0 / n as f64; n];
+
+        for _ in 0..max_iterations {
+            // Iterate with A + I so that bipartite graphs do not oscillate
+            let mut next = self.per_node(|node| {
+                scores[node] + incoming[node].iter().map(|&(source, weight)| weight * scores[source]).sum::<f64>()
+            });
+            let norm = next.iter().map(|score| score * score).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                return Ok(next);
+            }
+            next.iter_mut().for_each(|score| *score /= norm);
+
+            let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+            scores = next;
+            if change < n as f64 * tolerance {
+                return Ok(scores);
+            }
+        }
+
+        Err(GraphError::InvalidOperation(format!(
+            "Eigenvector centrality did not converge in {} iterations",
+            max_iterations
+        )))
+    }
+
+    fn page_rank(&self, config: &PageRankConfig) -> Result<Vec<f64>> {
+        let n = self.len();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        if !(0.0..=1.0).contains(&config.damping) {
+            return Err(GraphError::InvalidOperation(format!(
+                "PageRank damping must be between 0 and 1, got {}",
+                config.damping
+            )));
+        }
+
+        let teleport = self.personalization(config.personalization.as_ref())?;
+        let out_strength: Vec<f64> = self
+            .outgoing
+            .iter()
+            .map(|edges| edges.iter().map(|(_, weight)| weight).sum())
+            .collect();
+        let incoming = self.incoming();
+        let mut scores = teleport.clone();
+
+        for _ in 0..config.max_iterations {
+            let dangling: f64 = (0..n).filter(|&node| out_strength[node] == 0.0).map(|node| scores[node]).sum();
+            let next = self.per_node(|node| {
+                // Nodes whose edges all weigh zero only contribute as dangling
+                let inflow: f64 = incoming[node]
+                    .iter()
+                    .filter(|&&(source, _)| out_strength[source] > 0.0)
+                    .map(|&(source, weight)| scores[source] * weight / out_strength[source])
+                    .sum();
+                config.damping * (inflow + dangling * teleport[node]) + (1.0 - config.damping) * teleport[node]
+            });
+
+            let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+            scores = next;
+            if change < n as f64 * config.tolerance {
+                return Ok(scores);
+            }
+        }
+
+        Err(GraphError::InvalidOperation(format!(
+            "PageRank did not converge in {} iterations",
+            config.max_iterations
+        )))
+    }
+
+    /// Normalized teleport distribution
+    fn personalization(&self, weights: Option<&HashMap<String, f64>>) -> Result<Vec<f64>> {
+        let n = self.len();
+        let weights = match weights {
+            Some(weights) => weights,
+            None => return Ok(vec![1.0 / n as f64; n]),
+        };
+
+        let mut teleport = vec![0.0; n];
+        for (id, &weight) in weights {
+            let node = self
+                .ids
+                .binary_search(id)
+                .map_err(|_| GraphError::NodeNotFound(id.clone()))?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Personalization weight for {} is invalid: {}",
+                    id, weight
+                )));
+            }
+            teleport[node] = weight;
+        }
+
+        let total: f64 = teleport.iter().sum();
+        if total == 0.0 {
+            return Err(GraphError::InvalidOperation(
+                "Personalization weights sum to zero".to_string(),
+            ));
+        }
+        teleport.iter_mut().for_each(|weight| *weight /= total);
+        Ok(teleport)
+    }
+
+    /// Evaluate `score` for every node, in parallel for large graphs
+    fn per_node<F>(&self, score: F) -> Vec<f64>
+    where
+        F: Fn(usize) -> f64 + Sync + Send,
+    {
+        if self.len() >= PARALLEL_THRESHOLD {
+            (0..self.len()).into_par_iter().map(score).collect()
+        } else {
+            (0..self.len()).map(score).collect()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::fixtures::create_graph;
     use crate::graphs::workflow::{WorkflowNode, WorkflowEdge, WorkflowNodeType};
     use crate::core::projection_engine::GenericGraphProjection;
     use crate::core::GraphType;
@@ -511,4 +1002,222 @@ mod tests {
         // So clustering coefficient should be 0
         assert_eq!(result, 0.0);
     }
-}
\ No newline at end of file
+
+    // ========== Centrality Measure Tests ==========
+
+    fn create_diamond() -> TestProjection {
+        // A -> B -> D and A -> C -> D
+        create_graph(&["A", "B", "C", "D"], &[("A", "B"), ("A", "C"), ("B", "D"), ("C", "D")])
+    }
+
+    fn create_ring(size: usize) -> TestProjection {
+        let ids: Vec<String> = (0..size).map(|i| format!("n{:04}", i)).collect();
+        let id_refs: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
+        let edges: Vec<(&str, &str)> = (0..size).map(|i| (id_refs[i], id_refs[(i + 1) % size])).collect();
+        create_graph(&id_refs, &edges)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn test_degree_kind_matches_centrality() {
+        let projection = create_star_graph();
+        assert_eq!(
+            centrality_with(&projection, &CentralityKind::Degree).unwrap(),
+            centrality(&projection).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_degree_kind_counts_parallel_edges_once() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "B"), ("A", "B"), ("A", "C"), ("B", "C"), ("B", "C")]);
+
+        let unweighted = centrality_with(&projection, &CentralityKind::Degree).unwrap();
+        assert_close(unweighted["A"], 1.0);
+        assert_close(unweighted["B"], 0.5);
+        assert_close(unweighted["C"], 0.0);
+        assert_eq!(
+            weighted_centrality_with(&projection, &CentralityKind::Degree, |_, _| 1.0).unwrap(),
+            unweighted
+        );
+
+        // Plain degree centrality counts each parallel edge
+        assert_close(centrality(&projection).unwrap()["B"], 2.0 / 3.0);
+    }
+
+    #[test]
+    fn test_betweenness_linear_graph() {
+        let projection = create_linear_graph();
+        let result = centrality_with(&projection, &CentralityKind::Betweenness).unwrap();
+
+        // B lies on A->C and A->D, C on A->D and B->D; normalized by 3 * 2
+        assert_close(result["A"], 0.0);
+        assert_close(result["B"], 2.0 / 6.0);
+        assert_close(result["C"], 2.0 / 6.0);
+        assert_close(result["D"], 0.0);
+    }
+
+    #[test]
+    fn test_betweenness_splits_equal_paths() {
+        let projection = create_diamond();
+        let result = centrality_with(&projection, &CentralityKind::Betweenness).unwrap();
+
+        assert_close(result["B"], 0.5 / 6.0);
+        assert_close(result["C"], 0.5 / 6.0);
+    }
+
+    #[test]
+    fn test_weighted_betweenness_follows_cheapest_path() {
+        let projection = create_diamond();
+        let weight = |from: &str, to: &str| if from == "C" || to == "C" { 5.0 } else { 1.0 };
+        let result = weighted_centrality_with(&projection, &CentralityKind::Betweenness, weight).unwrap();
+
+        assert_close(result["B"], 1.0 / 6.0);
+        assert_close(result["C"], 0.0);
+    }
+
+    #[test]
+    fn test_closeness_and_harmonic_linear_graph() {
+        let projection = create_linear_graph();
+
+        let closeness = centrality_with(&projection, &CentralityKind::Closeness).unwrap();
+        assert_close(closeness["A"], 0.5);
+        assert_close(closeness["B"], (2.0 / 3.0) * (2.0 / 3.0));
+        assert_close(closeness["D"], 0.0);
+
+        let harmonic = centrality_with(&projection, &CentralityKind::Harmonic).unwrap();
+        assert_close(harmonic["A"], (1.0 + 0.5 + 1.0 / 3.0) / 3.0);
+        assert_close(harmonic["D"], 0.0);
+    }
+
+    #[test]
+    fn test_eigenvector_complete_triangle() {
+        let projection = create_complete_triangle();
+        let result = centrality_with(&projection, &CentralityKind::eigenvector()).unwrap();
+
+        for id in ["A", "B", "C"] {
+            assert_close(result[id], 1.0 / 3.0_f64.sqrt());
+        }
+    }
+
+    #[test]
+    fn test_eigenvector_reports_non_convergence() {
+        let projection = create_star_graph();
+        let result = centrality_with(&projection, &CentralityKind::eigenvector());
+        assert!(matches!(result, Err(GraphError::InvalidOperation(_))));
+    }
+
+    #[test]
+    fn test_page_rank_cycle_and_star() {
+        let cycle = create_graph(&["A", "B", "C"], &[("A", "B"), ("B", "C"), ("C", "A")]);
+        let result = centrality_with(&cycle, &CentralityKind::page_rank()).unwrap();
+        for id in ["A", "B", "C"] {
+            assert_close(result[id], 1.0 / 3.0);
+        }
+
+        let star = create_star_graph();
+        let result = centrality_with(&star, &CentralityKind::page_rank()).unwrap();
+        assert_close(result.values().sum::<f64>(), 1.0);
+        assert!(result["B"] > result["A"]);
+        assert_close(result["B"], result["E"]);
+    }
+
+    #[test]
+    fn test_page_rank_personalization() {
+        let projection = create_linear_graph();
+        let uniform = centrality_with(&projection, &CentralityKind::page_rank()).unwrap();
+
+        let config = PageRankConfig::default().with_personalization(HashMap::from([("C".to_string(), 1.0)]));
+        let personalized = centrality_with(&projection, &CentralityKind::PageRank(config)).unwrap();
+
+        assert_close(personalized.values().sum::<f64>(), 1.0);
+        assert!(personalized["C"] > uniform["C"]);
+        assert_close(personalized["A"], 0.0);
+        assert_close(personalized["B"], 0.0);
+    }
+
+    #[test]
+    fn test_page_rank_rejects_invalid_parameters() {
+        let projection = create_linear_graph();
+
+        let unknown = PageRankConfig::default().with_personalization(HashMap::from([("Z".to_string(), 1.0)]));
+        assert!(matches!(
+            centrality_with(&projection, &CentralityKind::PageRank(unknown)),
+            Err(GraphError::NodeNotFound(_))
+        ));
+
+        let damping = PageRankConfig::default().with_damping(1.5);
+        assert!(matches!(
+            centrality_with(&projection, &CentralityKind::PageRank(damping)),
+            Err(GraphError::InvalidOperation(_))
+        ));
+
+        assert!(weighted_centrality_with(&projection, &CentralityKind::Betweenness, |_, _| -1.0).is_err());
+    }
+
+    #[test]
+    fn test_weighted_page_rank_with_zero_weight_edges() {
+        let projection = create_graph(&["A", "B", "C"], &[("A", "B"), ("B", "A"), ("B", "C")]);
+        let weight = |from: &str, _: &str| if from == "A" { 0.0 } else { 1.0 };
+        let result = weighted_centrality_with(&projection, &CentralityKind::page_rank(), weight).unwrap();
+
+        // A's only edge weighs nothing, so A ranks as if it had no edges at all
+        let dangling = create_graph(&["A", "B", "C"], &[("B", "A"), ("B", "C")]);
+        let expected = centrality_with(&dangling, &CentralityKind::page_rank()).unwrap();
+        assert_close(result.values().sum::<f64>(), 1.0);
+        for id in ["A", "B", "C"] {
+            assert!(result[id].is_finite());
+            assert_close(result[id], expected[id]);
+        }
+    }
+
+    #[test]
+    fn test_centrality_measures_large_ring_in_parallel() {
+        let size = PARALLEL_THRESHOLD + 22;
+        let projection = create_ring(size);
+
+        let betweenness = centrality_with(&projection, &CentralityKind::Betweenness).unwrap();
+        let page_rank = centrality_with(&projection, &CentralityKind::page_rank()).unwrap();
+        let harmonic = centrality_with(&projection, &CentralityKind::Harmonic).unwrap();
+
+        // Every node of a directed ring is equivalent
+        let expected_betweenness = betweenness["n0000"];
+        assert!(expected_betweenness > 0.0);
+        for id in projection.nodes.keys() {
+            assert_close(betweenness[id], expected_betweenness);
+            assert_close(page_rank[id], 1.0 / size as f64);
+            assert_close(harmonic[id], harmonic["n0000"]);
+        }
+    }
+
+    #[test]
+    fn test_weighted_page_rank_on_concept_projection() {
+        use crate::graphs::concept::{ConceptEdge, ConceptNode, ConceptProjection};
+
+        let mut projection = ConceptProjection::new(Uuid::new_v4(), GraphType::ConceptGraph);
+        for id in ["animal", "dog", "cat", "pet"] {
+            projection.nodes.insert(id.to_string(), ConceptNode::concept(id, id));
+            projection.adjacency.insert(id.to_string(), vec![]);
+        }
+        for (id, source, target, strength) in [
+            ("e1", "dog", "animal", 0.9),
+            ("e2", "dog", "pet", 0.1),
+            ("e3", "cat", "animal", 0.9),
+            ("e4", "cat", "pet", 0.1),
+        ] {
+            projection.edges.insert(id.to_string(), ConceptEdge::is_a(id, source, target).with_strength(strength));
+            projection.adjacency.get_mut(source).unwrap().push(target.to_string());
+        }
+
+        let strength = |from: &str, to: &str| {
+            projection.edges_between(from, to).iter().map(|edge| edge.strength as f64).sum::<f64>()
+        };
+        let weighted = weighted_centrality_with(&projection, &CentralityKind::page_rank(), strength).unwrap();
+        let unweighted = centrality_with(&projection, &CentralityKind::page_rank()).unwrap();
+
+        assert!(weighted["animal"] > weighted["pet"]);
+        assert_close(unweighted["animal"], unweighted["pet"]);
+    }
+}
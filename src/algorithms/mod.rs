@@ -11,18 +11,60 @@
 //! - [`all_paths`] - Find all paths between two nodes
 //! - A* search (with heuristic function)
 //! - Bellman-Ford (handles negative weights)
+//! - [`yen_k_shortest_paths`] - Yen's k cheapest loopless paths
+//! - [`bidirectional_shortest_path`] - BFS from both ends
+//! - [`bidirectional_dijkstra`] - Dijkstra from both ends
+//! - [`LandmarkIndex`] - ALT landmarks for goal-directed A*
+//! - [`constrained_shortest_path`] - Cheapest path under avoidance, waypoint and budget constraints
+//! - [`pareto_paths`] - Pareto front of paths over two edge weights
 //!
 //! ## Traversal
 //! - [`bfs`] - Breadth-first search
 //! - [`dfs`] - Depth-first search
 //! - [`topological_sort`] - Order nodes in a DAG
+//! - [`articulation_points`] - Nodes whose removal disconnects the graph
+//! - [`bridges`] - Links whose removal disconnects the graph
+//! - [`biconnected_components`] - Hopcroft-Tarjan biconnected components
 //!
 //! ## Analysis & Metrics
-//! - [`centrality`] - Various centrality measures (degree, betweenness, etc.)
+//! - [`centrality`] - Degree centrality
+//! - [`centrality_with`] - Betweenness, closeness, harmonic, eigenvector and PageRank
 //! - [`clustering_coefficient`] - Measure graph clustering
 //! - Connected components
 //! - Cycle detection
 //!
+//! ## Connectivity
+//! - [`strongly_connected_components`] - Tarjan's strongly connected components
+//! - [`condensation`] - Condensation DAG as a projection
+//! - [`elementary_cycles`] - Johnson's elementary cycles, optionally length-bounded
+//!
+//! ## Reachability
+//! - [`transitive_closure`] - Bitset reachability between every pair of nodes
+//! - [`transitive_reduction`] - Minimal edge set of a DAG with the same reachability
+//!
+//! ## Incremental
+//! - [`IncrementalComponents`] - Union-find connected components
+//! - [`IncrementalDegree`] - Degree centrality
+//! - [`IncrementalReachability`] - Nodes reachable from a root
+//! - [`IncrementalTopologicalOrder`] - Pearce-Kelly topological order
+//!
+//! ## Dominators
+//! - [`dominator_tree`] - Lengauer-Tarjan dominator tree from a root
+//! - [`post_dominator_tree`] - Post-dominator tree towards one or more exits
+//!
+//! ## Pattern Matching
+//! - [`find_embeddings`] - VF2-style subgraph matching against a [`Pattern`]
+//!
+//! ## Network Flow
+//! - [`max_flow`] - Dinic's maximum flow
+//! - [`min_cut`] - Minimum s-t cut with both sides and the cut edges
+//! - [`min_cost_flow`] - Minimum-cost flow by successive shortest paths
+//!
+//! ## Community Detection
+//! - [`louvain`] - Louvain modularity optimization
+//! - [`label_propagation`] - Label propagation
+//! - [`modularity`] - Modularity score of a partition
+//!
 //! # Example
 //!
 //! Algorithms work with graph projections built from events:
@@ -43,7 +85,39 @@
 pub mod pathfinding;
 pub mod traversal;
 pub mod metrics;
+pub mod connectivity;
+pub mod community;
+pub mod flow;
+pub mod matching;
+pub mod dominators;
+pub mod closure;
+pub mod incremental;
+
+mod adjacency;
+
+#[cfg(test)]
+mod fixtures;
 
-pub use pathfinding::{shortest_path, all_paths};
-pub use traversal::{dfs, bfs, topological_sort};
-pub use metrics::{centrality, clustering_coefficient};
\ No newline at end of file
+pub use pathfinding::{
+    shortest_path, all_paths, yen_k_shortest_paths, bidirectional_shortest_path, bidirectional_dijkstra,
+    constrained_shortest_path, pareto_paths, CriteriaPath, KShortestPathsOptions, LandmarkIndex, PathConstraints,
+};
+pub use traversal::{dfs, bfs, topological_sort, articulation_points, bridges, biconnected_components};
+pub use community::{
+    label_propagation, louvain, modularity, weighted_label_propagation, weighted_louvain, weighted_modularity,
+    Communities, CommunityConfig,
+};
+pub use flow::{max_flow, min_cost_flow, min_cut, MaxFlow, MinCostFlow, MinCut};
+pub use closure::{transitive_closure, transitive_closure_by, transitive_reduction, TransitiveClosure};
+pub use incremental::{
+    IncrementalComponents, IncrementalDegree, IncrementalReachability, IncrementalTopologicalOrder,
+};
+pub use dominators::{dominator_tree, post_dominator_tree, DominatorTree};
+pub use matching::{contains_pattern, find_embeddings, Embedding, Pattern};
+pub use connectivity::{
+    condensation, elementary_cycles, strongly_connected_components, Condensation, CondensationProjection,
+};
+pub use metrics::{
+    centrality, centrality_with, weighted_centrality_with, clustering_coefficient, CentralityKind,
+    PageRankConfig,
+};
\ No newline at end of file
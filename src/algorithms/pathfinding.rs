@@ -1,6 +1,7 @@
 //! Pathfinding algorithms for graph projections
 
-use crate::core::{GraphProjection, Node};
+use super::adjacency::{reversed, IndexedAdjacency};
+use crate::core::{Edge, GraphProjection, Node};
 use crate::error::{GraphError, Result};
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::cmp::Ordering;
@@ -286,6 +287,33 @@ where
         return Ok(Some((vec![from.to_string()], 0)));
     }
 
+    Ok(restricted_dijkstra(projection, from, to, &edge_weight, &SearchRestrictions::default()))
+}
+
+/// Nodes, edges and a cost budget a restricted Dijkstra search must respect
+#[derive(Default)]
+struct SearchRestrictions<'a> {
+    /// Nodes the path may not enter
+    nodes: HashSet<&'a str>,
+    /// Directed edges the path may not use
+    edges: HashSet<(&'a str, &'a str)>,
+    /// Maximum path cost
+    max_cost: Option<u64>,
+}
+
+/// Dijkstra's algorithm that skips restricted nodes and edges and prunes
+/// paths over the cost budget
+fn restricted_dijkstra<P, W>(
+    projection: &P,
+    from: &str,
+    to: &str,
+    edge_weight: &W,
+    restrictions: &SearchRestrictions<'_>,
+) -> Option<(Vec<String>, u64)>
+where
+    P: GraphProjection,
+    W: Fn(&str, &str) -> u64,
+{
     let mut dist: HashMap<String, u64> = HashMap::new();
     let mut prev: HashMap<String, String> = HashMap::new();
     let mut heap = BinaryHeap::new();
@@ -306,7 +334,7 @@ where
                 current = parent;
             }
             path.reverse();
-            return Ok(Some((path, cost)));
+            return Some((path, cost));
         }
 
         if cost > *dist.get(&node).unwrap_or(&u64::MAX) {
@@ -314,8 +342,15 @@ where
         }
 
         for neighbor in projection.neighbors(&node) {
+            if restrictions.nodes.contains(neighbor) || restrictions.edges.contains(&(node.as_str(), neighbor)) {
+                continue;
+            }
+
             let weight = edge_weight(&node, neighbor);
             let next_cost = cost.saturating_add(weight);
+            if restrictions.max_cost.is_some_and(|max_cost| next_cost > max_cost) {
+                continue;
+            }
 
             if next_cost < *dist.get(neighbor).unwrap_or(&u64::MAX) {
                 dist.insert(neighbor.to_string(), next_cost);
@@ -328,7 +363,7 @@ where
         }
     }
 
-    Ok(None)
+    None
 }
 
 /// Dijkstra's algorithm with uniform edge weights (all equal to 1)
@@ -521,23 +556,835 @@ pub fn shortest_path_with_max_length<P: GraphProjection>(
     }
 }
 
-/// Find the k shortest paths between two nodes
+/// Find the k shortest paths between two nodes by hop count
+///
+/// Uses [`yen_k_shortest_paths`] with every edge weighted 1.
 pub fn k_shortest_paths<P: GraphProjection>(
     projection: &P,
     from: &str,
     to: &str,
     k: usize,
 ) -> Result<Vec<Vec<String>>> {
-    if k == 0 {
+    let paths = yen_k_shortest_paths(projection, from, to, k, |_, _| 1, &KShortestPathsOptions::default())?;
+    Ok(paths.into_iter().map(|(path, _)| path).collect())
+}
+
+// ============================================================================
+// Yen's K Shortest Paths
+// ============================================================================
+
+/// Constraints for [`yen_k_shortest_paths`]
+#[derive(Debug, Clone, Default)]
+pub struct KShortestPathsOptions {
+    /// Stop once paths would cost more than this
+    pub max_cost: Option<u64>,
+    /// Nodes no path may visit
+    pub excluded_nodes: HashSet<String>,
+    /// Directed `(source, target)` edges no path may use
+    pub excluded_edges: HashSet<(String, String)>,
+}
+
+impl KShortestPathsOptions {
+    /// Only return paths costing at most `max_cost`
+    pub fn with_max_cost(mut self, max_cost: u64) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Exclude a node from every path
+    pub fn excluding_node(mut self, node: impl Into<String>) -> Self {
+        self.excluded_nodes.insert(node.into());
+        self
+    }
+
+    /// Exclude the directed edge `source -> target` from every path
+    pub fn excluding_edge(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.excluded_edges.insert((source.into(), target.into()));
+        self
+    }
+}
+
+/// Yen's algorithm for the k cheapest loopless paths
+///
+/// Returns up to `k` paths with their costs, cheapest first (ties broken by
+/// path). `edge_weight` is the same closure [`dijkstra`] takes. Paths never
+/// repeat a node, avoid the excluded nodes and edges, and cost at most
+/// `max_cost` when one is set. An excluded endpoint yields no paths.
+pub fn yen_k_shortest_paths<P, W>(
+    projection: &P,
+    from: &str,
+    to: &str,
+    k: usize,
+    edge_weight: W,
+    options: &KShortestPathsOptions,
+) -> Result<Vec<(Vec<String>, u64)>>
+where
+    P: GraphProjection,
+    W: Fn(&str, &str) -> u64,
+{
+    if projection.get_node(from).is_none() {
+        return Err(GraphError::NodeNotFound(from.to_string()));
+    }
+    if projection.get_node(to).is_none() {
+        return Err(GraphError::NodeNotFound(to.to_string()));
+    }
+    if k == 0 || options.excluded_nodes.contains(from) || options.excluded_nodes.contains(to) {
+        return Ok(vec![]);
+    }
+    if from == to {
+        return Ok(vec![(vec![from.to_string()], 0)]);
+    }
+
+    let base = SearchRestrictions {
+        nodes: options.excluded_nodes.iter().map(String::as_str).collect(),
+        edges: options
+            .excluded_edges
+            .iter()
+            .map(|(source, target)| (source.as_str(), target.as_str()))
+            .collect(),
+        max_cost: options.max_cost,
+    };
+
+    let mut accepted: Vec<(Vec<String>, u64)> = Vec::new();
+    match restricted_dijkstra(projection, from, to, &edge_weight, &base) {
+        Some(shortest) => accepted.push(shortest),
+        None => return Ok(accepted),
+    }
+
+    let mut seen: HashSet<Vec<String>> = HashSet::from([accepted[0].0.clone()]);
+    let mut candidates: BinaryHeap<std::cmp::Reverse<(u64, Vec<String>)>> = BinaryHeap::new();
+
+    while accepted.len() < k {
+        let previous = accepted[accepted.len() - 1].0.clone();
+
+        for spur_index in 0..previous.len() - 1 {
+            let root = &previous[..=spur_index];
+            let root_cost = path_cost(root, &edge_weight);
+            if options.max_cost.is_some_and(|max_cost| root_cost > max_cost) {
+                break;
+            }
+
+            // Block the next edge of every accepted path sharing this root,
+            // and the root's nodes so that spur paths stay loopless
+            let mut restrictions = SearchRestrictions {
+                nodes: base.nodes.clone(),
+                edges: base.edges.clone(),
+                max_cost: options.max_cost.map(|max_cost| max_cost - root_cost),
+            };
+            for (path, _) in &accepted {
+                if path.len() > spur_index + 1 && path[..=spur_index] == *root {
+                    restrictions.edges.insert((path[spur_index].as_str(), path[spur_index + 1].as_str()));
+                }
+            }
+            restrictions.nodes.extend(root[..spur_index].iter().map(String::as_str));
+
+            let spur = &previous[spur_index];
+            if let Some((spur_path, spur_cost)) = restricted_dijkstra(projection, spur, to, &edge_weight, &restrictions) {
+                let mut path = root[..spur_index].to_vec();
+                path.extend(spur_path);
+                if seen.insert(path.clone()) {
+                    candidates.push(std::cmp::Reverse((root_cost.saturating_add(spur_cost), path)));
+                }
+            }
+        }
+
+        match candidates.pop() {
+            Some(std::cmp::Reverse((cost, path))) => accepted.push((path, cost)),
+            None => break,
+        }
+    }
+
+    Ok(accepted)
+}
+
+// ============================================================================
+// Bidirectional and Goal-Directed Search
+// ============================================================================
+
+/// Marks an unset parent
+const NO_PARENT: usize = usize::MAX;
+
+/// Shortest path by hop count, searching from both ends at once
+///
+/// Returns a path as short as [`shortest_path`]'s, usually after visiting
+/// far fewer nodes: each round expands whichever frontier is smaller.
+/// Building the reverse adjacency takes one pass over the projection.
+pub fn bidirectional_shortest_path<P>(projection: &P, from: &str, to: &str) -> Result<Option<Vec<String>>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+{
+    if projection.get_node(from).is_none() {
+        return Err(GraphError::NodeNotFound(from.to_string()));
+    }
+    if projection.get_node(to).is_none() {
+        return Err(GraphError::NodeNotFound(to.to_string()));
+    }
+
+    let graph = WeightedGraph::build(projection, |_, _| 1);
+    let (source, target) = (graph.lookup(from)?, graph.lookup(to)?);
+    Ok(graph.bidirectional_bfs(source, target).map(|path| graph.names(&path)))
+}
+
+/// Dijkstra's algorithm run from both ends at once
+///
+/// Takes the same `edge_weight` closure as [`dijkstra`] and returns the same
+/// cost; the search stops once the two frontiers together cannot improve on
+/// the best path found.
+pub fn bidirectional_dijkstra<P, W>(
+    projection: &P,
+    from: &str,
+    to: &str,
+    edge_weight: W,
+) -> Result<Option<(Vec<String>, u64)>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    W: Fn(&str, &str) -> u64,
+{
+    if projection.get_node(from).is_none() {
+        return Err(GraphError::NodeNotFound(from.to_string()));
+    }
+    if projection.get_node(to).is_none() {
+        return Err(GraphError::NodeNotFound(to.to_string()));
+    }
+
+    let graph = WeightedGraph::build(projection, edge_weight);
+    let (source, target) = (graph.lookup(from)?, graph.lookup(to)?);
+    Ok(graph
+        .bidirectional_dijkstra(source, target)
+        .map(|(path, cost)| (graph.names(&path), cost)))
+}
+
+/// ALT preprocessing: exact distances to and from a few landmark nodes
+///
+/// By the triangle inequality these give a lower bound on the distance
+/// between any two nodes, which steers A* towards the goal. Edge weights are
+/// fixed when the index is built, so build one per projection version and
+/// weight function and reuse it across queries, e.g. through
+/// [`GraphCache::get_landmarks`](crate::performance::GraphCache::get_landmarks).
+#[derive(Debug, Clone)]
+pub struct LandmarkIndex {
+    aggregate_id: uuid::Uuid,
+    version: u64,
+    graph: WeightedGraph,
+    /// Landmark node positions
+    landmarks: Vec<usize>,
+    /// Distance from each landmark to every node
+    from_landmark: Vec<Vec<u64>>,
+    /// Distance from every node to each landmark
+    to_landmark: Vec<Vec<u64>>,
+}
+
+impl LandmarkIndex {
+    /// Pick up to `count` landmarks and measure distances to and from them
+    ///
+    /// Landmarks are chosen greedily, each as far as possible from those
+    /// already chosen, with unreachable nodes first so that every part of a
+    /// disconnected projection gets one.
+    pub fn build<P, W>(projection: &P, count: usize, edge_weight: W) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        W: Fn(&str, &str) -> u64,
+    {
+        let graph = WeightedGraph::build(projection, edge_weight);
+        let count = count.min(graph.len());
+
+        let mut landmarks = Vec::with_capacity(count);
+        let mut from_landmark: Vec<Vec<u64>> = Vec::with_capacity(count);
+        let mut to_landmark = Vec::with_capacity(count);
+        // Seed the search from the first node without making it a landmark
+        let mut nearest = if count > 0 { graph.distances(0, true) } else { Vec::new() };
+        while landmarks.len() < count {
+            let Some(landmark) = (0..graph.len())
+                .filter(|node| !landmarks.contains(node))
+                .max_by(|&a, &b| nearest[a].cmp(&nearest[b]).then_with(|| b.cmp(&a)))
+            else {
+                break;
+            };
+            let distances = graph.distances(landmark, true);
+            for (closest, &distance) in nearest.iter_mut().zip(&distances) {
+                *closest = (*closest).min(distance);
+            }
+            landmarks.push(landmark);
+            from_landmark.push(distances);
+            to_landmark.push(graph.distances(landmark, false));
+        }
+
+        Self {
+            aggregate_id: projection.aggregate_id(),
+            version: projection.version(),
+            graph,
+            landmarks,
+            from_landmark,
+            to_landmark,
+        }
+    }
+
+    /// Aggregate of the projection the index was built from
+    pub fn aggregate_id(&self) -> uuid::Uuid {
+        self.aggregate_id
+    }
+
+    /// Version of the projection the index was built from
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The landmark node IDs, in the order they were chosen
+    pub fn landmarks(&self) -> Vec<&str> {
+        self.landmarks.iter().map(|&node| self.graph.nodes.ids[node].as_str()).collect()
+    }
+
+    /// A lower bound on the cost of any path from `from` to `to`
+    ///
+    /// Usable as the heuristic of [`a_star`] with the same edge weights.
+    /// Zero for unknown nodes.
+    pub fn lower_bound(&self, from: &str, to: &str) -> u64 {
+        match (self.graph.nodes.position(from), self.graph.nodes.position(to)) {
+            (Some(from), Some(to)) => self.bound(from, to),
+            _ => 0,
+        }
+    }
+
+    /// Cheapest path and its cost, by A* guided by the landmark bounds
+    pub fn shortest_path(&self, from: &str, to: &str) -> Result<Option<(Vec<String>, u64)>> {
+        let (source, target) = (self.graph.lookup(from)?, self.graph.lookup(to)?);
+        let count = self.graph.len();
+        let mut cost = vec![u64::MAX; count];
+        let mut parent = vec![NO_PARENT; count];
+        let mut closed = vec![false; count];
+        let mut open = BinaryHeap::new();
+
+        cost[source] = 0;
+        open.push(std::cmp::Reverse((self.bound(source, target), source)));
+        while let Some(std::cmp::Reverse((_, node))) = open.pop() {
+            if node == target {
+                return Ok(Some((self.graph.names(&self.graph.trace(&parent, target)), cost[target])));
+            }
+            if std::mem::replace(&mut closed[node], true) {
+                continue;
+            }
+            for &(next, weight) in &self.graph.successors[node] {
+                let next_cost = cost[node].saturating_add(weight);
+                if !closed[next] && next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    parent[next] = node;
+                    open.push(std::cmp::Reverse((next_cost.saturating_add(self.bound(next, target)), next)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn bound(&self, from: usize, to: usize) -> u64 {
+        let ahead = self
+            .from_landmark
+            .iter()
+            .filter(|distances| distances[from] != u64::MAX && distances[to] != u64::MAX)
+            .map(|distances| distances[to].saturating_sub(distances[from]));
+        let behind = self
+            .to_landmark
+            .iter()
+            .filter(|distances| distances[from] != u64::MAX && distances[to] != u64::MAX)
+            .map(|distances| distances[from].saturating_sub(distances[to]));
+        ahead.chain(behind).max().unwrap_or(0)
+    }
+}
+
+/// Projection adjacency by position in both directions, with edge weights
+#[derive(Debug, Clone)]
+struct WeightedGraph {
+    nodes: IndexedAdjacency,
+    successors: Vec<Vec<(usize, u64)>>,
+    predecessors: Vec<Vec<(usize, u64)>>,
+}
+
+impl WeightedGraph {
+    fn build<P, W>(projection: &P, edge_weight: W) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node,
+        W: Fn(&str, &str) -> u64,
+    {
+        let nodes = IndexedAdjacency::build(projection);
+        let successors = nodes.weighted(edge_weight);
+        let predecessors = reversed(&successors);
+        Self {
+            nodes,
+            successors,
+            predecessors,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn lookup(&self, id: &str) -> Result<usize> {
+        self.nodes.lookup(id)
+    }
+
+    fn names(&self, nodes: &[usize]) -> Vec<String> {
+        self.nodes.names(nodes)
+    }
+
+    /// Follow `parent` links back from `node`, returning the path in order
+    fn trace(&self, parent: &[usize], node: usize) -> Vec<usize> {
+        let mut path = vec![node];
+        while parent[path[path.len() - 1]] != NO_PARENT {
+            path.push(parent[path[path.len() - 1]]);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Dijkstra distances from `source`, following edges forwards or
+    /// backwards; `u64::MAX` marks unreachable nodes
+    fn distances(&self, source: usize, forwards: bool) -> Vec<u64> {
+        let links = if forwards { &self.successors } else { &self.predecessors };
+        let mut distance = vec![u64::MAX; self.len()];
+        let mut heap = BinaryHeap::from([std::cmp::Reverse((0, source))]);
+        distance[source] = 0;
+        while let Some(std::cmp::Reverse((cost, node))) = heap.pop() {
+            if cost > distance[node] {
+                continue;
+            }
+            for &(next, weight) in &links[node] {
+                let next_cost = cost.saturating_add(weight);
+                if next_cost < distance[next] {
+                    distance[next] = next_cost;
+                    heap.push(std::cmp::Reverse((next_cost, next)));
+                }
+            }
+        }
+        distance
+    }
+
+    fn bidirectional_bfs(&self, source: usize, target: usize) -> Option<Vec<usize>> {
+        if source == target {
+            return Some(vec![source]);
+        }
+        let count = self.len();
+        // Index 0 searches forwards from the source, index 1 backwards from the target
+        let mut depth = [vec![usize::MAX; count], vec![usize::MAX; count]];
+        let mut parent = [vec![NO_PARENT; count], vec![NO_PARENT; count]];
+        let mut frontier = [vec![source], vec![target]];
+        depth[0][source] = 0;
+        depth[1][target] = 0;
+
+        while !frontier[0].is_empty() && !frontier[1].is_empty() {
+            let side = usize::from(frontier[1].len() < frontier[0].len());
+            let links = if side == 0 { &self.successors } else { &self.predecessors };
+
+            // Finish the whole level so the shortest meeting wins
+            let mut next = Vec::new();
+            let mut meeting: Option<(usize, usize)> = None;
+            for node in std::mem::take(&mut frontier[side]) {
+                for &(neighbor, _) in &links[node] {
+                    if depth[side][neighbor] != usize::MAX {
+                        continue;
+                    }
+                    depth[side][neighbor] = depth[side][node] + 1;
+                    parent[side][neighbor] = node;
+                    next.push(neighbor);
+                    if depth[1 - side][neighbor] != usize::MAX {
+                        let length = depth[0][neighbor] + depth[1][neighbor];
+                        if !matches!(meeting, Some((best, _)) if best <= length) {
+                            meeting = Some((length, neighbor));
+                        }
+                    }
+                }
+            }
+            if let Some((_, node)) = meeting {
+                return Some(self.join(&parent, node));
+            }
+            frontier[side] = next;
+        }
+
+        None
+    }
+
+    fn bidirectional_dijkstra(&self, source: usize, target: usize) -> Option<(Vec<usize>, u64)> {
+        if source == target {
+            return Some((vec![source], 0));
+        }
+        let count = self.len();
+        let mut distance = [vec![u64::MAX; count], vec![u64::MAX; count]];
+        let mut parent = [vec![NO_PARENT; count], vec![NO_PARENT; count]];
+        let mut heaps = [
+            BinaryHeap::from([std::cmp::Reverse((0u64, source))]),
+            BinaryHeap::from([std::cmp::Reverse((0u64, target))]),
+        ];
+        distance[0][source] = 0;
+        distance[1][target] = 0;
+        let mut best: Option<(u64, usize)> = None;
+
+        while let (Some(forward), Some(backward)) = (heaps[0].peek(), heaps[1].peek()) {
+            let (forward, backward) = (forward.0 .0, backward.0 .0);
+            if best.is_some_and(|(cost, _)| forward.saturating_add(backward) >= cost) {
+                break;
+            }
+
+            let side = usize::from(backward < forward);
+            let links = if side == 0 { &self.successors } else { &self.predecessors };
+            let Some(std::cmp::Reverse((cost, node))) = heaps[side].pop() else {
+                break;
+            };
+            if cost > distance[side][node] {
+                continue;
+            }
+            for &(next, weight) in &links[node] {
+                let next_cost = cost.saturating_add(weight);
+                if next_cost >= distance[side][next] {
+                    continue;
+                }
+                distance[side][next] = next_cost;
+                parent[side][next] = node;
+                heaps[side].push(std::cmp::Reverse((next_cost, next)));
+                if distance[1 - side][next] != u64::MAX {
+                    let total = next_cost.saturating_add(distance[1 - side][next]);
+                    if !matches!(best, Some((cost, _)) if cost <= total) {
+                        best = Some((total, next));
+                    }
+                }
+            }
+        }
+
+        best.map(|(cost, node)| (self.join(&parent, node), cost))
+    }
+
+    /// Path through `node` from the forward and backward parent links
+    fn join(&self, parent: &[Vec<usize>; 2], node: usize) -> Vec<usize> {
+        let mut path = self.trace(&parent[0], node);
+        let mut current = node;
+        while parent[1][current] != NO_PARENT {
+            current = parent[1][current];
+            path.push(current);
+        }
+        path
+    }
+}
+
+// ============================================================================
+// Constrained and Multi-Objective Search
+// ============================================================================
+
+type NodeFilter<N> = Box<dyn Fn(&N) -> bool + Send + Sync>;
+
+/// Constraints for [`constrained_shortest_path`] and [`pareto_paths`]
+///
+/// ```rust,ignore
+/// use cim_graph::algorithms::PathConstraints;
+///
+/// // Route through review, never through an error state, within 30 minutes
+/// let constraints = PathConstraints::new()
+///     .avoiding(|n: &WorkflowNode| matches!(n.node_type, WorkflowNodeType::Error { .. }))
+///     .through("review")
+///     .with_budget(30 * 60 * 1000);
+/// ```
+pub struct PathConstraints<N> {
+    avoid: Vec<NodeFilter<N>>,
+    waypoints: Vec<String>,
+    budget: Option<u64>,
+}
+
+impl<N> Default for PathConstraints<N> {
+    fn default() -> Self {
+        Self {
+            avoid: Vec::new(),
+            waypoints: Vec::new(),
+            budget: None,
+        }
+    }
+}
+
+impl<N> std::fmt::Debug for PathConstraints<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathConstraints")
+            .field("avoid", &self.avoid.len())
+            .field("waypoints", &self.waypoints)
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
+
+impl<N> PathConstraints<N> {
+    /// No constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never visit a node satisfying `predicate`
+    pub fn avoiding<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&N) -> bool + Send + Sync + 'static,
+    {
+        self.avoid.push(Box::new(predicate));
+        self
+    }
+
+    /// Visit `node` after every waypoint added before it
+    pub fn through(mut self, node: impl Into<String>) -> Self {
+        self.waypoints.push(node.into());
+        self
+    }
+
+    /// Only return paths whose secondary cost is at most `budget`
+    pub fn with_budget(mut self, budget: u64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Required waypoints in visiting order
+    pub fn waypoints(&self) -> &[String] {
+        &self.waypoints
+    }
+
+    /// The secondary cost budget, if any
+    pub fn budget(&self) -> Option<u64> {
+        self.budget
+    }
+
+    fn avoids(&self, node: &N) -> bool {
+        self.avoid.iter().any(|predicate| predicate(node))
+    }
+}
+
+/// A path scored on two criteria
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriteriaPath {
+    /// Node IDs from source to target
+    pub nodes: Vec<String>,
+    /// IDs of the edges taken, one fewer than `nodes`
+    pub edges: Vec<String>,
+    /// Total primary cost
+    pub cost: u64,
+    /// Total secondary cost, the one a budget applies to
+    pub resource: u64,
+}
+
+/// Cheapest path under a budget on a second cost, avoided nodes and waypoints
+///
+/// `cost` is minimised and `resource` (time, risk, ...) must total at most
+/// the constraints' budget; ties on cost go to the smaller resource. Both are
+/// read from each traversed edge, so parallel edges are told apart and the
+/// result names the edges taken. This is exact resource-constrained
+/// label-setting: every node keeps the (cost, resource) labels not dominated
+/// by another, so the work grows with the size of the Pareto fronts rather
+/// than exponentially with the path length.
+///
+/// Waypoints are visited in the order they were added. A path may only
+/// revisit a node when the waypoint order forces it to. An avoided source,
+/// target or waypoint yields `None`; a waypoint missing from the projection
+/// is an error.
+pub fn constrained_shortest_path<P, C, R>(
+    projection: &P,
+    from: &str,
+    to: &str,
+    cost: C,
+    resource: R,
+    constraints: &PathConstraints<P::Node>,
+) -> Result<Option<CriteriaPath>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    C: Fn(&P::Edge) -> u64,
+    R: Fn(&P::Edge) -> u64,
+{
+    Ok(label_setting(projection, from, to, cost, resource, constraints, true)?.pop())
+}
+
+/// Every Pareto-optimal path between two nodes under two edge weights
+///
+/// A path is on the front when no other path is at least as good on both
+/// `first` and `second` and strictly better on one. The front is returned
+/// ordered by increasing `first` cost, hence decreasing `second` cost, with
+/// one path per distinct pair of totals. In the result `cost` holds the
+/// `first` total and `resource` the `second`. The constraints apply as in
+/// [`constrained_shortest_path`], the budget bounding `second`.
+pub fn pareto_paths<P, A, B>(
+    projection: &P,
+    from: &str,
+    to: &str,
+    first: A,
+    second: B,
+    constraints: &PathConstraints<P::Node>,
+) -> Result<Vec<CriteriaPath>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    A: Fn(&P::Edge) -> u64,
+    B: Fn(&P::Edge) -> u64,
+{
+    label_setting(projection, from, to, first, second, constraints, false)
+}
+
+/// A partial path in the label-setting search
+struct Label {
+    node: usize,
+    stage: usize,
+    cost: u64,
+    resource: u64,
+    /// Label this one extends, or [`NO_PARENT`]
+    parent: usize,
+    /// Arc taken from the parent's node
+    edge: usize,
+}
+
+/// Whether a label with `resource` is dominated by the last one settled at `state`
+fn dominated(settled: &[Option<u64>], state: usize, resource: u64) -> bool {
+    settled[state].is_some_and(|best| best <= resource)
+}
+
+/// Bi-criteria label-setting over (node, waypoints reached) states
+///
+/// Labels leave the queue in lexicographic (cost, resource) order, so each
+/// state's settled labels have rising cost and falling resource, and a label
+/// is dominated exactly when its resource is no smaller than the last settled
+/// one's. Weights are non-negative, so the same test against the target state
+/// discards any label that can no longer reach the front.
+fn label_setting<P, C, R>(
+    projection: &P,
+    from: &str,
+    to: &str,
+    cost: C,
+    resource: R,
+    constraints: &PathConstraints<P::Node>,
+    first_only: bool,
+) -> Result<Vec<CriteriaPath>>
+where
+    P: GraphProjection,
+    P::Node: Node,
+    P::Edge: Edge,
+    C: Fn(&P::Edge) -> u64,
+    R: Fn(&P::Edge) -> u64,
+{
+    let mut required = vec![from];
+    required.extend(constraints.waypoints.iter().map(String::as_str));
+    required.push(to);
+
+    let mut blocked_endpoint = false;
+    for id in &required {
+        match projection.get_node(id) {
+            Some(node) => blocked_endpoint |= constraints.avoids(node),
+            None => return Err(GraphError::NodeNotFound(id.to_string())),
+        }
+    }
+    if blocked_endpoint {
         return Ok(vec![]);
     }
 
-    let all = all_paths(projection, from, to)?;
+    let graph = IndexedAdjacency::nodes(projection);
+    let blocked: Vec<bool> = graph
+        .ids
+        .iter()
+        .map(|id| projection.get_node(id).is_some_and(|node| constraints.avoids(node)))
+        .collect();
+
+    let mut edges = projection.edges();
+    edges.sort_by_key(|edge| edge.id());
+    let mut edge_ids = Vec::with_capacity(edges.len());
+    let mut outgoing: Vec<Vec<(usize, usize, u64, u64)>> = vec![Vec::new(); graph.len()];
+    for edge in edges {
+        let (source, target) = match graph.endpoints(edge) {
+            Some((source, target)) if !blocked[source] && !blocked[target] => (source, target),
+            _ => continue,
+        };
+        outgoing[source].push((target, edge_ids.len(), cost(edge), resource(edge)));
+        edge_ids.push(edge.id());
+    }
+
+    let waypoints = constraints.waypoints.iter().map(|id| graph.lookup(id)).collect::<Result<Vec<_>>>()?;
+    let advance = |node: usize, mut stage: usize| {
+        while stage < waypoints.len() && waypoints[stage] == node {
+            stage += 1;
+        }
+        stage
+    };
+    let state = |node: usize, stage: usize| stage * graph.len() + node;
+    let (source, target) = (graph.lookup(from)?, graph.lookup(to)?);
+    let goal = state(target, waypoints.len());
+
+    // Resource of the last settled label per state
+    let mut settled: Vec<Option<u64>> = vec![None; graph.len() * (waypoints.len() + 1)];
+    let mut labels = vec![Label {
+        node: source,
+        stage: advance(source, 0),
+        cost: 0,
+        resource: 0,
+        parent: NO_PARENT,
+        edge: NO_PARENT,
+    }];
+    let mut queue = BinaryHeap::from([std::cmp::Reverse((0u64, 0u64, 0usize))]);
+    let mut front = Vec::new();
+
+    while let Some(std::cmp::Reverse((_, _, current))) = queue.pop() {
+        let label = &labels[current];
+        let (node, stage, base_cost, base_resource) = (label.node, label.stage, label.cost, label.resource);
+        let at = state(node, stage);
+        if dominated(&settled, at, base_resource) || dominated(&settled, goal, base_resource) {
+            continue;
+        }
+        settled[at] = Some(base_resource);
+
+        if at == goal {
+            front.push(current);
+            if first_only {
+                break;
+            }
+            continue;
+        }
 
-    let mut sorted_paths = all;
-    sorted_paths.sort_by_key(|p| p.len());
+        for &(next, edge, edge_cost, edge_resource) in &outgoing[node] {
+            let next_resource = base_resource.saturating_add(edge_resource);
+            if constraints.budget.is_some_and(|budget| next_resource > budget) {
+                continue;
+            }
+            let next_stage = advance(next, stage);
+            let next_state = state(next, next_stage);
+            if dominated(&settled, next_state, next_resource) || dominated(&settled, goal, next_resource) {
+                continue;
+            }
+            let next_cost = base_cost.saturating_add(edge_cost);
+            queue.push(std::cmp::Reverse((next_cost, next_resource, labels.len())));
+            labels.push(Label {
+                node: next,
+                stage: next_stage,
+                cost: next_cost,
+                resource: next_resource,
+                parent: current,
+                edge,
+            });
+        }
+    }
 
-    Ok(sorted_paths.into_iter().take(k).collect())
+    Ok(front
+        .into_iter()
+        .map(|found| {
+            let (mut nodes, mut taken) = (Vec::new(), Vec::new());
+            let mut step = found;
+            while step != NO_PARENT {
+                nodes.push(graph.ids[labels[step].node].clone());
+                if labels[step].edge != NO_PARENT {
+                    taken.push(edge_ids[labels[step].edge].clone());
+                }
+                step = labels[step].parent;
+            }
+            nodes.reverse();
+            taken.reverse();
+            CriteriaPath {
+                nodes,
+                edges: taken,
+                cost: labels[found].cost,
+                resource: labels[found].resource,
+            }
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -1555,4 +2402,401 @@ mod tests {
 
         assert!(result.is_empty());
     }
-}
\ No newline at end of file
+
+    // ========== Yen K Shortest Paths Tests ==========
+
+    fn complex_weight(from: &str, to: &str) -> u64 {
+        match (from, to) {
+            ("A", "B") => 1,
+            ("B", "C") => 5,
+            ("C", "F") => 1,
+            ("B", "E") => 2,
+            ("A", "D") => 2,
+            ("D", "E") => 2,
+            ("E", "F") => 1,
+            _ => 1,
+        }
+    }
+
+    fn to_path(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_yen_weighted_order_and_costs() {
+        let projection = create_complex_graph();
+        let options = KShortestPathsOptions::default();
+        let result = yen_k_shortest_paths(&projection, "A", "F", 5, complex_weight, &options).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                (to_path(&["A", "B", "E", "F"]), 4),
+                (to_path(&["A", "D", "E", "F"]), 5),
+                (to_path(&["A", "B", "C", "F"]), 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yen_max_cost() {
+        let projection = create_complex_graph();
+        let options = KShortestPathsOptions::default().with_max_cost(5);
+        let result = yen_k_shortest_paths(&projection, "A", "F", 5, complex_weight, &options).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|(_, cost)| *cost <= 5));
+
+        let options = KShortestPathsOptions::default().with_max_cost(3);
+        assert!(yen_k_shortest_paths(&projection, "A", "F", 5, complex_weight, &options).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_yen_excluded_nodes_and_edges() {
+        let projection = create_complex_graph();
+
+        let options = KShortestPathsOptions::default().excluding_node("E");
+        let result = yen_k_shortest_paths(&projection, "A", "F", 5, complex_weight, &options).unwrap();
+        assert_eq!(result, vec![(to_path(&["A", "B", "C", "F"]), 7)]);
+
+        let options = KShortestPathsOptions::default().excluding_edge("A", "B");
+        let result = yen_k_shortest_paths(&projection, "A", "F", 5, complex_weight, &options).unwrap();
+        assert_eq!(result, vec![(to_path(&["A", "D", "E", "F"]), 5)]);
+
+        let options = KShortestPathsOptions::default().excluding_node("A");
+        assert!(yen_k_shortest_paths(&projection, "A", "F", 5, complex_weight, &options).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_yen_paths_are_loopless() {
+        let mut projection = create_empty_projection();
+        for id in ["A", "B", "C"] {
+            projection.nodes.insert(id.to_string(), WorkflowNode::new(id, WorkflowNodeType::Start));
+        }
+        projection.adjacency.insert("A".to_string(), vec!["B".to_string(), "C".to_string()]);
+        projection.adjacency.insert("B".to_string(), vec!["A".to_string(), "C".to_string()]);
+        projection.adjacency.insert("C".to_string(), vec![]);
+
+        let result =
+            yen_k_shortest_paths(&projection, "A", "C", 10, |_, _| 1, &KShortestPathsOptions::default()).unwrap();
+
+        assert_eq!(result, vec![(to_path(&["A", "C"]), 1), (to_path(&["A", "B", "C"]), 2)]);
+    }
+
+    #[test]
+    fn test_yen_same_node_and_missing_nodes() {
+        let projection = create_complex_graph();
+        let options = KShortestPathsOptions::default();
+
+        let result = yen_k_shortest_paths(&projection, "A", "A", 3, complex_weight, &options).unwrap();
+        assert_eq!(result, vec![(to_path(&["A"]), 0)]);
+
+        assert!(yen_k_shortest_paths(&projection, "A", "Z", 3, complex_weight, &options).is_err());
+        assert!(yen_k_shortest_paths(&projection, "Z", "A", 3, complex_weight, &options).is_err());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_finds_every_simple_path() {
+        let projection = create_complex_graph();
+        let result = k_shortest_paths(&projection, "A", "F", 10).unwrap();
+
+        assert_eq!(result.len(), 3);
+        let unique: HashSet<_> = result.iter().collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    // ========== Bidirectional Search Tests ==========
+
+    #[test]
+    fn test_bidirectional_shortest_path() {
+        let projection = create_linear_graph();
+        let path = bidirectional_shortest_path(&projection, "A", "D").unwrap();
+        assert_eq!(path, Some(to_path(&["A", "B", "C", "D"])));
+
+        let projection = create_complex_graph();
+        let path = bidirectional_shortest_path(&projection, "A", "F").unwrap().unwrap();
+        assert_eq!(path.len(), 4);
+        assert!(is_valid_path(&projection, &path));
+
+        assert_eq!(bidirectional_shortest_path(&projection, "F", "A").unwrap(), None);
+        assert_eq!(bidirectional_shortest_path(&projection, "C", "C").unwrap(), Some(to_path(&["C"])));
+        assert!(bidirectional_shortest_path(&projection, "A", "Z").is_err());
+    }
+
+    #[test]
+    fn test_bidirectional_shortest_path_matches_bfs() {
+        // A ring of 12 nodes with chords from every third node
+        let mut projection = create_empty_projection();
+        let ids: Vec<String> = (0..12).map(|i| format!("n{:02}", i)).collect();
+        for (i, id) in ids.iter().enumerate() {
+            projection.nodes.insert(id.clone(), WorkflowNode::new(id.clone(), WorkflowNodeType::Start));
+            let mut targets = vec![ids[(i + 1) % 12].clone()];
+            if i % 3 == 0 {
+                targets.push(ids[(i + 5) % 12].clone());
+            }
+            projection.adjacency.insert(id.clone(), targets);
+        }
+
+        for from in &ids {
+            for to in &ids {
+                let expected = shortest_path(&projection, from, to).unwrap().map(|path| path.len());
+                let path = bidirectional_shortest_path(&projection, from, to).unwrap();
+                assert_eq!(path.as_ref().map(Vec::len), expected, "{} -> {}", from, to);
+                if let Some(path) = path {
+                    assert!(is_valid_path(&projection, &path));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra() {
+        let projection = create_complex_graph();
+        let result = bidirectional_dijkstra(&projection, "A", "F", complex_weight).unwrap();
+        assert_eq!(result, Some((to_path(&["A", "B", "E", "F"]), 4)));
+
+        for from in ["A", "B", "C", "D", "E", "F"] {
+            for to in ["A", "B", "C", "D", "E", "F"] {
+                let expected = dijkstra(&projection, from, to, complex_weight).unwrap().map(|(_, cost)| cost);
+                let result = bidirectional_dijkstra(&projection, from, to, complex_weight).unwrap();
+                assert_eq!(result.as_ref().map(|(_, cost)| *cost), expected, "{} -> {}", from, to);
+                if let Some((path, cost)) = result {
+                    assert_eq!(path_cost(&path, complex_weight), cost);
+                }
+            }
+        }
+
+        assert!(bidirectional_dijkstra(&projection, "Z", "A", complex_weight).is_err());
+    }
+
+    // ========== Landmark (ALT) Tests ==========
+
+    #[test]
+    fn test_landmark_index_shortest_paths() {
+        let projection = create_complex_graph();
+        let index = LandmarkIndex::build(&projection, 2, complex_weight);
+
+        assert_eq!(index.landmarks().len(), 2);
+        assert_eq!(index.aggregate_id(), projection.aggregate_id);
+        assert_eq!(index.version(), projection.version);
+
+        for from in ["A", "B", "C", "D", "E", "F"] {
+            for to in ["A", "B", "C", "D", "E", "F"] {
+                let expected = dijkstra(&projection, from, to, complex_weight).unwrap();
+                let result = index.shortest_path(from, to).unwrap();
+                assert_eq!(result.as_ref().map(|(_, cost)| *cost), expected.map(|(_, cost)| cost));
+                if let Some((_, cost)) = result {
+                    assert!(index.lower_bound(from, to) <= cost, "bound for {} -> {}", from, to);
+                }
+            }
+        }
+
+        assert_eq!(index.shortest_path("A", "F").unwrap(), Some((to_path(&["A", "B", "E", "F"]), 4)));
+        assert!(index.shortest_path("A", "Z").is_err());
+        assert_eq!(index.lower_bound("A", "Z"), 0);
+    }
+
+    #[test]
+    fn test_landmark_index_as_a_star_heuristic() {
+        let projection = create_complex_graph();
+        let index = LandmarkIndex::build(&projection, 3, complex_weight);
+
+        let path = a_star(
+            &projection,
+            "A",
+            "F",
+            |node, goal| index.lower_bound(node, goal),
+            Some(complex_weight),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(path, to_path(&["A", "B", "E", "F"]));
+    }
+
+    #[test]
+    fn test_landmark_index_covers_disconnected_parts() {
+        let mut projection = create_linear_graph();
+        projection.nodes.insert("X".to_string(), WorkflowNode::new("X", WorkflowNodeType::Start));
+        projection.adjacency.insert("X".to_string(), vec![]);
+
+        let index = LandmarkIndex::build(&projection, 2, |_, _| 1);
+        assert!(index.landmarks().contains(&"X"));
+        assert_eq!(index.shortest_path("A", "X").unwrap(), None);
+        assert_eq!(index.shortest_path("A", "D").unwrap(), Some((to_path(&["A", "B", "C", "D"]), 3)));
+
+        let empty = LandmarkIndex::build(&create_empty_projection(), 4, |_, _| 1);
+        assert!(empty.landmarks().is_empty());
+    }
+
+    // ========== Constrained and Pareto Search Tests ==========
+
+    fn create_routing_graph() -> TestProjection {
+        // Edges carry (cost, risk):
+        // S -> A (1, 5)   A -> T (1, 5)   A -> W (1, 0)   W -> T (1, 0)
+        // S -> B (2, 2)   B -> T (3, 1)   B -> W (1, 1)
+        // S -> X (1, 0)   X -> T (1, 4)   X is an error state
+        let mut projection = create_empty_projection();
+
+        for id in ["S", "A", "B", "W", "T"] {
+            projection.nodes.insert(id.to_string(), WorkflowNode::new(id, WorkflowNodeType::Start));
+        }
+        let error = WorkflowNodeType::Error { message: "failed".to_string() };
+        projection.nodes.insert("X".to_string(), WorkflowNode::new("X", error));
+
+        for (source, target, cost, risk) in [
+            ("S", "A", 1, 5),
+            ("S", "B", 2, 2),
+            ("S", "X", 1, 0),
+            ("A", "T", 1, 5),
+            ("A", "W", 1, 0),
+            ("B", "T", 3, 1),
+            ("B", "W", 1, 1),
+            ("W", "T", 1, 0),
+            ("X", "T", 1, 4),
+        ] {
+            let id = format!("{}{}", source, target);
+            let mut edge = WorkflowEdge::transition(id.clone(), source, target);
+            edge.metadata.insert("cost".to_string(), serde_json::json!(cost));
+            edge.metadata.insert("risk".to_string(), serde_json::json!(risk));
+            projection.edges.insert(id, edge);
+            projection.adjacency.entry(source.to_string()).or_default().push(target.to_string());
+        }
+
+        projection
+    }
+
+    fn edge_cost(edge: &WorkflowEdge) -> u64 {
+        edge.metadata["cost"].as_u64().unwrap()
+    }
+
+    fn edge_risk(edge: &WorkflowEdge) -> u64 {
+        edge.metadata["risk"].as_u64().unwrap()
+    }
+
+    fn avoid_errors() -> PathConstraints<WorkflowNode> {
+        PathConstraints::new().avoiding(|node: &WorkflowNode| matches!(node.node_type, WorkflowNodeType::Error { .. }))
+    }
+
+    fn route(result: Option<CriteriaPath>) -> Option<(Vec<String>, u64, u64)> {
+        result.map(|path| (path.nodes, path.cost, path.resource))
+    }
+
+    #[test]
+    fn test_constrained_shortest_path_without_constraints() {
+        let projection = create_routing_graph();
+        let result = constrained_shortest_path(&projection, "S", "T", edge_cost, edge_risk, &PathConstraints::new())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.nodes, to_path(&["S", "X", "T"]));
+        assert_eq!(result.edges, to_path(&["SX", "XT"]));
+        assert_eq!((result.cost, result.resource), (2, 4));
+    }
+
+    #[test]
+    fn test_constrained_shortest_path_avoids_node_types() {
+        let projection = create_routing_graph();
+        let result = constrained_shortest_path(&projection, "S", "T", edge_cost, edge_risk, &avoid_errors()).unwrap();
+        assert_eq!(route(result), Some((to_path(&["S", "A", "T"]), 2, 10)));
+
+        // An avoided endpoint has no route
+        let result = constrained_shortest_path(&projection, "S", "X", edge_cost, edge_risk, &avoid_errors()).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_constrained_shortest_path_respects_budget() {
+        let projection = create_routing_graph();
+        let cheapest = |budget| {
+            let constraints = avoid_errors().with_budget(budget);
+            route(constrained_shortest_path(&projection, "S", "T", edge_cost, edge_risk, &constraints).unwrap())
+        };
+
+        assert_eq!(cheapest(10), Some((to_path(&["S", "A", "T"]), 2, 10)));
+        assert_eq!(cheapest(9), Some((to_path(&["S", "A", "W", "T"]), 3, 5)));
+        assert_eq!(cheapest(4), Some((to_path(&["S", "B", "W", "T"]), 4, 3)));
+        assert_eq!(cheapest(2), None);
+    }
+
+    #[test]
+    fn test_constrained_shortest_path_waypoints() {
+        let projection = create_routing_graph();
+        let via = |waypoints: &[&str]| {
+            let constraints = waypoints.iter().fold(PathConstraints::new(), |c, w| c.through(*w));
+            route(constrained_shortest_path(&projection, "S", "T", edge_cost, edge_risk, &constraints).unwrap())
+        };
+
+        assert_eq!(via(&["W"]), Some((to_path(&["S", "A", "W", "T"]), 3, 5)));
+        assert_eq!(via(&["B"]), Some((to_path(&["S", "B", "W", "T"]), 4, 3)));
+        assert_eq!(via(&["B", "W"]), Some((to_path(&["S", "B", "W", "T"]), 4, 3)));
+        assert_eq!(via(&["W", "B"]), None);
+
+        let missing = PathConstraints::new().through("Z");
+        assert!(matches!(
+            constrained_shortest_path(&projection, "S", "T", edge_cost, edge_risk, &missing),
+            Err(GraphError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_constrained_shortest_path_revisits_for_waypoints() {
+        // A <-> B, with waypoints forcing A -> B -> A
+        let mut projection = create_empty_projection();
+        for id in ["A", "B"] {
+            projection.nodes.insert(id.to_string(), WorkflowNode::new(id, WorkflowNodeType::Start));
+        }
+        projection.edges.insert("AB".to_string(), WorkflowEdge::transition("AB", "A", "B"));
+        projection.edges.insert("BA".to_string(), WorkflowEdge::transition("BA", "B", "A"));
+
+        let constraints = PathConstraints::new().through("B");
+        let result = constrained_shortest_path(&projection, "A", "A", |_| 1, |_| 0, &constraints).unwrap();
+        assert_eq!(route(result), Some((to_path(&["A", "B", "A"]), 2, 0)));
+
+        let result = constrained_shortest_path(&projection, "A", "A", |_| 1, |_| 0, &PathConstraints::new()).unwrap();
+        assert_eq!(route(result), Some((to_path(&["A"]), 0, 0)));
+    }
+
+    #[test]
+    fn test_pareto_paths() {
+        let projection = create_routing_graph();
+        let front = |constraints: &PathConstraints<WorkflowNode>| {
+            pareto_paths(&projection, "S", "T", edge_cost, edge_risk, constraints)
+                .unwrap()
+                .into_iter()
+                .map(|path| (path.nodes, path.cost, path.resource))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            front(&PathConstraints::new()),
+            vec![(to_path(&["S", "X", "T"]), 2, 4), (to_path(&["S", "B", "W", "T"]), 4, 3)]
+        );
+        assert_eq!(
+            front(&avoid_errors()),
+            vec![
+                (to_path(&["S", "A", "T"]), 2, 10),
+                (to_path(&["S", "A", "W", "T"]), 3, 5),
+                (to_path(&["S", "B", "W", "T"]), 4, 3),
+            ]
+        );
+        assert_eq!(front(&avoid_errors().with_budget(5)).len(), 2);
+        assert!(front(&avoid_errors().with_budget(2)).is_empty());
+    }
+
+    #[test]
+    fn test_pareto_paths_match_single_objective_search() {
+        let projection = create_routing_graph();
+        let front = pareto_paths(&projection, "S", "T", edge_cost, edge_risk, &PathConstraints::new()).unwrap();
+
+        // The ends of the front are the optima of each criterion alone
+        let by_risk = pareto_paths(&projection, "S", "T", edge_risk, edge_cost, &PathConstraints::new()).unwrap();
+        assert_eq!(front[0].cost, by_risk[by_risk.len() - 1].resource);
+        assert_eq!(front[front.len() - 1].resource, by_risk[0].cost);
+
+        let mut reversed: Vec<_> = by_risk.into_iter().map(|path| path.nodes).collect();
+        reversed.reverse();
+        assert_eq!(front.into_iter().map(|path| path.nodes).collect::<Vec<_>>(), reversed);
+
+        assert!(pareto_paths(&projection, "S", "Z", edge_cost, edge_risk, &PathConstraints::new()).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Graph traversal algorithms for projections
 
+use super::adjacency::IndexedAdjacency;
 use crate::core::{GraphProjection, Node};
 use crate::error::{GraphError, Result};
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -510,9 +511,191 @@ fn count_paths_helper<P: GraphProjection>(
     count
 }
 
+// ============================================================================
+// Biconnectivity
+// ============================================================================
+
+/// Find articulation points: nodes whose removal disconnects their component
+///
+/// Edges are treated as undirected and self-loops are ignored, as in
+/// [`connected_components`]. Node IDs are returned sorted.
+pub fn articulation_points<P: GraphProjection>(projection: &P) -> Vec<String>
+where
+    P::Node: Node,
+{
+    let analysis = Biconnectivity::analyze(projection);
+    let mut points: Vec<String> = analysis
+        .articulation
+        .iter()
+        .enumerate()
+        .filter(|(_, &is_cut)| is_cut)
+        .map(|(node, _)| analysis.ids[node].clone())
+        .collect();
+    points.sort();
+    points
+}
+
+/// Find bridges: links whose removal disconnects their component
+///
+/// Edges are treated as undirected, so parallel edges and edges in opposite
+/// directions between the same two nodes form a single link. Each bridge is
+/// returned as a pair of node IDs with the smaller ID first, sorted.
+pub fn bridges<P: GraphProjection>(projection: &P) -> Vec<(String, String)>
+where
+    P::Node: Node,
+{
+    let analysis = Biconnectivity::analyze(projection);
+    let mut bridges: Vec<(String, String)> = analysis
+        .bridges
+        .iter()
+        .map(|&(u, v)| {
+            let (u, v) = (analysis.ids[u].clone(), analysis.ids[v].clone());
+            if u <= v { (u, v) } else { (v, u) }
+        })
+        .collect();
+    bridges.sort();
+    bridges
+}
+
+/// Find biconnected components: maximal sets of nodes that stay connected
+/// after removing any single node
+///
+/// Components partition the links rather than the nodes: an articulation
+/// point belongs to every component it joins, a bridge is a component of two
+/// nodes, and isolated nodes belong to none. Each component's node IDs are
+/// sorted, and components are sorted by their node IDs.
+pub fn biconnected_components<P: GraphProjection>(projection: &P) -> Vec<Vec<String>>
+where
+    P::Node: Node,
+{
+    let analysis = Biconnectivity::analyze(projection);
+    let mut components: Vec<Vec<String>> = analysis
+        .components
+        .iter()
+        .map(|component| component.iter().map(|&node| analysis.ids[node].clone()).collect())
+        .collect();
+    for component in &mut components {
+        component.sort();
+    }
+    components.sort();
+    components
+}
+
+/// Hopcroft-Tarjan depth-first search over the undirected view of a projection
+struct Biconnectivity {
+    /// Node IDs, sorted
+    ids: Vec<String>,
+    articulation: Vec<bool>,
+    bridges: Vec<(usize, usize)>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Biconnectivity {
+    fn analyze<P: GraphProjection>(projection: &P) -> Self
+    where
+        P::Node: Node,
+    {
+        let graph = IndexedAdjacency::build(projection);
+        let ids = graph.ids;
+
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+        for (source, targets) in graph.successors.iter().enumerate() {
+            for &target in targets {
+                if target != source {
+                    adjacency[source].push(target);
+                    adjacency[target].push(source);
+                }
+            }
+        }
+        for neighbors in &mut adjacency {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+
+        let unvisited = usize::MAX;
+        let mut discovery = vec![unvisited; ids.len()];
+        let mut low = vec![0; ids.len()];
+        let mut articulation = vec![false; ids.len()];
+        let mut bridges = Vec::new();
+        let mut components = Vec::new();
+        let mut time = 0;
+
+        for root in 0..ids.len() {
+            if discovery[root] != unvisited {
+                continue;
+            }
+            discovery[root] = time;
+            low[root] = time;
+            time += 1;
+
+            let mut root_children = 0;
+            // (node, parent, index of the next neighbour to look at)
+            let mut stack = vec![(root, root, 0)];
+            let mut edge_stack: Vec<(usize, usize)> = Vec::new();
+
+            while let Some(&mut (node, parent, ref mut next)) = stack.last_mut() {
+                if let Some(&neighbor) = adjacency[node].get(*next) {
+                    *next += 1;
+                    if discovery[neighbor] == unvisited {
+                        discovery[neighbor] = time;
+                        low[neighbor] = time;
+                        time += 1;
+                        edge_stack.push((node, neighbor));
+                        if node == root {
+                            root_children += 1;
+                        }
+                        stack.push((neighbor, node, 0));
+                    } else if neighbor != parent && discovery[neighbor] < discovery[node] {
+                        edge_stack.push((node, neighbor));
+                        low[node] = low[node].min(discovery[neighbor]);
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                if node == root {
+                    continue;
+                }
+                low[parent] = low[parent].min(low[node]);
+                if low[node] > discovery[parent] {
+                    bridges.push((parent, node));
+                }
+                if low[node] >= discovery[parent] {
+                    if parent != root {
+                        articulation[parent] = true;
+                    }
+                    let mut component = Vec::new();
+                    while let Some((u, v)) = edge_stack.pop() {
+                        component.push(u);
+                        component.push(v);
+                        if (u, v) == (parent, node) {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    component.dedup();
+                    components.push(component);
+                }
+            }
+
+            if root_children > 1 {
+                articulation[root] = true;
+            }
+        }
+
+        Self {
+            ids,
+            articulation,
+            bridges,
+            components,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::fixtures::create_graph_from_edges;
     use crate::graphs::workflow::{WorkflowNode, WorkflowEdge, WorkflowNodeType};
     use crate::core::projection_engine::GenericGraphProjection;
     use crate::core::GraphType;
@@ -1572,4 +1755,122 @@ mod tests {
         assert_eq!(result.count, 2);
         assert_eq!(result.largest_size, 2);
     }
-}
\ No newline at end of file
+
+    // ========== Biconnectivity Tests ==========
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|&(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    fn groups(groups: &[&[&str]]) -> Vec<Vec<String>> {
+        groups
+            .iter()
+            .map(|group| group.iter().map(|id| id.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_biconnectivity_linear_graph() {
+        let projection = create_linear_graph();
+
+        assert_eq!(articulation_points(&projection), vec!["B"]);
+        assert_eq!(bridges(&projection), pairs(&[("A", "B"), ("B", "C")]));
+        assert_eq!(biconnected_components(&projection), groups(&[&["A", "B"], &["B", "C"]]));
+    }
+
+    #[test]
+    fn test_biconnectivity_cycle_has_no_weak_points() {
+        let projection = create_branching_graph();
+
+        assert!(articulation_points(&projection).is_empty());
+        assert!(bridges(&projection).is_empty());
+        assert_eq!(biconnected_components(&projection), groups(&[&["A", "B", "C", "D"]]));
+    }
+
+    #[test]
+    fn test_biconnectivity_triangles_joined_by_bridge() {
+        // A-B-C triangle, C-D bridge, D-E-F triangle; edge directions don't matter
+        let projection = create_graph_from_edges(&[
+            ("A", "B"), ("B", "C"), ("C", "A"),
+            ("D", "C"),
+            ("D", "E"), ("F", "E"), ("D", "F"),
+        ]);
+
+        assert_eq!(articulation_points(&projection), vec!["C", "D"]);
+        assert_eq!(bridges(&projection), pairs(&[("C", "D")]));
+        assert_eq!(
+            biconnected_components(&projection),
+            groups(&[&["A", "B", "C"], &["C", "D"], &["D", "E", "F"]])
+        );
+    }
+
+    #[test]
+    fn test_biconnectivity_root_articulation_point() {
+        // Bowtie: two triangles sharing A, the first node the search starts from
+        let projection = create_graph_from_edges(&[
+            ("A", "B"), ("B", "C"), ("C", "A"),
+            ("A", "D"), ("D", "E"), ("E", "A"),
+        ]);
+
+        assert_eq!(articulation_points(&projection), vec!["A"]);
+        assert!(bridges(&projection).is_empty());
+        assert_eq!(biconnected_components(&projection), groups(&[&["A", "B", "C"], &["A", "D", "E"]]));
+    }
+
+    #[test]
+    fn test_biconnectivity_parallel_edges_and_self_loops() {
+        // A->B and B->A are a single link, so it is still a bridge
+        let projection = create_graph_from_edges(&[("A", "B"), ("B", "A"), ("B", "B"), ("C", "D")]);
+
+        assert!(articulation_points(&projection).is_empty());
+        assert_eq!(bridges(&projection), pairs(&[("A", "B"), ("C", "D")]));
+        assert_eq!(biconnected_components(&projection), groups(&[&["A", "B"], &["C", "D"]]));
+
+        let empty = create_empty_projection();
+        assert!(articulation_points(&empty).is_empty());
+        assert!(bridges(&empty).is_empty());
+        assert!(biconnected_components(&empty).is_empty());
+    }
+
+    #[test]
+    fn test_fragile_junctions_in_composed_graph() {
+        use crate::graphs::composed::{ComposedEdge, ComposedGraph, ComposedNode};
+
+        let (orders, billing) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut projection = ComposedGraph::new(Uuid::new_v4(), GraphType::ComposedGraph);
+        let nodes = [
+            ComposedNode::workflow_ref("orders", orders),
+            ComposedNode::transform("validate", "validate"),
+            ComposedNode::junction("hub", vec![orders, billing]),
+            ComposedNode::junction("mirror", vec![orders, billing]),
+            ComposedNode::concept_ref("billing", billing),
+        ];
+        for node in nodes {
+            projection.adjacency.insert(node.id.clone(), vec![]);
+            projection.nodes.insert(node.id.clone(), node);
+        }
+        // Only "hub" carries traffic into billing; "mirror" has a redundant route back
+        let edges = [
+            ComposedEdge::data_flow("f1", "orders", "validate", "stream"),
+            ComposedEdge::data_flow("f2", "validate", "hub", "stream"),
+            ComposedEdge::cross_graph_link("x1", "hub", "billing", orders, billing),
+            ComposedEdge::control_flow("c1", "orders", "mirror"),
+            ComposedEdge::control_flow("c2", "mirror", "validate"),
+        ];
+        for edge in edges {
+            projection.adjacency.get_mut(&edge.source).unwrap().push(edge.target.clone());
+            projection.edges.insert(edge.id.clone(), edge);
+        }
+
+        let cut_points = articulation_points(&projection);
+        let fragile: Vec<&str> = projection
+            .get_junctions()
+            .into_iter()
+            .map(|junction| junction.id.as_str())
+            .filter(|id| cut_points.iter().any(|point| point == id))
+            .collect();
+
+        assert_eq!(fragile, vec!["hub"]);
+        assert_eq!(bridges(&projection), pairs(&[("billing", "hub"), ("hub", "validate")]));
+    }
+}
@@ -0,0 +1,775 @@
+//! Content identifiers (CIDv1) for event payloads
+//!
+//! Payloads are encoded canonically as DAG-CBOR, hashed into a multihash
+//! (SHA2-256 or BLAKE3) and rendered as a multibase base32 string, so the
+//! CIDs stored alongside events in JetStream can be checked by any IPLD tool.
+//!
+//! Binary layout of a CIDv1:
+//!
+//! ```text
+//! <varint version=1><varint codec><varint hash code><varint digest length><digest>
+//! ```
+
+use crate::error::{GraphError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// CID version produced and accepted by this module
+pub const CID_VERSION: u64 = 1;
+
+/// Multibase prefix for lowercase RFC 4648 base32 without padding
+pub const MULTIBASE_BASE32: char = 'b';
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Hash functions supported for multihash digests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default)]
+pub enum HashAlgorithm {
+    /// SHA2-256 (multihash code 0x12)
+    #[default]
+    Sha2_256,
+    /// BLAKE3 with a 32 byte output (multihash code 0x1e)
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Multihash code of the algorithm
+    pub fn code(&self) -> u64 {
+        match self {
+            HashAlgorithm::Sha2_256 => 0x12,
+            HashAlgorithm::Blake3 => 0x1e,
+        }
+    }
+
+    /// Look up an algorithm by its multihash code
+    pub fn from_code(code: u64) -> Result<Self> {
+        match code {
+            0x12 => Ok(HashAlgorithm::Sha2_256),
+            0x1e => Ok(HashAlgorithm::Blake3),
+            other => Err(GraphError::InvalidCid(format!(
+                "unsupported multihash code 0x{:x}",
+                other
+            ))),
+        }
+    }
+
+    /// Length of the digest in bytes
+    pub fn digest_len(&self) -> usize {
+        32
+    }
+
+    /// Hash the given bytes
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::Sha2_256 => {
+                use sha2::Digest;
+                sha2::Sha256::digest(data).to_vec()
+            }
+            HashAlgorithm::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+}
+
+/// IPLD codecs (multicodec table) a CID can declare for its content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default)]
+pub enum Codec {
+    /// Raw bytes (0x55)
+    Raw,
+    /// MerkleDAG protobuf (0x70)
+    DagPb,
+    /// DAG-CBOR (0x71)
+    #[default]
+    DagCbor,
+    /// DAG-JSON (0x0129)
+    DagJson,
+}
+
+impl Codec {
+    /// Multicodec code
+    pub fn code(&self) -> u64 {
+        match self {
+            Codec::Raw => 0x55,
+            Codec::DagPb => 0x70,
+            Codec::DagCbor => 0x71,
+            Codec::DagJson => 0x0129,
+        }
+    }
+
+    /// Look up a codec by its multicodec code
+    pub fn from_code(code: u64) -> Result<Self> {
+        match code {
+            0x55 => Ok(Codec::Raw),
+            0x70 => Ok(Codec::DagPb),
+            0x71 => Ok(Codec::DagCbor),
+            0x0129 => Ok(Codec::DagJson),
+            other => Err(GraphError::InvalidCid(format!(
+                "unsupported codec 0x{:x}",
+                other
+            ))),
+        }
+    }
+
+    /// Human readable multicodec name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Codec::Raw => "raw",
+            Codec::DagPb => "dag-pb",
+            Codec::DagCbor => "dag-cbor",
+            Codec::DagJson => "dag-json",
+        }
+    }
+}
+
+/// A self-describing hash digest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Multihash {
+    algorithm: HashAlgorithm,
+    digest: Vec<u8>,
+}
+
+impl Multihash {
+    /// Hash data with the given algorithm
+    pub fn of(algorithm: HashAlgorithm, data: &[u8]) -> Self {
+        Self {
+            algorithm,
+            digest: algorithm.digest(data),
+        }
+    }
+
+    /// Wrap an existing digest, checking its length
+    pub fn from_digest(algorithm: HashAlgorithm, digest: Vec<u8>) -> Result<Self> {
+        if digest.len() != algorithm.digest_len() {
+            return Err(GraphError::InvalidCid(format!(
+                "digest length {} does not match {:?} ({} bytes)",
+                digest.len(),
+                algorithm,
+                algorithm.digest_len()
+            )));
+        }
+        Ok(Self { algorithm, digest })
+    }
+
+    /// Hash algorithm of the digest
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Raw digest bytes
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Binary multihash: `<varint code><varint length><digest>`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.digest.len() + 2);
+        write_varint(&mut bytes, self.algorithm.code());
+        write_varint(&mut bytes, self.digest.len() as u64);
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+
+    /// Parse a binary multihash, returning it and the number of bytes read
+    pub fn read_bytes(bytes: &[u8]) -> Result<(Self, usize)> {
+        let (code, mut offset) = read_varint(bytes)?;
+        let algorithm = HashAlgorithm::from_code(code)?;
+        let (len, read) = read_varint(&bytes[offset..])?;
+        offset += read;
+        let end = offset
+            .checked_add(len as usize)
+            .filter(|end| *end <= bytes.len())
+            .ok_or_else(|| GraphError::InvalidCid("truncated multihash digest".to_string()))?;
+        let hash = Self::from_digest(algorithm, bytes[offset..end].to_vec())?;
+        Ok((hash, end))
+    }
+}
+
+/// Version 1 content identifier
+///
+/// This is the single CID type used across the crate. Values can only be
+/// built from a codec and multihash or by parsing, so every `Cid` is a valid
+/// CIDv1. The canonical base32 string is kept alongside the parsed parts so
+/// it can be borrowed as a node id without re-encoding; equality, ordering
+/// and hashing all follow that string.
+#[derive(Debug, Clone)]
+pub struct Cid {
+    text: String,
+    codec: Codec,
+    hash: Multihash,
+}
+
+impl Cid {
+    /// Create a CID from a codec and multihash
+    pub fn new(codec: Codec, hash: Multihash) -> Self {
+        let mut cid = Self {
+            text: String::new(),
+            codec,
+            hash,
+        };
+        cid.text = format!("{}{}", MULTIBASE_BASE32, base32_encode(&cid.to_bytes()));
+        cid
+    }
+
+    /// CID of already-encoded bytes
+    pub fn for_bytes(codec: Codec, algorithm: HashAlgorithm, bytes: &[u8]) -> Self {
+        Self::new(codec, Multihash::of(algorithm, bytes))
+    }
+
+    /// CID of a JSON payload, encoded canonically as DAG-CBOR
+    pub fn for_payload(payload: &serde_json::Value, algorithm: HashAlgorithm) -> Self {
+        Self::for_bytes(Codec::DagCbor, algorithm, &encode_dag_cbor(payload))
+    }
+
+    /// Check that this CID addresses the given payload
+    pub fn matches_payload(&self, payload: &serde_json::Value) -> bool {
+        let encoded = match self.codec {
+            Codec::DagCbor => encode_dag_cbor(payload),
+            Codec::DagJson => encode_dag_json(payload),
+            Codec::Raw | Codec::DagPb => return false,
+        };
+        self.hash.algorithm().digest(&encoded) == self.hash.digest()
+    }
+
+    /// Codec of the addressed content
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Multihash of the addressed content
+    pub fn hash(&self) -> &Multihash {
+        &self.hash
+    }
+
+    /// CID version (always 1)
+    pub fn version(&self) -> u64 {
+        CID_VERSION
+    }
+
+    /// Canonical multibase string form
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Binary CID
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40);
+        write_varint(&mut bytes, CID_VERSION);
+        write_varint(&mut bytes, self.codec.code());
+        bytes.extend_from_slice(&self.hash.to_bytes());
+        bytes
+    }
+
+    /// Parse and validate a binary CID
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (cid, read) = Self::read_bytes(bytes)?;
+        if read != bytes.len() {
+            return Err(GraphError::InvalidCid(
+                "trailing bytes after multihash".to_string(),
+            ));
+        }
+        Ok(cid)
+    }
+
+    /// Read a binary CID from the front of a buffer, returning bytes consumed
+    pub fn read_bytes(bytes: &[u8]) -> Result<(Self, usize)> {
+        let (version, mut offset) = read_varint(bytes)?;
+        if version != CID_VERSION {
+            return Err(GraphError::InvalidCid(format!(
+                "unsupported CID version {}",
+                version
+            )));
+        }
+        let (codec, read) = read_varint(&bytes[offset..])?;
+        offset += read;
+        let codec = Codec::from_code(codec)?;
+        let (hash, read) = Multihash::read_bytes(&bytes[offset..])?;
+        Ok((Self::new(codec, hash), offset + read))
+    }
+
+    /// Parse and validate the multibase string form
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(MULTIBASE_BASE32) => Self::from_bytes(&base32_decode(chars.as_str())?),
+            Some('Q') if s.starts_with("Qm") => Err(GraphError::InvalidCid(format!(
+                "CIDv0 is not supported: {}",
+                s
+            ))),
+            Some(prefix) => Err(GraphError::InvalidCid(format!(
+                "unsupported multibase prefix '{}'",
+                prefix
+            ))),
+            None => Err(GraphError::InvalidCid("empty CID".to_string())),
+        }
+    }
+}
+
+impl PartialEq for Cid {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for Cid {}
+
+impl std::hash::Hash for Cid {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+    }
+}
+
+impl PartialOrd for Cid {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cid {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.text.cmp(&other.text)
+    }
+}
+
+impl AsRef<str> for Cid {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl FromStr for Cid {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for Cid {
+    type Error = GraphError;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Cid {
+    type Error = GraphError;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl From<Cid> for String {
+    fn from(cid: Cid) -> Self {
+        cid.text
+    }
+}
+
+impl Serialize for Cid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Cid::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deterministic CID for a label, for tests that need distinct valid CIDs
+#[cfg(test)]
+pub(crate) fn test_cid(label: &str) -> Cid {
+    Cid::for_bytes(Codec::Raw, HashAlgorithm::Sha2_256, label.as_bytes())
+}
+
+/// Encode a JSON value as canonical DAG-CBOR
+///
+/// Map keys are sorted length-first then bytewise, integers use the smallest
+/// header, and every float is written as a 64-bit IEEE 754 value.
+pub fn encode_dag_cbor(value: &serde_json::Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_cbor(&mut out, value);
+    out
+}
+
+/// Encode a JSON value as canonical DAG-JSON (sorted keys, no whitespace)
+pub fn encode_dag_json(value: &serde_json::Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_json(&mut out, value);
+    out
+}
+
+fn write_cbor_header(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    if value < 24 {
+        out.push(major | value as u8);
+    } else if value <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(value as u8);
+    } else if value <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(value as u16).to_be_bytes());
+    } else if value <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(value as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_cbor(out: &mut Vec<u8>, value: &serde_json::Value) {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                write_cbor_header(out, 0, u);
+            } else if let Some(i) = n.as_i64() {
+                // Negative integers encode -1 - n
+                write_cbor_header(out, 1, (-1 - i) as u64);
+            } else {
+                out.push(0xfb);
+                out.extend_from_slice(&n.as_f64().unwrap_or(0.0).to_be_bytes());
+            }
+        }
+        Value::String(s) => {
+            write_cbor_header(out, 3, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            write_cbor_header(out, 4, items.len() as u64);
+            for item in items {
+                write_cbor(out, item);
+            }
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| {
+                a.len().cmp(&b.len()).then_with(|| a.as_bytes().cmp(b.as_bytes()))
+            });
+            write_cbor_header(out, 5, entries.len() as u64);
+            for (key, item) in entries {
+                write_cbor_header(out, 3, key.len() as u64);
+                out.extend_from_slice(key.as_bytes());
+                write_cbor(out, item);
+            }
+        }
+    }
+}
+
+fn write_json(out: &mut Vec<u8>, value: &serde_json::Value) {
+    use serde_json::Value;
+
+    match value {
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_json(out, item);
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            out.push(b'{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                // Serializing a string cannot fail
+                out.extend_from_slice(serde_json::to_string(key).unwrap_or_default().as_bytes());
+                out.push(b':');
+                write_json(out, item);
+            }
+            out.push(b'}');
+        }
+        scalar => out.extend_from_slice(scalar.to_string().as_bytes()),
+    }
+}
+
+/// Append an unsigned LEB128 varint
+pub fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Read an unsigned LEB128 varint, returning the value and bytes consumed
+pub fn read_varint(bytes: &[u8]) -> Result<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(GraphError::InvalidCid("truncated or oversized varint".to_string()))
+}
+
+/// Lowercase RFC 4648 base32 without padding
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// Decode lowercase RFC 4648 base32 without padding
+pub fn base32_decode(s: &str) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in s.bytes() {
+        let value = match c {
+            b'a'..=b'z' => c - b'a',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => {
+                return Err(GraphError::InvalidCid(format!(
+                    "invalid base32 character '{}'",
+                    c as char
+                )))
+            }
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32_rfc4648_vectors() {
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "my");
+        assert_eq!(base32_encode(b"fo"), "mzxq");
+        assert_eq!(base32_encode(b"foobar"), "mzxw6ytboi");
+        assert_eq!(base32_decode("mzxw6ytboi").unwrap(), b"foobar");
+        assert!(base32_decode("MZXW").is_err());
+    }
+
+    #[test]
+    fn test_varint_roundtrip() {
+        for value in [0u64, 1, 127, 128, 300, 0x0129, u32::MAX as u64, u64::MAX >> 1] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+            assert_eq!(read_varint(&bytes).unwrap(), (value, bytes.len()));
+        }
+
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 300);
+        assert_eq!(bytes, vec![0xac, 0x02]);
+        assert!(read_varint(&[0x80]).is_err());
+    }
+
+    #[test]
+    fn test_known_raw_cid() {
+        // CIDv1 of the empty file as produced by `ipfs add --cid-version 1 --raw-leaves`
+        let cid = Cid::for_bytes(Codec::Raw, HashAlgorithm::Sha2_256, b"");
+        assert_eq!(
+            cid.to_string(),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+    }
+
+    #[test]
+    fn test_dag_cbor_encoding() {
+        assert_eq!(encode_dag_cbor(&serde_json::json!({"a": 1})), vec![0xa1, 0x61, 0x61, 0x01]);
+        assert_eq!(encode_dag_cbor(&serde_json::json!(-1)), vec![0x20]);
+        assert_eq!(encode_dag_cbor(&serde_json::json!(500)), vec![0x19, 0x01, 0xf4]);
+        assert_eq!(
+            encode_dag_cbor(&serde_json::json!(1.5)),
+            vec![0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]
+        );
+
+        // Keys sort length-first: "b" before "aa"
+        assert_eq!(
+            encode_dag_cbor(&serde_json::json!({"aa": null, "b": true})),
+            vec![0xa2, 0x61, b'b', 0xf5, 0x62, b'a', b'a', 0xf6]
+        );
+    }
+
+    #[test]
+    fn test_payload_cid_is_stable() {
+        let cid = Cid::for_payload(&serde_json::json!({"a": 1}), HashAlgorithm::Sha2_256);
+        assert_eq!(
+            cid.to_string(),
+            "bafyreihltcnuuyqp2jm24aqydpnlj7b6w3ogwrplomrjtg5rifv44mmjey"
+        );
+        assert_eq!(cid.codec(), Codec::DagCbor);
+    }
+
+    #[test]
+    fn test_payload_cid_ignores_key_order() {
+        let a: serde_json::Value = serde_json::from_str(r#"{"x": 1, "y": [1, 2]}"#).unwrap();
+        let b: serde_json::Value = serde_json::from_str(r#"{"y": [1, 2], "x": 1}"#).unwrap();
+        assert_eq!(
+            Cid::for_payload(&a, HashAlgorithm::Blake3),
+            Cid::for_payload(&b, HashAlgorithm::Blake3)
+        );
+    }
+
+    #[test]
+    fn test_cid_string_roundtrip() {
+        let payload = serde_json::json!({"type": "NodeAdded", "node_id": "n1"});
+        for algorithm in [HashAlgorithm::Sha2_256, HashAlgorithm::Blake3] {
+            let cid = Cid::for_payload(&payload, algorithm);
+            let parsed: Cid = cid.to_string().parse().unwrap();
+            assert_eq!(parsed, cid);
+            assert_eq!(parsed.hash().algorithm(), algorithm);
+            assert!(parsed.matches_payload(&payload));
+            assert!(!parsed.matches_payload(&serde_json::json!({})));
+        }
+    }
+
+    #[test]
+    fn test_cid_serde_validates() {
+        let cid = test_cid("serde");
+        let json = serde_json::to_string(&cid).unwrap();
+        assert_eq!(json, format!("\"{}\"", cid));
+        assert_eq!(serde_json::from_str::<Cid>(&json).unwrap(), cid);
+
+        assert!(serde_json::from_str::<Cid>("\"QmNotACid\"").is_err());
+        assert!(serde_json::from_str::<Cid>("\"bafy\"").is_err());
+    }
+
+    #[test]
+    fn test_cid_ord_hash_follow_string_form() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let a = test_cid("a");
+        let b = test_cid("b");
+        assert_eq!(a.cmp(&b), a.as_str().cmp(b.as_str()));
+
+        let hashed: HashSet<_> = [a.clone(), b.clone(), a.clone()].into_iter().collect();
+        assert_eq!(hashed.len(), 2);
+
+        let ordered: BTreeSet<_> = [b.clone(), a.clone()].into_iter().collect();
+        let strings: Vec<_> = ordered.iter().map(|c| c.as_str()).collect();
+        let mut expected = vec![a.as_str(), b.as_str()];
+        expected.sort();
+        assert_eq!(strings, expected);
+    }
+
+    #[test]
+    fn test_cid_conversions() {
+        let cid = test_cid("conversions");
+        let text: String = cid.clone().into();
+        assert_eq!(Cid::try_from(text.as_str()).unwrap(), cid);
+        assert_eq!(Cid::try_from(text).unwrap(), cid);
+        assert_eq!(cid.version(), 1);
+        assert_eq!(cid.as_ref(), cid.as_str());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_codec_and_hash() {
+        let digest = HashAlgorithm::Sha2_256.digest(b"x");
+
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 1);
+        write_varint(&mut bytes, 0x9999);
+        write_varint(&mut bytes, 0x12);
+        write_varint(&mut bytes, 32);
+        bytes.extend_from_slice(&digest);
+        assert!(Cid::from_bytes(&bytes).is_err());
+
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 1);
+        write_varint(&mut bytes, 0x71);
+        write_varint(&mut bytes, 0x13);
+        write_varint(&mut bytes, 32);
+        bytes.extend_from_slice(&digest);
+        assert!(Cid::from_bytes(&bytes).is_err());
+
+        // Digest length must match the algorithm
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 1);
+        write_varint(&mut bytes, 0x71);
+        write_varint(&mut bytes, 0x12);
+        write_varint(&mut bytes, 16);
+        bytes.extend_from_slice(&digest[..16]);
+        assert!(Cid::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_dag_json_cid_matches_payload() {
+        let payload = serde_json::json!({"b": [true, null], "a": "x"});
+        assert_eq!(encode_dag_json(&payload), br#"{"a":"x","b":[true,null]}"#.to_vec());
+
+        let cid = Cid::for_bytes(Codec::DagJson, HashAlgorithm::Sha2_256, &encode_dag_json(&payload));
+        assert!(cid.matches_payload(&payload));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_cids() {
+        assert!(Cid::parse("").is_err());
+        assert!(Cid::parse("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG").is_err());
+        assert!(Cid::parse("zabc").is_err());
+        assert!(Cid::parse("bafy").is_err());
+
+        // Wrong version
+        let mut bytes = Cid::for_bytes(Codec::Raw, HashAlgorithm::Sha2_256, b"x").to_bytes();
+        bytes[0] = 2;
+        assert!(Cid::from_bytes(&bytes).is_err());
+
+        // Truncated digest
+        let bytes = Cid::for_bytes(Codec::Raw, HashAlgorithm::Sha2_256, b"x").to_bytes();
+        assert!(Cid::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_read_bytes_reports_consumed_length() {
+        let first = test_cid("first");
+        let second = Cid::for_payload(&serde_json::json!({"a": 1}), HashAlgorithm::Blake3);
+        let mut bytes = first.to_bytes();
+        bytes.extend_from_slice(&second.to_bytes());
+
+        let (read_first, used) = Cid::read_bytes(&bytes).unwrap();
+        assert_eq!(read_first, first);
+        assert_eq!(used, first.to_bytes().len());
+
+        let (read_second, rest) = Cid::read_bytes(&bytes[used..]).unwrap();
+        assert_eq!(read_second, second);
+        assert_eq!(used + rest, bytes.len());
+
+        // Concatenated CIDs are not a single valid CID
+        assert!(Cid::from_bytes(&bytes).is_err());
+    }
+}
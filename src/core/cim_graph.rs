@@ -77,7 +77,7 @@ pub struct GraphEvent {
 }
 
 /// Event data variants - these define ALL possible state changes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EventData {
     /// Graph initialized
     GraphInitialized {
@@ -0,0 +1,685 @@
+//! Structural diff between two versions of a projection
+//!
+//! [`GraphDiff::between`] matches nodes and edges by id and compares them
+//! through the `*Added` event that would create them ([`ToEventData`]), so a
+//! diff only reports what events can express. Data changes are listed as
+//! JSON-patch operations for review, and [`GraphDiff::to_events`] turns the
+//! diff back into the shortest event sequence that reproduces it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use uuid::Uuid;
+
+use crate::core::cim_graph::{EventData, GraphProjection};
+use crate::core::projection_engine::ToEventData;
+use crate::core::{Edge, Node};
+
+/// One JSON-patch (RFC 6902) operation; `path` is a JSON pointer into the data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Add a field that did not exist
+    Add {
+        /// Location of the new field
+        path: String,
+        /// Value of the new field
+        value: Value,
+    },
+    /// Remove a field
+    Remove {
+        /// Location of the removed field
+        path: String,
+    },
+    /// Replace a value
+    Replace {
+        /// Location of the replaced value
+        path: String,
+        /// New value
+        value: Value,
+    },
+}
+
+/// A node or edge, described by the `*Added` event that creates it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementState {
+    /// Node or edge id
+    pub id: String,
+    /// `node_type` or `edge_type`
+    pub element_type: String,
+    /// Source and target node ids (edges only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoints: Option<(String, String)>,
+    /// Event payload
+    pub data: Value,
+}
+
+impl ElementState {
+    fn of_node<N: Node + ToEventData>(node: &N) -> Self {
+        Self {
+            id: node.id(),
+            element_type: node.event_type(),
+            endpoints: None,
+            data: node.event_payload(),
+        }
+    }
+
+    fn of_edge<E: Edge + ToEventData>(edge: &E) -> Self {
+        Self {
+            id: edge.id(),
+            element_type: edge.event_type(),
+            endpoints: Some((edge.source(), edge.target())),
+            data: edge.event_payload(),
+        }
+    }
+
+    /// Whether this edge starts or ends at one of `nodes`
+    fn touches(&self, nodes: &HashSet<&str>) -> bool {
+        match &self.endpoints {
+            Some((source, target)) => nodes.contains(source.as_str()) || nodes.contains(target.as_str()),
+            None => false,
+        }
+    }
+
+    fn added_event(&self) -> EventData {
+        match &self.endpoints {
+            Some((source, target)) => EventData::EdgeAdded {
+                edge_id: self.id.clone(),
+                source_id: source.clone(),
+                target_id: target.clone(),
+                edge_type: self.element_type.clone(),
+                data: self.data.clone(),
+            },
+            None => EventData::NodeAdded {
+                node_id: self.id.clone(),
+                node_type: self.element_type.clone(),
+                data: self.data.clone(),
+            },
+        }
+    }
+}
+
+/// How one node or edge differs between the two versions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ElementChange {
+    /// Only present in the newer version
+    Added {
+        /// The new element
+        after: ElementState,
+    },
+    /// Only present in the older version
+    Removed {
+        /// The removed element
+        before: ElementState,
+    },
+    /// Present in both with a different type, endpoints or data
+    Changed {
+        /// The element in the older version
+        before: ElementState,
+        /// The element in the newer version
+        after: ElementState,
+        /// Changes from `before.data` to `after.data`
+        patch: Vec<PatchOperation>,
+        /// `*Updated` payload that applies the change, or `None` when the
+        /// element has to be removed and added again
+        update: Option<Value>,
+    },
+}
+
+impl ElementChange {
+    /// Id of the changed node or edge
+    pub fn id(&self) -> &str {
+        match self {
+            ElementChange::Added { after } => &after.id,
+            ElementChange::Removed { before } => &before.id,
+            ElementChange::Changed { before, .. } => &before.id,
+        }
+    }
+
+    /// `*Updated` payload of a change that can be applied in place
+    fn update(&self) -> Option<&Value> {
+        match self {
+            ElementChange::Changed { update, .. } => update.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the element must be removed and added again
+    fn is_replacement(&self) -> bool {
+        matches!(self, ElementChange::Changed { update: None, .. })
+    }
+}
+
+/// Nodes and edges added, removed or changed between two projection versions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphDiff {
+    /// Aggregate of the newer version
+    pub aggregate_id: Uuid,
+    /// Version of the older projection
+    pub from_version: u64,
+    /// Version of the newer projection
+    pub to_version: u64,
+    /// Node changes, ordered by id
+    pub nodes: Vec<ElementChange>,
+    /// Edge changes, ordered by id
+    pub edges: Vec<ElementChange>,
+    /// Unchanged edges of replaced nodes; removing a node drops its edges,
+    /// so these are added again
+    pub reattached_edges: Vec<ElementState>,
+}
+
+impl GraphDiff {
+    /// Compare two versions of a projection
+    pub fn between<P>(from: &P, to: &P) -> Self
+    where
+        P: GraphProjection,
+        P::Node: Node + ToEventData,
+        P::Edge: Edge + ToEventData,
+    {
+        let nodes = diff_elements(
+            from.nodes().into_iter().map(ElementState::of_node).collect(),
+            to.nodes().into_iter().map(ElementState::of_node).collect(),
+            merged::<P::Node>,
+        );
+
+        let to_edges: Vec<ElementState> = to.edges().into_iter().map(ElementState::of_edge).collect();
+        let edges = diff_elements(
+            from.edges().into_iter().map(ElementState::of_edge).collect(),
+            to_edges.clone(),
+            merged::<P::Edge>,
+        );
+
+        let replaced: HashSet<&str> = nodes
+            .iter()
+            .filter(|change| change.is_replacement())
+            .map(ElementChange::id)
+            .collect();
+        let changed_edges: HashSet<&str> = edges.iter().map(ElementChange::id).collect();
+        let reattached_edges = to_edges
+            .into_iter()
+            .filter(|edge| edge.touches(&replaced) && !changed_edges.contains(edge.id.as_str()))
+            .collect();
+
+        Self {
+            aggregate_id: to.aggregate_id(),
+            from_version: from.version(),
+            to_version: to.version(),
+            nodes,
+            edges,
+            reattached_edges,
+        }
+    }
+
+    /// Whether the two versions have the same nodes and edges
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty()
+    }
+
+    /// Events that turn the older projection into the newer one
+    ///
+    /// Edge removals come first, then node removals, node additions and
+    /// updates, and finally edge additions and updates. Edges that go away
+    /// with a removed node are not removed separately.
+    pub fn to_events(&self) -> Vec<EventData> {
+        let gone: HashSet<&str> = self
+            .nodes
+            .iter()
+            .filter(|change| !matches!(change, ElementChange::Added { .. }) && change.update().is_none())
+            .map(ElementChange::id)
+            .collect();
+
+        let mut edge_removals = Vec::new();
+        let mut edge_additions = Vec::new();
+        let mut edge_updates = Vec::new();
+        for change in &self.edges {
+            match change {
+                ElementChange::Added { after } => edge_additions.push(after.added_event()),
+                ElementChange::Removed { before } => {
+                    if !before.touches(&gone) {
+                        edge_removals.push(EventData::EdgeRemoved { edge_id: before.id.clone() });
+                    }
+                }
+                ElementChange::Changed { before, after, update, .. } => match update {
+                    Some(data) if !before.touches(&gone) => edge_updates.push(EventData::EdgeUpdated {
+                        edge_id: before.id.clone(),
+                        data: data.clone(),
+                    }),
+                    _ => {
+                        if !before.touches(&gone) {
+                            edge_removals.push(EventData::EdgeRemoved { edge_id: before.id.clone() });
+                        }
+                        edge_additions.push(after.added_event());
+                    }
+                },
+            }
+        }
+        edge_additions.extend(self.reattached_edges.iter().map(ElementState::added_event));
+
+        let mut node_removals = Vec::new();
+        let mut node_additions = Vec::new();
+        let mut node_updates = Vec::new();
+        for change in &self.nodes {
+            match change {
+                ElementChange::Added { after } => node_additions.push(after.added_event()),
+                ElementChange::Removed { before } => {
+                    node_removals.push(EventData::NodeRemoved { node_id: before.id.clone() });
+                }
+                ElementChange::Changed { before, after, update, .. } => match update {
+                    Some(data) => node_updates.push(EventData::NodeUpdated {
+                        node_id: before.id.clone(),
+                        data: data.clone(),
+                    }),
+                    None => {
+                        node_removals.push(EventData::NodeRemoved { node_id: before.id.clone() });
+                        node_additions.push(after.added_event());
+                    }
+                },
+            }
+        }
+
+        edge_removals
+            .into_iter()
+            .chain(node_removals)
+            .chain(node_additions)
+            .chain(node_updates)
+            .chain(edge_additions)
+            .chain(edge_updates)
+            .collect()
+    }
+}
+
+/// Match elements by id and describe every difference
+///
+/// `apply` rebuilds an element from its state, merges an `*Updated` payload
+/// into it and returns the resulting state, if the element type allows it.
+fn diff_elements(
+    before: Vec<ElementState>,
+    after: Vec<ElementState>,
+    apply: impl Fn(&ElementState, &Value) -> Option<ElementState>,
+) -> Vec<ElementChange> {
+    let before: BTreeMap<String, ElementState> = before.into_iter().map(|s| (s.id.clone(), s)).collect();
+    let mut after: BTreeMap<String, ElementState> = after.into_iter().map(|s| (s.id.clone(), s)).collect();
+
+    let mut changes = Vec::new();
+    for (id, old) in before {
+        let new = match after.remove(&id) {
+            Some(new) => new,
+            None => {
+                changes.push(ElementChange::Removed { before: old });
+                continue;
+            }
+        };
+        if old == new {
+            continue;
+        }
+
+        let update = if old.element_type == new.element_type && old.endpoints == new.endpoints {
+            merge_patches(&old.data, &new.data)
+                .into_iter()
+                .find(|patch| apply(&old, patch).as_ref() == Some(&new))
+        } else {
+            None
+        };
+        changes.push(ElementChange::Changed {
+            patch: json_patch(&old.data, &new.data),
+            before: old,
+            after: new,
+            update,
+        });
+    }
+    changes.extend(after.into_values().map(|after| ElementChange::Added { after }));
+    changes.sort_by(|a, b| a.id().cmp(b.id()));
+    changes
+}
+
+/// State of an element after merging an `*Updated` payload into it
+fn merged<T>(state: &ElementState, update: &Value) -> Option<ElementState>
+where
+    T: ToEventData,
+{
+    let mut element = T::from_event_data(&state.added_event())?;
+    element.merge_event_data(update);
+    Some(ElementState {
+        element_type: element.event_type(),
+        data: element.event_payload(),
+        ..state.clone()
+    })
+}
+
+/// Candidate `*Updated` payloads from `before` to `after`: a shallow merge
+/// patch of the top-level fields, then a deep one (RFC 7386) if it differs
+fn merge_patches(before: &Value, after: &Value) -> Vec<Value> {
+    let (old, new) = match (before.as_object(), after.as_object()) {
+        (Some(old), Some(new)) => (old, new),
+        _ => return Vec::new(),
+    };
+
+    let mut shallow = serde_json::Map::new();
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        shallow.insert(key.clone(), Value::Null);
+    }
+    for (key, value) in new {
+        if old.get(key) != Some(value) {
+            shallow.insert(key.clone(), value.clone());
+        }
+    }
+    let shallow = Value::Object(shallow);
+
+    let deep = deep_merge_patch(before, after);
+    if deep == shallow {
+        vec![shallow]
+    } else {
+        vec![shallow, deep]
+    }
+}
+
+/// RFC 7386 merge patch from `before` to `after`
+fn deep_merge_patch(before: &Value, after: &Value) -> Value {
+    let (old, new) = match (before.as_object(), after.as_object()) {
+        (Some(old), Some(new)) => (old, new),
+        _ => return after.clone(),
+    };
+
+    let mut patch = serde_json::Map::new();
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    for (key, value) in new {
+        match old.get(key) {
+            Some(previous) if previous == value => {}
+            Some(previous) => {
+                patch.insert(key.clone(), deep_merge_patch(previous, value));
+            }
+            None => {
+                patch.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(patch)
+}
+
+/// RFC 6902 operations from `before` to `after`; arrays are replaced whole
+pub fn json_patch(before: &Value, after: &Value) -> Vec<PatchOperation> {
+    let mut operations = Vec::new();
+    diff_values("", before, after, &mut operations);
+    operations
+}
+
+fn diff_values(path: &str, before: &Value, after: &Value, operations: &mut Vec<PatchOperation>) {
+    let (old, new) = match (before.as_object(), after.as_object()) {
+        (Some(old), Some(new)) => (old, new),
+        _ => {
+            if before != after {
+                operations.push(PatchOperation::Replace {
+                    path: path.to_string(),
+                    value: after.clone(),
+                });
+            }
+            return;
+        }
+    };
+
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for key in keys {
+        let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+        match (old.get(key), new.get(key)) {
+            (Some(_), None) => operations.push(PatchOperation::Remove { path: child }),
+            (None, Some(value)) => operations.push(PatchOperation::Add {
+                path: child,
+                value: value.clone(),
+            }),
+            (Some(previous), Some(value)) => diff_values(&child, previous, value, operations),
+            (None, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cim_graph::GraphEvent;
+    use crate::core::projection_engine::{GenericGraphProjection, ProjectionEngine};
+    use crate::core::test_support::{edge_added, node_added};
+    use crate::graphs::concept::{ConceptEdge, ConceptNode};
+    use crate::graphs::workflow::{WorkflowEdge, WorkflowNode};
+    use chrono::Utc;
+    use serde_json::json;
+
+    type Workflow = GenericGraphProjection<WorkflowNode, WorkflowEdge>;
+    type Concepts = GenericGraphProjection<ConceptNode, ConceptEdge>;
+
+    fn events(aggregate_id: Uuid, first_sequence: u64, data: Vec<EventData>) -> Vec<GraphEvent> {
+        data.into_iter()
+            .zip(first_sequence..)
+            .map(|(data, sequence)| GraphEvent {
+                event_id: Uuid::new_v4(),
+                aggregate_id,
+                sequence,
+                subject: format!("cim.graph.evt.{}", aggregate_id),
+                timestamp: Utc::now(),
+                correlation_id: Uuid::new_v4(),
+                causation_id: None,
+                data,
+            })
+            .collect()
+    }
+
+    fn workflow(aggregate_id: Uuid, data: Vec<EventData>) -> Workflow {
+        ProjectionEngine::new().project(events(aggregate_id, 1, data))
+    }
+
+    /// Apply a diff's events to a copy of `from` and check it now matches `to`
+    fn assert_reproduces<N, E>(from: &GenericGraphProjection<N, E>, to: &GenericGraphProjection<N, E>)
+    where
+        N: Node + ToEventData,
+        E: Edge + ToEventData,
+    {
+        let diff = GraphDiff::between(from, to);
+        let engine = ProjectionEngine::new();
+        let mut replayed = from.clone();
+        for event in events(from.aggregate_id, from.version + 1, diff.to_events()) {
+            engine.apply(&mut replayed, &event);
+        }
+        let remaining = GraphDiff::between(&replayed, to);
+        assert!(remaining.is_empty(), "diff not reproduced: {:?}", remaining);
+    }
+
+    // ========== JSON Patch Tests ==========
+
+    #[test]
+    fn test_json_patch_operations() {
+        let before = json!({"name": "a", "gone": 1, "nested": {"x": 1, "y": 2}, "list": [1]});
+        let after = json!({"name": "b", "new": true, "nested": {"x": 1, "y": 3}, "list": [1, 2]});
+
+        assert_eq!(
+            json_patch(&before, &after),
+            vec![
+                PatchOperation::Remove { path: "/gone".to_string() },
+                PatchOperation::Replace { path: "/list".to_string(), value: json!([1, 2]) },
+                PatchOperation::Replace { path: "/name".to_string(), value: json!("b") },
+                PatchOperation::Replace { path: "/nested/y".to_string(), value: json!(3) },
+                PatchOperation::Add { path: "/new".to_string(), value: json!(true) },
+            ]
+        );
+        assert!(json_patch(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_json_patch_escapes_pointer_segments() {
+        let patch = json_patch(&json!({}), &json!({"a/b~c": 1}));
+        assert_eq!(patch, vec![PatchOperation::Add { path: "/a~1b~0c".to_string(), value: json!(1) }]);
+
+        let serialized = serde_json::to_value(&patch[0]).unwrap();
+        assert_eq!(serialized, json!({"op": "add", "path": "/a~1b~0c", "value": 1}));
+    }
+
+    // ========== GraphDiff Tests ==========
+
+    #[test]
+    fn test_identical_projections_have_empty_diff() {
+        let aggregate_id = Uuid::new_v4();
+        let data = vec![node_added("a", "start", json!({})), node_added("b", "end", json!({}))];
+        let diff = GraphDiff::between(&workflow(aggregate_id, data.clone()), &workflow(aggregate_id, data));
+
+        assert!(diff.is_empty());
+        assert!(diff.to_events().is_empty());
+    }
+
+    #[test]
+    fn test_diff_lists_added_removed_and_changed() {
+        let aggregate_id = Uuid::new_v4();
+        let from = workflow(aggregate_id, vec![
+            node_added("start", "start", json!({})),
+            node_added("review", "state", json!({"name": "Review", "owner": "ann"})),
+            node_added("old", "state", json!({})),
+            edge_added("t1", "start", "review", "transition", json!({})),
+        ]);
+        let to = workflow(aggregate_id, vec![
+            node_added("start", "start", json!({})),
+            node_added("review", "state", json!({"name": "Approval", "owner": "ann"})),
+            node_added("done", "end", json!({})),
+            edge_added("t1", "start", "review", "transition", json!({})),
+            edge_added("t2", "review", "done", "transition", json!({"trigger": "approve"})),
+        ]);
+
+        let diff = GraphDiff::between(&from, &to);
+        assert_eq!(diff.from_version, 4);
+        assert_eq!(diff.to_version, 5);
+
+        let ids: Vec<&str> = diff.nodes.iter().map(ElementChange::id).collect();
+        assert_eq!(ids, vec!["done", "old", "review"]);
+        assert!(matches!(&diff.nodes[0], ElementChange::Added { .. }));
+        assert!(matches!(&diff.nodes[1], ElementChange::Removed { .. }));
+        match &diff.nodes[2] {
+            ElementChange::Changed { patch, update, .. } => {
+                assert_eq!(patch, &vec![PatchOperation::Replace {
+                    path: "/name".to_string(),
+                    value: json!("Approval"),
+                }]);
+                assert_eq!(update, &Some(json!({"name": "Approval"})));
+            }
+            other => panic!("expected a change, got {:?}", other),
+        }
+
+        assert_eq!(diff.edges.len(), 1);
+        assert!(matches!(&diff.edges[0], ElementChange::Added { after } if after.id == "t2"));
+    }
+
+    #[test]
+    fn test_to_events_is_minimal_for_data_changes() {
+        let aggregate_id = Uuid::new_v4();
+        let from = workflow(aggregate_id, vec![node_added("review", "state", json!({"name": "Review", "sla": 3}))]);
+        let to = workflow(aggregate_id, vec![node_added("review", "state", json!({"name": "Review"}))]);
+
+        let events = GraphDiff::between(&from, &to).to_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            EventData::NodeUpdated { node_id, data } if node_id == "review" && *data == json!({"sla": null})
+        ));
+        assert_reproduces(&from, &to);
+    }
+
+    #[test]
+    fn test_type_change_replaces_node_and_reattaches_edges() {
+        let aggregate_id = Uuid::new_v4();
+        let from = workflow(aggregate_id, vec![
+            node_added("a", "start", json!({})),
+            node_added("b", "state", json!({"name": "B"})),
+            node_added("c", "end", json!({})),
+            edge_added("ab", "a", "b", "transition", json!({})),
+            edge_added("bc", "b", "c", "transition", json!({})),
+        ]);
+        let to = workflow(aggregate_id, vec![
+            node_added("a", "start", json!({})),
+            node_added("b", "decision", json!({"condition": "ok"})),
+            node_added("c", "end", json!({})),
+            edge_added("ab", "a", "b", "transition", json!({})),
+            edge_added("bc", "b", "c", "conditional", json!({"condition": "ok"})),
+        ]);
+
+        let diff = GraphDiff::between(&from, &to);
+        assert!(matches!(&diff.nodes[0], ElementChange::Changed { update: None, .. }));
+        let reattached: Vec<&str> = diff.reattached_edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(reattached, vec!["ab"]);
+
+        let events = diff.to_events();
+        assert!(matches!(&events[0], EventData::NodeRemoved { node_id } if node_id == "b"));
+        // The changed edge went away with its node, so it is added rather than removed again
+        assert!(!events.iter().any(|e| matches!(e, EventData::EdgeRemoved { .. })));
+        assert_reproduces(&from, &to);
+    }
+
+    #[test]
+    fn test_edge_endpoint_change_replaces_edge() {
+        let aggregate_id = Uuid::new_v4();
+        let nodes = || {
+            vec![
+                node_added("a", "start", json!({})),
+                node_added("b", "end", json!({})),
+                node_added("c", "end", json!({})),
+            ]
+        };
+        let from = workflow(aggregate_id, [nodes(), vec![edge_added("e", "a", "b", "transition", json!({}))]].concat());
+        let to = workflow(aggregate_id, [nodes(), vec![edge_added("e", "a", "c", "transition", json!({}))]].concat());
+
+        let events = GraphDiff::between(&from, &to).to_events();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], EventData::EdgeRemoved { edge_id } if edge_id == "e"));
+        assert!(matches!(&events[1], EventData::EdgeAdded { target_id, .. } if target_id == "c"));
+        assert_reproduces(&from, &to);
+    }
+
+    #[test]
+    fn test_removed_node_takes_its_edges() {
+        let aggregate_id = Uuid::new_v4();
+        let from = workflow(aggregate_id, vec![
+            node_added("a", "start", json!({})),
+            node_added("b", "end", json!({})),
+            edge_added("ab", "a", "b", "transition", json!({})),
+        ]);
+        let to = workflow(aggregate_id, vec![node_added("a", "start", json!({}))]);
+
+        let events = GraphDiff::between(&from, &to).to_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], EventData::NodeRemoved { node_id } if node_id == "b"));
+        assert_reproduces(&from, &to);
+    }
+
+    #[test]
+    fn test_nested_changes_use_deep_merge_patch() {
+        let aggregate_id = Uuid::new_v4();
+        let engine: ProjectionEngine<Concepts> = ProjectionEngine::new();
+        let from = engine.project(events(aggregate_id, 1, vec![
+            node_added("c", "concept", json!({"name": "Car", "properties": {"wheels": 4, "doors": 2}})),
+        ]));
+        let to = engine.project(events(aggregate_id, 1, vec![
+            node_added("c", "concept", json!({"name": "Car", "properties": {"wheels": 4}})),
+        ]));
+
+        let diff = GraphDiff::between(&from, &to);
+        match &diff.nodes[0] {
+            ElementChange::Changed { update, .. } => {
+                assert_eq!(update, &Some(json!({"properties": {"doors": null}})));
+            }
+            other => panic!("expected a change, got {:?}", other),
+        }
+        assert_reproduces(&from, &to);
+    }
+
+    #[test]
+    fn test_diff_serializes_for_review() {
+        let aggregate_id = Uuid::new_v4();
+        let from = workflow(aggregate_id, vec![node_added("a", "start", json!({}))]);
+        let to = workflow(aggregate_id, vec![node_added("a", "start", json!({"owner": "ann"}))]);
+
+        let diff = GraphDiff::between(&from, &to);
+        let value = serde_json::to_value(&diff).unwrap();
+        assert_eq!(value["nodes"][0]["change"], "changed");
+        assert_eq!(value["nodes"][0]["patch"][0], json!({"op": "add", "path": "/owner", "value": "ann"}));
+
+        let restored: GraphDiff = serde_json::from_value(value).unwrap();
+        assert_eq!(restored, diff);
+    }
+}
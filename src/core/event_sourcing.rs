@@ -11,6 +11,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use crate::core::GraphType;
+use crate::error::GraphError;
 
 /// Event metadata that provides correlation, causation, and ordering
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -157,6 +158,50 @@ pub trait EventStore {
     
     /// Get events in a specific version range
     fn get_events_in_range(&self, aggregate_id: Uuid, from_version: u64, to_version: u64) -> Vec<GraphEvent>;
+
+    /// Current version of an aggregate, 0 when it has no events
+    fn current_version(&self, aggregate_id: Uuid) -> u64 {
+        self.get_events(aggregate_id)
+            .iter()
+            .map(|e| e.metadata.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Append events only if the aggregate is still at `expected_version`
+    ///
+    /// Fails with [`GraphError::ConcurrencyConflict`] when another writer
+    /// appended first, so a command handler can reload and retry. The events
+    /// must carry versions `expected_version + 1` onwards with no gaps.
+    fn append_expected(
+        &mut self,
+        aggregate_id: Uuid,
+        expected_version: u64,
+        events: Vec<GraphEvent>,
+    ) -> crate::Result<()> {
+        let actual = self.current_version(aggregate_id);
+        if actual != expected_version {
+            return Err(GraphError::ConcurrencyConflict {
+                expected: expected_version,
+                actual,
+            });
+        }
+        for (event, version) in events.iter().zip(expected_version + 1..) {
+            if event.metadata.aggregate_id != aggregate_id {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Event {} belongs to aggregate {}, not {}",
+                    event.metadata.event_id, event.metadata.aggregate_id, aggregate_id
+                )));
+            }
+            if event.metadata.version != version {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Event {} has version {}, expected {}",
+                    event.metadata.event_id, event.metadata.version, version
+                )));
+            }
+        }
+        self.append(events).map_err(GraphError::InvalidOperation)
+    }
 }
 
 /// In-memory event store for testing
@@ -233,6 +278,13 @@ impl EventStore for MemoryEventStore {
             .filter(|e| e.metadata.version >= from_version && e.metadata.version <= to_version)
             .collect()
     }
+
+    fn current_version(&self, aggregate_id: Uuid) -> u64 {
+        self.by_aggregate
+            .get(&aggregate_id)
+            .and_then(|events| events.iter().map(|e| e.metadata.version).max())
+            .unwrap_or(0)
+    }
 }
 
 /// Event-sourced graph aggregate
@@ -328,6 +380,7 @@ impl GraphAggregate {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_support::node_added_event;
     
     #[test]
     fn test_event_correlation_and_causation() {
@@ -472,4 +525,67 @@ mod tests {
             assert!(all_events[i].metadata.occurred_at <= all_events[i + 1].metadata.occurred_at);
         }
     }
+
+    #[test]
+    fn test_current_version() {
+        let aggregate_id = Uuid::new_v4();
+        let mut store = MemoryEventStore::default();
+        assert_eq!(store.current_version(aggregate_id), 0);
+
+        store.append(vec![node_added_event(aggregate_id, 1), node_added_event(aggregate_id, 2)]).unwrap();
+        assert_eq!(store.current_version(aggregate_id), 2);
+        assert_eq!(store.current_version(Uuid::new_v4()), 0);
+    }
+
+    #[test]
+    fn test_append_expected_detects_interleaved_writers() {
+        let aggregate_id = Uuid::new_v4();
+        let mut store = MemoryEventStore::default();
+        store.append_expected(aggregate_id, 0, vec![node_added_event(aggregate_id, 1)]).unwrap();
+
+        // Both handlers loaded version 1; only the first append may succeed
+        store.append_expected(aggregate_id, 1, vec![node_added_event(aggregate_id, 2)]).unwrap();
+        let err = store
+            .append_expected(aggregate_id, 1, vec![node_added_event(aggregate_id, 2)])
+            .unwrap_err();
+        assert!(matches!(err, GraphError::ConcurrencyConflict { expected: 1, actual: 2 }));
+        assert_eq!(store.get_events(aggregate_id).len(), 2);
+
+        // Retrying against the fresh version succeeds
+        let version = store.current_version(aggregate_id);
+        store.append_expected(aggregate_id, version, vec![node_added_event(aggregate_id, 3)]).unwrap();
+        assert_eq!(store.current_version(aggregate_id), 3);
+    }
+
+    #[test]
+    fn test_append_expected_rejects_foreign_events() {
+        let aggregate_id = Uuid::new_v4();
+        let mut store = MemoryEventStore::default();
+        let err = store
+            .append_expected(aggregate_id, 0, vec![node_added_event(Uuid::new_v4(), 1)])
+            .unwrap_err();
+        assert!(matches!(err, GraphError::InvalidOperation(_)));
+        assert_eq!(store.current_version(aggregate_id), 0);
+    }
+
+    #[test]
+    fn test_append_expected_rejects_version_gaps() {
+        let aggregate_id = Uuid::new_v4();
+        let mut store = MemoryEventStore::default();
+        store.append_expected(aggregate_id, 0, vec![node_added_event(aggregate_id, 1)]).unwrap();
+
+        for batch in [
+            vec![node_added_event(aggregate_id, 3)],
+            vec![node_added_event(aggregate_id, 2), node_added_event(aggregate_id, 4)],
+            vec![node_added_event(aggregate_id, 2), node_added_event(aggregate_id, 2)],
+        ] {
+            let err = store.append_expected(aggregate_id, 1, batch).unwrap_err();
+            assert!(matches!(err, GraphError::InvalidOperation(_)));
+        }
+        assert_eq!(store.current_version(aggregate_id), 1);
+
+        let batch = vec![node_added_event(aggregate_id, 2), node_added_event(aggregate_id, 3)];
+        store.append_expected(aggregate_id, 1, batch).unwrap();
+        assert_eq!(store.current_version(aggregate_id), 3);
+    }
 }
\ No newline at end of file
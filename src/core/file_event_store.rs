@@ -0,0 +1,937 @@
+//! Durable file-backed event store
+//!
+//! [`FileEventStore`] appends event-sourcing [`GraphEvent`]s to segmented log
+//! files in a directory, so graphs survive restarts on nodes that run without
+//! NATS. It implements the same [`EventStore`] trait as
+//! [`MemoryEventStore`](super::MemoryEventStore).
+//!
+//! Each segment is named after the first store sequence it holds
+//! (`00000000000000000001.log`) and contains length-prefixed records:
+//!
+//! ```text
+//! | len: u32 LE | crc32: u32 LE | sequence: u64 LE | JSON event (len bytes) |
+//! ```
+//!
+//! The checksum covers the sequence and the payload. Opening a store scans
+//! every segment to rebuild the per-aggregate offset index. A torn record at
+//! the end of the last segment, left by a crash mid-write, is truncated away;
+//! a bad record anywhere else is reported as corruption.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use super::event_sourcing::{EventStore, GraphEvent};
+use crate::error::{GraphError, Result};
+
+/// File extension of segment files
+const SEGMENT_EXTENSION: &str = "log";
+
+/// Bytes before the payload: length, checksum and sequence
+const HEADER_LEN: usize = 16;
+
+/// When appended records are flushed to stable storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Sync at the end of every append
+    Always,
+    /// Sync once at least this many records are unsynced
+    EveryRecords(usize),
+    /// Only sync when a segment rolls or [`FileEventStore::sync`] is called
+    Never,
+}
+
+/// Configuration for a [`FileEventStore`]
+#[derive(Debug, Clone)]
+pub struct FileEventStoreConfig {
+    /// Size in bytes after which a new segment is started
+    pub segment_max_bytes: u64,
+
+    /// Durability policy for appends
+    pub fsync: FsyncPolicy,
+}
+
+impl Default for FileEventStoreConfig {
+    fn default() -> Self {
+        Self {
+            segment_max_bytes: 64 * 1024 * 1024, // 64 MiB
+            fsync: FsyncPolicy::Always,
+        }
+    }
+}
+
+/// Position of a record inside the segment files
+#[derive(Debug, Clone, Copy)]
+struct RecordLocation {
+    /// Base sequence of the segment holding the record
+    segment: u64,
+    /// Byte offset of the record header
+    offset: u64,
+    /// Payload length
+    len: u32,
+}
+
+/// Per-aggregate index entry
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    /// Store sequence of the event
+    sequence: u64,
+    /// Aggregate version carried in the event metadata
+    version: u64,
+}
+
+/// In-memory indexes rebuilt from the segments on open
+#[derive(Debug, Default)]
+struct StoreIndex {
+    /// Record locations, position `n` holding sequence `n + 1`
+    locations: Vec<RecordLocation>,
+    /// Events of each aggregate in append order
+    by_aggregate: HashMap<Uuid, Vec<IndexEntry>>,
+    /// Sequences of the events sharing a correlation ID
+    by_correlation: HashMap<Uuid, Vec<u64>>,
+    /// Sequence and causation ID of each event
+    by_event_id: HashMap<Uuid, (u64, Option<Uuid>)>,
+}
+
+impl StoreIndex {
+    fn next_sequence(&self) -> u64 {
+        self.locations.len() as u64 + 1
+    }
+
+    fn insert(&mut self, sequence: u64, location: RecordLocation, event: &GraphEvent) {
+        let metadata = &event.metadata;
+        self.locations.push(location);
+        self.by_aggregate
+            .entry(metadata.aggregate_id)
+            .or_default()
+            .push(IndexEntry { sequence, version: metadata.version });
+        self.by_correlation
+            .entry(metadata.correlation_id)
+            .or_default()
+            .push(sequence);
+        self.by_event_id
+            .insert(metadata.event_id, (sequence, metadata.causation_id));
+    }
+}
+
+/// A segment file
+#[derive(Debug)]
+struct Segment {
+    /// First sequence stored in the segment
+    base: u64,
+    /// Bytes of valid records
+    size: u64,
+}
+
+/// Append-only event store persisted in segmented log files
+#[derive(Debug)]
+pub struct FileEventStore {
+    /// Directory holding the segments
+    dir: PathBuf,
+    /// Store configuration
+    config: FileEventStoreConfig,
+    /// Segments in sequence order, the last one being active
+    segments: Vec<Segment>,
+    /// Append handle of the active segment
+    active: File,
+    /// Offset indexes
+    index: StoreIndex,
+    /// Records written since the last sync
+    unsynced: usize,
+    /// Set when a failed append could not be rolled back
+    poisoned: bool,
+}
+
+impl FileEventStore {
+    /// Open the store in `dir`, creating it if needed and recovering from crashes
+    pub fn open<P: AsRef<Path>>(dir: P, config: FileEventStoreConfig) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error("create store directory", e))?;
+
+        let mut bases = list_segments(&dir)?;
+        if bases.is_empty() {
+            File::create(segment_path(&dir, 1)).map_err(|e| io_error("create segment", e))?;
+            sync_dir(&dir)?;
+            bases.push(1);
+        }
+
+        let mut index = StoreIndex::default();
+        let mut segments = Vec::with_capacity(bases.len());
+        let last = bases.len() - 1;
+        for (position, base) in bases.into_iter().enumerate() {
+            let size = recover_segment(&dir, base, position == last, &mut index)?;
+            segments.push(Segment { base, size });
+        }
+
+        let active_base = segments.last().map_or(1, |segment| segment.base);
+        let active = OpenOptions::new()
+            .append(true)
+            .open(segment_path(&dir, active_base))
+            .map_err(|e| io_error("open segment", e))?;
+
+        Ok(Self {
+            dir,
+            config,
+            segments,
+            active,
+            index,
+            unsynced: 0,
+            poisoned: false,
+        })
+    }
+
+    /// Get the store directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the store configuration
+    pub fn config(&self) -> &FileEventStoreConfig {
+        &self.config
+    }
+
+    /// Sequence of the last stored event, 0 when empty
+    pub fn last_sequence(&self) -> u64 {
+        self.index.locations.len() as u64
+    }
+
+    /// Number of segment files
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Append events, returning the sequence of the last one
+    ///
+    /// Events are written in order; if a write fails, the events before it
+    /// stay in the store and the partial record is removed. If removing it
+    /// fails too, the store refuses further appends until it is reopened,
+    /// which truncates the partial record.
+    pub fn append_events(&mut self, events: Vec<GraphEvent>) -> Result<u64> {
+        if self.poisoned {
+            return Err(GraphError::InvalidOperation(format!(
+                "Store in {} holds a partial record; reopen it to recover",
+                self.dir.display()
+            )));
+        }
+        for event in events {
+            let sequence = self.index.next_sequence();
+            let record = encode_record(sequence, &event)?;
+
+            let active_size = self.segments.last().map_or(0, |segment| segment.size);
+            if active_size > 0 && active_size + record.len() as u64 > self.config.segment_max_bytes {
+                self.roll(sequence)?;
+            }
+
+            let segment = match self.segments.last_mut() {
+                Some(segment) => segment,
+                None => return Err(GraphError::InvalidOperation("Store has no segments".to_string())),
+            };
+            if let Err(e) = self.active.write_all(&record) {
+                // Drop the partial record so the next append starts on a record boundary
+                if let Err(rollback) = self.active.set_len(segment.size) {
+                    self.poisoned = true;
+                    return Err(GraphError::SerializationError(format!(
+                        "Failed to append event: {}; failed to roll back partial record: {}",
+                        e, rollback
+                    )));
+                }
+                return Err(io_error("append event", e));
+            }
+
+            let location = RecordLocation {
+                segment: segment.base,
+                offset: segment.size,
+                len: (record.len() - HEADER_LEN) as u32,
+            };
+            segment.size += record.len() as u64;
+            self.index.insert(sequence, location, &event);
+            self.unsynced += 1;
+        }
+
+        let due = match self.config.fsync {
+            FsyncPolicy::Always => self.unsynced > 0,
+            FsyncPolicy::EveryRecords(n) => self.unsynced >= n.max(1),
+            FsyncPolicy::Never => false,
+        };
+        if due {
+            self.sync()?;
+        }
+        Ok(self.last_sequence())
+    }
+
+    /// Flush the active segment to stable storage
+    pub fn sync(&mut self) -> Result<()> {
+        self.active.sync_data().map_err(|e| io_error("sync segment", e))?;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Read events with sequences in `from..=to`, clamped to the stored range
+    pub fn read_range(&self, from: u64, to: u64) -> Result<Vec<(u64, GraphEvent)>> {
+        let from = from.max(1);
+        let to = to.min(self.last_sequence());
+        if from > to {
+            return Ok(Vec::new());
+        }
+        let events = self.read_sequences(from..=to)?;
+        Ok((from..=to).zip(events).collect())
+    }
+
+    /// Read all events of an aggregate in append order
+    pub fn read_aggregate(&self, aggregate_id: Uuid) -> Result<Vec<GraphEvent>> {
+        match self.index.by_aggregate.get(&aggregate_id) {
+            Some(entries) => self.read_sequences(entries.iter().map(|entry| entry.sequence)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Read events by sequence, reusing the open segment between records
+    fn read_sequences<I: IntoIterator<Item = u64>>(&self, sequences: I) -> Result<Vec<GraphEvent>> {
+        let mut open: Option<(u64, File)> = None;
+        let mut events = Vec::new();
+        for sequence in sequences {
+            let position = sequence.checked_sub(1).map(|position| position as usize);
+            let location = match position.and_then(|position| self.index.locations.get(position)) {
+                Some(location) => *location,
+                None => return Err(GraphError::InvalidOperation(format!("No event with sequence {}", sequence))),
+            };
+            let file = match &mut open {
+                Some((base, file)) if *base == location.segment => file,
+                _ => {
+                    let file = File::open(segment_path(&self.dir, location.segment))
+                        .map_err(|e| io_error("open segment", e))?;
+                    &mut open.insert((location.segment, file)).1
+                }
+            };
+            events.push(read_record(file, location, sequence, &self.dir)?);
+        }
+        Ok(events)
+    }
+
+    /// Events of an infallible trait read, panicking on I/O errors and corruption
+    fn expect_read(&self, events: Result<Vec<GraphEvent>>) -> Vec<GraphEvent> {
+        events.unwrap_or_else(|e| panic!("Event store in {} is unreadable: {}", self.dir.display(), e))
+    }
+
+    /// Seal the active segment and start a new one at `base`
+    fn roll(&mut self, base: u64) -> Result<()> {
+        if self.unsynced > 0 {
+            self.sync()?;
+        }
+        let file = OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(segment_path(&self.dir, base))
+            .map_err(|e| io_error("create segment", e))?;
+        sync_dir(&self.dir)?;
+        self.active = file;
+        self.segments.push(Segment { base, size: 0 });
+        Ok(())
+    }
+}
+
+impl EventStore for FileEventStore {
+    fn append(&mut self, events: Vec<GraphEvent>) -> std::result::Result<(), String> {
+        self.append_events(events).map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    // The trait reads are infallible, so a damaged store panics here rather
+    // than passing off a short history; use `read_aggregate` and
+    // `read_range` where I/O errors need to be handled.
+    fn get_events(&self, aggregate_id: Uuid) -> Vec<GraphEvent> {
+        self.expect_read(self.read_aggregate(aggregate_id))
+    }
+
+    fn get_correlated_events(&self, correlation_id: Uuid) -> Vec<GraphEvent> {
+        match self.index.by_correlation.get(&correlation_id) {
+            Some(sequences) => self.expect_read(self.read_sequences(sequences.iter().copied())),
+            None => Vec::new(),
+        }
+    }
+
+    fn get_causation_chain(&self, event_id: Uuid) -> Vec<GraphEvent> {
+        let mut sequences = Vec::new();
+        let mut current_id = Some(event_id);
+
+        while let Some(id) = current_id {
+            match self.index.by_event_id.get(&id) {
+                Some((sequence, causation_id)) => {
+                    sequences.push(*sequence);
+                    current_id = *causation_id;
+                }
+                None => break,
+            }
+        }
+
+        sequences.reverse(); // Return in chronological order
+        self.expect_read(self.read_sequences(sequences))
+    }
+
+    fn get_events_in_range(&self, aggregate_id: Uuid, from_version: u64, to_version: u64) -> Vec<GraphEvent> {
+        let entries = match self.index.by_aggregate.get(&aggregate_id) {
+            Some(entries) => entries,
+            None => return Vec::new(),
+        };
+        let sequences = entries
+            .iter()
+            .filter(|entry| entry.version >= from_version && entry.version <= to_version)
+            .map(|entry| entry.sequence);
+        self.expect_read(self.read_sequences(sequences))
+    }
+
+    fn current_version(&self, aggregate_id: Uuid) -> u64 {
+        self.index
+            .by_aggregate
+            .get(&aggregate_id)
+            .and_then(|entries| entries.iter().map(|entry| entry.version).max())
+            .unwrap_or(0)
+    }
+}
+
+/// Path of the segment starting at `base`
+fn segment_path(dir: &Path, base: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", base, SEGMENT_EXTENSION))
+}
+
+/// Base sequences of the segments in `dir`, ascending
+fn list_segments(dir: &Path) -> Result<Vec<u64>> {
+    let entries = fs::read_dir(dir).map_err(|e| io_error("list segments", e))?;
+    let mut bases = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| io_error("list segments", e))?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(base) = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok()) {
+            bases.push(base);
+        }
+    }
+    bases.sort_unstable();
+    Ok(bases)
+}
+
+/// Scan a segment into the index, truncating a torn tail of the last segment
+///
+/// Returns the size of the valid records.
+fn recover_segment(dir: &Path, base: u64, is_last: bool, index: &mut StoreIndex) -> Result<u64> {
+    let path = segment_path(dir, base);
+    if base != index.next_sequence() {
+        return Err(GraphError::SerializationError(format!(
+            "Segment {} starts at sequence {}, expected {}",
+            path.display(),
+            base,
+            index.next_sequence()
+        )));
+    }
+
+    let bytes = fs::read(&path).map_err(|e| io_error("read segment", e))?;
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let sequence = index.next_sequence();
+        match decode_record(&bytes[offset..], sequence, &path, offset as u64)? {
+            Some((event, record_len)) => {
+                let location = RecordLocation {
+                    segment: base,
+                    offset: offset as u64,
+                    len: (record_len - HEADER_LEN) as u32,
+                };
+                index.insert(sequence, location, &event);
+                offset += record_len;
+            }
+            None if is_last => {
+                let file = OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .map_err(|e| io_error("open segment", e))?;
+                file.set_len(offset as u64).map_err(|e| io_error("truncate torn record", e))?;
+                file.sync_all().map_err(|e| io_error("sync segment", e))?;
+                break;
+            }
+            None => return Err(corrupt_record(&path, offset as u64)),
+        }
+    }
+    Ok(offset as u64)
+}
+
+/// Encode an event as a framed record
+fn encode_record(sequence: u64, event: &GraphEvent) -> Result<Vec<u8>> {
+    let payload = serde_json::to_vec(event)?;
+    let len = u32::try_from(payload.len()).map_err(|_| {
+        GraphError::SerializationError(format!("Event of {} bytes is too large to store", payload.len()))
+    })?;
+    let sequence = sequence.to_le_bytes();
+
+    let mut record = Vec::with_capacity(HEADER_LEN + payload.len());
+    record.extend_from_slice(&len.to_le_bytes());
+    record.extend_from_slice(&crc32(&[&sequence, &payload]).to_le_bytes());
+    record.extend_from_slice(&sequence);
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+/// Decode the record at the start of `bytes`, found at `offset` in `path`
+///
+/// Returns `None` for a torn record: one cut short, or failing its checksum
+/// while running to the end of `bytes`. Otherwise returns the event with the
+/// record length. A record that fails its checksum with more data after it,
+/// or passes it but holds the wrong sequence or an undecodable event, is
+/// corruption.
+fn decode_record(
+    bytes: &[u8],
+    sequence: u64,
+    path: &Path,
+    offset: u64,
+) -> Result<Option<(GraphEvent, usize)>> {
+    if bytes.len() < HEADER_LEN {
+        return Ok(None);
+    }
+    let len = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+    let checksum = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    let record_len = HEADER_LEN + len;
+    if bytes.len() < record_len {
+        return Ok(None);
+    }
+
+    let sequence_bytes = &bytes[8..HEADER_LEN];
+    let payload = &bytes[HEADER_LEN..record_len];
+    if crc32(&[sequence_bytes, payload]) != checksum {
+        return if bytes.len() == record_len {
+            Ok(None)
+        } else {
+            Err(corrupt_record(path, offset))
+        };
+    }
+
+    let stored = u64::from_le_bytes([
+        sequence_bytes[0], sequence_bytes[1], sequence_bytes[2], sequence_bytes[3],
+        sequence_bytes[4], sequence_bytes[5], sequence_bytes[6], sequence_bytes[7],
+    ]);
+    if stored != sequence {
+        return Err(GraphError::SerializationError(format!(
+            "Record in {} has sequence {}, expected {}",
+            path.display(),
+            stored,
+            sequence
+        )));
+    }
+    let event = serde_json::from_slice(payload)?;
+    Ok(Some((event, record_len)))
+}
+
+/// Read one record from an open segment
+fn read_record(file: &mut File, location: RecordLocation, sequence: u64, dir: &Path) -> Result<GraphEvent> {
+    let mut buffer = vec![0; HEADER_LEN + location.len as usize];
+    file.seek(SeekFrom::Start(location.offset))
+        .and_then(|_| file.read_exact(&mut buffer))
+        .map_err(|e| io_error("read event", e))?;
+
+    let path = segment_path(dir, location.segment);
+    match decode_record(&buffer, sequence, &path, location.offset)? {
+        Some((event, _)) => Ok(event),
+        None => Err(corrupt_record(&path, location.offset)),
+    }
+}
+
+/// Make segment creation durable
+fn sync_dir(dir: &Path) -> Result<()> {
+    // Directories cannot be opened as files on every platform; skip there
+    if let Ok(handle) = File::open(dir) {
+        handle.sync_all().map_err(|e| io_error("sync store directory", e))?;
+    }
+    Ok(())
+}
+
+fn io_error(action: &str, err: std::io::Error) -> GraphError {
+    GraphError::SerializationError(format!("Failed to {}: {}", action, err))
+}
+
+fn corrupt_record(path: &Path, offset: u64) -> GraphError {
+    GraphError::SerializationError(format!(
+        "Corrupt event record in {} at offset {}",
+        path.display(),
+        offset
+    ))
+}
+
+/// CRC-32 (IEEE) lookup table
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 (IEEE) over the concatenation of `chunks`
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    let mut crc = !0u32;
+    for chunk in chunks {
+        for &byte in *chunk {
+            crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::event_sourcing::{EventMetadata, GraphEventPayload};
+    use crate::core::test_support::node_added_event;
+
+    fn ids(events: &[GraphEvent]) -> Vec<Uuid> {
+        events.iter().map(|e| e.metadata.event_id).collect()
+    }
+
+    fn small_segments() -> FileEventStoreConfig {
+        FileEventStoreConfig {
+            segment_max_bytes: 1,
+            ..Default::default()
+        }
+    }
+
+    fn last_segment(dir: &Path) -> PathBuf {
+        let bases = list_segments(dir).unwrap();
+        segment_path(dir, *bases.last().unwrap())
+    }
+
+    // ========== Record Format Tests ==========
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(&[b"123456789"]), 0xCBF4_3926);
+        assert_eq!(crc32(&[b"1234", b"56789"]), 0xCBF4_3926);
+        assert_eq!(crc32(&[]), 0);
+    }
+
+    #[test]
+    fn test_record_roundtrip() {
+        let event = node_added_event(Uuid::new_v4(), 1);
+        let record = encode_record(7, &event).unwrap();
+        let path = Path::new("test.log");
+
+        let (decoded, len) = decode_record(&record, 7, path, 0).unwrap().unwrap();
+        assert_eq!(len, record.len());
+        assert_eq!(decoded.metadata.event_id, event.metadata.event_id);
+
+        // Short records, and damaged ones running to the end, read as torn
+        assert!(decode_record(&record[..record.len() - 1], 7, path, 0).unwrap().is_none());
+        assert!(decode_record(&record[..4], 7, path, 0).unwrap().is_none());
+        let mut damaged = record.clone();
+        let last = damaged.len() - 1;
+        damaged[last] ^= 0xFF;
+        assert!(decode_record(&damaged, 7, path, 0).unwrap().is_none());
+
+        // A damaged record followed by more data is corruption
+        damaged.extend_from_slice(&record);
+        assert!(decode_record(&damaged, 7, path, 0).is_err());
+
+        // A valid record out of place is corruption
+        assert!(decode_record(&record, 8, path, 0).is_err());
+    }
+
+    // ========== Append and Read Tests ==========
+
+    #[test]
+    fn test_append_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        assert_eq!(store.last_sequence(), 0);
+
+        let events = vec![
+            node_added_event(aggregate_id, 1),
+            node_added_event(other, 1),
+            node_added_event(aggregate_id, 2),
+        ];
+        assert_eq!(store.append_events(events.clone()).unwrap(), 3);
+
+        let fetched = store.get_events(aggregate_id);
+        assert_eq!(ids(&fetched), vec![events[0].metadata.event_id, events[2].metadata.event_id]);
+        assert_eq!(store.get_events(other).len(), 1);
+        assert!(store.get_events(Uuid::new_v4()).is_empty());
+    }
+
+    #[test]
+    fn test_reopen_restores_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let events: Vec<_> = (1..=4).map(|v| node_added_event(aggregate_id, v)).collect();
+
+        {
+            let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+            store.append(events.clone()).unwrap();
+        }
+
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        assert_eq!(store.last_sequence(), 4);
+        assert_eq!(store.current_version(aggregate_id), 4);
+        assert_eq!(ids(&store.get_events(aggregate_id)), ids(&events));
+
+        assert_eq!(store.append_events(vec![node_added_event(aggregate_id, 5)]).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_read_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let events: Vec<_> = (1..=5).map(|v| node_added_event(aggregate_id, v)).collect();
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        store.append(events.clone()).unwrap();
+
+        let range = store.read_range(2, 4).unwrap();
+        let sequences: Vec<u64> = range.iter().map(|(seq, _)| *seq).collect();
+        assert_eq!(sequences, vec![2, 3, 4]);
+        assert_eq!(range[0].1.metadata.event_id, events[1].metadata.event_id);
+
+        // Bounds are clamped to what is stored
+        assert_eq!(store.read_range(0, 100).unwrap().len(), 5);
+        assert!(store.read_range(6, 10).unwrap().is_empty());
+        assert!(store.read_range(4, 2).unwrap().is_empty());
+    }
+
+    // ========== Segment Tests ==========
+
+    #[test]
+    fn test_segments_roll_at_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let events: Vec<_> = (1..=3).map(|v| node_added_event(aggregate_id, v)).collect();
+        let mut store = FileEventStore::open(dir.path(), small_segments()).unwrap();
+        store.append(events.clone()).unwrap();
+
+        // Every record overflows the limit, so each gets its own segment
+        assert_eq!(store.segment_count(), 3);
+        assert_eq!(list_segments(dir.path()).unwrap(), vec![1, 2, 3]);
+        assert_eq!(ids(&store.get_events(aggregate_id)), ids(&events));
+
+        drop(store);
+        let store = FileEventStore::open(dir.path(), small_segments()).unwrap();
+        assert_eq!(store.segment_count(), 3);
+        assert_eq!(store.read_range(2, 3).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_segment_files_are_named_by_base_sequence() {
+        let dir = tempfile::tempdir().unwrap();
+        FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+
+        assert!(dir.path().join("00000000000000000001.log").exists());
+    }
+
+    // ========== Crash Recovery Tests ==========
+
+    #[test]
+    fn test_recovery_truncates_partial_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        {
+            let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+            store.append((1..=3).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+        }
+
+        // Simulate a crash halfway through writing the fourth record
+        let path = last_segment(dir.path());
+        let valid_len = fs::metadata(&path).unwrap().len();
+        let record = encode_record(4, &node_added_event(aggregate_id, 4)).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&record[..record.len() / 2]).unwrap();
+        drop(file);
+
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        assert_eq!(store.last_sequence(), 3);
+        assert_eq!(fs::metadata(&path).unwrap().len(), valid_len);
+
+        assert_eq!(store.append_events(vec![node_added_event(aggregate_id, 4)]).unwrap(), 4);
+        drop(store);
+        let store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        assert_eq!(store.current_version(aggregate_id), 4);
+    }
+
+    #[test]
+    fn test_recovery_drops_record_failing_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        {
+            let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+            store.append((1..=2).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+        }
+
+        let path = last_segment(dir.path());
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+
+        let store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        assert_eq!(store.last_sequence(), 1);
+        assert_eq!(store.current_version(aggregate_id), 1);
+    }
+
+    #[test]
+    fn test_recovery_keeps_records_after_a_damaged_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        {
+            let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+            store.append((1..=3).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+        }
+
+        // Damage the first record's payload; valid records follow it
+        let path = last_segment(dir.path());
+        let mut bytes = fs::read(&path).unwrap();
+        let len = bytes.len();
+        bytes[HEADER_LEN + 2] ^= 0xFF;
+        fs::write(&path, &bytes).unwrap();
+
+        let result = FileEventStore::open(dir.path(), FileEventStoreConfig::default());
+        assert!(matches!(result, Err(GraphError::SerializationError(_))));
+        assert_eq!(fs::metadata(&path).unwrap().len(), len as u64);
+    }
+
+    #[test]
+    #[should_panic(expected = "unreadable")]
+    fn test_trait_reads_panic_on_damaged_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        store.append((1..=2).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+
+        // Damage a record behind the open store's back
+        let path = last_segment(dir.path());
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[HEADER_LEN + 2] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+
+        assert!(store.read_aggregate(aggregate_id).is_err());
+        store.get_events(aggregate_id);
+    }
+
+    #[test]
+    fn test_corruption_in_sealed_segment_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut store = FileEventStore::open(dir.path(), small_segments()).unwrap();
+            let aggregate_id = Uuid::new_v4();
+            store.append((1..=3).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+        }
+
+        let sealed = segment_path(dir.path(), 1);
+        let mut bytes = fs::read(&sealed).unwrap();
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0xFF;
+        fs::write(&sealed, bytes).unwrap();
+
+        let result = FileEventStore::open(dir.path(), small_segments());
+        assert!(matches!(result, Err(GraphError::SerializationError(_))));
+    }
+
+    #[test]
+    fn test_missing_segment_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut store = FileEventStore::open(dir.path(), small_segments()).unwrap();
+            let aggregate_id = Uuid::new_v4();
+            store.append((1..=3).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+        }
+        fs::remove_file(segment_path(dir.path(), 2)).unwrap();
+
+        assert!(FileEventStore::open(dir.path(), small_segments()).is_err());
+    }
+
+    // ========== EventStore Trait Tests ==========
+
+    #[test]
+    fn test_correlation_and_causation() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let correlation_id = Uuid::new_v4();
+
+        let first = GraphEvent {
+            metadata: EventMetadata::new(aggregate_id, correlation_id, 1, "test".to_string()),
+            ..node_added_event(aggregate_id, 1)
+        };
+        let second = GraphEvent {
+            metadata: EventMetadata::new(aggregate_id, correlation_id, 2, "test".to_string())
+                .with_causation(first.metadata.event_id),
+            ..node_added_event(aggregate_id, 2)
+        };
+
+        let mut store = FileEventStore::open(dir.path(), small_segments()).unwrap();
+        store.append(vec![first.clone(), node_added_event(Uuid::new_v4(), 1), second.clone()]).unwrap();
+
+        let correlated = store.get_correlated_events(correlation_id);
+        assert_eq!(ids(&correlated), ids(&[first.clone(), second.clone()]));
+
+        let chain = store.get_causation_chain(second.metadata.event_id);
+        assert_eq!(ids(&chain), ids(&[first, second]));
+        assert!(store.get_causation_chain(Uuid::new_v4()).is_empty());
+    }
+
+    #[test]
+    fn test_events_in_version_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+        store.append((1..=5).map(|v| node_added_event(aggregate_id, v)).collect()).unwrap();
+
+        let versions: Vec<u64> = store
+            .get_events_in_range(aggregate_id, 2, 4)
+            .iter()
+            .map(|e| e.metadata.version)
+            .collect();
+        assert_eq!(versions, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_append_expected_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let mut store = FileEventStore::open(dir.path(), FileEventStoreConfig::default()).unwrap();
+
+        store.append_expected(aggregate_id, 0, vec![node_added_event(aggregate_id, 1)]).unwrap();
+        let result = store.append_expected(aggregate_id, 0, vec![node_added_event(aggregate_id, 1)]);
+        assert!(matches!(
+            result,
+            Err(GraphError::ConcurrencyConflict { expected: 0, actual: 1 })
+        ));
+        assert_eq!(store.last_sequence(), 1);
+    }
+
+    // ========== Fsync Policy Tests ==========
+
+    #[test]
+    fn test_fsync_policies() {
+        let aggregate_id = Uuid::new_v4();
+        for (fsync, unsynced) in [
+            (FsyncPolicy::Always, 0),
+            (FsyncPolicy::EveryRecords(2), 1),
+            (FsyncPolicy::Never, 3),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let config = FileEventStoreConfig { fsync, ..Default::default() };
+            let mut store = FileEventStore::open(dir.path(), config).unwrap();
+
+            store.append(vec![node_added_event(aggregate_id, 1), node_added_event(aggregate_id, 2)]).unwrap();
+            store.append(vec![node_added_event(aggregate_id, 3)]).unwrap();
+            assert_eq!(store.unsynced, unsynced, "{:?}", fsync);
+
+            store.sync().unwrap();
+            assert_eq!(store.unsynced, 0);
+        }
+    }
+}
@@ -5,39 +5,22 @@
 //! - Maintaining the Merkle DAG of events
 //! - Verifying chain integrity
 //! - Providing content-addressed storage
+//!
+//! CIDs are real CIDv1 values (DAG-CBOR payload encoding, SHA2-256 or BLAKE3
+//! multihash, base32 multibase), see [`crate::core::cid`].
 
+pub use crate::core::cid::Cid;
+use crate::core::cid::{base32_decode, base32_encode, read_varint, write_varint, Codec, HashAlgorithm};
 use crate::error::{GraphError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-/// Content Identifier (simplified for now, real impl would use cim-ipld)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct Cid(String);
-
-impl Cid {
-    /// Create a new CID from a string
-    pub fn new(s: impl Into<String>) -> Self {
-        Self(s.into())
-    }
-    
-    /// Get the string representation
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
-
-impl std::fmt::Display for Cid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
 /// IPLD Chain Aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IpldChainAggregate {
-    /// Root CID of the chain
-    pub root_cid: Cid,
+    /// Root CID of the chain (`None` until the first event is added)
+    pub root_cid: Option<Cid>,
     
     /// Chain of CIDs (newest first)
     pub chain: Vec<Cid>,
@@ -69,6 +52,10 @@ pub struct ChainMetadata {
     
     /// Codec used for CID generation
     pub codec: String,
+
+    /// Hash function used for CID multihashes
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
     
     /// Is the chain verified
     pub verified: bool,
@@ -77,8 +64,13 @@ pub struct ChainMetadata {
 impl IpldChainAggregate {
     /// Create a new IPLD chain for an aggregate
     pub fn new(aggregate_id: Uuid) -> Self {
+        Self::with_hash_algorithm(aggregate_id, HashAlgorithm::default())
+    }
+
+    /// Create a new IPLD chain whose CIDs use the given hash function
+    pub fn with_hash_algorithm(aggregate_id: Uuid, hash_algorithm: HashAlgorithm) -> Self {
         Self {
-            root_cid: Cid::new(""),
+            root_cid: None,
             chain: Vec::new(),
             links: HashMap::new(),
             payloads: HashMap::new(),
@@ -88,6 +80,7 @@ impl IpldChainAggregate {
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
                 codec: "dag-cbor".to_string(),
+                hash_algorithm,
                 verified: true,
             },
         }
@@ -111,7 +104,7 @@ impl IpldChainAggregate {
         self.chain.insert(0, cid.clone());
         
         // Update root and metadata
-        self.root_cid = cid.clone();
+        self.root_cid = Some(cid.clone());
         self.metadata.length += 1;
         self.metadata.updated_at = chrono::Utc::now();
         
@@ -120,15 +113,7 @@ impl IpldChainAggregate {
     
     /// Generate a CID for a payload
     fn generate_cid(&self, payload: &serde_json::Value) -> Result<Cid> {
-        // Simplified CID generation - real impl would use cim-ipld
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        payload.to_string().hash(&mut hasher);
-        let hash = hasher.finish();
-        
-        Ok(Cid::new(format!("Qm{:x}", hash)))
+        generate_cid_with(payload, self.metadata.hash_algorithm)
     }
     
     /// Get the previous CID for a given CID
@@ -148,7 +133,7 @@ impl IpldChainAggregate {
         }
         
         // Start from root and traverse backwards
-        let mut current = Some(&self.root_cid);
+        let mut current = self.root_cid.as_ref();
         let mut visited = 0;
         
         while let Some(cid) = current {
@@ -184,6 +169,23 @@ impl IpldChainAggregate {
             .collect()
     }
     
+    /// Event CIDs in chain order (oldest first), the leaves of the Merkle tree
+    fn merkle_leaves(&self) -> Vec<Cid> {
+        self.chain.iter().rev().cloned().collect()
+    }
+    
+    /// Merkle root over every event CID in the chain
+    ///
+    /// Unlike [`root_cid`](Self::root_cid), which only addresses the newest
+    /// payload, this commits to the whole history. It is what an auditor
+    /// checks [`MerkleProof`]s against.
+    pub fn merkle_root(&self) -> Option<Cid> {
+        if self.chain.is_empty() {
+            return None;
+        }
+        Some(merkle_root_of(&self.merkle_leaves(), self.metadata.hash_algorithm))
+    }
+    
     /// Check if chain forms a DAG (no cycles)
     pub fn is_dag(&self) -> bool {
         // By construction, our chain is always a DAG
@@ -287,34 +289,371 @@ pub enum IpldChainEvent {
 }
 
 /// System: Generate CID for any payload
+///
+/// Produces a CIDv1 over the canonical DAG-CBOR encoding of the payload with
+/// a SHA2-256 multihash, rendered in base32 (`bafyrei...`).
 pub fn generate_cid_for_payload(payload: &serde_json::Value) -> Result<Cid> {
-    // This would use cim-ipld in real implementation
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    payload.to_string().hash(&mut hasher);
-    let hash = hasher.finish();
-    
-    Ok(Cid::new(format!("Qm{:x}", hash)))
+    generate_cid_with(payload, HashAlgorithm::Sha2_256)
 }
 
-/// System: Create a Merkle proof for a CID in the chain
-pub fn create_merkle_proof(chain: &IpldChainAggregate, target_cid: &Cid) -> Result<Vec<Cid>> {
-    let mut proof = Vec::new();
-    let mut current = Some(target_cid);
-    
-    while let Some(cid) = current {
-        proof.push(cid.clone());
-        current = chain.get_previous(cid);
+/// System: Generate CID for a payload using a specific hash function
+pub fn generate_cid_with(payload: &serde_json::Value, algorithm: HashAlgorithm) -> Result<Cid> {
+    Ok(Cid::for_payload(payload, algorithm))
+}
+
+/// System: Check that a CID addresses the given payload
+pub fn verify_cid_for_payload(cid: &Cid, payload: &serde_json::Value) -> bool {
+    cid.matches_payload(payload)
+}
+
+/// Which side of the running hash a Merkle sibling sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SiblingPosition {
+    /// Sibling is the left child; the running hash is the right child
+    Left,
+    /// Sibling is the right child; the running hash is the left child
+    Right,
+}
+
+/// One level of a Merkle inclusion proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Hash of the sibling subtree
+    pub sibling: Cid,
+    /// Side the sibling sits on
+    pub position: SiblingPosition,
+    /// Hash of the parent formed from the running hash and the sibling
+    pub parent: Cid,
+}
+
+/// Merkle inclusion proof for one event of an [`IpldChainAggregate`]
+///
+/// The tree is built over the chain's event CIDs, oldest first, splitting
+/// each range at the largest power of two below its size (the RFC 6962
+/// shape, so a proof never needs padding leaves). An interior node is a raw
+/// CID over the binary forms of its two children, hashed with the chain's
+/// hash function. The root is [`IpldChainAggregate::merkle_root`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Position of the event in the chain (0 = oldest)
+    pub leaf_index: u64,
+    /// Number of events the tree was built over
+    pub tree_size: u64,
+    /// CID of the proven event
+    pub leaf: Cid,
+    /// Hash function used for interior nodes
+    pub hash_algorithm: HashAlgorithm,
+    /// Path from the leaf up to the root
+    pub steps: Vec<ProofStep>,
+}
+
+/// Version byte of the compact proof encoding
+const MERKLE_PROOF_VERSION: u8 = 1;
+
+impl MerkleProof {
+    /// Check that `target_cid` addresses `payload` and sits under `root_cid`
+    ///
+    /// Every parent hash is recomputed from the leaf up; the carried parents
+    /// must match and the last one must equal the root.
+    pub fn verify(
+        &self,
+        root_cid: &Cid,
+        target_cid: &Cid,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        if !target_cid.matches_payload(payload) {
+            return Err(GraphError::InvalidProof(format!(
+                "payload does not match CID {}",
+                target_cid
+            )));
+        }
+        if &self.leaf != target_cid {
+            return Err(GraphError::InvalidProof(format!(
+                "proof is for {}, not {}",
+                self.leaf, target_cid
+            )));
+        }
+        if self.leaf_index >= self.tree_size {
+            return Err(GraphError::InvalidProof(format!(
+                "leaf index {} outside tree of size {}",
+                self.leaf_index, self.tree_size
+            )));
+        }
+
+        if self.steps.len() > merkle_height(self.tree_size) {
+            return Err(GraphError::InvalidProof(format!(
+                "{} steps exceed the height of a tree of size {}",
+                self.steps.len(),
+                self.tree_size
+            )));
+        }
+
+        let positions: Vec<SiblingPosition> = self.steps.iter().map(|s| s.position).collect();
+        if positions != merkle_path_positions(self.leaf_index, self.tree_size) {
+            return Err(GraphError::InvalidProof(format!(
+                "path shape does not match leaf {} of {}",
+                self.leaf_index, self.tree_size
+            )));
+        }
+
+        let mut current = self.leaf.clone();
+        for (level, step) in self.steps.iter().enumerate() {
+            let parent = match step.position {
+                SiblingPosition::Left => merkle_parent(&step.sibling, &current, self.hash_algorithm),
+                SiblingPosition::Right => merkle_parent(&current, &step.sibling, self.hash_algorithm),
+            };
+            if parent != step.parent {
+                return Err(GraphError::InvalidProof(format!(
+                    "parent hash mismatch at level {}",
+                    level
+                )));
+            }
+            current = parent;
+        }
+
+        if &current != root_cid {
+            return Err(GraphError::InvalidProof(format!(
+                "computed root {} does not match {}",
+                current, root_cid
+            )));
+        }
+        Ok(())
+    }
+
+    /// Compact binary form
+    ///
+    /// Layout: version byte, then varints for hash code, leaf index, tree
+    /// size and step count, then the binary leaf CID, then per step a
+    /// position byte (0 = left, 1 = right) and the binary sibling CID.
+    /// Parent hashes are left out and recomputed by [`MerkleProof::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![MERKLE_PROOF_VERSION];
+        write_varint(&mut out, self.hash_algorithm.code());
+        write_varint(&mut out, self.leaf_index);
+        write_varint(&mut out, self.tree_size);
+        write_varint(&mut out, self.steps.len() as u64);
+        out.extend_from_slice(&self.leaf.to_bytes());
+        for step in &self.steps {
+            out.push(match step.position {
+                SiblingPosition::Left => 0,
+                SiblingPosition::Right => 1,
+            });
+            out.extend_from_slice(&step.sibling.to_bytes());
+        }
+        out
+    }
+
+    /// Decode the compact binary form
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.first() {
+            Some(&MERKLE_PROOF_VERSION) => {}
+            Some(version) => {
+                return Err(GraphError::InvalidProof(format!(
+                    "unsupported proof version {}",
+                    version
+                )))
+            }
+            None => return Err(GraphError::InvalidProof("empty proof".to_string())),
+        }
+        let mut offset = 1;
+        let next_varint = |offset: &mut usize| -> Result<u64> {
+            let (value, read) = read_varint(&bytes[*offset..])
+                .map_err(|e| GraphError::InvalidProof(e.to_string()))?;
+            *offset += read;
+            Ok(value)
+        };
+        let hash_algorithm = HashAlgorithm::from_code(next_varint(&mut offset)?)
+            .map_err(|e| GraphError::InvalidProof(e.to_string()))?;
+        let leaf_index = next_varint(&mut offset)?;
+        let tree_size = next_varint(&mut offset)?;
+        let step_count = next_varint(&mut offset)?;
+        // A path over a u64-sized tree is at most 64 levels deep
+        if step_count > 64 {
+            return Err(GraphError::InvalidProof(format!(
+                "too many proof steps: {}",
+                step_count
+            )));
+        }
+
+        let (leaf, read) = Cid::read_bytes(&bytes[offset..])
+            .map_err(|e| GraphError::InvalidProof(e.to_string()))?;
+        offset += read;
+
+        let mut steps = Vec::with_capacity(step_count as usize);
+        let mut current = leaf.clone();
+        for _ in 0..step_count {
+            let position = match bytes.get(offset) {
+                Some(0) => SiblingPosition::Left,
+                Some(1) => SiblingPosition::Right,
+                Some(other) => {
+                    return Err(GraphError::InvalidProof(format!(
+                        "invalid sibling position {}",
+                        other
+                    )))
+                }
+                None => return Err(GraphError::InvalidProof("truncated proof".to_string())),
+            };
+            offset += 1;
+            let (sibling, read) = Cid::read_bytes(&bytes[offset..])
+                .map_err(|e| GraphError::InvalidProof(e.to_string()))?;
+            offset += read;
+
+            let parent = match position {
+                SiblingPosition::Left => merkle_parent(&sibling, &current, hash_algorithm),
+                SiblingPosition::Right => merkle_parent(&current, &sibling, hash_algorithm),
+            };
+            current = parent.clone();
+            steps.push(ProofStep {
+                sibling,
+                position,
+                parent,
+            });
+        }
+
+        if offset != bytes.len() {
+            return Err(GraphError::InvalidProof(
+                "trailing bytes after proof".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            leaf_index,
+            tree_size,
+            leaf,
+            hash_algorithm,
+            steps,
+        })
+    }
+
+    /// Compact form as a base32 multibase string
+    pub fn to_compact_string(&self) -> String {
+        format!("b{}", base32_encode(&self.to_bytes()))
+    }
+
+    /// Decode the base32 multibase string form
+    pub fn from_compact_string(s: &str) -> Result<Self> {
+        let body = s
+            .strip_prefix('b')
+            .ok_or_else(|| GraphError::InvalidProof("expected base32 multibase prefix 'b'".to_string()))?;
+        let bytes = base32_decode(body).map_err(|e| GraphError::InvalidProof(e.to_string()))?;
+        Self::from_bytes(&bytes)
     }
-    
-    Ok(proof)
+}
+
+/// Interior Merkle node: a raw CID over the concatenated binary child CIDs
+fn merkle_parent(left: &Cid, right: &Cid, algorithm: HashAlgorithm) -> Cid {
+    let mut bytes = left.to_bytes();
+    bytes.extend_from_slice(&right.to_bytes());
+    Cid::for_bytes(Codec::Raw, algorithm, &bytes)
+}
+
+/// Largest power of two strictly below `n` (for `n >= 2`)
+fn merkle_split(n: u64) -> u64 {
+    1 << (63 - (n - 1).leading_zeros())
+}
+
+/// Longest leaf-to-root path in a tree over `size` leaves (for `size >= 1`)
+fn merkle_height(size: u64) -> usize {
+    (u64::BITS - (size - 1).leading_zeros()) as usize
+}
+
+/// Merkle root over a non-empty slice of leaves
+fn merkle_root_of(leaves: &[Cid], algorithm: HashAlgorithm) -> Cid {
+    if leaves.len() == 1 {
+        return leaves[0].clone();
+    }
+    let k = merkle_split(leaves.len() as u64) as usize;
+    merkle_parent(
+        &merkle_root_of(&leaves[..k], algorithm),
+        &merkle_root_of(&leaves[k..], algorithm),
+        algorithm,
+    )
+}
+
+/// Sibling subtrees from leaf `index` up to the root, leaf level first
+fn merkle_path(index: usize, leaves: &[Cid], algorithm: HashAlgorithm) -> Vec<(Cid, SiblingPosition)> {
+    if leaves.len() <= 1 {
+        return Vec::new();
+    }
+    let k = merkle_split(leaves.len() as u64) as usize;
+    let (mut path, sibling) = if index < k {
+        (
+            merkle_path(index, &leaves[..k], algorithm),
+            (merkle_root_of(&leaves[k..], algorithm), SiblingPosition::Right),
+        )
+    } else {
+        (
+            merkle_path(index - k, &leaves[k..], algorithm),
+            (merkle_root_of(&leaves[..k], algorithm), SiblingPosition::Left),
+        )
+    };
+    path.push(sibling);
+    path
+}
+
+/// Sibling sides implied by a leaf's position, leaf level first
+fn merkle_path_positions(index: u64, size: u64) -> Vec<SiblingPosition> {
+    let mut positions = Vec::new();
+    let (mut index, mut size) = (index, size);
+    while size > 1 {
+        let k = merkle_split(size);
+        if index < k {
+            positions.push(SiblingPosition::Right);
+            size = k;
+        } else {
+            positions.push(SiblingPosition::Left);
+            index -= k;
+            size -= k;
+        }
+    }
+    positions.reverse();
+    positions
+}
+
+/// System: Create a Merkle inclusion proof for a CID in the chain
+///
+/// The proof verifies against [`IpldChainAggregate::merkle_root`] as of the
+/// chain's current length.
+pub fn create_merkle_proof(chain: &IpldChainAggregate, target_cid: &Cid) -> Result<MerkleProof> {
+    let leaves = chain.merkle_leaves();
+    let leaf_index = leaves
+        .iter()
+        .position(|cid| cid == target_cid)
+        .ok_or_else(|| {
+            GraphError::InvalidOperation(format!("CID not in chain: {}", target_cid))
+        })?;
+    let algorithm = chain.metadata.hash_algorithm;
+
+    let mut current = target_cid.clone();
+    let steps = merkle_path(leaf_index, &leaves, algorithm)
+        .into_iter()
+        .map(|(sibling, position)| {
+            let parent = match position {
+                SiblingPosition::Left => merkle_parent(&sibling, &current, algorithm),
+                SiblingPosition::Right => merkle_parent(&current, &sibling, algorithm),
+            };
+            current = parent.clone();
+            ProofStep {
+                sibling,
+                position,
+                parent,
+            }
+        })
+        .collect();
+
+    Ok(MerkleProof {
+        leaf_index: leaf_index as u64,
+        tree_size: leaves.len() as u64,
+        leaf: target_cid.clone(),
+        hash_algorithm: algorithm,
+        steps,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::cid::test_cid;
 
     #[test]
     fn test_ipld_chain_creation() {
@@ -338,7 +677,7 @@ mod tests {
         let cid1 = chain.add_event(event1.clone()).unwrap();
 
         assert_eq!(chain.metadata.length, 1);
-        assert_eq!(chain.root_cid, cid1);
+        assert_eq!(chain.root_cid, Some(cid1.clone()));
         assert!(chain.get_previous(&cid1).is_none());
 
         // Add second event
@@ -349,7 +688,7 @@ mod tests {
         let cid2 = chain.add_event(event2.clone()).unwrap();
 
         assert_eq!(chain.metadata.length, 2);
-        assert_eq!(chain.root_cid, cid2);
+        assert_eq!(chain.root_cid, Some(cid2.clone()));
         assert_eq!(chain.get_previous(&cid2), Some(&cid1));
     }
 
@@ -411,51 +750,56 @@ mod tests {
             cids.push(cid);
         }
 
-        // Create proof for the second event (index 1)
-        // Remember: chain stores newest first, so cids[2] is the second event
+        // Prove the third event against the chain head's Merkle root
+        let root = chain.merkle_root().unwrap();
         let proof = create_merkle_proof(&chain, &cids[2]).unwrap();
 
-        // Proof should contain path from target to root
-        assert_eq!(proof.len(), 3); // cids[2] -> cids[1] -> cids[0]
-        assert_eq!(proof[0], cids[2]);
-        assert_eq!(proof[1], cids[1]);
-        assert_eq!(proof[2], cids[0]);
+        assert_eq!(proof.leaf_index, 2);
+        assert_eq!(proof.tree_size, 4);
+        assert_eq!(proof.steps.len(), 2); // balanced tree of 4 leaves
+        assert_eq!(proof.steps.last().unwrap().parent, root);
+        assert!(proof
+            .verify(&root, &cids[2], &serde_json::json!({"index": 2}))
+            .is_ok());
     }
 
     // ========== Cid Tests ==========
 
     #[test]
-    fn test_cid_new() {
-        let cid = Cid::new("QmTestCid");
-        assert_eq!(cid.as_str(), "QmTestCid");
+    fn test_cid_is_crate_wide_type() {
+        // The chain uses the shared, validated CID type
+        let cid: crate::core::cid::Cid = test_cid("QmTestCid");
+        let chain_cid: Cid = cid.clone();
+        assert_eq!(chain_cid, cid);
     }
 
     #[test]
     fn test_cid_display() {
-        let cid = Cid::new("QmDisplayTest");
+        let cid = test_cid("QmDisplayTest");
         let display_str = format!("{}", cid);
-        assert_eq!(display_str, "QmDisplayTest");
+        assert_eq!(display_str, cid.as_str());
+        assert!(display_str.starts_with('b'));
     }
 
     #[test]
     fn test_cid_debug() {
-        let cid = Cid::new("QmDebugTest");
+        let cid = test_cid("QmDebugTest");
         let debug_str = format!("{:?}", cid);
-        assert!(debug_str.contains("QmDebugTest"));
+        assert!(debug_str.contains(cid.as_str()));
     }
 
     #[test]
     fn test_cid_clone() {
-        let cid1 = Cid::new("QmClone");
+        let cid1 = test_cid("QmClone");
         let cid2 = cid1.clone();
         assert_eq!(cid1, cid2);
     }
 
     #[test]
     fn test_cid_eq() {
-        let cid1 = Cid::new("QmSame");
-        let cid2 = Cid::new("QmSame");
-        let cid3 = Cid::new("QmDifferent");
+        let cid1 = test_cid("QmSame");
+        let cid2 = test_cid("QmSame");
+        let cid3 = test_cid("QmDifferent");
 
         assert_eq!(cid1, cid2);
         assert_ne!(cid1, cid3);
@@ -465,9 +809,9 @@ mod tests {
     fn test_cid_hash() {
         use std::collections::HashSet;
 
-        let cid1 = Cid::new("QmHash1");
-        let cid2 = Cid::new("QmHash2");
-        let cid3 = Cid::new("QmHash1"); // Same as cid1
+        let cid1 = test_cid("QmHash1");
+        let cid2 = test_cid("QmHash2");
+        let cid3 = test_cid("QmHash1"); // Same as cid1
 
         let mut set = HashSet::new();
         set.insert(cid1.clone());
@@ -480,12 +824,18 @@ mod tests {
 
     #[test]
     fn test_cid_serialize_deserialize() {
-        let cid = Cid::new("QmSerialize");
+        let cid = test_cid("QmSerialize");
         let json = serde_json::to_string(&cid).unwrap();
         let deserialized: Cid = serde_json::from_str(&json).unwrap();
         assert_eq!(cid, deserialized);
     }
 
+    #[test]
+    fn test_cid_rejects_unvalidated_strings() {
+        assert!(Cid::parse("QmSerialize").is_err());
+        assert!(serde_json::from_str::<Cid>("\"QmSerialize\"").is_err());
+    }
+
     // ========== ChainMetadata Tests ==========
 
     #[test]
@@ -547,7 +897,7 @@ mod tests {
     #[test]
     fn test_ipld_chain_get_payload_nonexistent() {
         let chain = IpldChainAggregate::new(Uuid::new_v4());
-        let fake_cid = Cid::new("QmFake");
+        let fake_cid = test_cid("QmFake");
         assert!(chain.get_payload(&fake_cid).is_none());
     }
 
@@ -580,7 +930,7 @@ mod tests {
     #[test]
     fn test_ipld_chain_pin_nonexistent_cid() {
         let mut chain = IpldChainAggregate::new(Uuid::new_v4());
-        let fake_cid = Cid::new("QmFakePin");
+        let fake_cid = test_cid("QmFakePin");
 
         let result = chain.pin_cid(&fake_cid);
         assert!(result.is_err());
@@ -606,7 +956,7 @@ mod tests {
     #[test]
     fn test_ipld_chain_unpin_nonexistent_cid() {
         let mut chain = IpldChainAggregate::new(Uuid::new_v4());
-        let fake_cid = Cid::new("QmFakeUnpin");
+        let fake_cid = test_cid("QmFakeUnpin");
 
         let result = chain.unpin_cid(&fake_cid);
         assert!(result.is_err());
@@ -676,7 +1026,7 @@ mod tests {
     fn test_ipld_chain_command_pin_cid() {
         let command = IpldChainCommand::PinCid {
             aggregate_id: Uuid::new_v4(),
-            cid: Cid::new("QmPin"),
+            cid: test_cid("QmPin"),
         };
         let debug_str = format!("{:?}", command);
         assert!(debug_str.contains("PinCid"));
@@ -686,7 +1036,7 @@ mod tests {
     fn test_ipld_chain_command_unpin_cid() {
         let command = IpldChainCommand::UnpinCid {
             aggregate_id: Uuid::new_v4(),
-            cid: Cid::new("QmUnpin"),
+            cid: test_cid("QmUnpin"),
         };
         let debug_str = format!("{:?}", command);
         assert!(debug_str.contains("UnpinCid"));
@@ -718,8 +1068,8 @@ mod tests {
     fn test_ipld_chain_event_event_added() {
         let event = IpldChainEvent::EventAdded {
             aggregate_id: Uuid::new_v4(),
-            cid: Cid::new("QmEvent"),
-            previous_cid: Some(Cid::new("QmPrevious")),
+            cid: test_cid("QmEvent"),
+            previous_cid: Some(test_cid("QmPrevious")),
             sequence: 1,
         };
         let debug_str = format!("{:?}", event);
@@ -730,7 +1080,7 @@ mod tests {
     fn test_ipld_chain_event_chain_verified() {
         let event = IpldChainEvent::ChainVerified {
             aggregate_id: Uuid::new_v4(),
-            root_cid: Cid::new("QmRoot"),
+            root_cid: test_cid("QmRoot"),
             length: 10,
             is_valid: true,
         };
@@ -743,7 +1093,7 @@ mod tests {
     fn test_ipld_chain_event_cid_pinned() {
         let event = IpldChainEvent::CidPinned {
             aggregate_id: Uuid::new_v4(),
-            cid: Cid::new("QmPinned"),
+            cid: test_cid("QmPinned"),
         };
         let debug_str = format!("{:?}", event);
         assert!(debug_str.contains("CidPinned"));
@@ -753,7 +1103,7 @@ mod tests {
     fn test_ipld_chain_event_cid_unpinned() {
         let event = IpldChainEvent::CidUnpinned {
             aggregate_id: Uuid::new_v4(),
-            cid: Cid::new("QmUnpinned"),
+            cid: test_cid("QmUnpinned"),
         };
         let debug_str = format!("{:?}", event);
         assert!(debug_str.contains("CidUnpinned"));
@@ -763,7 +1113,7 @@ mod tests {
     fn test_ipld_chain_event_clone() {
         let event = IpldChainEvent::EventAdded {
             aggregate_id: Uuid::new_v4(),
-            cid: Cid::new("QmClone"),
+            cid: test_cid("QmClone"),
             previous_cid: None,
             sequence: 0,
         };
@@ -776,7 +1126,7 @@ mod tests {
     fn test_ipld_chain_event_serialize() {
         let event = IpldChainEvent::ChainVerified {
             aggregate_id: Uuid::new_v4(),
-            root_cid: Cid::new("QmRoot"),
+            root_cid: test_cid("QmRoot"),
             length: 5,
             is_valid: true,
         };
@@ -791,8 +1141,40 @@ mod tests {
         let payload = serde_json::json!({"test": "data"});
         let cid = generate_cid_for_payload(&payload).unwrap();
 
-        // CID should start with "Qm"
-        assert!(cid.as_str().starts_with("Qm"));
+        // CIDv1, dag-cbor, sha2-256 in base32
+        assert!(cid.as_str().starts_with("bafyrei"));
+
+        let parsed = Cid::parse(cid.as_str()).unwrap();
+        assert_eq!(parsed, cid);
+        assert!(verify_cid_for_payload(&cid, &payload));
+        assert!(!verify_cid_for_payload(&cid, &serde_json::json!({"test": "other"})));
+    }
+
+    #[test]
+    fn test_generate_cid_blake3() {
+        let payload = serde_json::json!({"test": "data"});
+        let sha = generate_cid_for_payload(&payload).unwrap();
+        let blake = generate_cid_with(&payload, HashAlgorithm::Blake3).unwrap();
+
+        assert_ne!(sha, blake);
+        assert_eq!(blake.hash().algorithm(), HashAlgorithm::Blake3);
+        assert!(verify_cid_for_payload(&blake, &payload));
+    }
+
+    #[test]
+    fn test_chain_uses_configured_hash_algorithm() {
+        let mut chain = IpldChainAggregate::with_hash_algorithm(Uuid::new_v4(), HashAlgorithm::Blake3);
+        let payload = serde_json::json!({"index": 0});
+        let cid = chain.add_event(payload.clone()).unwrap();
+
+        assert_eq!(cid, generate_cid_with(&payload, HashAlgorithm::Blake3).unwrap());
+        assert_eq!(chain.metadata.hash_algorithm, HashAlgorithm::Blake3);
+    }
+
+    #[test]
+    fn test_verify_cid_rejects_raw_cid() {
+        let cid = test_cid("{}");
+        assert!(!verify_cid_for_payload(&cid, &serde_json::json!({})));
     }
 
     #[test]
@@ -818,39 +1200,229 @@ mod tests {
 
     // ========== create_merkle_proof Tests ==========
 
+    fn chain_of(n: usize) -> (IpldChainAggregate, Vec<Cid>) {
+        let mut chain = IpldChainAggregate::new(Uuid::new_v4());
+        let cids = (0..n)
+            .map(|i| chain.add_event(serde_json::json!({"index": i})).unwrap())
+            .collect();
+        (chain, cids)
+    }
+
     #[test]
     fn test_create_merkle_proof_single_event() {
         let mut chain = IpldChainAggregate::new(Uuid::new_v4());
 
-        let cid = chain.add_event(serde_json::json!({"single": true})).unwrap();
+        let payload = serde_json::json!({"single": true});
+        let cid = chain.add_event(payload.clone()).unwrap();
         let proof = create_merkle_proof(&chain, &cid).unwrap();
 
-        assert_eq!(proof.len(), 1);
-        assert_eq!(proof[0], cid);
+        // A one-leaf tree's root is the leaf itself
+        assert!(proof.steps.is_empty());
+        assert_eq!(chain.merkle_root(), Some(cid.clone()));
+        assert!(proof.verify(&cid, &cid, &payload).is_ok());
     }
 
     #[test]
     fn test_create_merkle_proof_root() {
-        let mut chain = IpldChainAggregate::new(Uuid::new_v4());
+        let (chain, _) = chain_of(5);
+
+        // The newest event is provable too
+        let head = chain.root_cid.as_ref().unwrap();
+        let proof = create_merkle_proof(&chain, head).unwrap();
+        assert_eq!(proof.leaf_index, 4);
+        assert_eq!(proof.tree_size, 5);
+        assert_eq!(proof.steps.len(), 1); // right subtree of a 4+1 split
+        assert!(proof
+            .verify(&chain.merkle_root().unwrap(), head, &serde_json::json!({"index": 4}))
+            .is_ok());
+    }
 
-        for i in 0..5 {
-            chain.add_event(serde_json::json!({"index": i})).unwrap();
+    #[test]
+    fn test_create_merkle_proof_empty_chain() {
+        let chain = IpldChainAggregate::new(Uuid::new_v4());
+        let fake_cid = test_cid("QmFake");
+
+        assert!(chain.merkle_root().is_none());
+        match create_merkle_proof(&chain, &fake_cid).unwrap_err() {
+            GraphError::InvalidOperation(msg) => assert!(msg.contains("not in chain")),
+            other => panic!("Expected InvalidOperation error, got {:?}", other),
         }
+    }
 
-        // Proof for root (most recent) should be entire chain
-        let proof = create_merkle_proof(&chain, &chain.root_cid).unwrap();
-        assert_eq!(proof.len(), 5);
+    #[test]
+    fn test_merkle_proof_every_leaf_every_size() {
+        for n in 1..=17 {
+            let (chain, cids) = chain_of(n);
+            let root = chain.merkle_root().unwrap();
+            for (i, cid) in cids.iter().enumerate() {
+                let proof = create_merkle_proof(&chain, cid).unwrap();
+                assert_eq!(proof.leaf_index, i as u64);
+                assert!(
+                    proof.verify(&root, cid, &serde_json::json!({"index": i})).is_ok(),
+                    "leaf {} of {}",
+                    i,
+                    n
+                );
+                // Path length is logarithmic, not linear in the chain
+                assert!(proof.steps.len() <= 5);
+            }
+        }
     }
 
     #[test]
-    fn test_create_merkle_proof_empty_chain() {
-        let chain = IpldChainAggregate::new(Uuid::new_v4());
-        let fake_cid = Cid::new("QmFake");
+    fn test_merkle_root_commits_to_history() {
+        let (a, _) = chain_of(4);
+        let mut b = IpldChainAggregate::new(Uuid::new_v4());
+        for i in [0, 1, 9, 3] {
+            b.add_event(serde_json::json!({"index": i})).unwrap();
+        }
+
+        // Same head payload, different history
+        assert_eq!(a.root_cid, b.root_cid);
+        assert_ne!(a.merkle_root(), b.merkle_root());
+    }
+
+    #[test]
+    fn test_merkle_proof_rejects_wrong_payload() {
+        let (chain, cids) = chain_of(6);
+        let root = chain.merkle_root().unwrap();
+        let proof = create_merkle_proof(&chain, &cids[3]).unwrap();
+
+        let err = proof
+            .verify(&root, &cids[3], &serde_json::json!({"index": 99}))
+            .unwrap_err();
+        assert!(matches!(err, GraphError::InvalidProof(_)));
+    }
+
+    #[test]
+    fn test_merkle_proof_rejects_wrong_target_or_root() {
+        let (chain, cids) = chain_of(6);
+        let root = chain.merkle_root().unwrap();
+        let proof = create_merkle_proof(&chain, &cids[3]).unwrap();
+
+        // Proof for one leaf does not vouch for another
+        assert!(proof
+            .verify(&root, &cids[2], &serde_json::json!({"index": 2}))
+            .is_err());
+
+        // A stale root (before the last event) does not match
+        let (shorter, _) = chain_of(5);
+        assert!(proof
+            .verify(&shorter.merkle_root().unwrap(), &cids[3], &serde_json::json!({"index": 3}))
+            .is_err());
+    }
+
+    #[test]
+    fn test_merkle_proof_rejects_tampered_steps() {
+        let (chain, cids) = chain_of(8);
+        let root = chain.merkle_root().unwrap();
+        let payload = serde_json::json!({"index": 5});
+        let proof = create_merkle_proof(&chain, &cids[5]).unwrap();
+
+        let mut bad_sibling = proof.clone();
+        bad_sibling.steps[1].sibling = test_cid("forged");
+        assert!(bad_sibling.verify(&root, &cids[5], &payload).is_err());
+
+        let mut bad_parent = proof.clone();
+        bad_parent.steps[0].parent = test_cid("forged");
+        let err = bad_parent.verify(&root, &cids[5], &payload).unwrap_err();
+        assert!(err.to_string().contains("level 0"));
+
+        // Claiming a different position changes the required path shape
+        let mut bad_index = proof.clone();
+        bad_index.leaf_index = 4;
+        assert!(bad_index.verify(&root, &cids[5], &payload).is_err());
+
+        let mut out_of_range = proof;
+        out_of_range.leaf_index = 8;
+        assert!(out_of_range.verify(&root, &cids[5], &payload).is_err());
+    }
+
+    #[test]
+    fn test_merkle_proof_with_huge_tree_size() {
+        let (chain, cids) = chain_of(4);
+        let root = chain.merkle_root().unwrap();
+        let payload = serde_json::json!({"index": 1});
+        let mut proof = create_merkle_proof(&chain, &cids[1]).unwrap();
+        proof.tree_size = u64::MAX;
+
+        // A crafted size must be rejected, not overflow or loop forever
+        assert!(proof.verify(&root, &cids[1], &payload).is_err());
+        let decoded = MerkleProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(decoded.verify(&root, &cids[1], &payload).is_err());
+
+        // More steps than the tree is high fail before the path shape is derived
+        proof.steps = proof.steps.repeat(33);
+        let err = proof.verify(&root, &cids[1], &payload).unwrap_err();
+        assert!(err.to_string().contains("height"));
+
+        assert_eq!(merkle_split(u64::MAX), 1 << 63);
+        assert_eq!(merkle_height(u64::MAX), 64);
+        assert_eq!(merkle_path_positions(0, u64::MAX).len(), 64);
+    }
+
+    #[test]
+    fn test_merkle_proof_blake3_chain() {
+        let mut chain = IpldChainAggregate::with_hash_algorithm(Uuid::new_v4(), HashAlgorithm::Blake3);
+        let cids: Vec<Cid> = (0..3)
+            .map(|i| chain.add_event(serde_json::json!({"index": i})).unwrap())
+            .collect();
+        let root = chain.merkle_root().unwrap();
+        assert_eq!(root.hash().algorithm(), HashAlgorithm::Blake3);
+
+        let proof = create_merkle_proof(&chain, &cids[0]).unwrap();
+        assert_eq!(proof.hash_algorithm, HashAlgorithm::Blake3);
+        assert!(proof.verify(&root, &cids[0], &serde_json::json!({"index": 0})).is_ok());
+    }
+
+    #[test]
+    fn test_merkle_proof_compact_roundtrip() {
+        let (chain, cids) = chain_of(11);
+        let root = chain.merkle_root().unwrap();
+        let proof = create_merkle_proof(&chain, &cids[6]).unwrap();
+
+        let bytes = proof.to_bytes();
+        let decoded = MerkleProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(&root, &cids[6], &serde_json::json!({"index": 6})).is_ok());
+
+        // Parents are recomputed, so the compact form is smaller than JSON
+        assert!(bytes.len() < serde_json::to_vec(&proof).unwrap().len() / 2);
+
+        let text = proof.to_compact_string();
+        assert!(text.starts_with('b'));
+        assert_eq!(MerkleProof::from_compact_string(&text).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_merkle_proof_json_roundtrip() {
+        let (chain, cids) = chain_of(3);
+        let proof = create_merkle_proof(&chain, &cids[1]).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: MerkleProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+    }
+
+    #[test]
+    fn test_merkle_proof_from_bytes_rejects_malformed() {
+        let (chain, cids) = chain_of(4);
+        let bytes = create_merkle_proof(&chain, &cids[1]).unwrap().to_bytes();
+
+        assert!(MerkleProof::from_bytes(&[]).is_err());
+        assert!(MerkleProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 9;
+        assert!(MerkleProof::from_bytes(&wrong_version).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(MerkleProof::from_bytes(&trailing).is_err());
 
-        // Proof for non-existent CID should just contain the CID
-        let proof = create_merkle_proof(&chain, &fake_cid).unwrap();
-        assert_eq!(proof.len(), 1);
-        assert_eq!(proof[0], fake_cid);
+        assert!(matches!(
+            MerkleProof::from_compact_string("zabc"),
+            Err(GraphError::InvalidProof(_))
+        ));
     }
 
     // ========== Chain Verification Edge Cases ==========
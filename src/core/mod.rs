@@ -1,13 +1,17 @@
 //! Core graph abstractions and traits
 
+pub mod cid;
 pub mod edge;
 pub mod event;
 pub mod event_sourcing;
 pub mod event_driven;
+pub mod file_event_store;
 pub mod cim_graph;
+pub mod diff;
 pub mod graph;
 pub mod node;
 pub mod projection_engine;
+pub mod snapshot_store;
 pub mod aggregate_projection;
 pub mod state_machine;
 pub mod ipld_chain;
@@ -16,6 +20,10 @@ pub mod policies;
 #[cfg(test)]
 mod event_tests;
 
+#[cfg(test)]
+pub(crate) mod test_support;
+
+pub use self::cid::{Codec, HashAlgorithm, Multihash};
 pub use self::edge::{Edge, GenericEdge};
 pub use self::event::{EventHandler, GraphEvent, MemoryEventHandler};
 pub use self::event_sourcing::{
@@ -24,10 +32,20 @@ pub use self::event_sourcing::{
 };
 pub use self::graph::{GraphId, GraphMetadata, GraphType};
 pub use self::node::{GenericNode, Node};
+pub use self::file_event_store::{FileEventStore, FileEventStoreConfig, FsyncPolicy};
 pub use self::cim_graph::{GraphProjection, GraphEvent as CimGraphEvent, EventData, GraphCommand};
-pub use self::projection_engine::{ProjectionEngine, GenericGraphProjection, ProjectionCache};
+pub use self::projection_engine::{
+    ProjectionEngine, GenericGraphProjection, ProjectionCache, FromEventData, ToEventData, AsOf,
+};
+pub use self::diff::{GraphDiff, ElementChange, ElementState, PatchOperation};
+pub use self::snapshot_store::{
+    SnapshotStore, SnapshotRetention, MemorySnapshotStore, FileSnapshotStore,
+};
 pub use self::aggregate_projection::{GraphAggregateProjection, build_projection};
-pub use self::ipld_chain::{IpldChainAggregate, Cid, IpldChainCommand, IpldChainEvent};
+pub use self::ipld_chain::{
+    IpldChainAggregate, Cid, IpldChainCommand, IpldChainEvent, MerkleProof, ProofStep,
+    SiblingPosition,
+};
 pub use self::state_machine::{GraphStateMachine, GraphState, WorkflowState};
 pub use self::policies::{
     Policy, PolicyEngine, PolicyContext, PolicyAction, PolicyMetrics,
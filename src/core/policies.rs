@@ -406,7 +406,7 @@ mod tests {
     fn test_policy_action_generate_cid() {
         let action = PolicyAction::GenerateCid {
             event_id: Uuid::new_v4(),
-            cid: Cid::new("QmTest"),
+            cid: crate::core::cid::test_cid("QmTest"),
         };
         let debug_str = format!("{:?}", action);
         assert!(debug_str.contains("GenerateCid"));
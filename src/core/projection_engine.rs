@@ -7,6 +7,10 @@
 
 use crate::core::cim_graph::{GraphEvent, EventData, GraphProjection};
 use crate::core::{Node, Edge, GraphType, GraphMetadata};
+use crate::core::cid::{Cid, HashAlgorithm};
+use crate::core::snapshot_store::{MemorySnapshotStore, SnapshotStore};
+use crate::optimization::EventCompressor;
+use crate::error::{GraphError, Result};
 use uuid::Uuid;
 use std::collections::HashMap;
 
@@ -66,6 +70,38 @@ impl<N: Node, E: Edge> GenericGraphProjection<N, E> {
     }
 }
 
+impl<N, E> GenericGraphProjection<N, E>
+where
+    N: Node + Clone + serde::Serialize + serde::de::DeserializeOwned,
+    E: Edge + Clone + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// Capture the projection state in a snapshot at its current version
+    pub fn to_snapshot(&self) -> Result<ProjectionSnapshot> {
+        let state = SnapshotState {
+            graph_type: self.graph_type,
+            metadata: self.metadata.clone(),
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            adjacency: self.adjacency.clone(),
+        };
+        Ok(ProjectionSnapshot::new(self.aggregate_id, self.version, serde_json::to_value(state)?))
+    }
+
+    /// Rebuild a projection from a snapshot taken with [`Self::to_snapshot`]
+    pub fn from_snapshot(snapshot: ProjectionSnapshot) -> Result<Self> {
+        let state: SnapshotState<N, E> = serde_json::from_value(snapshot.data)?;
+        Ok(Self {
+            aggregate_id: snapshot.aggregate_id,
+            graph_type: state.graph_type,
+            version: snapshot.version,
+            metadata: state.metadata,
+            nodes: state.nodes,
+            edges: state.edges,
+            adjacency: state.adjacency,
+        })
+    }
+}
+
 impl<N: Node, E: Edge> GraphProjection for GenericGraphProjection<N, E> {
     type Node = N;
     type Edge = E;
@@ -116,6 +152,128 @@ impl<N: Node, E: Edge> GraphProjection for GenericGraphProjection<N, E> {
     }
 }
 
+/// Factory for typed nodes and edges materialized from event payloads
+///
+/// Node types build themselves from [`EventData::NodeAdded`] and edge types
+/// from [`EventData::EdgeAdded`]; the payload of the matching `*Updated`
+/// event is merged into the existing value. Returning `None` rejects the
+/// event and leaves the projection unchanged.
+pub trait FromEventData: Sized {
+    /// Build a value from an `*Added` event
+    fn from_event_data(data: &EventData) -> Option<Self>;
+
+    /// Merge an `*Updated` payload into this value
+    fn merge_event_data(&mut self, data: &serde_json::Value);
+}
+
+/// Inverse of [`FromEventData`]: the `*Added` event that rebuilds a value
+///
+/// Feeding [`event_type`](Self::event_type) and
+/// [`event_payload`](Self::event_payload) back through
+/// [`FromEventData::from_event_data`] must produce a value with the same
+/// type and payload. State that events never carry is not included.
+pub trait ToEventData: FromEventData {
+    /// `node_type` or `edge_type` of the `*Added` event
+    fn event_type(&self) -> String;
+
+    /// Payload of the `*Added` event
+    fn event_payload(&self) -> serde_json::Value;
+}
+
+/// JSON object of a property map with extra fields set on top
+pub(crate) fn payload_with(
+    fields: &HashMap<String, serde_json::Value>,
+    extra: impl IntoIterator<Item = (&'static str, serde_json::Value)>,
+) -> serde_json::Value {
+    let mut payload: serde_json::Map<String, serde_json::Value> =
+        fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    for (key, value) in extra {
+        payload.insert(key.to_string(), value);
+    }
+    serde_json::Value::Object(payload)
+}
+
+/// Normalize a node/edge type name for matching (`"Valueobject"`, `"value_object"`
+/// and `"value-object"` all become `"valueobject"`)
+pub(crate) fn type_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-' && !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Top-level fields of a JSON object payload (empty for non-objects)
+pub(crate) fn payload_fields(data: &serde_json::Value) -> HashMap<String, serde_json::Value> {
+    data.as_object()
+        .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
+/// Merge a JSON object into a property map; `null` values delete the key
+pub(crate) fn merge_fields(
+    target: &mut HashMap<String, serde_json::Value>,
+    patch: &serde_json::Value,
+) {
+    merge_fields_except(target, patch, &[]);
+}
+
+/// [`merge_fields`], skipping keys the caller maps to dedicated fields
+pub(crate) fn merge_fields_except(
+    target: &mut HashMap<String, serde_json::Value>,
+    patch: &serde_json::Value,
+    skip: &[&str],
+) {
+    if let Some(obj) = patch.as_object() {
+        for (key, value) in obj {
+            if skip.contains(&key.as_str()) {
+                continue;
+            }
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// String field of a JSON payload
+pub(crate) fn payload_str<'a>(data: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    data.get(key).and_then(|v| v.as_str())
+}
+
+/// Graph type named by a `GraphInitialized` event; unknown names are generic
+fn graph_type_named(name: &str) -> GraphType {
+    match name {
+        "generic" => GraphType::Generic,
+        "ipld" => GraphType::IpldGraph,
+        "context" => GraphType::ContextGraph,
+        "workflow" => GraphType::WorkflowGraph,
+        "concept" => GraphType::ConceptGraph,
+        "composed" => GraphType::ComposedGraph,
+        _ => GraphType::Generic,
+    }
+}
+
+/// Point in an aggregate's history for time-travel queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsOf {
+    /// After the event with this sequence number
+    Sequence(u64),
+    /// After the last event recorded at or before this time
+    Time(chrono::DateTime<chrono::Utc>),
+}
+
+impl AsOf {
+    /// Whether an event happened at or before this point
+    pub fn includes(&self, event: &GraphEvent) -> bool {
+        match self {
+            AsOf::Sequence(sequence) => event.sequence <= *sequence,
+            AsOf::Time(time) => event.timestamp <= *time,
+        }
+    }
+}
+
 /// Engine for building and updating projections from events
 #[derive(Debug)]
 pub struct ProjectionEngine<P: GraphProjection> {
@@ -131,10 +289,12 @@ impl<P: GraphProjection> ProjectionEngine<P> {
     }
 }
 
-impl<N: Node, E: Edge> ProjectionEngine<GenericGraphProjection<N, E>>
+impl<N, E> ProjectionEngine<GenericGraphProjection<N, E>>
+where
+    N: Node + FromEventData,
+    E: Edge + FromEventData,
 {
     /// Build a projection from a stream of events
-    /// Note: This creates an empty projection - specific graph types should handle their own node/edge creation
     pub fn project(&self, events: Vec<GraphEvent>) -> GenericGraphProjection<N, E> {
         if events.is_empty() {
             panic!("Cannot build projection from empty event stream");
@@ -155,6 +315,64 @@ impl<N: Node, E: Edge> ProjectionEngine<GenericGraphProjection<N, E>>
         projection
     }
     
+    /// Build a projection as it was at a point in the aggregate's history
+    ///
+    /// Starts from the newest snapshot at or before that point and replays
+    /// the remaining events. Without a usable snapshot the whole history up
+    /// to the point is replayed; before the first event the projection is
+    /// empty at version 0.
+    ///
+    /// The graph type comes from the last `GraphInitialized` event up to the
+    /// point, or is `graph_type` if there is none, whether or not a snapshot
+    /// was used.
+    pub fn project_as_of(
+        &self,
+        aggregate_id: Uuid,
+        graph_type: GraphType,
+        as_of: AsOf,
+        events: &EventStore,
+        snapshots: &dyn SnapshotStore,
+    ) -> Result<GenericGraphProjection<N, E>>
+    where
+        N: Clone + serde::Serialize + serde::de::DeserializeOwned,
+        E: Clone + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let history = events.get_events_as_of(&aggregate_id, as_of);
+        let version = match history.last() {
+            Some(event) => event.sequence,
+            None => return Ok(GenericGraphProjection::new(aggregate_id, graph_type)),
+        };
+
+        // A snapshot that cannot be loaded or verified only costs replay time
+        let snapshot = snapshots
+            .latest_at(aggregate_id, version)
+            .ok()
+            .flatten()
+            .and_then(|snapshot| GenericGraphProjection::from_snapshot(snapshot).ok());
+        let mut projection = match snapshot {
+            Some(mut projection) => {
+                let restored = projection.version;
+                projection.graph_type = history
+                    .iter()
+                    .filter(|event| event.sequence <= restored)
+                    .filter_map(|event| match &event.data {
+                        EventData::GraphInitialized { graph_type, .. } => Some(graph_type_named(graph_type)),
+                        _ => None,
+                    })
+                    .last()
+                    .unwrap_or(graph_type);
+                projection
+            }
+            None => GenericGraphProjection::new(aggregate_id, graph_type),
+        };
+
+        let start = projection.version;
+        for event in history.iter().filter(|event| event.sequence > start) {
+            self.apply(&mut projection, event);
+        }
+        Ok(projection)
+    }
+
     /// Apply a single event to update a projection
     pub fn apply(&self, projection: &mut GenericGraphProjection<N, E>, event: &GraphEvent) {
         // Validate event is for this aggregate
@@ -169,15 +387,7 @@ impl<N: Node, E: Edge> ProjectionEngine<GenericGraphProjection<N, E>>
         // Apply event data
         match &event.data {
             EventData::GraphInitialized { graph_type, metadata } => {
-                projection.graph_type = match graph_type.as_str() {
-                    "generic" => GraphType::Generic,
-                    "ipld" => GraphType::IpldGraph,
-                    "context" => GraphType::ContextGraph,
-                    "workflow" => GraphType::WorkflowGraph,
-                    "concept" => GraphType::ConceptGraph,
-                    "composed" => GraphType::ComposedGraph,
-                    _ => GraphType::Generic,
-                };
+                projection.graph_type = graph_type_named(graph_type);
                 
                 // Update metadata from event
                 for (key, value) in metadata {
@@ -185,38 +395,20 @@ impl<N: Node, E: Edge> ProjectionEngine<GenericGraphProjection<N, E>>
                 }
             }
             
-            EventData::NodeAdded { node_id, node_type, data } => {
-                // Generic projection doesn't create typed nodes
-                // Specific graph types (WorkflowNode, ConceptNode, etc.) handle their own node creation
-                // Here we just track the node exists for adjacency purposes
-                projection.adjacency.insert(node_id.clone(), Vec::new());
-                
-                // Store metadata about the node for later use
-                projection.metadata.properties.insert(
-                    format!("node_{}", node_id),
-                    serde_json::json!({
-                        "type": node_type,
-                        "data": data
-                    })
-                );
+            EventData::NodeAdded { node_id, .. } => {
+                if let Some(node) = N::from_event_data(&event.data) {
+                    projection.nodes.insert(node_id.clone(), node);
+                    projection.adjacency.entry(node_id.clone()).or_default();
+                }
             }
             
-            EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, data } => {
-                // Update adjacency list
-                if let Some(adj) = projection.adjacency.get_mut(source_id) {
-                    adj.push(target_id.clone());
+            EventData::EdgeAdded { edge_id, source_id, target_id, .. } => {
+                if let Some(edge) = E::from_event_data(&event.data) {
+                    projection.edges.insert(edge_id.clone(), edge);
+                    if let Some(adj) = projection.adjacency.get_mut(source_id) {
+                        adj.push(target_id.clone());
+                    }
                 }
-                
-                // Store edge metadata for later use
-                projection.metadata.properties.insert(
-                    format!("edge_{}", edge_id),
-                    serde_json::json!({
-                        "source": source_id,
-                        "target": target_id,
-                        "type": edge_type,
-                        "data": data
-                    })
-                );
             }
             
             EventData::NodeRemoved { node_id } => {
@@ -242,39 +434,26 @@ impl<N: Node, E: Edge> ProjectionEngine<GenericGraphProjection<N, E>>
             
             EventData::EdgeRemoved { edge_id } => {
                 if let Some(edge) = projection.edges.remove(edge_id) {
-                    // Update adjacency
+                    // Update adjacency, keeping entries for parallel edges
                     if let Some(adj) = projection.adjacency.get_mut(&edge.source()) {
-                        adj.retain(|id| id != &edge.target());
+                        let target = edge.target();
+                        if let Some(pos) = adj.iter().position(|id| *id == target) {
+                            adj.remove(pos);
+                        }
                     }
                 }
             }
             
             EventData::NodeUpdated { node_id, data } => {
-                // Update node metadata
-                projection.metadata.properties.insert(
-                    format!("node_{}_updated", node_id),
-                    data.clone()
-                );
-                
-                // Update timestamp to track when node was last modified
-                projection.metadata.properties.insert(
-                    format!("node_{}_updated_at", node_id),
-                    serde_json::json!(event.timestamp.to_rfc3339())
-                );
+                if let Some(node) = projection.nodes.get_mut(node_id) {
+                    node.merge_event_data(data);
+                }
             }
             
             EventData::EdgeUpdated { edge_id, data } => {
-                // Update edge metadata
-                projection.metadata.properties.insert(
-                    format!("edge_{}_updated", edge_id),
-                    data.clone()
-                );
-                
-                // Update timestamp to track when edge was last modified
-                projection.metadata.properties.insert(
-                    format!("edge_{}_updated_at", edge_id),
-                    serde_json::json!(event.timestamp.to_rfc3339())
-                );
+                if let Some(edge) = projection.edges.get_mut(edge_id) {
+                    edge.merge_event_data(data);
+                }
             }
         }
     }
@@ -369,19 +548,36 @@ impl ProjectionSnapshot {
         }
     }
 
-    /// Compute checksum for data integrity
+    /// Compute checksum for data integrity: the CID of the data as DAG-CBOR
     fn compute_checksum(data: &serde_json::Value) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        data.to_string().hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+        Cid::for_payload(data, HashAlgorithm::Sha2_256).as_str().to_string()
     }
 
-    /// Verify snapshot integrity
+    /// Verify snapshot integrity by re-hashing the data against the checksum CID
     pub fn verify_integrity(&self) -> bool {
-        Self::compute_checksum(&self.data) == self.checksum
+        Cid::parse(&self.checksum)
+            .map(|cid| cid.matches_payload(&self.data))
+            .unwrap_or(false)
+    }
+
+    /// Serialize and zstd-compress the snapshot for storage
+    pub fn encode(&self, compressor: &EventCompressor) -> Result<Vec<u8>> {
+        compressor.compress_bytes(&serde_json::to_vec(self)?)
+    }
+
+    /// Decompress and deserialize a stored snapshot
+    ///
+    /// Fails with [`GraphError::SerializationError`] when the data no longer
+    /// matches its checksum.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let snapshot: Self = serde_json::from_slice(&EventCompressor::decompress_bytes(bytes)?)?;
+        if !snapshot.verify_integrity() {
+            return Err(GraphError::SerializationError(format!(
+                "Snapshot of {} at version {} failed its integrity check",
+                snapshot.aggregate_id, snapshot.version
+            )));
+        }
+        Ok(snapshot)
     }
 }
 
@@ -411,6 +607,56 @@ impl EventStore {
             .extend(events);
     }
 
+    /// Append events only if the aggregate is still at `expected_version`
+    ///
+    /// Returns the new version. Fails with [`GraphError::ConcurrencyConflict`]
+    /// when another writer appended first, and with
+    /// [`GraphError::InvalidOperation`] when an event belongs to another
+    /// aggregate or its sequence does not follow the previous one.
+    pub fn append_expected(
+        &mut self,
+        aggregate_id: Uuid,
+        expected_version: u64,
+        events: Vec<GraphEvent>,
+    ) -> Result<u64> {
+        let actual = self.version(&aggregate_id);
+        if actual != expected_version {
+            return Err(GraphError::ConcurrencyConflict {
+                expected: expected_version,
+                actual,
+            });
+        }
+
+        let mut previous = actual;
+        for event in &events {
+            if event.aggregate_id != aggregate_id {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Event {} belongs to aggregate {}, not {}",
+                    event.event_id, event.aggregate_id, aggregate_id
+                )));
+            }
+            if event.sequence <= previous {
+                return Err(GraphError::InvalidOperation(format!(
+                    "Event sequence {} does not follow version {}",
+                    event.sequence, previous
+                )));
+            }
+            previous = event.sequence;
+        }
+
+        self.append(aggregate_id, events);
+        Ok(previous)
+    }
+
+    /// Current version of an aggregate: the sequence of its last event, or 0
+    pub fn version(&self, aggregate_id: &Uuid) -> u64 {
+        self.events
+            .get(aggregate_id)
+            .and_then(|events| events.last())
+            .map(|event| event.sequence)
+            .unwrap_or(0)
+    }
+
     /// Get all events for an aggregate
     pub fn get_events(&self, aggregate_id: &Uuid) -> Vec<GraphEvent> {
         self.events.get(aggregate_id).cloned().unwrap_or_default()
@@ -430,6 +676,19 @@ impl EventStore {
             .unwrap_or_default()
     }
 
+    /// Get events up to and including a specific version
+    pub fn get_events_until(&self, aggregate_id: &Uuid, version: u64) -> Vec<GraphEvent> {
+        self.get_events_as_of(aggregate_id, AsOf::Sequence(version))
+    }
+
+    /// Get events that happened at or before a point in history
+    pub fn get_events_as_of(&self, aggregate_id: &Uuid, as_of: AsOf) -> Vec<GraphEvent> {
+        self.events
+            .get(aggregate_id)
+            .map(|events| events.iter().filter(|e| as_of.includes(e)).cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Save a snapshot
     pub fn save_snapshot(&mut self, snapshot: ProjectionSnapshot) {
         self.snapshots.insert(snapshot.aggregate_id, snapshot);
@@ -465,24 +724,47 @@ pub struct EnhancedProjectionEngine<P: GraphProjection> {
     engine: ProjectionEngine<P>,
     /// Event store for replay
     event_store: EventStore,
+    /// Snapshot backend
+    snapshots: Box<dyn SnapshotStore>,
     /// Snapshot interval (events between snapshots)
     snapshot_interval: u64,
 }
 
+/// Projection state stored in the data of a [`GenericGraphProjection`] snapshot
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SnapshotState<N, E> {
+    graph_type: GraphType,
+    metadata: GraphMetadata,
+    nodes: HashMap<String, N>,
+    edges: HashMap<String, E>,
+    adjacency: HashMap<String, Vec<String>>,
+}
+
 impl<N: Node + Clone, E: Edge + Clone> EnhancedProjectionEngine<GenericGraphProjection<N, E>>
 where
-    N: serde::Serialize + serde::de::DeserializeOwned,
-    E: serde::Serialize + serde::de::DeserializeOwned,
+    N: FromEventData + serde::Serialize + serde::de::DeserializeOwned,
+    E: FromEventData + serde::Serialize + serde::de::DeserializeOwned,
 {
-    /// Create a new enhanced projection engine
+    /// Create a new enhanced projection engine with in-memory snapshots
     pub fn new(snapshot_interval: u64) -> Self {
+        Self::with_snapshot_store(snapshot_interval, Box::new(MemorySnapshotStore::default()))
+    }
+
+    /// Create a new enhanced projection engine with a snapshot backend
+    pub fn with_snapshot_store(snapshot_interval: u64, snapshots: Box<dyn SnapshotStore>) -> Self {
         Self {
             engine: ProjectionEngine::new(),
             event_store: EventStore::new(),
+            snapshots,
             snapshot_interval,
         }
     }
 
+    /// Get the snapshot backend
+    pub fn snapshot_store(&self) -> &dyn SnapshotStore {
+        self.snapshots.as_ref()
+    }
+
     /// Rebuild projection from events
     pub fn rebuild_from_events(&mut self, aggregate_id: Uuid) -> GenericGraphProjection<N, E> {
         let events = self.event_store.get_events(&aggregate_id);
@@ -492,42 +774,22 @@ where
         self.engine.project(events)
     }
 
-    /// Create a snapshot of the current projection
-    pub fn snapshot(&mut self, projection: &GenericGraphProjection<N, E>) -> ProjectionSnapshot {
-        let data = serde_json::json!({
-            "aggregate_id": projection.aggregate_id.to_string(),
-            "version": projection.version,
-            "graph_type": format!("{:?}", projection.graph_type),
-            "node_count": projection.nodes.len(),
-            "edge_count": projection.edges.len(),
-            "metadata": projection.metadata.properties,
-        });
-
-        let snapshot = ProjectionSnapshot::new(
-            projection.aggregate_id,
-            projection.version,
-            data,
-        );
-
-        self.event_store.save_snapshot(snapshot.clone());
-        snapshot
+    /// Create a snapshot of the current projection and save it to the snapshot store
+    pub fn snapshot(&mut self, projection: &GenericGraphProjection<N, E>) -> Result<ProjectionSnapshot> {
+        let snapshot = projection.to_snapshot()?;
+        self.snapshots.save(&snapshot)?;
+        Ok(snapshot)
     }
 
-    /// Restore projection from snapshot and replay newer events
+    /// Restore projection from the latest snapshot and replay newer events
+    ///
+    /// Returns `None` when there is no snapshot or it fails its integrity check.
     pub fn restore_from_snapshot(&mut self, aggregate_id: Uuid) -> Option<GenericGraphProjection<N, E>> {
-        let snapshot = self.event_store.get_snapshot(&aggregate_id)?;
-
-        // Verify snapshot integrity
-        if !snapshot.verify_integrity() {
-            return None;
-        }
-
-        // Create base projection from snapshot
-        let mut projection = GenericGraphProjection::new(aggregate_id, GraphType::Generic);
-        projection.version = snapshot.version;
+        let snapshot = self.snapshots.latest(aggregate_id).ok().flatten()?;
+        let mut projection = GenericGraphProjection::from_snapshot(snapshot).ok()?;
 
         // Replay events since snapshot
-        let newer_events = self.event_store.get_events_since(&aggregate_id, snapshot.version);
+        let newer_events = self.event_store.get_events_since(&aggregate_id, projection.version);
         for event in newer_events {
             self.engine.apply(&mut projection, &event);
         }
@@ -535,6 +797,24 @@ where
         Some(projection)
     }
 
+    /// Build the projection as it was at a point in history
+    ///
+    /// See [`ProjectionEngine::project_as_of`].
+    pub fn project_as_of(
+        &self,
+        aggregate_id: Uuid,
+        graph_type: GraphType,
+        as_of: AsOf,
+    ) -> Result<GenericGraphProjection<N, E>> {
+        self.engine.project_as_of(
+            aggregate_id,
+            graph_type,
+            as_of,
+            &self.event_store,
+            self.snapshots.as_ref(),
+        )
+    }
+
     /// Apply event and auto-snapshot if needed
     pub fn apply_with_snapshot(
         &mut self,
@@ -547,9 +827,9 @@ where
         // Apply event
         self.engine.apply(projection, &event);
 
-        // Auto-snapshot if interval reached
+        // Auto-snapshot if interval reached; a failed snapshot only costs replay time
         if projection.version > 0 && projection.version % self.snapshot_interval == 0 {
-            self.snapshot(projection);
+            let _ = self.snapshot(projection);
         }
     }
 
@@ -590,6 +870,23 @@ mod tests {
         }
     }
 
+    impl FromEventData for SerializableNode {
+        fn from_event_data(data: &EventData) -> Option<Self> {
+            match data {
+                EventData::NodeAdded { node_id, node_type, .. } => {
+                    Some(Self::new(node_id.clone(), node_type.clone()))
+                }
+                _ => None,
+            }
+        }
+
+        fn merge_event_data(&mut self, data: &serde_json::Value) {
+            if let Some(node_type) = payload_str(data, "node_type") {
+                self.node_type = node_type.to_string();
+            }
+        }
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize)]
     struct SerializableEdge {
         id: String,
@@ -623,6 +920,23 @@ mod tests {
         }
     }
 
+    impl FromEventData for SerializableEdge {
+        fn from_event_data(data: &EventData) -> Option<Self> {
+            match data {
+                EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, .. } => Some(
+                    Self::new(edge_id.clone(), source_id.clone(), target_id.clone(), edge_type.clone()),
+                ),
+                _ => None,
+            }
+        }
+
+        fn merge_event_data(&mut self, data: &serde_json::Value) {
+            if let Some(edge_type) = payload_str(data, "edge_type") {
+                self.edge_type = edge_type.to_string();
+            }
+        }
+    }
+
     type SerializableProjection = GenericGraphProjection<SerializableNode, SerializableEdge>;
 
     fn create_test_event(
@@ -849,6 +1163,11 @@ mod tests {
         // Nodes are tracked in adjacency
         assert!(projection.adjacency.contains_key("start"));
         assert!(projection.adjacency.contains_key("end"));
+
+        // ...and materialized as typed nodes
+        assert_eq!(projection.node_count(), 2);
+        assert_eq!(projection.get_node("start").unwrap().node_type, WorkflowNodeType::Start);
+        assert_eq!(projection.get_node("end").unwrap().node_type, WorkflowNodeType::End);
     }
 
     #[test]
@@ -902,6 +1221,11 @@ mod tests {
         // Check adjacency was updated
         let neighbors_a = projection.neighbors("A");
         assert!(neighbors_a.contains(&"B"));
+
+        let edge = projection.get_edge("e1").unwrap();
+        assert_eq!(edge.source, "A");
+        assert_eq!(edge.target, "B");
+        assert_eq!(projection.edges_between("A", "B").len(), 1);
     }
 
     #[test]
@@ -1012,22 +1336,52 @@ mod tests {
         let agg_id = Uuid::new_v4();
 
         let mut projection: TestProjection = GenericGraphProjection::new(agg_id, GraphType::Generic);
-        projection.adjacency.insert("node1".to_string(), vec![]);
+        engine.apply(&mut projection, &create_test_event(
+            agg_id,
+            1,
+            EventData::NodeAdded {
+                node_id: "node1".to_string(),
+                node_type: "state".to_string(),
+                data: serde_json::json!({"name": "Pending", "owner": "ops"}),
+            },
+        ));
 
         let update_event = create_test_event(
             agg_id,
-            1,
+            2,
             EventData::NodeUpdated {
                 node_id: "node1".to_string(),
-                data: serde_json::json!({"status": "updated"}),
+                data: serde_json::json!({"status": "updated", "owner": null, "name": "Review"}),
             },
         );
 
         engine.apply(&mut projection, &update_event);
 
-        // Check metadata was updated
-        assert!(projection.metadata.properties.contains_key("node_node1_updated"));
-        assert!(projection.metadata.properties.contains_key("node_node1_updated_at"));
+        // The update is merged into the node, not stored beside it
+        let node = projection.get_node("node1").unwrap();
+        assert_eq!(node.metadata.get("status"), Some(&serde_json::json!("updated")));
+        assert!(!node.metadata.contains_key("owner"));
+        assert_eq!(node.node_type, WorkflowNodeType::State { name: "Review".to_string() });
+        assert!(!projection.metadata.properties.contains_key("node_node1_updated"));
+    }
+
+    #[test]
+    fn test_engine_apply_node_updated_unknown_node_ignored() {
+        let engine: ProjectionEngine<TestProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+
+        let mut projection: TestProjection = GenericGraphProjection::new(agg_id, GraphType::Generic);
+        engine.apply(&mut projection, &create_test_event(
+            agg_id,
+            1,
+            EventData::NodeUpdated {
+                node_id: "missing".to_string(),
+                data: serde_json::json!({"status": "updated"}),
+            },
+        ));
+
+        assert_eq!(projection.node_count(), 0);
+        assert_eq!(projection.version, 1);
     }
 
     #[test]
@@ -1036,21 +1390,122 @@ mod tests {
         let agg_id = Uuid::new_v4();
 
         let mut projection: TestProjection = GenericGraphProjection::new(agg_id, GraphType::Generic);
+        engine.apply(&mut projection, &create_test_event(
+            agg_id,
+            1,
+            EventData::EdgeAdded {
+                edge_id: "edge1".to_string(),
+                source_id: "A".to_string(),
+                target_id: "B".to_string(),
+                edge_type: "transition".to_string(),
+                data: serde_json::json!({}),
+            },
+        ));
 
         let update_event = create_test_event(
             agg_id,
-            1,
+            2,
             EventData::EdgeUpdated {
                 edge_id: "edge1".to_string(),
-                data: serde_json::json!({"weight": 10}),
+                data: serde_json::json!({"weight": 10, "trigger": "approve"}),
             },
         );
 
         engine.apply(&mut projection, &update_event);
 
-        // Check metadata was updated
-        assert!(projection.metadata.properties.contains_key("edge_edge1_updated"));
-        assert!(projection.metadata.properties.contains_key("edge_edge1_updated_at"));
+        let edge = projection.get_edge("edge1").unwrap();
+        assert_eq!(edge.metadata.get("weight"), Some(&serde_json::json!(10)));
+        assert_eq!(edge.trigger.as_deref(), Some("approve"));
+        assert!(!projection.metadata.properties.contains_key("edge_edge1_updated"));
+    }
+
+    #[test]
+    fn test_engine_apply_edge_removed_keeps_parallel_edge() {
+        let engine: ProjectionEngine<TestProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let mut projection: TestProjection = GenericGraphProjection::new(agg_id, GraphType::Generic);
+
+        let events = vec![
+            EventData::NodeAdded {
+                node_id: "A".to_string(),
+                node_type: "start".to_string(),
+                data: serde_json::json!({}),
+            },
+            EventData::NodeAdded {
+                node_id: "B".to_string(),
+                node_type: "end".to_string(),
+                data: serde_json::json!({}),
+            },
+            EventData::EdgeAdded {
+                edge_id: "e1".to_string(),
+                source_id: "A".to_string(),
+                target_id: "B".to_string(),
+                edge_type: "transition".to_string(),
+                data: serde_json::json!({}),
+            },
+            EventData::EdgeAdded {
+                edge_id: "e2".to_string(),
+                source_id: "A".to_string(),
+                target_id: "B".to_string(),
+                edge_type: "error".to_string(),
+                data: serde_json::json!({}),
+            },
+            EventData::EdgeRemoved {
+                edge_id: "e1".to_string(),
+            },
+        ];
+        for (i, data) in events.into_iter().enumerate() {
+            engine.apply(&mut projection, &create_test_event(agg_id, i as u64 + 1, data));
+        }
+
+        assert_eq!(projection.edge_count(), 1);
+        assert_eq!(projection.neighbors("A"), vec!["B"]);
+    }
+
+    #[test]
+    fn test_engine_project_materializes_serializable_types() {
+        let engine: ProjectionEngine<SerializableProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+
+        let projection = engine.project(vec![
+            create_test_event(agg_id, 1, EventData::NodeAdded {
+                node_id: "n1".to_string(),
+                node_type: "Entity".to_string(),
+                data: serde_json::json!({}),
+            }),
+            create_test_event(agg_id, 2, EventData::NodeUpdated {
+                node_id: "n1".to_string(),
+                data: serde_json::json!({"node_type": "Aggregate"}),
+            }),
+        ]);
+
+        assert_eq!(projection.get_node("n1").unwrap().node_type, "Aggregate");
+    }
+
+    #[test]
+    fn test_type_key_normalizes() {
+        assert_eq!(type_key("ValueObject"), "valueobject");
+        assert_eq!(type_key("value_object"), "valueobject");
+        assert_eq!(type_key("value-object"), "valueobject");
+        assert_eq!(type_key("Is A"), "isa");
+    }
+
+    #[test]
+    fn test_merge_fields_null_deletes() {
+        let mut fields = payload_fields(&serde_json::json!({"a": 1, "b": 2}));
+        merge_fields(&mut fields, &serde_json::json!({"a": null, "c": 3}));
+        assert!(!fields.contains_key("a"));
+        assert_eq!(fields.get("b"), Some(&serde_json::json!(2)));
+        assert_eq!(fields.get("c"), Some(&serde_json::json!(3)));
+
+        // Non-object payloads are ignored
+        merge_fields(&mut fields, &serde_json::json!("scalar"));
+        assert_eq!(fields.len(), 2);
+
+        merge_fields_except(&mut fields, &serde_json::json!({"b": null, "d": 4}), &["b"]);
+        assert!(fields.contains_key("b"));
+        assert_eq!(fields.get("d"), Some(&serde_json::json!(4)));
+        assert!(payload_fields(&serde_json::json!([1, 2])).is_empty());
     }
 
     #[test]
@@ -1302,6 +1757,51 @@ mod tests {
         assert_ne!(snapshot1.checksum, snapshot2.checksum);
     }
 
+    #[test]
+    fn test_snapshot_checksum_is_content_cid() {
+        let data = serde_json::json!({"b": 2, "a": 1});
+        let snapshot = ProjectionSnapshot::new(Uuid::new_v4(), 1, data.clone());
+
+        let cid = Cid::parse(&snapshot.checksum).unwrap();
+        assert!(cid.matches_payload(&data));
+
+        // Key order does not change the content hash
+        let reordered = ProjectionSnapshot::new(Uuid::new_v4(), 1, serde_json::json!({"a": 1, "b": 2}));
+        assert_eq!(snapshot.checksum, reordered.checksum);
+
+        let mut forged = snapshot.clone();
+        forged.checksum = "not-a-cid".to_string();
+        assert!(!forged.verify_integrity());
+    }
+
+    #[test]
+    fn test_snapshot_encode_decode() {
+        let agg_id = Uuid::new_v4();
+        let data = serde_json::json!({"nodes": vec!["A"; 50]});
+        let snapshot = ProjectionSnapshot::new(agg_id, 7, data.clone());
+
+        let encoded = snapshot.encode(&EventCompressor::default()).unwrap();
+        assert!(encoded.len() < serde_json::to_vec(&snapshot).unwrap().len());
+
+        let decoded = ProjectionSnapshot::decode(&encoded).unwrap();
+        assert_eq!(decoded.aggregate_id, agg_id);
+        assert_eq!(decoded.version, 7);
+        assert_eq!(decoded.data, data);
+    }
+
+    #[test]
+    fn test_snapshot_decode_rejects_tampering() {
+        let mut snapshot = ProjectionSnapshot::new(Uuid::new_v4(), 3, serde_json::json!({"a": 1}));
+        snapshot.data = serde_json::json!({"a": 2});
+        let encoded = snapshot.encode(&EventCompressor::default()).unwrap();
+
+        assert!(matches!(
+            ProjectionSnapshot::decode(&encoded),
+            Err(GraphError::SerializationError(_))
+        ));
+        assert!(ProjectionSnapshot::decode(b"garbage").is_err());
+    }
+
     #[test]
     fn test_snapshot_serialization() {
         let agg_id = Uuid::new_v4();
@@ -1381,6 +1881,91 @@ mod tests {
         assert_eq!(store.event_count(&agg_id), 3);
     }
 
+    #[test]
+    fn test_event_store_version() {
+        let mut store = EventStore::new();
+        let agg_id = Uuid::new_v4();
+        assert_eq!(store.version(&agg_id), 0);
+
+        store.append(agg_id, vec![
+            create_test_event(agg_id, 1, EventData::NodeRemoved { node_id: "A".to_string() }),
+            create_test_event(agg_id, 2, EventData::NodeRemoved { node_id: "B".to_string() }),
+        ]);
+        assert_eq!(store.version(&agg_id), 2);
+    }
+
+    #[test]
+    fn test_event_store_append_expected() {
+        let mut store = EventStore::new();
+        let agg_id = Uuid::new_v4();
+
+        let version = store
+            .append_expected(agg_id, 0, vec![
+                create_test_event(agg_id, 1, EventData::NodeRemoved { node_id: "A".to_string() }),
+                create_test_event(agg_id, 2, EventData::NodeRemoved { node_id: "B".to_string() }),
+            ])
+            .unwrap();
+        assert_eq!(version, 2);
+
+        let version = store
+            .append_expected(agg_id, 2, vec![
+                create_test_event(agg_id, 3, EventData::NodeRemoved { node_id: "C".to_string() }),
+            ])
+            .unwrap();
+        assert_eq!(version, 3);
+        assert_eq!(store.event_count(&agg_id), 3);
+
+        // An empty append leaves the version unchanged
+        assert_eq!(store.append_expected(agg_id, 3, vec![]).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_event_store_append_expected_conflict() {
+        let mut store = EventStore::new();
+        let agg_id = Uuid::new_v4();
+        store.append(agg_id, vec![
+            create_test_event(agg_id, 1, EventData::NodeRemoved { node_id: "A".to_string() }),
+        ]);
+
+        // Two writers read version 1; the second one must lose
+        store
+            .append_expected(agg_id, 1, vec![
+                create_test_event(agg_id, 2, EventData::NodeRemoved { node_id: "B".to_string() }),
+            ])
+            .unwrap();
+        let result = store.append_expected(agg_id, 1, vec![
+            create_test_event(agg_id, 2, EventData::NodeRemoved { node_id: "C".to_string() }),
+        ]);
+
+        match result {
+            Err(GraphError::ConcurrencyConflict { expected, actual }) => {
+                assert_eq!(expected, 1);
+                assert_eq!(actual, 2);
+            }
+            other => panic!("expected a concurrency conflict, got {:?}", other),
+        }
+        assert_eq!(store.event_count(&agg_id), 2);
+    }
+
+    #[test]
+    fn test_event_store_append_expected_validates_events() {
+        let mut store = EventStore::new();
+        let agg_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+
+        let foreign = store.append_expected(agg_id, 0, vec![
+            create_test_event(other_id, 1, EventData::NodeRemoved { node_id: "A".to_string() }),
+        ]);
+        assert!(matches!(foreign, Err(GraphError::InvalidOperation(_))));
+
+        let out_of_order = store.append_expected(agg_id, 0, vec![
+            create_test_event(agg_id, 2, EventData::NodeRemoved { node_id: "A".to_string() }),
+            create_test_event(agg_id, 2, EventData::NodeRemoved { node_id: "B".to_string() }),
+        ]);
+        assert!(matches!(out_of_order, Err(GraphError::InvalidOperation(_))));
+        assert_eq!(store.event_count(&agg_id), 0);
+    }
+
     #[test]
     fn test_event_store_get_events() {
         let mut store = EventStore::new();
@@ -1460,6 +2045,26 @@ mod tests {
         assert!(since.is_empty());
     }
 
+    #[test]
+    fn test_event_store_get_events_until() {
+        let mut store = EventStore::new();
+        let agg_id = Uuid::new_v4();
+
+        let events = (1..=4)
+            .map(|i| create_test_event(agg_id, i, EventData::NodeAdded {
+                node_id: format!("node{}", i),
+                node_type: "Node".to_string(),
+                data: serde_json::json!({}),
+            }))
+            .collect();
+        store.append(agg_id, events);
+
+        let until: Vec<u64> = store.get_events_until(&agg_id, 2).iter().map(|e| e.sequence).collect();
+        assert_eq!(until, vec![1, 2]);
+        assert!(store.get_events_until(&agg_id, 0).is_empty());
+        assert_eq!(store.get_events_until(&agg_id, 10).len(), 4);
+    }
+
     #[test]
     fn test_event_store_get_events_since_all() {
         let mut store = EventStore::new();
@@ -1645,14 +2250,14 @@ mod tests {
         let mut projection: SerializableProjection = GenericGraphProjection::new(agg_id, GraphType::WorkflowGraph);
         projection.version = 5;
 
-        let snapshot = engine.snapshot(&projection);
+        let snapshot = engine.snapshot(&projection).unwrap();
 
         assert_eq!(snapshot.aggregate_id, agg_id);
         assert_eq!(snapshot.version, 5);
         assert!(snapshot.verify_integrity());
 
         // Verify snapshot was saved
-        let saved = engine.event_store.get_snapshot(&agg_id);
+        let saved = engine.snapshot_store().latest(agg_id).unwrap();
         assert!(saved.is_some());
     }
 
@@ -1661,13 +2266,16 @@ mod tests {
         let mut engine: EnhancedProjectionEngine<SerializableProjection> = EnhancedProjectionEngine::new(10);
         let agg_id = Uuid::new_v4();
 
-        // Create and save snapshot
-        let snapshot = ProjectionSnapshot::new(
-            agg_id,
-            5,
-            serde_json::json!({"nodes": 3}),
-        );
-        engine.event_store.save_snapshot(snapshot);
+        // Build a projection and snapshot it at version 5
+        let mut projection: SerializableProjection = GenericGraphProjection::new(agg_id, GraphType::WorkflowGraph);
+        for i in 1..=5 {
+            engine.apply_with_snapshot(&mut projection, create_test_event(agg_id, i, EventData::NodeAdded {
+                node_id: format!("node{}", i),
+                node_type: "Node".to_string(),
+                data: serde_json::json!({}),
+            }));
+        }
+        engine.snapshot(&projection).unwrap();
 
         // Add some events after snapshot
         engine.event_store.append(agg_id, vec![
@@ -1685,6 +2293,9 @@ mod tests {
         let projection = result.unwrap();
         assert_eq!(projection.aggregate_id, agg_id);
         assert_eq!(projection.version, 6); // Updated by replayed event
+        assert!(matches!(projection.graph_type, GraphType::WorkflowGraph));
+        assert_eq!(projection.nodes.len(), 6); // Five from the snapshot, one replayed
+        assert!(projection.nodes.contains_key("node3"));
     }
 
     #[test]
@@ -1708,7 +2319,7 @@ mod tests {
             serde_json::json!({"original": "data"}),
         );
         snapshot.data = serde_json::json!({"tampered": "data"});
-        engine.event_store.save_snapshot(snapshot);
+        engine.snapshots.put(&snapshot).unwrap();
 
         // Restore should fail due to integrity check
         let result = engine.restore_from_snapshot(agg_id);
@@ -1733,7 +2344,7 @@ mod tests {
         }
 
         // Should have created a snapshot at version 5
-        let snapshot = engine.event_store.get_snapshot(&agg_id);
+        let snapshot = engine.snapshot_store().latest(agg_id).unwrap();
         assert!(snapshot.is_some());
         assert_eq!(snapshot.unwrap().version, 5);
     }
@@ -1756,7 +2367,7 @@ mod tests {
         }
 
         // Should not have created a snapshot
-        let snapshot = engine.event_store.get_snapshot(&agg_id);
+        let snapshot = engine.snapshot_store().latest(agg_id).unwrap();
         assert!(snapshot.is_none());
     }
 
@@ -1784,6 +2395,234 @@ mod tests {
         assert_eq!(events[1].sequence, 5);
     }
 
+    // ========== Time-Travel Tests ==========
+
+    fn node_added_at(
+        aggregate_id: Uuid,
+        sequence: u64,
+        timestamp: chrono::DateTime<Utc>,
+    ) -> GraphEvent {
+        GraphEvent {
+            timestamp,
+            ..create_test_event(aggregate_id, sequence, EventData::NodeAdded {
+                node_id: format!("node{}", sequence),
+                node_type: "Node".to_string(),
+                data: serde_json::json!({}),
+            })
+        }
+    }
+
+    fn history(aggregate_id: Uuid, count: u64) -> EventStore {
+        let start = Utc::now() - chrono::Duration::days(count as i64);
+        let mut store = EventStore::new();
+        store.append(
+            aggregate_id,
+            (1..=count)
+                .map(|i| node_added_at(aggregate_id, i, start + chrono::Duration::days(i as i64)))
+                .collect(),
+        );
+        store
+    }
+
+    #[test]
+    fn test_as_of_includes() {
+        let agg_id = Uuid::new_v4();
+        let now = Utc::now();
+        let event = node_added_at(agg_id, 3, now);
+
+        assert!(AsOf::Sequence(3).includes(&event));
+        assert!(!AsOf::Sequence(2).includes(&event));
+        assert!(AsOf::Time(now).includes(&event));
+        assert!(!AsOf::Time(now - chrono::Duration::seconds(1)).includes(&event));
+    }
+
+    #[test]
+    fn test_project_as_of_sequence_without_snapshot() {
+        let engine: ProjectionEngine<TestProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let store = history(agg_id, 5);
+
+        let projection = engine
+            .project_as_of(
+                agg_id,
+                GraphType::Generic,
+                AsOf::Sequence(3),
+                &store,
+                &MemorySnapshotStore::default(),
+            )
+            .unwrap();
+
+        assert_eq!(projection.version, 3);
+        assert_eq!(projection.nodes.len(), 3);
+        assert!(projection.nodes.contains_key("node3"));
+        assert!(!projection.nodes.contains_key("node4"));
+    }
+
+    #[test]
+    fn test_project_as_of_time() {
+        let engine: ProjectionEngine<TestProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let store = history(agg_id, 5);
+        let fourth = store.get_events(&agg_id)[3].timestamp;
+
+        let snapshots = MemorySnapshotStore::default();
+        let at_fourth = engine
+            .project_as_of(agg_id, GraphType::Generic, AsOf::Time(fourth), &store, &snapshots)
+            .unwrap();
+        assert_eq!(at_fourth.version, 4);
+
+        let just_before = fourth - chrono::Duration::seconds(1);
+        let before_fourth = engine
+            .project_as_of(agg_id, GraphType::Generic, AsOf::Time(just_before), &store, &snapshots)
+            .unwrap();
+        assert_eq!(before_fourth.version, 3);
+        assert_eq!(before_fourth.nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_project_as_of_uses_nearest_earlier_snapshot() {
+        let engine: ProjectionEngine<SerializableProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let store = history(agg_id, 5);
+        let mut snapshots = MemorySnapshotStore::default();
+
+        // Snapshots carry a marker node the events never add, so we can tell which one was used
+        for version in [2, 4] {
+            let mut projection = engine.project(store.get_events_until(&agg_id, version));
+            let marker = format!("snapshot{}", version);
+            projection.nodes.insert(marker.clone(), SerializableNode::new(marker, "Marker"));
+            snapshots.save(&projection.to_snapshot().unwrap()).unwrap();
+        }
+
+        let projection = engine
+            .project_as_of(agg_id, GraphType::Generic, AsOf::Sequence(3), &store, &snapshots)
+            .unwrap();
+        assert_eq!(projection.version, 3);
+        assert!(projection.nodes.contains_key("snapshot2"));
+        assert!(!projection.nodes.contains_key("snapshot4"));
+        assert!(projection.nodes.contains_key("node3"));
+
+        let projection = engine
+            .project_as_of(agg_id, GraphType::Generic, AsOf::Sequence(4), &store, &snapshots)
+            .unwrap();
+        assert!(projection.nodes.contains_key("snapshot4"));
+        assert_eq!(projection.nodes.len(), 5);
+    }
+
+    #[test]
+    fn test_project_as_of_before_first_event() {
+        let engine: ProjectionEngine<TestProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let store = history(agg_id, 3);
+        let first = store.get_events(&agg_id)[0].timestamp;
+
+        let projection = engine
+            .project_as_of(
+                agg_id,
+                GraphType::Generic,
+                AsOf::Time(first - chrono::Duration::seconds(1)),
+                &store,
+                &MemorySnapshotStore::default(),
+            )
+            .unwrap();
+
+        assert_eq!(projection.aggregate_id, agg_id);
+        assert_eq!(projection.version, 0);
+        assert!(projection.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_enhanced_engine_project_as_of_workflow() {
+        let mut engine: EnhancedProjectionEngine<TestProjection> = EnhancedProjectionEngine::new(2);
+        let agg_id = Uuid::new_v4();
+
+        let mut projection: TestProjection = GenericGraphProjection::new(agg_id, GraphType::WorkflowGraph);
+        for event in history(agg_id, 5).get_events(&agg_id) {
+            engine.apply_with_snapshot(&mut projection, event);
+        }
+        assert_eq!(engine.snapshot_store().versions(agg_id).unwrap(), vec![2, 4]);
+
+        let past = engine.project_as_of(agg_id, GraphType::WorkflowGraph, AsOf::Sequence(3)).unwrap();
+        assert_eq!(past.version, 3);
+        assert_eq!(past.nodes.len(), 3);
+        assert!(matches!(past.graph_type, GraphType::WorkflowGraph));
+    }
+
+    #[test]
+    fn test_project_as_of_replays_past_unusable_snapshots() {
+        let engine: ProjectionEngine<SerializableProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let store = history(agg_id, 5);
+        let mut snapshots = MemorySnapshotStore::default();
+
+        // Fails its integrity check on load
+        let mut tampered = engine.project(store.get_events_until(&agg_id, 2)).to_snapshot().unwrap();
+        tampered.checksum = "0".repeat(64);
+        snapshots.put(&tampered).unwrap();
+        // Loads, but is not a projection
+        snapshots.put(&ProjectionSnapshot::new(agg_id, 4, serde_json::json!("garbage"))).unwrap();
+
+        let projection = engine
+            .project_as_of(agg_id, GraphType::ConceptGraph, AsOf::Sequence(3), &store, &snapshots)
+            .unwrap();
+        assert_eq!(projection.version, 3);
+        assert_eq!(projection.nodes.len(), 3);
+        assert_eq!(projection.graph_type, GraphType::ConceptGraph);
+
+        let projection = engine
+            .project_as_of(agg_id, GraphType::ConceptGraph, AsOf::Sequence(5), &store, &snapshots)
+            .unwrap();
+        assert_eq!(projection.version, 5);
+        assert_eq!(projection.nodes.len(), 5);
+    }
+
+    #[test]
+    fn test_project_as_of_matches_with_and_without_snapshots() {
+        let engine: ProjectionEngine<SerializableProjection> = ProjectionEngine::new();
+        let agg_id = Uuid::new_v4();
+        let mut events = vec![create_test_event(agg_id, 1, EventData::GraphInitialized {
+            graph_type: "workflow".to_string(),
+            metadata: HashMap::new(),
+        })];
+        events.extend((2..=4).map(|i| node_added_at(agg_id, i, Utc::now())));
+        events.push(create_test_event(agg_id, 5, EventData::EdgeAdded {
+            edge_id: "e1".to_string(),
+            source_id: "node2".to_string(),
+            target_id: "node4".to_string(),
+            edge_type: "Edge".to_string(),
+            data: serde_json::json!({}),
+        }));
+        let mut store = EventStore::new();
+        store.append(agg_id, events);
+
+        // A snapshot claiming another graph type must not leak into the result
+        let mut snapshots = MemorySnapshotStore::default();
+        let mut at_three = engine.project(store.get_events_until(&agg_id, 3));
+        at_three.graph_type = GraphType::ComposedGraph;
+        snapshots.save(&at_three.to_snapshot().unwrap()).unwrap();
+
+        for version in 1..=5 {
+            let as_of = AsOf::Sequence(version);
+            let replayed = engine
+                .project_as_of(agg_id, GraphType::Generic, as_of, &store, &MemorySnapshotStore::default())
+                .unwrap();
+            let restored = engine
+                .project_as_of(agg_id, GraphType::Generic, as_of, &store, &snapshots)
+                .unwrap();
+
+            assert_eq!(restored.version, replayed.version);
+            assert_eq!(restored.graph_type, GraphType::WorkflowGraph);
+            assert_eq!(replayed.graph_type, GraphType::WorkflowGraph);
+            let mut restored_nodes: Vec<_> = restored.nodes.keys().collect();
+            let mut replayed_nodes: Vec<_> = replayed.nodes.keys().collect();
+            restored_nodes.sort();
+            replayed_nodes.sort();
+            assert_eq!(restored_nodes, replayed_nodes);
+            assert_eq!(restored.edges.keys().collect::<Vec<_>>(), replayed.edges.keys().collect::<Vec<_>>());
+            assert_eq!(restored.adjacency, replayed.adjacency);
+        }
+    }
+
     // ========== Integration Tests ==========
 
     #[test]
@@ -1816,8 +2655,8 @@ mod tests {
         assert!(matches!(projection.graph_type, GraphType::WorkflowGraph));
 
         // Verify snapshot was created at version 5
-        let snapshot = engine.event_store.get_snapshot(&agg_id);
-        assert!(snapshot.is_some());
+        let snapshot = engine.snapshot_store().latest(agg_id).unwrap();
+        assert_eq!(snapshot.map(|s| s.version), Some(5));
 
         // Verify events are stored
         let all_events = engine.event_store.get_events(&agg_id);
@@ -1872,6 +2711,9 @@ mod tests {
         assert_eq!(projection.adjacency.len(), rebuilt.adjacency.len());
         assert!(projection.adjacency.contains_key("A"));
         assert!(rebuilt.adjacency.contains_key("A"));
+        assert_eq!(projection.node_count(), rebuilt.node_count());
+        assert_eq!(rebuilt.node_count(), 2);
+        assert_eq!(rebuilt.get_edge("e1").unwrap().edge_type, "Transition");
     }
 
     #[test]
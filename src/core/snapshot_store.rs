@@ -0,0 +1,491 @@
+//! Snapshot storage for projections
+//!
+//! A [`SnapshotStore`] keeps [`ProjectionSnapshot`]s per aggregate and version
+//! so replay can start from the latest snapshot instead of the first event.
+//! Backends store snapshots zstd-compressed through [`EventCompressor`] and
+//! verify the content hash when loading, so a damaged snapshot is reported
+//! instead of silently restored.
+//!
+//! Backends:
+//! - [`MemorySnapshotStore`] for tests and short-lived processes
+//! - [`FileSnapshotStore`] for nodes with a local disk
+//! - `JetStreamSnapshotStore` (feature `nats`) in a JetStream key-value bucket
+//!
+//! Every backend prunes old snapshots with the same [`SnapshotRetention`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use super::projection_engine::ProjectionSnapshot;
+use crate::error::{GraphError, Result};
+use crate::optimization::EventCompressor;
+
+/// File extension of stored snapshots
+const SNAPSHOT_EXTENSION: &str = "snap";
+
+/// Which snapshots of an aggregate survive pruning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRetention {
+    /// Keep this many of the most recent snapshots (at least one is always kept)
+    pub keep_last: usize,
+
+    /// Also keep snapshots whose version is a multiple of this
+    pub keep_every: Option<u64>,
+}
+
+impl Default for SnapshotRetention {
+    fn default() -> Self {
+        Self {
+            keep_last: 5,
+            keep_every: None,
+        }
+    }
+}
+
+impl SnapshotRetention {
+    /// Keep only the `n` most recent snapshots
+    pub fn keep_last(n: usize) -> Self {
+        Self {
+            keep_last: n,
+            keep_every: None,
+        }
+    }
+
+    /// Also keep every snapshot whose version is a multiple of `k`
+    pub fn with_keep_every(mut self, k: u64) -> Self {
+        self.keep_every = Some(k);
+        self
+    }
+
+    /// Versions to delete from an ascending list of stored versions
+    pub fn expired(&self, versions: &[u64]) -> Vec<u64> {
+        let recent_from = versions.len().saturating_sub(self.keep_last.max(1));
+        versions[..recent_from]
+            .iter()
+            .copied()
+            .filter(|version| match self.keep_every {
+                Some(k) if k > 0 => version % k != 0,
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+/// Storage backend for projection snapshots
+pub trait SnapshotStore: std::fmt::Debug + Send + Sync {
+    /// Store a snapshot without applying retention
+    fn put(&mut self, snapshot: &ProjectionSnapshot) -> Result<()>;
+
+    /// Load the snapshot of an aggregate at exactly `version`
+    fn load(&self, aggregate_id: Uuid, version: u64) -> Result<Option<ProjectionSnapshot>>;
+
+    /// Versions stored for an aggregate, ascending
+    fn versions(&self, aggregate_id: Uuid) -> Result<Vec<u64>>;
+
+    /// Delete one snapshot; deleting a missing snapshot is not an error
+    fn delete(&mut self, aggregate_id: Uuid, version: u64) -> Result<()>;
+
+    /// Aggregates with at least one stored snapshot
+    fn aggregate_ids(&self) -> Result<Vec<Uuid>>;
+
+    /// Stored (compressed) size of a snapshot in bytes
+    fn stored_size(&self, aggregate_id: Uuid, version: u64) -> Result<Option<u64>>;
+
+    /// Retention policy applied by [`save`](Self::save)
+    fn retention(&self) -> &SnapshotRetention;
+
+    /// Store a snapshot and prune the aggregate's older snapshots
+    fn save(&mut self, snapshot: &ProjectionSnapshot) -> Result<()> {
+        self.put(snapshot)?;
+        let versions = self.versions(snapshot.aggregate_id)?;
+        for version in self.retention().expired(&versions) {
+            self.delete(snapshot.aggregate_id, version)?;
+        }
+        Ok(())
+    }
+
+    /// Version of the newest snapshot at or below `max_version`
+    fn latest_version_at(&self, aggregate_id: Uuid, max_version: u64) -> Result<Option<u64>> {
+        Ok(self
+            .versions(aggregate_id)?
+            .into_iter()
+            .rev()
+            .find(|version| *version <= max_version))
+    }
+
+    /// Newest snapshot at or below `max_version`
+    fn latest_at(&self, aggregate_id: Uuid, max_version: u64) -> Result<Option<ProjectionSnapshot>> {
+        match self.latest_version_at(aggregate_id, max_version)? {
+            Some(version) => self.load(aggregate_id, version),
+            None => Ok(None),
+        }
+    }
+
+    /// Newest snapshot of an aggregate
+    fn latest(&self, aggregate_id: Uuid) -> Result<Option<ProjectionSnapshot>> {
+        self.latest_at(aggregate_id, u64::MAX)
+    }
+
+    /// Delete every snapshot of an aggregate
+    fn remove_aggregate(&mut self, aggregate_id: Uuid) -> Result<()> {
+        for version in self.versions(aggregate_id)? {
+            self.delete(aggregate_id, version)?;
+        }
+        Ok(())
+    }
+}
+
+/// In-memory snapshot store holding compressed snapshots
+#[derive(Debug, Default)]
+pub struct MemorySnapshotStore {
+    /// Retention policy
+    retention: SnapshotRetention,
+    /// Compressor for stored snapshots
+    compressor: EventCompressor,
+    /// Encoded snapshots by aggregate and version
+    snapshots: HashMap<Uuid, BTreeMap<u64, Vec<u8>>>,
+}
+
+impl MemorySnapshotStore {
+    /// Create an empty store with a retention policy
+    pub fn new(retention: SnapshotRetention) -> Self {
+        Self {
+            retention,
+            compressor: EventCompressor::default(),
+            snapshots: HashMap::new(),
+        }
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn put(&mut self, snapshot: &ProjectionSnapshot) -> Result<()> {
+        let encoded = snapshot.encode(&self.compressor)?;
+        self.snapshots
+            .entry(snapshot.aggregate_id)
+            .or_default()
+            .insert(snapshot.version, encoded);
+        Ok(())
+    }
+
+    fn load(&self, aggregate_id: Uuid, version: u64) -> Result<Option<ProjectionSnapshot>> {
+        match self.snapshots.get(&aggregate_id).and_then(|versions| versions.get(&version)) {
+            Some(encoded) => ProjectionSnapshot::decode(encoded).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn versions(&self, aggregate_id: Uuid) -> Result<Vec<u64>> {
+        Ok(self
+            .snapshots
+            .get(&aggregate_id)
+            .map(|versions| versions.keys().copied().collect())
+            .unwrap_or_default())
+    }
+
+    fn delete(&mut self, aggregate_id: Uuid, version: u64) -> Result<()> {
+        if let Some(versions) = self.snapshots.get_mut(&aggregate_id) {
+            versions.remove(&version);
+            if versions.is_empty() {
+                self.snapshots.remove(&aggregate_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn aggregate_ids(&self) -> Result<Vec<Uuid>> {
+        Ok(self.snapshots.keys().copied().collect())
+    }
+
+    fn stored_size(&self, aggregate_id: Uuid, version: u64) -> Result<Option<u64>> {
+        Ok(self
+            .snapshots
+            .get(&aggregate_id)
+            .and_then(|versions| versions.get(&version))
+            .map(|encoded| encoded.len() as u64))
+    }
+
+    fn retention(&self) -> &SnapshotRetention {
+        &self.retention
+    }
+}
+
+/// Snapshot store writing one compressed file per snapshot
+///
+/// Snapshots live at `{dir}/{aggregate_id}/{version:020}.snap`. Files are
+/// written to a temporary name, synced and renamed into place, so a crash
+/// never leaves a half-written snapshot under its final name.
+#[derive(Debug)]
+pub struct FileSnapshotStore {
+    /// Root directory
+    dir: PathBuf,
+    /// Retention policy
+    retention: SnapshotRetention,
+    /// Compressor for stored snapshots
+    compressor: EventCompressor,
+}
+
+impl FileSnapshotStore {
+    /// Open a store rooted at `dir`, creating the directory if needed
+    pub fn open<P: AsRef<Path>>(dir: P, retention: SnapshotRetention) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error("create snapshot directory", e))?;
+        Ok(Self {
+            dir,
+            retention,
+            compressor: EventCompressor::default(),
+        })
+    }
+
+    /// Get the root directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn aggregate_dir(&self, aggregate_id: Uuid) -> PathBuf {
+        self.dir.join(aggregate_id.to_string())
+    }
+
+    fn snapshot_path(&self, aggregate_id: Uuid, version: u64) -> PathBuf {
+        self.aggregate_dir(aggregate_id)
+            .join(format!("{:020}.{}", version, SNAPSHOT_EXTENSION))
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn put(&mut self, snapshot: &ProjectionSnapshot) -> Result<()> {
+        let encoded = snapshot.encode(&self.compressor)?;
+        let aggregate_dir = self.aggregate_dir(snapshot.aggregate_id);
+        fs::create_dir_all(&aggregate_dir).map_err(|e| io_error("create snapshot directory", e))?;
+
+        let path = self.snapshot_path(snapshot.aggregate_id, snapshot.version);
+        let temp = path.with_extension("tmp");
+        let mut file = File::create(&temp).map_err(|e| io_error("create snapshot", e))?;
+        file.write_all(&encoded)
+            .and_then(|_| file.sync_all())
+            .map_err(|e| io_error("write snapshot", e))?;
+        fs::rename(&temp, &path).map_err(|e| io_error("store snapshot", e))?;
+        Ok(())
+    }
+
+    fn load(&self, aggregate_id: Uuid, version: u64) -> Result<Option<ProjectionSnapshot>> {
+        match fs::read(self.snapshot_path(aggregate_id, version)) {
+            Ok(encoded) => ProjectionSnapshot::decode(&encoded).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("read snapshot", e)),
+        }
+    }
+
+    fn versions(&self, aggregate_id: Uuid) -> Result<Vec<u64>> {
+        let entries = match fs::read_dir(self.aggregate_dir(aggregate_id)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error("list snapshots", e)),
+        };
+
+        let mut versions = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| io_error("list snapshots", e))?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_EXTENSION) {
+                continue;
+            }
+            if let Some(version) = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok()) {
+                versions.push(version);
+            }
+        }
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    fn delete(&mut self, aggregate_id: Uuid, version: u64) -> Result<()> {
+        match fs::remove_file(self.snapshot_path(aggregate_id, version)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error("delete snapshot", e)),
+        }
+    }
+
+    fn aggregate_ids(&self) -> Result<Vec<Uuid>> {
+        let entries = fs::read_dir(&self.dir).map_err(|e| io_error("list snapshots", e))?;
+        let mut ids = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| io_error("list snapshots", e))?;
+            let id = match entry.file_name().to_str().and_then(|name| Uuid::parse_str(name).ok()) {
+                Some(id) => id,
+                None => continue,
+            };
+            if !self.versions(id)?.is_empty() {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    fn stored_size(&self, aggregate_id: Uuid, version: u64) -> Result<Option<u64>> {
+        match fs::metadata(self.snapshot_path(aggregate_id, version)) {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("stat snapshot", e)),
+        }
+    }
+
+    fn retention(&self) -> &SnapshotRetention {
+        &self.retention
+    }
+}
+
+fn io_error(action: &str, err: std::io::Error) -> GraphError {
+    GraphError::SerializationError(format!("Failed to {}: {}", action, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(aggregate_id: Uuid, version: u64) -> ProjectionSnapshot {
+        ProjectionSnapshot::new(aggregate_id, version, serde_json::json!({ "version": version }))
+    }
+
+    fn exercise_store(store: &mut dyn SnapshotStore) {
+        let aggregate_id = Uuid::new_v4();
+        assert!(store.latest(aggregate_id).unwrap().is_none());
+
+        for version in [10, 20, 30] {
+            store.save(&snapshot(aggregate_id, version)).unwrap();
+        }
+        assert_eq!(store.versions(aggregate_id).unwrap(), vec![10, 20, 30]);
+
+        let latest = store.latest(aggregate_id).unwrap().unwrap();
+        assert_eq!(latest.version, 30);
+        assert_eq!(latest.data, serde_json::json!({ "version": 30 }));
+
+        assert_eq!(store.latest_at(aggregate_id, 25).unwrap().unwrap().version, 20);
+        assert!(store.latest_at(aggregate_id, 5).unwrap().is_none());
+        assert!(store.load(aggregate_id, 15).unwrap().is_none());
+        assert!(store.stored_size(aggregate_id, 10).unwrap().unwrap() > 0);
+        assert_eq!(store.aggregate_ids().unwrap(), vec![aggregate_id]);
+
+        store.delete(aggregate_id, 30).unwrap();
+        store.delete(aggregate_id, 30).unwrap();
+        assert_eq!(store.latest(aggregate_id).unwrap().unwrap().version, 20);
+
+        store.remove_aggregate(aggregate_id).unwrap();
+        assert!(store.versions(aggregate_id).unwrap().is_empty());
+        assert!(store.aggregate_ids().unwrap().is_empty());
+    }
+
+    // ========== Retention Tests ==========
+
+    #[test]
+    fn test_retention_keep_last() {
+        let retention = SnapshotRetention::keep_last(2);
+        assert_eq!(retention.expired(&[1, 2, 3, 4]), vec![1, 2]);
+        assert!(retention.expired(&[1, 2]).is_empty());
+        assert!(retention.expired(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_retention_keep_every() {
+        let retention = SnapshotRetention::keep_last(1).with_keep_every(100);
+        assert_eq!(retention.expired(&[50, 100, 150, 200, 250]), vec![50, 150]);
+    }
+
+    #[test]
+    fn test_retention_always_keeps_newest() {
+        let retention = SnapshotRetention::keep_last(0);
+        assert_eq!(retention.expired(&[1, 2, 3]), vec![1, 2]);
+
+        let retention = SnapshotRetention::keep_last(0).with_keep_every(0);
+        assert_eq!(retention.expired(&[1, 2]), vec![1]);
+    }
+
+    // ========== MemorySnapshotStore Tests ==========
+
+    #[test]
+    fn test_memory_store() {
+        exercise_store(&mut MemorySnapshotStore::default());
+    }
+
+    #[test]
+    fn test_memory_store_applies_retention() {
+        let mut store = MemorySnapshotStore::new(SnapshotRetention::keep_last(2).with_keep_every(10));
+        let aggregate_id = Uuid::new_v4();
+        for version in 5..=25 {
+            store.save(&snapshot(aggregate_id, version)).unwrap();
+        }
+
+        assert_eq!(store.versions(aggregate_id).unwrap(), vec![10, 20, 24, 25]);
+    }
+
+    #[test]
+    fn test_memory_store_rejects_tampered_snapshot() {
+        let mut store = MemorySnapshotStore::default();
+        let aggregate_id = Uuid::new_v4();
+        let mut tampered = snapshot(aggregate_id, 1);
+        tampered.data = serde_json::json!({ "version": 2 });
+        store.put(&tampered).unwrap();
+
+        assert!(store.latest(aggregate_id).is_err());
+    }
+
+    // ========== FileSnapshotStore Tests ==========
+
+    #[test]
+    fn test_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        exercise_store(&mut FileSnapshotStore::open(dir.path(), SnapshotRetention::default()).unwrap());
+    }
+
+    #[test]
+    fn test_file_store_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        {
+            let mut store = FileSnapshotStore::open(dir.path(), SnapshotRetention::keep_last(1)).unwrap();
+            store.save(&snapshot(aggregate_id, 1)).unwrap();
+            store.save(&snapshot(aggregate_id, 2)).unwrap();
+        }
+
+        let store = FileSnapshotStore::open(dir.path(), SnapshotRetention::keep_last(1)).unwrap();
+        assert_eq!(store.versions(aggregate_id).unwrap(), vec![2]);
+        assert_eq!(store.latest(aggregate_id).unwrap().unwrap().version, 2);
+        assert!(dir
+            .path()
+            .join(aggregate_id.to_string())
+            .join("00000000000000000002.snap")
+            .exists());
+    }
+
+    #[test]
+    fn test_file_store_detects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let mut store = FileSnapshotStore::open(dir.path(), SnapshotRetention::default()).unwrap();
+        store.save(&snapshot(aggregate_id, 1)).unwrap();
+
+        let path = store.snapshot_path(aggregate_id, 1);
+        let mut bytes = fs::read(&path).unwrap();
+        let middle = bytes.len() / 2;
+        bytes[middle] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+
+        assert!(store.load(aggregate_id, 1).is_err());
+    }
+
+    #[test]
+    fn test_file_store_ignores_foreign_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let mut store = FileSnapshotStore::open(dir.path(), SnapshotRetention::default()).unwrap();
+        store.save(&snapshot(aggregate_id, 1)).unwrap();
+
+        fs::write(dir.path().join("README"), b"notes").unwrap();
+        fs::write(store.aggregate_dir(aggregate_id).join("2.tmp"), b"partial").unwrap();
+
+        assert_eq!(store.aggregate_ids().unwrap(), vec![aggregate_id]);
+        assert_eq!(store.versions(aggregate_id).unwrap(), vec![1]);
+    }
+}
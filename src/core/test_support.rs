@@ -0,0 +1,37 @@
+//! Event builders shared by unit tests
+
+use crate::core::cim_graph::EventData;
+use crate::core::event_sourcing::{EventMetadata, GraphEvent, GraphEventPayload};
+use uuid::Uuid;
+
+/// `NodeAdded` event data
+pub(crate) fn node_added(id: &str, node_type: &str, data: serde_json::Value) -> EventData {
+    EventData::NodeAdded {
+        node_id: id.to_string(),
+        node_type: node_type.to_string(),
+        data,
+    }
+}
+
+/// `EdgeAdded` event data
+pub(crate) fn edge_added(id: &str, source: &str, target: &str, edge_type: &str, data: serde_json::Value) -> EventData {
+    EventData::EdgeAdded {
+        edge_id: id.to_string(),
+        source_id: source.to_string(),
+        target_id: target.to_string(),
+        edge_type: edge_type.to_string(),
+        data,
+    }
+}
+
+/// Stored `NodeAdded` event at `version` of an aggregate
+pub(crate) fn node_added_event(aggregate_id: Uuid, version: u64) -> GraphEvent {
+    GraphEvent {
+        metadata: EventMetadata::new(aggregate_id, Uuid::new_v4(), version, "test".to_string()),
+        payload: GraphEventPayload::NodeAdded {
+            node_id: format!("node-{}", version),
+            node_type: "Node".to_string(),
+            data: serde_json::json!({ "version": version }),
+        },
+    }
+}
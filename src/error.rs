@@ -93,6 +93,32 @@ pub enum GraphError {
     #[error("Serialization error: {0}")]
     SerializationError(String),
     
+    /// Malformed or unsupported content identifier
+    #[error("Invalid CID: {0}")]
+    InvalidCid(String),
+
+    /// Merkle inclusion proof is malformed or does not verify
+    #[error("Invalid Merkle proof: {0}")]
+    InvalidProof(String),
+
+    /// Aggregate moved past the version a writer expected to append after
+    #[error("Concurrency conflict: expected version {expected}, found {actual}")]
+    ConcurrencyConflict {
+        /// Version the writer expected
+        expected: u64,
+        /// Version the aggregate is actually at
+        actual: u64,
+    },
+
+    /// Query text could not be parsed
+    #[error("Query syntax error at {position}: {message}")]
+    QuerySyntax {
+        /// Byte offset in the query text
+        position: usize,
+        /// What was wrong
+        message: String,
+    },
+
     /// External error (from dependencies)
     #[error("External error: {0}")]
     External(String),
@@ -290,6 +316,22 @@ mod tests {
                 GraphError::SerializationError("se".to_string()),
                 "Serialization error: se",
             ),
+            (
+                GraphError::InvalidCid("ic".to_string()),
+                "Invalid CID: ic",
+            ),
+            (
+                GraphError::InvalidProof("ip".to_string()),
+                "Invalid Merkle proof: ip",
+            ),
+            (
+                GraphError::ConcurrencyConflict { expected: 3, actual: 5 },
+                "Concurrency conflict: expected version 3, found 5",
+            ),
+            (
+                GraphError::QuerySyntax { position: 7, message: "qs".to_string() },
+                "Query syntax error at 7: qs",
+            ),
             (
                 GraphError::External("ex".to_string()),
                 "External error: ex",
@@ -314,6 +356,10 @@ mod tests {
                 GraphError::InvalidOperation(_) => "invalid_operation",
                 GraphError::NotInitialized => "not_initialized",
                 GraphError::SerializationError(_) => "serialization_error",
+                GraphError::InvalidCid(_) => "invalid_cid",
+                GraphError::InvalidProof(_) => "invalid_proof",
+                GraphError::ConcurrencyConflict { .. } => "concurrency_conflict",
+                GraphError::QuerySyntax { .. } => "query_syntax",
                 GraphError::External(_) => "external",
             }
         }
@@ -413,6 +459,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_concurrency_conflict_extraction() {
+        let err = GraphError::ConcurrencyConflict { expected: 1, actual: 2 };
+
+        if let GraphError::ConcurrencyConflict { expected, actual } = err {
+            assert_eq!(expected, 1);
+            assert_eq!(actual, 2);
+        } else {
+            panic!("Wrong error variant");
+        }
+    }
+
     #[test]
     fn test_type_mismatch_extraction() {
         let err = GraphError::TypeMismatch {
@@ -0,0 +1,878 @@
+//! Bridge between domain events and projection events
+//!
+//! Domain events ([`GraphEvent`]) carry typed [`EventPayload`]s and leave
+//! sequence, subject and timestamp to JetStream. Projections consume
+//! [`CimGraphEvent`]s, which carry generic [`EventData`] plus that stream
+//! metadata. This module converts between the two.
+//!
+//! Typed payloads are encoded as the graph-shaped `EventData` variants the
+//! projection node and edge types understand: a workflow `StateAdded`
+//! becomes `NodeAdded`, a concept `RelationAdded` becomes `EdgeAdded`, and
+//! so on. Decoding only yields a typed payload when encoding it again
+//! reproduces the same `EventData`; everything else decodes to
+//! [`EventPayload::Generic`] holding the variant verbatim, so a conversion
+//! never drops information.
+//!
+//! Concept property lists are keyed by name once encoded, so they decode
+//! in name order with the last value of a repeated name.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::graph_events::{
+    ComposedPayload, ConceptPayload, ContextPayload, EventPayload, GenericPayload, GraphEvent,
+    IpldPayload, WorkflowPayload,
+};
+use super::subjects::GraphType;
+use crate::core::projection_engine::payload_str;
+use crate::core::{CimGraphEvent, EventData};
+use crate::error::{GraphError, Result};
+
+/// Stream metadata JetStream assigns when it stores an event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamMetadata {
+    /// Stream sequence number
+    pub sequence: u64,
+    /// Subject the event was published on
+    pub subject: String,
+    /// When the stream stored the event
+    pub timestamp: DateTime<Utc>,
+}
+
+impl GraphEvent {
+    /// Build the projection event for this event as stored in the stream
+    pub fn to_cim_event(&self, stream: &StreamMetadata) -> Result<CimGraphEvent> {
+        Ok(CimGraphEvent {
+            event_id: self.event_id,
+            aggregate_id: self.aggregate_id,
+            sequence: stream.sequence,
+            subject: stream.subject.clone(),
+            timestamp: stream.timestamp,
+            correlation_id: self.correlation_id,
+            causation_id: self.causation_id,
+            data: self.payload.to_event_data()?,
+        })
+    }
+
+    /// Recover the domain event and its stream metadata from a projection event
+    ///
+    /// The graph type is read from the subject segment preceding the
+    /// aggregate ID, which both the JetStream store and
+    /// [`build_event_subject`](super::build_event_subject) produce.
+    pub fn from_cim_event(event: &CimGraphEvent) -> Result<(Self, StreamMetadata)> {
+        let graph_type = graph_type_from_subject(&event.subject, event.aggregate_id);
+        let domain_event = GraphEvent {
+            event_id: event.event_id,
+            aggregate_id: event.aggregate_id,
+            correlation_id: event.correlation_id,
+            causation_id: event.causation_id,
+            payload: EventPayload::from_event_data(graph_type, &event.data)?,
+        };
+        let stream = StreamMetadata {
+            sequence: event.sequence,
+            subject: event.subject.clone(),
+            timestamp: event.timestamp,
+        };
+        Ok((domain_event, stream))
+    }
+}
+
+impl EventPayload {
+    /// Graph type this payload belongs to, `None` for generic payloads
+    pub fn graph_type(&self) -> Option<GraphType> {
+        match self {
+            EventPayload::Generic(_) => None,
+            EventPayload::Ipld(_) => Some(GraphType::Ipld),
+            EventPayload::Context(_) => Some(GraphType::Context),
+            EventPayload::Workflow(_) => Some(GraphType::Workflow),
+            EventPayload::Concept(_) => Some(GraphType::Concept),
+            EventPayload::Composed(_) => Some(GraphType::Composed),
+        }
+    }
+
+    /// Encode this payload as projection event data
+    ///
+    /// Fails only for generic payloads whose `event_type` and `data` do not
+    /// name an [`EventData`] variant.
+    pub fn to_event_data(&self) -> Result<EventData> {
+        Ok(match self {
+            EventPayload::Generic(payload) => generic_to_event_data(payload)?,
+            EventPayload::Ipld(payload) => ipld_to_event_data(payload),
+            EventPayload::Context(payload) => context_to_event_data(payload),
+            EventPayload::Workflow(payload) => workflow_to_event_data(payload),
+            EventPayload::Concept(payload) => concept_to_event_data(payload),
+            EventPayload::Composed(payload) => composed_to_event_data(payload),
+        })
+    }
+
+    /// Decode projection event data published for `graph_type`
+    pub fn from_event_data(graph_type: Option<GraphType>, data: &EventData) -> Result<Self> {
+        let typed = match graph_type {
+            Some(GraphType::Ipld) => ipld_from_event_data(data).map(EventPayload::Ipld),
+            Some(GraphType::Context) => context_from_event_data(data).map(EventPayload::Context),
+            Some(GraphType::Workflow) => workflow_from_event_data(data).map(EventPayload::Workflow),
+            Some(GraphType::Concept) => concept_from_event_data(data).map(EventPayload::Concept),
+            Some(GraphType::Composed) => composed_from_event_data(data).map(EventPayload::Composed),
+            None => None,
+        };
+        match typed {
+            Some(payload) if payload.to_event_data().ok().as_ref() == Some(data) => Ok(payload),
+            _ => generic_from_event_data(data).map(EventPayload::Generic),
+        }
+    }
+}
+
+/// Find the graph type in a subject, taken from the segment before the aggregate ID
+fn graph_type_from_subject(subject: &str, aggregate_id: Uuid) -> Option<GraphType> {
+    let aggregate = aggregate_id.to_string();
+    let segments: Vec<&str> = subject.split('.').collect();
+    let position = segments.iter().position(|segment| *segment == aggregate)?;
+    GraphType::from_segment(segments.get(position.checked_sub(1)?)?)
+}
+
+/// Deterministic ID for an edge derived from a domain event
+fn link_id(source: &str, label: &str, target: &str) -> String {
+    format!("{}-[{}]->{}", source, label, target)
+}
+
+fn payload_string(data: &Value, key: &str) -> Option<String> {
+    payload_str(data, key).map(str::to_string)
+}
+
+fn payload_uuid(data: &Value, key: &str) -> Option<Uuid> {
+    payload_str(data, key).and_then(|s| Uuid::parse_str(s).ok())
+}
+
+fn payload_f64(data: &Value, key: &str) -> Option<f64> {
+    data.get(key).and_then(Value::as_f64)
+}
+
+// ========== Generic ==========
+
+fn generic_to_event_data(payload: &GenericPayload) -> Result<EventData> {
+    let mut tagged = serde_json::Map::new();
+    tagged.insert(payload.event_type.clone(), payload.data.clone());
+    serde_json::from_value(Value::Object(tagged)).map_err(|e| {
+        GraphError::InvalidOperation(format!(
+            "Generic payload '{}' is not projection event data: {}",
+            payload.event_type, e
+        ))
+    })
+}
+
+fn generic_from_event_data(data: &EventData) -> Result<GenericPayload> {
+    let encoded = serde_json::to_value(data)
+        .map_err(|e| GraphError::SerializationError(e.to_string()))?;
+    match encoded {
+        Value::Object(tagged) if tagged.len() == 1 => tagged
+            .into_iter()
+            .next()
+            .map(|(event_type, data)| GenericPayload { event_type, data })
+            .ok_or_else(|| GraphError::SerializationError("Empty event data".to_string())),
+        other => Err(GraphError::SerializationError(format!(
+            "Unexpected event data encoding: {}",
+            other
+        ))),
+    }
+}
+
+// ========== IPLD ==========
+
+fn ipld_to_event_data(payload: &IpldPayload) -> EventData {
+    match payload {
+        IpldPayload::CidAdded { cid, codec, size, data } => EventData::NodeAdded {
+            node_id: cid.clone(),
+            node_type: "cid".to_string(),
+            data: json!({ "cid": cid, "codec": codec, "size": size, "data": data }),
+        },
+        IpldPayload::CidLinkAdded { cid, link_name, target_cid } => EventData::EdgeAdded {
+            edge_id: link_id(cid, link_name, target_cid),
+            source_id: cid.clone(),
+            target_id: target_cid.clone(),
+            edge_type: "link".to_string(),
+            data: json!({ "label": link_name }),
+        },
+        IpldPayload::CidPinned { cid, recursive } => EventData::NodeUpdated {
+            node_id: cid.clone(),
+            data: json!({ "pinned": true, "recursive": recursive }),
+        },
+        IpldPayload::CidUnpinned { cid } => EventData::NodeUpdated {
+            node_id: cid.clone(),
+            data: json!({ "pinned": false }),
+        },
+    }
+}
+
+fn ipld_from_event_data(data: &EventData) -> Option<IpldPayload> {
+    match data {
+        EventData::NodeAdded { node_id, data, .. } => Some(IpldPayload::CidAdded {
+            cid: node_id.clone(),
+            codec: payload_string(data, "codec")?,
+            size: data.get("size")?.as_u64()?,
+            data: data.get("data")?.clone(),
+        }),
+        EventData::EdgeAdded { source_id, target_id, data, .. } => Some(IpldPayload::CidLinkAdded {
+            cid: source_id.clone(),
+            link_name: payload_string(data, "label")?,
+            target_cid: target_id.clone(),
+        }),
+        EventData::NodeUpdated { node_id, data } => {
+            if data.get("pinned")?.as_bool()? {
+                Some(IpldPayload::CidPinned {
+                    cid: node_id.clone(),
+                    recursive: data.get("recursive")?.as_bool()?,
+                })
+            } else {
+                Some(IpldPayload::CidUnpinned { cid: node_id.clone() })
+            }
+        }
+        _ => None,
+    }
+}
+
+// ========== Context ==========
+
+fn context_to_event_data(payload: &ContextPayload) -> EventData {
+    match payload {
+        ContextPayload::BoundedContextCreated { context_id, name, description } => EventData::NodeAdded {
+            node_id: context_id.clone(),
+            node_type: "bounded_context".to_string(),
+            data: json!({ "name": name, "description": description }),
+        },
+        ContextPayload::AggregateAdded { context_id, aggregate_id, aggregate_type } => EventData::NodeAdded {
+            node_id: aggregate_id.to_string(),
+            node_type: "aggregate".to_string(),
+            data: json!({ "context_id": context_id, "aggregate_type": aggregate_type }),
+        },
+        ContextPayload::EntityAdded { aggregate_id, entity_id, entity_type, properties } => EventData::NodeAdded {
+            node_id: entity_id.to_string(),
+            node_type: "entity".to_string(),
+            data: json!({
+                "aggregate_id": aggregate_id,
+                "entity_type": entity_type,
+                "properties": properties,
+            }),
+        },
+        ContextPayload::ValueObjectAttached { parent_id, value_type, value_data } => {
+            let mut fields = serde_json::Map::new();
+            fields.insert(value_type.clone(), value_data.clone());
+            EventData::NodeUpdated {
+                node_id: parent_id.to_string(),
+                data: Value::Object(fields),
+            }
+        }
+        ContextPayload::RelationshipEstablished { source_id, target_id, relationship_type } => {
+            let (source, target) = (source_id.to_string(), target_id.to_string());
+            EventData::EdgeAdded {
+                edge_id: link_id(&source, relationship_type, &target),
+                source_id: source,
+                target_id: target,
+                edge_type: relationship_type.clone(),
+                data: json!({}),
+            }
+        }
+    }
+}
+
+fn context_from_event_data(data: &EventData) -> Option<ContextPayload> {
+    match data {
+        EventData::NodeAdded { node_id, node_type, data } => match node_type.as_str() {
+            "bounded_context" => Some(ContextPayload::BoundedContextCreated {
+                context_id: node_id.clone(),
+                name: payload_string(data, "name")?,
+                description: payload_string(data, "description")?,
+            }),
+            "aggregate" => Some(ContextPayload::AggregateAdded {
+                context_id: payload_string(data, "context_id")?,
+                aggregate_id: Uuid::parse_str(node_id).ok()?,
+                aggregate_type: payload_string(data, "aggregate_type")?,
+            }),
+            "entity" => Some(ContextPayload::EntityAdded {
+                aggregate_id: payload_uuid(data, "aggregate_id")?,
+                entity_id: Uuid::parse_str(node_id).ok()?,
+                entity_type: payload_string(data, "entity_type")?,
+                properties: data.get("properties")?.clone(),
+            }),
+            _ => None,
+        },
+        EventData::NodeUpdated { node_id, data } => {
+            let fields = data.as_object()?;
+            if fields.len() != 1 {
+                return None;
+            }
+            let (value_type, value_data) = fields.iter().next()?;
+            Some(ContextPayload::ValueObjectAttached {
+                parent_id: Uuid::parse_str(node_id).ok()?,
+                value_type: value_type.clone(),
+                value_data: value_data.clone(),
+            })
+        }
+        EventData::EdgeAdded { source_id, target_id, edge_type, .. } => {
+            Some(ContextPayload::RelationshipEstablished {
+                source_id: Uuid::parse_str(source_id).ok()?,
+                target_id: Uuid::parse_str(target_id).ok()?,
+                relationship_type: edge_type.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+// ========== Workflow ==========
+
+fn workflow_to_event_data(payload: &WorkflowPayload) -> EventData {
+    match payload {
+        WorkflowPayload::WorkflowDefined { workflow_id, name, version } => EventData::GraphInitialized {
+            graph_type: "workflow".to_string(),
+            metadata: HashMap::from([
+                ("workflow_id".to_string(), json!(workflow_id)),
+                ("name".to_string(), json!(name)),
+                ("version".to_string(), json!(version)),
+            ]),
+        },
+        WorkflowPayload::StateAdded { workflow_id, state_id, state_type } => EventData::NodeAdded {
+            node_id: state_id.clone(),
+            node_type: state_type.clone(),
+            data: json!({ "workflow_id": workflow_id }),
+        },
+        WorkflowPayload::TransitionAdded { workflow_id, from_state, to_state, trigger } => EventData::EdgeAdded {
+            edge_id: link_id(from_state, trigger, to_state),
+            source_id: from_state.clone(),
+            target_id: to_state.clone(),
+            edge_type: "transition".to_string(),
+            data: json!({ "workflow_id": workflow_id, "trigger": trigger }),
+        },
+        WorkflowPayload::InstanceCreated { workflow_id, instance_id, initial_state } => EventData::GraphInitialized {
+            graph_type: "workflow_instance".to_string(),
+            metadata: HashMap::from([
+                ("workflow_id".to_string(), json!(workflow_id)),
+                ("instance_id".to_string(), json!(instance_id)),
+                ("initial_state".to_string(), json!(initial_state)),
+            ]),
+        },
+        WorkflowPayload::StateTransitioned { instance_id, from_state, to_state } => EventData::NodeUpdated {
+            node_id: instance_id.to_string(),
+            data: json!({ "from_state": from_state, "to_state": to_state }),
+        },
+    }
+}
+
+fn workflow_from_event_data(data: &EventData) -> Option<WorkflowPayload> {
+    match data {
+        EventData::GraphInitialized { graph_type, metadata } => {
+            let text = |key: &str| metadata.get(key)?.as_str().map(str::to_string);
+            let uuid = |key: &str| Uuid::parse_str(metadata.get(key)?.as_str()?).ok();
+            match graph_type.as_str() {
+                "workflow" => Some(WorkflowPayload::WorkflowDefined {
+                    workflow_id: uuid("workflow_id")?,
+                    name: text("name")?,
+                    version: text("version")?,
+                }),
+                "workflow_instance" => Some(WorkflowPayload::InstanceCreated {
+                    workflow_id: uuid("workflow_id")?,
+                    instance_id: uuid("instance_id")?,
+                    initial_state: text("initial_state")?,
+                }),
+                _ => None,
+            }
+        }
+        EventData::NodeAdded { node_id, node_type, data } => Some(WorkflowPayload::StateAdded {
+            workflow_id: payload_uuid(data, "workflow_id")?,
+            state_id: node_id.clone(),
+            state_type: node_type.clone(),
+        }),
+        EventData::EdgeAdded { source_id, target_id, data, .. } => Some(WorkflowPayload::TransitionAdded {
+            workflow_id: payload_uuid(data, "workflow_id")?,
+            from_state: source_id.clone(),
+            to_state: target_id.clone(),
+            trigger: payload_string(data, "trigger")?,
+        }),
+        EventData::NodeUpdated { node_id, data } => Some(WorkflowPayload::StateTransitioned {
+            instance_id: Uuid::parse_str(node_id).ok()?,
+            from_state: payload_string(data, "from_state")?,
+            to_state: payload_string(data, "to_state")?,
+        }),
+        _ => None,
+    }
+}
+
+// ========== Concept ==========
+
+fn concept_to_event_data(payload: &ConceptPayload) -> EventData {
+    match payload {
+        ConceptPayload::ConceptDefined { concept_id, name, definition } => EventData::NodeAdded {
+            node_id: concept_id.clone(),
+            node_type: "concept".to_string(),
+            data: json!({ "name": name, "description": definition }),
+        },
+        ConceptPayload::PropertiesAdded { concept_id, properties } => {
+            let properties: serde_json::Map<String, Value> = properties
+                .iter()
+                .map(|(name, value)| (name.clone(), json!(value)))
+                .collect();
+            EventData::NodeUpdated {
+                node_id: concept_id.clone(),
+                data: json!({ "properties": properties }),
+            }
+        }
+        ConceptPayload::RelationAdded { source_concept, target_concept, relation_type, strength } => {
+            EventData::EdgeAdded {
+                edge_id: link_id(source_concept, relation_type, target_concept),
+                source_id: source_concept.clone(),
+                target_id: target_concept.clone(),
+                edge_type: relation_type.clone(),
+                data: json!({ "strength": strength }),
+            }
+        }
+        ConceptPayload::PropertyInferred { concept_id, property_name, inferred_value, confidence } => {
+            EventData::NodeUpdated {
+                node_id: concept_id.clone(),
+                data: json!({
+                    "properties": { property_name.as_str(): inferred_value },
+                    "inferred": { property_name.as_str(): confidence },
+                }),
+            }
+        }
+    }
+}
+
+fn concept_from_event_data(data: &EventData) -> Option<ConceptPayload> {
+    match data {
+        EventData::NodeAdded { node_id, data, .. } => Some(ConceptPayload::ConceptDefined {
+            concept_id: node_id.clone(),
+            name: payload_string(data, "name")?,
+            definition: payload_string(data, "description")?,
+        }),
+        EventData::EdgeAdded { source_id, target_id, edge_type, data, .. } => Some(ConceptPayload::RelationAdded {
+            source_concept: source_id.clone(),
+            target_concept: target_id.clone(),
+            relation_type: edge_type.clone(),
+            strength: payload_f64(data, "strength")?,
+        }),
+        EventData::NodeUpdated { node_id, data } => {
+            let properties = data.get("properties")?.as_object()?;
+            match data.get("inferred") {
+                Some(inferred) => {
+                    let (property_name, confidence) = inferred.as_object()?.iter().next()?;
+                    Some(ConceptPayload::PropertyInferred {
+                        concept_id: node_id.clone(),
+                        property_name: property_name.clone(),
+                        inferred_value: properties.get(property_name)?.as_f64()?,
+                        confidence: confidence.as_f64()?,
+                    })
+                }
+                None => Some(ConceptPayload::PropertiesAdded {
+                    concept_id: node_id.clone(),
+                    properties: properties
+                        .iter()
+                        .map(|(name, value)| Some((name.clone(), value.as_f64()?)))
+                        .collect::<Option<Vec<_>>>()?,
+                }),
+            }
+        }
+        _ => None,
+    }
+}
+
+// ========== Composed ==========
+
+fn composed_to_event_data(payload: &ComposedPayload) -> EventData {
+    match payload {
+        ComposedPayload::SubGraphAdded { subgraph_id, graph_type, namespace } => EventData::NodeAdded {
+            node_id: subgraph_id.to_string(),
+            node_type: "graph_reference".to_string(),
+            data: json!({ "graph_id": subgraph_id, "domain": graph_type, "namespace": namespace }),
+        },
+        ComposedPayload::CrossGraphLinkCreated { source_graph, source_node, target_graph, target_node } => {
+            EventData::EdgeAdded {
+                edge_id: link_id(
+                    &format!("{}/{}", source_graph, source_node),
+                    "cross_graph_link",
+                    &format!("{}/{}", target_graph, target_node),
+                ),
+                source_id: source_node.clone(),
+                target_id: target_node.clone(),
+                edge_type: "cross_graph_link".to_string(),
+                data: json!({ "source_graph": source_graph, "target_graph": target_graph }),
+            }
+        }
+    }
+}
+
+fn composed_from_event_data(data: &EventData) -> Option<ComposedPayload> {
+    match data {
+        EventData::NodeAdded { data, .. } => Some(ComposedPayload::SubGraphAdded {
+            subgraph_id: payload_uuid(data, "graph_id")?,
+            graph_type: payload_string(data, "domain")?,
+            namespace: payload_string(data, "namespace")?,
+        }),
+        EventData::EdgeAdded { source_id, target_id, data, .. } => Some(ComposedPayload::CrossGraphLinkCreated {
+            source_graph: payload_uuid(data, "source_graph")?,
+            source_node: source_id.clone(),
+            target_graph: payload_uuid(data, "target_graph")?,
+            target_node: target_id.clone(),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ProjectionEngine;
+    use crate::events::{build_event_subject, EventType};
+    use crate::graphs::workflow::{WorkflowNodeType, WorkflowProjection};
+
+    fn event(aggregate_id: Uuid, payload: EventPayload) -> GraphEvent {
+        GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: Some(Uuid::new_v4()),
+            payload,
+        }
+    }
+
+    fn stream(sequence: u64, subject: String) -> StreamMetadata {
+        StreamMetadata {
+            sequence,
+            subject,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn typed_payloads() -> Vec<EventPayload> {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        vec![
+            EventPayload::Ipld(IpldPayload::CidAdded {
+                cid: "bafkreiabc".to_string(),
+                codec: "dag-cbor".to_string(),
+                size: 42,
+                data: json!({"nested": [1, 2, 3]}),
+            }),
+            EventPayload::Ipld(IpldPayload::CidLinkAdded {
+                cid: "bafkreiabc".to_string(),
+                link_name: "parent".to_string(),
+                target_cid: "bafkreidef".to_string(),
+            }),
+            EventPayload::Ipld(IpldPayload::CidPinned { cid: "bafkreiabc".to_string(), recursive: true }),
+            EventPayload::Ipld(IpldPayload::CidUnpinned { cid: "bafkreiabc".to_string() }),
+            EventPayload::Context(ContextPayload::BoundedContextCreated {
+                context_id: "sales".to_string(),
+                name: "Sales".to_string(),
+                description: "Order handling".to_string(),
+            }),
+            EventPayload::Context(ContextPayload::AggregateAdded {
+                context_id: "sales".to_string(),
+                aggregate_id: a,
+                aggregate_type: "Order".to_string(),
+            }),
+            EventPayload::Context(ContextPayload::EntityAdded {
+                aggregate_id: a,
+                entity_id: b,
+                entity_type: "OrderLine".to_string(),
+                properties: json!({"qty": 2}),
+            }),
+            EventPayload::Context(ContextPayload::ValueObjectAttached {
+                parent_id: b,
+                value_type: "Money".to_string(),
+                value_data: json!({"amount": 10, "currency": "EUR"}),
+            }),
+            EventPayload::Context(ContextPayload::RelationshipEstablished {
+                source_id: a,
+                target_id: b,
+                relationship_type: "contains".to_string(),
+            }),
+            EventPayload::Workflow(WorkflowPayload::WorkflowDefined {
+                workflow_id: a,
+                name: "Review".to_string(),
+                version: "1.0".to_string(),
+            }),
+            EventPayload::Workflow(WorkflowPayload::StateAdded {
+                workflow_id: a,
+                state_id: "draft".to_string(),
+                state_type: "initial".to_string(),
+            }),
+            EventPayload::Workflow(WorkflowPayload::TransitionAdded {
+                workflow_id: a,
+                from_state: "draft".to_string(),
+                to_state: "published".to_string(),
+                trigger: "publish".to_string(),
+            }),
+            EventPayload::Workflow(WorkflowPayload::InstanceCreated {
+                workflow_id: a,
+                instance_id: b,
+                initial_state: "draft".to_string(),
+            }),
+            EventPayload::Workflow(WorkflowPayload::StateTransitioned {
+                instance_id: b,
+                from_state: "draft".to_string(),
+                to_state: "published".to_string(),
+            }),
+            EventPayload::Concept(ConceptPayload::ConceptDefined {
+                concept_id: "dog".to_string(),
+                name: "Dog".to_string(),
+                definition: "A domesticated canine".to_string(),
+            }),
+            EventPayload::Concept(ConceptPayload::PropertiesAdded {
+                concept_id: "dog".to_string(),
+                properties: vec![("legs".to_string(), 4.0), ("loyalty".to_string(), 0.9)],
+            }),
+            EventPayload::Concept(ConceptPayload::RelationAdded {
+                source_concept: "dog".to_string(),
+                target_concept: "animal".to_string(),
+                relation_type: "is_a".to_string(),
+                strength: 0.95,
+            }),
+            EventPayload::Concept(ConceptPayload::PropertyInferred {
+                concept_id: "dog".to_string(),
+                property_name: "barks".to_string(),
+                inferred_value: 1.0,
+                confidence: 0.8,
+            }),
+            EventPayload::Composed(ComposedPayload::SubGraphAdded {
+                subgraph_id: a,
+                graph_type: "ipld".to_string(),
+                namespace: "storage".to_string(),
+            }),
+            EventPayload::Composed(ComposedPayload::CrossGraphLinkCreated {
+                source_graph: a,
+                source_node: "n1".to_string(),
+                target_graph: b,
+                target_node: "n2".to_string(),
+            }),
+        ]
+    }
+
+    fn assert_same_payload(left: &EventPayload, right: &EventPayload) {
+        assert_eq!(
+            serde_json::to_value(left).unwrap(),
+            serde_json::to_value(right).unwrap()
+        );
+    }
+
+    // ========== Payload Conversion Tests ==========
+
+    #[test]
+    fn test_every_typed_payload_roundtrips() {
+        for payload in typed_payloads() {
+            let data = payload.to_event_data().unwrap();
+            let decoded = EventPayload::from_event_data(payload.graph_type(), &data).unwrap();
+            assert_same_payload(&payload, &decoded);
+        }
+    }
+
+    #[test]
+    fn test_typed_payload_without_graph_type_decodes_as_generic() {
+        for payload in typed_payloads() {
+            let data = payload.to_event_data().unwrap();
+            let decoded = EventPayload::from_event_data(None, &data).unwrap();
+            assert!(matches!(decoded, EventPayload::Generic(_)));
+            assert_eq!(decoded.to_event_data().unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_generic_payload_roundtrips() {
+        let payload = EventPayload::Generic(GenericPayload {
+            event_type: "NodeAdded".to_string(),
+            data: json!({"node_id": "n1", "node_type": "task", "data": {"x": 1}}),
+        });
+        let data = payload.to_event_data().unwrap();
+        assert_eq!(
+            data,
+            EventData::NodeAdded {
+                node_id: "n1".to_string(),
+                node_type: "task".to_string(),
+                data: json!({"x": 1}),
+            }
+        );
+        let decoded = EventPayload::from_event_data(None, &data).unwrap();
+        assert_same_payload(&payload, &decoded);
+    }
+
+    #[test]
+    fn test_generic_payload_rejects_unknown_event_type() {
+        let payload = EventPayload::Generic(GenericPayload {
+            event_type: "Test".to_string(),
+            data: json!({}),
+        });
+        assert!(matches!(payload.to_event_data(), Err(GraphError::InvalidOperation(_))));
+    }
+
+    #[test]
+    fn test_unrecognized_event_data_decodes_as_generic() {
+        // A workflow node without a workflow_id is not a StateAdded
+        let data = EventData::NodeAdded {
+            node_id: "s1".to_string(),
+            node_type: "state".to_string(),
+            data: json!({}),
+        };
+        let decoded = EventPayload::from_event_data(Some(GraphType::Workflow), &data).unwrap();
+        assert!(matches!(decoded, EventPayload::Generic(_)));
+        assert_eq!(decoded.to_event_data().unwrap(), data);
+    }
+
+    #[test]
+    fn test_extra_fields_prevent_typed_decoding() {
+        let payload = EventPayload::Concept(ConceptPayload::RelationAdded {
+            source_concept: "a".to_string(),
+            target_concept: "b".to_string(),
+            relation_type: "is_a".to_string(),
+            strength: 0.5,
+        });
+        let mut data = payload.to_event_data().unwrap();
+        if let EventData::EdgeAdded { edge_id, .. } = &mut data {
+            *edge_id = "custom".to_string();
+        }
+        let decoded = EventPayload::from_event_data(Some(GraphType::Concept), &data).unwrap();
+        assert!(matches!(decoded, EventPayload::Generic(_)));
+        assert_eq!(decoded.to_event_data().unwrap(), data);
+    }
+
+    #[test]
+    fn test_properties_decode_keyed_by_name() {
+        let payload = EventPayload::Concept(ConceptPayload::PropertiesAdded {
+            concept_id: "dog".to_string(),
+            properties: vec![("size".to_string(), 0.5), ("age".to_string(), 3.0), ("size".to_string(), 0.7)],
+        });
+        let data = payload.to_event_data().unwrap();
+        match EventPayload::from_event_data(Some(GraphType::Concept), &data).unwrap() {
+            EventPayload::Concept(ConceptPayload::PropertiesAdded { properties, .. }) => {
+                assert_eq!(properties, vec![("age".to_string(), 3.0), ("size".to_string(), 0.7)]);
+            }
+            other => panic!("unexpected payload: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_payload_graph_type() {
+        let generic = EventPayload::Generic(GenericPayload {
+            event_type: "NodeRemoved".to_string(),
+            data: json!({"node_id": "n"}),
+        });
+        assert_eq!(generic.graph_type(), None);
+        let workflow = EventPayload::Workflow(WorkflowPayload::StateTransitioned {
+            instance_id: Uuid::new_v4(),
+            from_state: "a".to_string(),
+            to_state: "b".to_string(),
+        });
+        assert_eq!(workflow.graph_type(), Some(GraphType::Workflow));
+    }
+
+    // ========== Event Conversion Tests ==========
+
+    #[test]
+    fn test_cim_event_carries_stream_metadata() {
+        let aggregate_id = Uuid::new_v4();
+        let domain_event = event(aggregate_id, typed_payloads().remove(10));
+        let meta = stream(7, format!("cim.graph.workflow.{}", aggregate_id));
+
+        let cim_event = domain_event.to_cim_event(&meta).unwrap();
+        assert_eq!(cim_event.event_id, domain_event.event_id);
+        assert_eq!(cim_event.aggregate_id, aggregate_id);
+        assert_eq!(cim_event.correlation_id, domain_event.correlation_id);
+        assert_eq!(cim_event.causation_id, domain_event.causation_id);
+        assert_eq!(cim_event.sequence, 7);
+        assert_eq!(cim_event.subject, meta.subject);
+        assert_eq!(cim_event.timestamp, meta.timestamp);
+        assert_eq!(cim_event.data, domain_event.payload.to_event_data().unwrap());
+    }
+
+    #[test]
+    fn test_cim_event_roundtrip_recovers_typed_payload() {
+        let aggregate_id = Uuid::new_v4();
+        for payload in typed_payloads() {
+            let graph_type = payload.graph_type().unwrap();
+            let domain_event = event(aggregate_id, payload);
+            let meta = stream(
+                3,
+                build_event_subject(graph_type, aggregate_id, EventType::Updated),
+            );
+
+            let cim_event = domain_event.to_cim_event(&meta).unwrap();
+            let (decoded, decoded_meta) = GraphEvent::from_cim_event(&cim_event).unwrap();
+
+            assert_eq!(decoded.event_id, domain_event.event_id);
+            assert_eq!(decoded.correlation_id, domain_event.correlation_id);
+            assert_eq!(decoded.causation_id, domain_event.causation_id);
+            assert_same_payload(&decoded.payload, &domain_event.payload);
+            assert_eq!(decoded_meta, meta);
+        }
+    }
+
+    #[test]
+    fn test_graph_type_from_subject() {
+        let aggregate_id = Uuid::new_v4();
+        assert_eq!(
+            graph_type_from_subject(&format!("cim.graph.concept.{}", aggregate_id), aggregate_id),
+            Some(GraphType::Concept)
+        );
+        assert_eq!(
+            graph_type_from_subject(&format!("cim.graph.ipld.{}.node_added", aggregate_id), aggregate_id),
+            Some(GraphType::Ipld)
+        );
+        assert_eq!(graph_type_from_subject(&aggregate_id.to_string(), aggregate_id), None);
+        assert_eq!(graph_type_from_subject("cim.graph.workflow.other", aggregate_id), None);
+    }
+
+    #[test]
+    fn test_converted_events_feed_projection_engine() {
+        let aggregate_id = Uuid::new_v4();
+        let workflow_id = Uuid::new_v4();
+        let subject = format!("cim.graph.workflow.{}", aggregate_id);
+        let payloads = vec![
+            WorkflowPayload::StateAdded {
+                workflow_id,
+                state_id: "draft".to_string(),
+                state_type: "initial".to_string(),
+            },
+            WorkflowPayload::StateAdded {
+                workflow_id,
+                state_id: "review".to_string(),
+                state_type: "intermediate".to_string(),
+            },
+            WorkflowPayload::StateAdded {
+                workflow_id,
+                state_id: "published".to_string(),
+                state_type: "final".to_string(),
+            },
+            WorkflowPayload::TransitionAdded {
+                workflow_id,
+                from_state: "draft".to_string(),
+                to_state: "review".to_string(),
+                trigger: "submit".to_string(),
+            },
+            WorkflowPayload::TransitionAdded {
+                workflow_id,
+                from_state: "review".to_string(),
+                to_state: "published".to_string(),
+                trigger: "approve".to_string(),
+            },
+        ];
+        let events = payloads
+            .into_iter()
+            .enumerate()
+            .map(|(i, payload)| {
+                event(aggregate_id, EventPayload::Workflow(payload))
+                    .to_cim_event(&stream(i as u64 + 1, subject.clone()))
+                    .unwrap()
+            })
+            .collect();
+
+        let projection = ProjectionEngine::<WorkflowProjection>::new().project(events);
+
+        assert_eq!(projection.version, 5);
+        assert_eq!(projection.get_start_node().unwrap().id, "draft");
+        assert!(matches!(
+            projection.nodes.get("published").unwrap().node_type,
+            WorkflowNodeType::End
+        ));
+        assert!(projection.validate().is_ok());
+        let edge = projection.edges.get("draft-[submit]->review").unwrap();
+        assert_eq!(edge.trigger.as_deref(), Some("submit"));
+    }
+}
@@ -7,7 +7,11 @@
 pub mod graph_events;
 pub mod command_handlers;
 pub mod subjects;
+pub mod bridge;
+pub mod upcast;
 
 pub use self::graph_events::*;
 pub use self::command_handlers::*;
-pub use self::subjects::*;
\ No newline at end of file
+pub use self::subjects::*;
+pub use self::bridge::StreamMetadata;
+pub use self::upcast::{UpcasterRegistry, CURRENT_SCHEMA_VERSION};
\ No newline at end of file
@@ -35,6 +35,18 @@ impl GraphType {
             GraphType::Composed => "composed",
         }
     }
+
+    /// Parse a graph type from its subject segment
+    pub(crate) fn from_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "ipld" => Some(GraphType::Ipld),
+            "context" => Some(GraphType::Context),
+            "workflow" => Some(GraphType::Workflow),
+            "concept" => Some(GraphType::Concept),
+            "composed" => Some(GraphType::Composed),
+            _ => None,
+        }
+    }
 }
 
 /// Event type components
@@ -125,14 +137,7 @@ pub fn parse_event_subject(subject: &str) -> Result<(GraphType, Uuid, EventType)
     if segs.len() != 5 || segs[0] != "cim" || segs[1] != "graph" {
         return Err("Invalid subject format".to_string());
     }
-    let graph_type = match segs[2] {
-        "ipld" => GraphType::Ipld,
-        "context" => GraphType::Context,
-        "workflow" => GraphType::Workflow,
-        "concept" => GraphType::Concept,
-        "composed" => GraphType::Composed,
-        _ => return Err("Unknown graph type".to_string()),
-    };
+    let graph_type = GraphType::from_segment(segs[2]).ok_or_else(|| "Unknown graph type".to_string())?;
     let aggregate_id = Uuid::parse_str(segs[3]).map_err(|_| "Invalid UUID in subject".to_string())?;
     let event_type = match segs[4] {
         "created" => EventType::Created,
@@ -196,6 +201,20 @@ mod tests {
         assert_eq!(GraphType::Composed.as_str(), "composed");
     }
 
+    #[test]
+    fn test_graph_type_from_segment_roundtrip() {
+        for graph_type in [
+            GraphType::Ipld,
+            GraphType::Context,
+            GraphType::Workflow,
+            GraphType::Concept,
+            GraphType::Composed,
+        ] {
+            assert_eq!(GraphType::from_segment(graph_type.as_str()), Some(graph_type));
+        }
+        assert_eq!(GraphType::from_segment("unknown"), None);
+    }
+
     #[test]
     fn test_graph_type_equality() {
         assert_eq!(GraphType::Ipld, GraphType::Ipld);
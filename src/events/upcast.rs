@@ -0,0 +1,443 @@
+//! Schema versioning and upcasting for serialized events
+//!
+//! A serialized [`GraphEvent`] is its JSON object plus a `schema_version`
+//! field naming the payload schema it was written with. Messages written
+//! before versioning have no such field and count as version 1.
+//!
+//! Decoding parses the raw JSON, runs every registered upcaster from the
+//! message's version up to [`CURRENT_SCHEMA_VERSION`], and only then
+//! deserializes. When a payload changes shape, bump
+//! [`CURRENT_SCHEMA_VERSION`], register the vN → vN+1 migration in
+//! [`UpcasterRegistry::standard`] and add an event of the old shape to the
+//! corpus in `tests/fixtures/events`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+use super::graph_events::GraphEvent;
+use crate::error::{GraphError, Result};
+
+/// Payload schema version written by this crate
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Schema version of events serialized without a `schema_version` field
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// Field holding the schema version in a serialized event
+const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Migration of raw event JSON from one schema version to the next
+type UpcastFn = Box<dyn Fn(&mut Value) -> Result<()> + Send + Sync>;
+
+/// A registered migration
+struct Upcaster {
+    /// Version the migration starts from
+    from_version: u32,
+    /// Payload kind it applies to (`None` for the whole event)
+    payload_kind: Option<String>,
+    /// The migration itself
+    apply: UpcastFn,
+}
+
+/// Ordered set of schema migrations for serialized events
+pub struct UpcasterRegistry {
+    /// Version events are migrated to
+    current_version: u32,
+    /// Registered migrations, in registration order
+    upcasters: Vec<Upcaster>,
+}
+
+impl std::fmt::Debug for UpcasterRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpcasterRegistry")
+            .field("current_version", &self.current_version)
+            .field("upcasters", &self.upcasters.len())
+            .finish()
+    }
+}
+
+impl UpcasterRegistry {
+    /// Create an empty registry that migrates events up to `current_version`
+    pub fn new(current_version: u32) -> Self {
+        Self {
+            current_version,
+            upcasters: Vec::new(),
+        }
+    }
+
+    /// The migrations for this crate's own payload history, used by every
+    /// built-in decoding path
+    pub fn standard() -> &'static UpcasterRegistry {
+        static STANDARD: OnceLock<UpcasterRegistry> = OnceLock::new();
+        STANDARD.get_or_init(|| UpcasterRegistry::new(CURRENT_SCHEMA_VERSION))
+    }
+
+    /// Version events are migrated to
+    pub fn current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    /// Add a migration of the whole event JSON from `from_version` to the next version
+    pub fn with_upcaster(
+        mut self,
+        from_version: u32,
+        upcaster: impl Fn(&mut Value) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.upcasters.push(Upcaster {
+            from_version,
+            payload_kind: None,
+            apply: Box::new(upcaster),
+        });
+        self
+    }
+
+    /// Add a migration of one payload kind from `from_version` to the next version
+    ///
+    /// `payload_kind` is `"Generic"` or `"<Graph>::<Variant>"`, e.g.
+    /// `"Workflow::StateTransitioned"`, and the upcaster receives the
+    /// variant's fields object.
+    pub fn with_payload_upcaster(
+        mut self,
+        from_version: u32,
+        payload_kind: &str,
+        upcaster: impl Fn(&mut Value) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.upcasters.push(Upcaster {
+            from_version,
+            payload_kind: Some(payload_kind.to_string()),
+            apply: Box::new(upcaster),
+        });
+        self
+    }
+
+    /// Migrate raw event JSON to the current schema version
+    ///
+    /// Fails with [`GraphError::SerializationError`] when the event is not a
+    /// JSON object, its version is not a number, it was written by a newer
+    /// schema, or an upcaster fails.
+    pub fn upcast(&self, mut event: Value) -> Result<Value> {
+        let version = schema_version(&event)?;
+        if version > self.current_version {
+            return Err(GraphError::SerializationError(format!(
+                "Event schema version {} is newer than supported version {}",
+                version, self.current_version
+            )));
+        }
+
+        for from_version in version..self.current_version {
+            for upcaster in self.upcasters.iter().filter(|u| u.from_version == from_version) {
+                match &upcaster.payload_kind {
+                    None => (upcaster.apply)(&mut event)?,
+                    Some(kind) => {
+                        if payload_kind(&event).as_deref() == Some(kind.as_str()) {
+                            if let Some(fields) = payload_fields_mut(&mut event) {
+                                (upcaster.apply)(fields)?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(fields) = event.as_object_mut() {
+            fields.insert(SCHEMA_VERSION_FIELD.to_string(), self.current_version.into());
+        }
+        Ok(event)
+    }
+
+    /// Upcast raw event JSON and deserialize it
+    pub fn decode(&self, event: Value) -> Result<GraphEvent> {
+        serde_json::from_value(self.upcast(event)?)
+            .map_err(|e| GraphError::SerializationError(format!("Failed to deserialize event: {}", e)))
+    }
+
+    /// Upcast and deserialize an event from JSON bytes
+    pub fn decode_slice(&self, bytes: &[u8]) -> Result<GraphEvent> {
+        let event = serde_json::from_slice(bytes)
+            .map_err(|e| GraphError::SerializationError(format!("Failed to parse event: {}", e)))?;
+        self.decode(event)
+    }
+
+    /// Serialize an event tagged with the current schema version
+    pub fn encode(&self, event: &GraphEvent) -> Result<Value> {
+        let mut encoded = serde_json::to_value(event)
+            .map_err(|e| GraphError::SerializationError(format!("Failed to serialize event: {}", e)))?;
+        if let Some(fields) = encoded.as_object_mut() {
+            fields.insert(SCHEMA_VERSION_FIELD.to_string(), self.current_version.into());
+        }
+        Ok(encoded)
+    }
+}
+
+/// Schema version of raw event JSON
+pub fn schema_version(event: &Value) -> Result<u32> {
+    let fields = event
+        .as_object()
+        .ok_or_else(|| GraphError::SerializationError("Event is not a JSON object".to_string()))?;
+    match fields.get(SCHEMA_VERSION_FIELD) {
+        None => Ok(UNVERSIONED_SCHEMA_VERSION),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| GraphError::SerializationError(format!("Invalid schema version: {}", version))),
+    }
+}
+
+/// Payload kind of raw event JSON: `"Generic"` or `"<Graph>::<Variant>"`
+pub fn payload_kind(event: &Value) -> Option<String> {
+    let (graph, body) = single_entry(event.get("payload")?)?;
+    if graph == "Generic" {
+        return Some(graph.clone());
+    }
+    let (variant, _) = single_entry(body)?;
+    Some(format!("{}::{}", graph, variant))
+}
+
+/// Fields object of the payload variant in raw event JSON
+fn payload_fields_mut(event: &mut Value) -> Option<&mut Value> {
+    let payload = event.get_mut("payload")?.as_object_mut()?;
+    if payload.len() != 1 {
+        return None;
+    }
+    let (graph, body) = payload.iter_mut().next()?;
+    if graph == "Generic" {
+        return Some(body);
+    }
+    let variants = body.as_object_mut()?;
+    if variants.len() != 1 {
+        return None;
+    }
+    variants.values_mut().next()
+}
+
+/// Key and value of a single-entry JSON object (an externally tagged enum)
+fn single_entry(value: &Value) -> Option<(&String, &Value)> {
+    let fields = value.as_object()?;
+    if fields.len() != 1 {
+        return None;
+    }
+    fields.iter().next()
+}
+
+/// `#[serde(with = "versioned")]` for a [`GraphEvent`] field: written with
+/// the current schema version, upcast with the standard registry on read
+pub mod versioned {
+    use super::*;
+
+    /// Serialize an event with its schema version
+    pub fn serialize<S: Serializer>(event: &GraphEvent, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        UpcasterRegistry::standard()
+            .encode(event)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Deserialize and upcast an event
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<GraphEvent, D::Error> {
+        UpcasterRegistry::standard()
+            .decode(Value::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// `#[serde(with = "versioned_list")]` for a `Vec<GraphEvent>` field
+pub mod versioned_list {
+    use super::*;
+
+    /// Serialize events with their schema version
+    pub fn serialize<S: Serializer>(events: &[GraphEvent], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let registry = UpcasterRegistry::standard();
+        events
+            .iter()
+            .map(|event| registry.encode(event))
+            .collect::<Result<Vec<Value>>>()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Deserialize and upcast events
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<GraphEvent>, D::Error> {
+        let registry = UpcasterRegistry::standard();
+        Vec::<Value>::deserialize(deserializer)?
+            .into_iter()
+            .map(|event| registry.decode(event))
+            .collect::<Result<Vec<_>>>()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventPayload, WorkflowPayload};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn transitioned(instance_id: Uuid) -> GraphEvent {
+        GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id: instance_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Workflow(WorkflowPayload::StateTransitioned {
+                instance_id,
+                from_state: "draft".to_string(),
+                to_state: "review".to_string(),
+            }),
+        }
+    }
+
+    /// Version 1 of a made-up schema where `StateTransitioned` used `from`/`to`
+    fn legacy_transition(instance_id: Uuid) -> Value {
+        json!({
+            "event_id": Uuid::new_v4(),
+            "aggregate_id": instance_id,
+            "correlation_id": Uuid::new_v4(),
+            "causation_id": null,
+            "payload": {"Workflow": {"StateTransitioned": {
+                "instance_id": instance_id,
+                "from": "draft",
+                "to": "review",
+            }}},
+        })
+    }
+
+    fn rename(fields: &mut Value, from: &str, to: &str) -> Result<()> {
+        let object = fields
+            .as_object_mut()
+            .ok_or_else(|| GraphError::SerializationError("Payload is not an object".to_string()))?;
+        if let Some(value) = object.remove(from) {
+            object.insert(to.to_string(), value);
+        }
+        Ok(())
+    }
+
+    // ========== Versioning Tests ==========
+
+    #[test]
+    fn test_schema_version_defaults_for_unversioned_events() {
+        assert_eq!(schema_version(&json!({})).unwrap(), UNVERSIONED_SCHEMA_VERSION);
+        assert_eq!(schema_version(&json!({"schema_version": 3})).unwrap(), 3);
+        assert!(schema_version(&json!({"schema_version": "3"})).is_err());
+        assert!(schema_version(&json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_payload_kind() {
+        let event = UpcasterRegistry::standard().encode(&transitioned(Uuid::new_v4())).unwrap();
+        assert_eq!(payload_kind(&event).as_deref(), Some("Workflow::StateTransitioned"));
+
+        let generic = json!({"payload": {"Generic": {"event_type": "NodeAdded", "data": {}}}});
+        assert_eq!(payload_kind(&generic).as_deref(), Some("Generic"));
+        assert_eq!(payload_kind(&json!({})), None);
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let registry = UpcasterRegistry::standard();
+        let event = transitioned(Uuid::new_v4());
+
+        let encoded = registry.encode(&event).unwrap();
+        assert_eq!(encoded["schema_version"], CURRENT_SCHEMA_VERSION);
+
+        let decoded = registry.decode(encoded).unwrap();
+        assert_eq!(decoded.event_id, event.event_id);
+    }
+
+    #[test]
+    fn test_rejects_newer_schema_versions() {
+        let registry = UpcasterRegistry::new(2);
+        let result = registry.upcast(json!({"schema_version": 3}));
+        assert!(matches!(result, Err(GraphError::SerializationError(_))));
+    }
+
+    // ========== Upcaster Tests ==========
+
+    #[test]
+    fn test_payload_upcaster_migrates_old_shape() {
+        let registry = UpcasterRegistry::new(2).with_payload_upcaster(1, "Workflow::StateTransitioned", |fields| {
+            rename(fields, "from", "from_state")?;
+            rename(fields, "to", "to_state")
+        });
+        let instance_id = Uuid::new_v4();
+
+        // The old shape no longer deserializes on its own
+        assert!(serde_json::from_value::<GraphEvent>(legacy_transition(instance_id)).is_err());
+
+        let event = registry.decode(legacy_transition(instance_id)).unwrap();
+        match event.payload {
+            EventPayload::Workflow(WorkflowPayload::StateTransitioned { from_state, to_state, .. }) => {
+                assert_eq!(from_state, "draft");
+                assert_eq!(to_state, "review");
+            }
+            other => panic!("unexpected payload {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_upcasters_run_in_version_order_from_message_version() {
+        let registry = UpcasterRegistry::new(3)
+            .with_upcaster(2, |event| {
+                let steps = event["steps"].as_str().unwrap_or_default().to_string();
+                event["steps"] = json!(format!("{}2", steps));
+                Ok(())
+            })
+            .with_upcaster(1, |event| {
+                event["steps"] = json!("1");
+                Ok(())
+            });
+
+        let from_one = registry.upcast(json!({})).unwrap();
+        assert_eq!(from_one["steps"], "12");
+        assert_eq!(from_one["schema_version"], 3);
+
+        let from_two = registry.upcast(json!({"schema_version": 2, "steps": ""})).unwrap();
+        assert_eq!(from_two["steps"], "2");
+
+        let current = registry.upcast(json!({"schema_version": 3, "steps": ""})).unwrap();
+        assert_eq!(current["steps"], "");
+    }
+
+    #[test]
+    fn test_payload_upcaster_skips_other_kinds() {
+        let registry = UpcasterRegistry::new(2).with_payload_upcaster(1, "Workflow::StateAdded", |_| {
+            Err(GraphError::SerializationError("should not run".to_string()))
+        });
+        let event = registry.encode(&transitioned(Uuid::new_v4())).unwrap();
+        let mut unversioned = event.clone();
+        unversioned.as_object_mut().unwrap().remove("schema_version");
+
+        assert!(registry.decode(unversioned).is_ok());
+    }
+
+    #[test]
+    fn test_upcaster_errors_propagate() {
+        let registry = UpcasterRegistry::new(2)
+            .with_upcaster(1, |_| Err(GraphError::SerializationError("broken".to_string())));
+        assert!(registry.upcast(json!({})).is_err());
+    }
+
+    #[test]
+    fn test_versioned_serde_helpers() {
+        #[derive(Serialize, Deserialize)]
+        struct Holder {
+            #[serde(with = "versioned")]
+            event: GraphEvent,
+            #[serde(with = "versioned_list")]
+            history: Vec<GraphEvent>,
+        }
+
+        let holder = Holder {
+            event: transitioned(Uuid::new_v4()),
+            history: vec![transitioned(Uuid::new_v4())],
+        };
+        let json = serde_json::to_value(&holder).unwrap();
+        assert_eq!(json["event"]["schema_version"], CURRENT_SCHEMA_VERSION);
+        assert_eq!(json["history"][0]["schema_version"], CURRENT_SCHEMA_VERSION);
+
+        let restored: Holder = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.event.event_id, holder.event.event_id);
+        assert_eq!(restored.history.len(), 1);
+    }
+}
@@ -6,6 +6,10 @@ use uuid::Uuid;
 
 pub use crate::core::projection_engine::GenericGraphProjection;
 pub use crate::core::{Node, Edge};
+use crate::core::cim_graph::EventData;
+use crate::core::projection_engine::{
+    merge_fields, payload_fields, payload_str, payload_with, type_key, FromEventData, ToEventData,
+};
 
 /// Composed graph projection
 pub type ComposedGraph = GenericGraphProjection<ComposedNode, ComposedEdge>;
@@ -164,6 +168,110 @@ impl Node for ComposedNode {
     }
 }
 
+/// UUID field of a JSON payload
+fn payload_uuid(data: &serde_json::Value, key: &str) -> Option<Uuid> {
+    payload_str(data, key).and_then(|s| Uuid::parse_str(s).ok())
+}
+
+/// Builds composed nodes from `NodeAdded` events
+///
+/// Unlike the other graph types there is no sensible default kind, so the
+/// event is rejected unless `node_type` and the payload fully describe one:
+/// - `graph_reference`: `domain` (`ipld`, `context`, `workflow`, `concept`,
+///   `composed`) and `graph_id`
+/// - `node_reference`: `graph_id` and `node_id`
+/// - `junction`: `connected_graphs` (array of graph ids)
+/// - `transform`: `operation`
+/// - `aggregate`: `aggregation_type`
+///
+/// The payload is kept in `metadata`.
+impl FromEventData for ComposedNode {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (node_id, node_type, payload) = match data {
+            EventData::NodeAdded { node_id, node_type, data } => (node_id, node_type, data),
+            _ => return None,
+        };
+        let text = |key: &str| payload_str(payload, key).map(str::to_string);
+        let kind = match type_key(node_type).as_str() {
+            "graphreference" | "graphref" => {
+                let graph_id = payload_uuid(payload, "graph_id")?;
+                let domain = match type_key(payload_str(payload, "domain")?).as_str() {
+                    "ipld" => GraphDomain::Ipld { graph_id },
+                    "context" => GraphDomain::Context { graph_id },
+                    "workflow" => GraphDomain::Workflow { graph_id },
+                    "concept" => GraphDomain::Concept { graph_id },
+                    "composed" => GraphDomain::Composed { graph_id },
+                    _ => return None,
+                };
+                ComposedNodeType::GraphReference { domain }
+            }
+            "nodereference" | "noderef" => ComposedNodeType::NodeReference {
+                graph_id: payload_uuid(payload, "graph_id")?,
+                node_id: text("node_id")?,
+            },
+            "junction" => ComposedNodeType::Junction {
+                connected_graphs: payload
+                    .get("connected_graphs")?
+                    .as_array()?
+                    .iter()
+                    .map(|v| v.as_str().and_then(|s| Uuid::parse_str(s).ok()))
+                    .collect::<Option<Vec<_>>>()?,
+            },
+            "transform" => ComposedNodeType::Transform { operation: text("operation")? },
+            "aggregate" => ComposedNodeType::Aggregate { aggregation_type: text("aggregation_type")? },
+            _ => return None,
+        };
+        let mut node = ComposedNode::new(node_id.clone(), kind);
+        node.metadata = payload_fields(payload);
+        Some(node)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        merge_fields(&mut self.metadata, data);
+    }
+}
+
+impl ToEventData for ComposedNode {
+    fn event_type(&self) -> String {
+        match self.node_type {
+            ComposedNodeType::GraphReference { .. } => "graph_reference",
+            ComposedNodeType::NodeReference { .. } => "node_reference",
+            ComposedNodeType::Junction { .. } => "junction",
+            ComposedNodeType::Transform { .. } => "transform",
+            ComposedNodeType::Aggregate { .. } => "aggregate",
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let fields: Vec<(&'static str, serde_json::Value)> = match &self.node_type {
+            ComposedNodeType::GraphReference { domain } => {
+                let (name, graph_id) = match domain {
+                    GraphDomain::Ipld { graph_id } => ("ipld", graph_id),
+                    GraphDomain::Context { graph_id } => ("context", graph_id),
+                    GraphDomain::Workflow { graph_id } => ("workflow", graph_id),
+                    GraphDomain::Concept { graph_id } => ("concept", graph_id),
+                    GraphDomain::Composed { graph_id } => ("composed", graph_id),
+                };
+                vec![("domain", name.into()), ("graph_id", graph_id.to_string().into())]
+            }
+            ComposedNodeType::NodeReference { graph_id, node_id } => vec![
+                ("graph_id", graph_id.to_string().into()),
+                ("node_id", node_id.clone().into()),
+            ],
+            ComposedNodeType::Junction { connected_graphs } => vec![(
+                "connected_graphs",
+                connected_graphs.iter().map(|id| id.to_string()).collect::<Vec<_>>().into(),
+            )],
+            ComposedNodeType::Transform { operation } => vec![("operation", operation.clone().into())],
+            ComposedNodeType::Aggregate { aggregation_type } => {
+                vec![("aggregation_type", aggregation_type.clone().into())]
+            }
+        };
+        payload_with(&self.metadata, fields)
+    }
+}
+
 /// Type of composed edge
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ComposedEdgeType {
@@ -311,6 +419,73 @@ impl Edge for ComposedEdge {
     }
 }
 
+/// Builds composed edges from `EdgeAdded` events
+///
+/// `edge_type` selects the kind; `cross_graph_link` needs `source_graph`
+/// and `target_graph`, while `data_flow`, `dependency` and `transformation`
+/// read `flow_type`, `dependency_type` and `transform` (empty if absent).
+/// Unknown kinds are rejected. The payload is kept in `metadata`.
+impl FromEventData for ComposedEdge {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (edge_id, source_id, target_id, edge_type, payload) = match data {
+            EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, data } => {
+                (edge_id, source_id, target_id, edge_type, data)
+            }
+            _ => return None,
+        };
+        let field = |key: &str| payload_str(payload, key).unwrap_or_default().to_string();
+        let kind = match type_key(edge_type).as_str() {
+            "crossgraphlink" => ComposedEdgeType::CrossGraphLink {
+                source_graph: payload_uuid(payload, "source_graph")?,
+                target_graph: payload_uuid(payload, "target_graph")?,
+            },
+            "dataflow" => ComposedEdgeType::DataFlow { flow_type: field("flow_type") },
+            "controlflow" => ComposedEdgeType::ControlFlow,
+            "dependency" => ComposedEdgeType::Dependency { dependency_type: field("dependency_type") },
+            "transformation" => ComposedEdgeType::Transformation { transform: field("transform") },
+            "synchronization" => ComposedEdgeType::Synchronization,
+            _ => return None,
+        };
+        let mut edge = ComposedEdge::new(edge_id.clone(), source_id.clone(), target_id.clone(), kind);
+        edge.metadata = payload_fields(payload);
+        Some(edge)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        merge_fields(&mut self.metadata, data);
+    }
+}
+
+impl ToEventData for ComposedEdge {
+    fn event_type(&self) -> String {
+        match self.edge_type {
+            ComposedEdgeType::CrossGraphLink { .. } => "cross_graph_link",
+            ComposedEdgeType::DataFlow { .. } => "data_flow",
+            ComposedEdgeType::ControlFlow => "control_flow",
+            ComposedEdgeType::Dependency { .. } => "dependency",
+            ComposedEdgeType::Transformation { .. } => "transformation",
+            ComposedEdgeType::Synchronization => "synchronization",
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let fields: Vec<(&'static str, serde_json::Value)> = match &self.edge_type {
+            ComposedEdgeType::CrossGraphLink { source_graph, target_graph } => vec![
+                ("source_graph", source_graph.to_string().into()),
+                ("target_graph", target_graph.to_string().into()),
+            ],
+            ComposedEdgeType::DataFlow { flow_type } => vec![("flow_type", flow_type.clone().into())],
+            ComposedEdgeType::Dependency { dependency_type } => {
+                vec![("dependency_type", dependency_type.clone().into())]
+            }
+            ComposedEdgeType::Transformation { transform } => vec![("transform", transform.clone().into())],
+            ComposedEdgeType::ControlFlow | ComposedEdgeType::Synchronization => Vec::new(),
+        };
+        payload_with(&self.metadata, fields)
+    }
+}
+
 /// Extension methods for ComposedProjection
 impl ComposedProjection {
     /// Get all graph references
@@ -454,6 +629,18 @@ impl ComposedProjection {
             .collect()
     }
 
+    /// Dependency edges left after dropping every one implied by a chain of others
+    ///
+    /// The result has the same transitive dependencies as all `Dependency`
+    /// edges, and of parallel dependencies only the one with the smallest ID
+    /// is kept. Fails with `InvalidOperation` if the dependencies are cyclic.
+    pub fn minimal_dependencies(&self) -> crate::error::Result<Vec<&ComposedEdge>> {
+        let kept = crate::algorithms::transitive_reduction(self, |edge| {
+            matches!(edge.edge_type, ComposedEdgeType::Dependency { .. })
+        })?;
+        Ok(kept.iter().filter_map(|id| self.edges.get(id)).collect())
+    }
+
     /// Validate the composition
     pub fn validate(&self) -> Result<(), String> {
         // Check for orphaned graph references
@@ -511,6 +698,7 @@ impl ComposedProjection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_support::{edge_added, node_added};
 
     #[test]
     fn test_composed_node_creation() {
@@ -1162,6 +1350,29 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_minimal_dependencies() {
+        let mut projection = ComposedProjection::new(Uuid::new_v4(), crate::core::GraphType::ComposedGraph);
+        for id in ["a", "b", "c"] {
+            projection.nodes.insert(id.to_string(), ComposedNode::transform(id, "op"));
+        }
+
+        // a -> b -> c makes a -> c redundant; control flow is left alone
+        projection.edges.insert("d1".to_string(), ComposedEdge::dependency("d1", "a", "b", "requires"));
+        projection.edges.insert("d2".to_string(), ComposedEdge::dependency("d2", "b", "c", "requires"));
+        projection.edges.insert("d3".to_string(), ComposedEdge::dependency("d3", "a", "c", "provides"));
+        projection.edges.insert("f1".to_string(), ComposedEdge::control_flow("f1", "c", "a"));
+
+        let kept: Vec<&str> = projection.minimal_dependencies().unwrap().iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(kept, vec!["d1", "d2"]);
+
+        projection.edges.insert("d4".to_string(), ComposedEdge::dependency("d4", "c", "a", "requires"));
+        assert!(matches!(
+            projection.minimal_dependencies(),
+            Err(crate::error::GraphError::InvalidOperation(_))
+        ));
+    }
+
     #[test]
     fn test_validate_cycle_in_control_flow() {
         let mut projection = ComposedProjection::new(Uuid::new_v4(), crate::core::GraphType::ComposedGraph);
@@ -1810,4 +2021,183 @@ mod tests {
         let neighbors_missing = GraphProjection::neighbors(&projection, "missing");
         assert_eq!(neighbors_missing.len(), 0);
     }
-}
\ No newline at end of file
+
+    // ========== FromEventData Tests ==========
+
+    #[test]
+    fn test_composed_node_from_event_data_graph_reference() {
+        let graph_id = Uuid::new_v4();
+        let node = ComposedNode::from_event_data(&node_added(
+            "ref",
+            "graph_reference",
+            serde_json::json!({"domain": "Workflow", "graph_id": graph_id.to_string()}),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            node.node_type,
+            ComposedNodeType::GraphReference { domain: GraphDomain::Workflow { graph_id } }
+        );
+        assert_eq!(node.metadata.get("domain"), Some(&serde_json::json!("Workflow")));
+    }
+
+    #[test]
+    fn test_composed_node_from_event_data_other_kinds() {
+        let g1 = Uuid::new_v4();
+        let g2 = Uuid::new_v4();
+
+        let node_ref = ComposedNode::from_event_data(&node_added(
+            "n",
+            "NodeReference",
+            serde_json::json!({"graph_id": g1.to_string(), "node_id": "start"}),
+        ))
+        .unwrap();
+        assert_eq!(
+            node_ref.node_type,
+            ComposedNodeType::NodeReference { graph_id: g1, node_id: "start".to_string() }
+        );
+
+        let junction = ComposedNode::from_event_data(&node_added(
+            "j",
+            "junction",
+            serde_json::json!({"connected_graphs": [g1.to_string(), g2.to_string()]}),
+        ))
+        .unwrap();
+        assert_eq!(junction.node_type, ComposedNodeType::Junction { connected_graphs: vec![g1, g2] });
+
+        let transform = ComposedNode::from_event_data(&node_added(
+            "t",
+            "transform",
+            serde_json::json!({"operation": "normalize"}),
+        ))
+        .unwrap();
+        assert_eq!(transform.node_type, ComposedNodeType::Transform { operation: "normalize".to_string() });
+
+        let aggregate = ComposedNode::from_event_data(&node_added(
+            "g",
+            "aggregate",
+            serde_json::json!({"aggregation_type": "sum"}),
+        ))
+        .unwrap();
+        assert_eq!(aggregate.node_type, ComposedNodeType::Aggregate { aggregation_type: "sum".to_string() });
+    }
+
+    #[test]
+    fn test_composed_node_rejects_incomplete_payloads() {
+        let g = Uuid::new_v4().to_string();
+        let rejected = vec![
+            node_added("x", "graph_reference", serde_json::json!({"graph_id": g})),
+            node_added("x", "graph_reference", serde_json::json!({"domain": "ipld", "graph_id": "nope"})),
+            node_added("x", "graph_reference", serde_json::json!({"domain": "other", "graph_id": g})),
+            node_added("x", "node_reference", serde_json::json!({"graph_id": g})),
+            node_added("x", "junction", serde_json::json!({"connected_graphs": ["bad"]})),
+            node_added("x", "transform", serde_json::json!({})),
+            node_added("x", "mystery", serde_json::json!({})),
+        ];
+        for data in rejected {
+            assert!(ComposedNode::from_event_data(&data).is_none(), "{:?}", data);
+        }
+    }
+
+    #[test]
+    fn test_composed_edge_from_event_data() {
+        let (g1, g2) = (Uuid::new_v4(), Uuid::new_v4());
+        let link = ComposedEdge::from_event_data(&edge_added(
+            "l",
+            "a",
+            "b",
+            "CrossGraphLink",
+            serde_json::json!({"source_graph": g1.to_string(), "target_graph": g2.to_string()}),
+        ))
+        .unwrap();
+        assert_eq!(
+            link.edge_type,
+            ComposedEdgeType::CrossGraphLink { source_graph: g1, target_graph: g2 }
+        );
+
+        let flow_data = serde_json::json!({"flow_type": "stream"});
+        let flow = ComposedEdge::from_event_data(&edge_added("f", "a", "b", "data_flow", flow_data)).unwrap();
+        assert_eq!(flow.edge_type, ComposedEdgeType::DataFlow { flow_type: "stream".to_string() });
+
+        let sync = ComposedEdge::from_event_data(&edge_added("s", "a", "b", "synchronization", serde_json::json!({})))
+            .unwrap();
+        assert_eq!(sync.edge_type, ComposedEdgeType::Synchronization);
+
+        for edge_type in ["cross_graph_link", "unknown"] {
+            let data = edge_added("l", "a", "b", edge_type, serde_json::json!({}));
+            assert!(ComposedEdge::from_event_data(&data).is_none());
+        }
+    }
+
+    #[test]
+    fn test_composed_merge_updates_metadata() {
+        let mut node = ComposedNode::transform("t", "normalize");
+        node.merge_event_data(&serde_json::json!({"owner": "etl"}));
+        assert_eq!(node.metadata.get("owner"), Some(&serde_json::json!("etl")));
+
+        let mut edge = ComposedEdge::control_flow("c", "a", "b");
+        edge.merge_event_data(&serde_json::json!({"priority": 1}));
+        assert_eq!(edge.metadata.get("priority"), Some(&serde_json::json!(1)));
+    }
+
+    #[test]
+    fn test_composed_projection_built_by_engine() {
+        use crate::core::cim_graph::GraphEvent;
+        use crate::core::ProjectionEngine;
+
+        let aggregate_id = Uuid::new_v4();
+        let ipld_graph = Uuid::new_v4();
+        let event = |sequence: u64, data: EventData| GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            sequence,
+            subject: "cim.graph.composed.evt".to_string(),
+            timestamp: chrono::Utc::now(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            data,
+        };
+
+        let projection = ProjectionEngine::<ComposedProjection>::new().project(vec![
+            event(1, node_added(
+                "ipld",
+                "graph_reference",
+                serde_json::json!({"domain": "ipld", "graph_id": ipld_graph.to_string()}),
+            )),
+            event(2, node_added("bad", "graph_reference", serde_json::json!({}))),
+        ]);
+
+        assert_eq!(projection.nodes.len(), 1);
+        assert_eq!(projection.get_ipld_graphs()[0].1, ipld_graph);
+    }
+
+    #[test]
+    fn test_composed_to_event_data_roundtrip() {
+        let graph_id = Uuid::new_v4();
+        let mut junction = ComposedNode::junction("j", vec![graph_id, Uuid::new_v4()]);
+        junction.metadata.insert("label".to_string(), serde_json::json!("hub"));
+        let nodes = [
+            ComposedNode::workflow_ref("w", graph_id),
+            ComposedNode::node_ref("n", graph_id, "start"),
+            ComposedNode::aggregate("sum", "count"),
+            junction,
+        ];
+        for node in nodes {
+            let rebuilt = ComposedNode::from_event_data(&node_added(&node.id, &node.event_type(), node.event_payload())).unwrap();
+            assert_eq!(rebuilt.node_type, node.node_type);
+            assert_eq!(rebuilt.event_payload(), node.event_payload());
+        }
+
+        let edges = [
+            ComposedEdge::cross_graph_link("l", "a", "b", graph_id, Uuid::new_v4()),
+            ComposedEdge::dependency("d", "a", "b", "requires"),
+            ComposedEdge::synchronization("s", "a", "b"),
+        ];
+        for edge in edges {
+            let data = edge_added(&edge.id, &edge.source, &edge.target, &edge.event_type(), edge.event_payload());
+            let rebuilt = ComposedEdge::from_event_data(&data).unwrap();
+            assert_eq!(rebuilt.edge_type, edge.edge_type);
+            assert_eq!(rebuilt.event_payload(), edge.event_payload());
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! Concept graph - semantic reasoning (event-driven projection)
 
 // Projections are ephemeral - no serialization
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 pub use crate::core::projection_engine::GenericGraphProjection;
 pub use crate::core::{Node, Edge};
+use crate::core::cim_graph::EventData;
+use crate::core::projection_engine::{
+    merge_fields, merge_fields_except, payload_str, payload_with, type_key, FromEventData, ToEventData,
+};
 
 /// Concept graph projection
 pub type ConceptGraph = GenericGraphProjection<ConceptNode, ConceptEdge>;
@@ -108,6 +112,75 @@ impl Node for ConceptNode {
     }
 }
 
+/// Payload keys with a dedicated `ConceptNode` field
+const CONCEPT_NODE_FIELDS: [&str; 3] = ["name", "description", "properties"];
+
+/// Builds concept nodes from `NodeAdded` events
+///
+/// `node_type` selects the kind (unknown kinds are concepts). `name`
+/// (defaulting to the node id), `description` and the `properties` object
+/// come from the payload; remaining payload fields go to `metadata`.
+impl FromEventData for ConceptNode {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (node_id, node_type, payload) = match data {
+            EventData::NodeAdded { node_id, node_type, data } => (node_id, node_type, data),
+            _ => return None,
+        };
+        let kind = match type_key(node_type).as_str() {
+            "property" => ConceptNodeType::Property,
+            "instance" => ConceptNodeType::Instance,
+            "category" => ConceptNodeType::Category,
+            "rule" => ConceptNodeType::Rule,
+            "axiom" => ConceptNodeType::Axiom,
+            _ => ConceptNodeType::Concept,
+        };
+        let name = payload_str(payload, "name").unwrap_or(node_id);
+        let mut node = ConceptNode::new(node_id.clone(), name, kind);
+        node.merge_event_data(payload);
+        Some(node)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        if let Some(name) = payload_str(data, "name") {
+            self.name = name.to_string();
+        }
+        match data.get("description") {
+            Some(serde_json::Value::String(description)) => self.description = Some(description.clone()),
+            Some(serde_json::Value::Null) => self.description = None,
+            _ => {}
+        }
+        if let Some(properties) = data.get("properties") {
+            merge_fields(&mut self.properties, properties);
+        }
+        merge_fields_except(&mut self.metadata, data, &CONCEPT_NODE_FIELDS);
+    }
+}
+
+impl ToEventData for ConceptNode {
+    fn event_type(&self) -> String {
+        match self.node_type {
+            ConceptNodeType::Concept => "concept",
+            ConceptNodeType::Property => "property",
+            ConceptNodeType::Instance => "instance",
+            ConceptNodeType::Category => "category",
+            ConceptNodeType::Rule => "rule",
+            ConceptNodeType::Axiom => "axiom",
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let mut fields = vec![("name", self.name.clone().into())];
+        if let Some(description) = &self.description {
+            fields.push(("description", description.clone().into()));
+        }
+        if !self.properties.is_empty() {
+            fields.push(("properties", payload_with(&self.properties, [])));
+        }
+        payload_with(&self.metadata, fields)
+    }
+}
+
 /// Type of semantic relationship
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum RelationType {
@@ -231,6 +304,74 @@ impl Edge for ConceptEdge {
     }
 }
 
+/// Builds semantic relationships from `EdgeAdded` events
+///
+/// `edge_type` names the relation (`is_a`, `HasA`, `part-of`, ...); unknown
+/// names become [`RelationType::Custom`]. `strength` is read from the
+/// payload and the other payload fields go to `metadata`.
+impl FromEventData for ConceptEdge {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (edge_id, source_id, target_id, edge_type, payload) = match data {
+            EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, data } => {
+                (edge_id, source_id, target_id, edge_type, data)
+            }
+            _ => return None,
+        };
+        let relation = match type_key(edge_type).as_str() {
+            "isa" => RelationType::IsA,
+            "hasa" => RelationType::HasA,
+            "partof" => RelationType::PartOf,
+            "relatedto" => RelationType::RelatedTo,
+            "dependson" => RelationType::DependsOn,
+            "implies" => RelationType::Implies,
+            "contradicts" => RelationType::Contradicts,
+            "similarto" => RelationType::SimilarTo,
+            "differentfrom" => RelationType::DifferentFrom,
+            "instanceof" => RelationType::InstanceOf,
+            "propertyof" => RelationType::PropertyOf,
+            "causes" => RelationType::Causes,
+            "precedes" => RelationType::Precedes,
+            _ => RelationType::Custom(edge_type.clone()),
+        };
+        let mut edge = ConceptEdge::new(edge_id.clone(), source_id.clone(), target_id.clone(), relation);
+        edge.merge_event_data(payload);
+        Some(edge)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        if let Some(strength) = data.get("strength").and_then(|v| v.as_f64()) {
+            self.strength = (strength as f32).clamp(0.0, 1.0);
+        }
+        merge_fields_except(&mut self.metadata, data, &["strength"]);
+    }
+}
+
+impl ToEventData for ConceptEdge {
+    fn event_type(&self) -> String {
+        match &self.relation_type {
+            RelationType::IsA => "is_a",
+            RelationType::HasA => "has_a",
+            RelationType::PartOf => "part_of",
+            RelationType::RelatedTo => "related_to",
+            RelationType::DependsOn => "depends_on",
+            RelationType::Implies => "implies",
+            RelationType::Contradicts => "contradicts",
+            RelationType::SimilarTo => "similar_to",
+            RelationType::DifferentFrom => "different_from",
+            RelationType::InstanceOf => "instance_of",
+            RelationType::PropertyOf => "property_of",
+            RelationType::Causes => "causes",
+            RelationType::Precedes => "precedes",
+            RelationType::Custom(name) => name,
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        payload_with(&self.metadata, [("strength", f64::from(self.strength).into())])
+    }
+}
+
 /// Extension methods for ConceptProjection
 impl ConceptProjection {
     /// Get all concepts
@@ -395,62 +536,56 @@ impl ConceptProjection {
     }
 
     /// Infer new relationships based on existing ones
+    ///
+    /// Follows IS-A chains to any depth: every concept IS-A each ancestor,
+    /// and every instance is an INSTANCE-OF each ancestor of its concepts.
+    /// Relationships already present as edges are not repeated, and the
+    /// result is sorted.
     pub fn infer_relationships(&self) -> Vec<(String, String, RelationType)> {
-        let mut inferred = Vec::new();
-        
-        // Transitivity of IS-A relationships
-        for node in self.nodes() {
-            let parents = self.get_parents(&node.id);
-            for parent in &parents {
-                let grandparents = self.get_parents(&parent.id);
-                for grandparent in grandparents {
-                    // If A IS-A B and B IS-A C, then A IS-A C
-                    if !self.edges().any(|e| {
-                        matches!(e.relation_type, RelationType::IsA)
-                            && e.source() == node.id
-                            && e.target() == grandparent.id
-                    }) {
-                        inferred.push((node.id.clone(), grandparent.id.clone(), RelationType::IsA));
-                    }
-                }
+        let is_a =
+            crate::algorithms::transitive_closure_by(self, |edge| matches!(edge.relation_type, RelationType::IsA));
+        let existing: HashSet<(&str, &str, bool)> = self
+            .edges()
+            .filter_map(|edge| match edge.relation_type {
+                RelationType::IsA => Some((edge.source.as_str(), edge.target.as_str(), true)),
+                RelationType::InstanceOf => Some((edge.source.as_str(), edge.target.as_str(), false)),
+                _ => None,
+            })
+            .collect();
+
+        // Transitivity of IS-A relationships: A IS-A B and B IS-A C gives A IS-A C
+        let mut is_a_pairs: BTreeSet<(&str, &str)> = BTreeSet::new();
+        for (concept, ancestor) in is_a.pairs() {
+            if concept != ancestor && !existing.contains(&(concept, ancestor, true)) {
+                is_a_pairs.insert((concept, ancestor));
             }
         }
-        
-        // Instance inheritance
-        for edge in self.edges() {
-            if matches!(edge.relation_type, RelationType::InstanceOf) {
-                let instance_id = &edge.source;
-                let concept_id = &edge.target;
-                
-                // If A INSTANCE-OF B and B IS-A C, then A INSTANCE-OF C
-                for parent_edge in self.edges() {
-                    if matches!(parent_edge.relation_type, RelationType::IsA)
-                        && parent_edge.source() == *concept_id
-                    {
-                        let parent_concept = &parent_edge.target;
-                        if !self.edges().any(|e| {
-                            matches!(e.relation_type, RelationType::InstanceOf)
-                                && e.source() == *instance_id
-                                && e.target() == *parent_concept
-                        }) {
-                            inferred.push((
-                                instance_id.clone(),
-                                parent_concept.clone(),
-                                RelationType::InstanceOf,
-                            ));
-                        }
-                    }
+
+        // Instance inheritance: A INSTANCE-OF B and B IS-A C gives A INSTANCE-OF C
+        let mut instance_pairs: BTreeSet<(&str, &str)> = BTreeSet::new();
+        for edge in self.edges().filter(|edge| matches!(edge.relation_type, RelationType::InstanceOf)) {
+            let instance = edge.source.as_str();
+            for ancestor in is_a.reachable_from(&edge.target) {
+                if instance != ancestor && !existing.contains(&(instance, ancestor, false)) {
+                    instance_pairs.insert((instance, ancestor));
                 }
             }
         }
-        
-        inferred
+
+        let inferred = is_a_pairs
+            .into_iter()
+            .map(|(source, target)| (source.to_string(), target.to_string(), RelationType::IsA));
+        let inherited = instance_pairs
+            .into_iter()
+            .map(|(source, target)| (source.to_string(), target.to_string(), RelationType::InstanceOf));
+        inferred.chain(inherited).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_support::{edge_added, node_added};
 
     #[test]
     fn test_concept_node_creation() {
@@ -1091,6 +1226,40 @@ mod tests {
         assert!(fido_mammal.is_some(), "Should infer fido INSTANCE-OF mammal");
     }
 
+    #[test]
+    fn test_infer_relationships_follows_full_chains() {
+        let projection = create_animal_taxonomy();
+
+        let inferred = projection.infer_relationships();
+        let has = |source: &str, target: &str, instance: bool| {
+            inferred.iter().any(|(src, tgt, rel)| {
+                src == source
+                    && tgt == target
+                    && if instance {
+                        matches!(rel, RelationType::InstanceOf)
+                    } else {
+                        matches!(rel, RelationType::IsA)
+                    }
+            })
+        };
+
+        // Two IS-A hops above the direct parent
+        assert!(has("dog", "living_thing", false));
+        assert!(has("eagle", "living_thing", false));
+        assert!(has("fido", "animal", true));
+        assert!(has("fido", "living_thing", true));
+        assert!(!has("fido", "bird", true));
+
+        // Direct edges are not repeated and nothing is inferred twice
+        assert!(!has("dog", "mammal", false));
+        let mut unique: Vec<_> = inferred.iter().map(|(src, tgt, _)| (src.clone(), tgt.clone())).collect();
+        unique.dedup();
+        assert_eq!(unique.len(), inferred.len());
+        // dog, cat, eagle: animal, living_thing; mammal, bird: living_thing;
+        // fido, whiskers: mammal, animal, living_thing
+        assert_eq!(inferred.len(), 14);
+    }
+
     #[test]
     fn test_infer_relationships_empty_graph() {
         let projection = ConceptProjection::new(uuid::Uuid::new_v4(), crate::core::GraphType::ConceptGraph);
@@ -1173,4 +1342,149 @@ mod tests {
         assert!(node.properties["null"].is_null());
         assert!(node.properties["float"].is_number());
     }
-}
\ No newline at end of file
+
+    // ========== FromEventData Tests ==========
+
+    #[test]
+    fn test_concept_node_from_event_data() {
+        let node = ConceptNode::from_event_data(&node_added(
+            "dog",
+            "Category",
+            serde_json::json!({
+                "name": "Dog",
+                "description": "Domestic canine",
+                "properties": {"legs": 4},
+                "source": "wordnet",
+            }),
+        ))
+        .unwrap();
+
+        assert_eq!(node.id, "dog");
+        assert_eq!(node.name, "Dog");
+        assert_eq!(node.node_type, ConceptNodeType::Category);
+        assert_eq!(node.description.as_deref(), Some("Domestic canine"));
+        assert_eq!(node.properties.get("legs"), Some(&serde_json::json!(4)));
+        assert_eq!(node.metadata.get("source"), Some(&serde_json::json!("wordnet")));
+        assert!(!node.metadata.contains_key("name"));
+    }
+
+    #[test]
+    fn test_concept_node_defaults() {
+        let node = ConceptNode::from_event_data(&node_added("x", "whatever", serde_json::json!({}))).unwrap();
+        assert_eq!(node.name, "x");
+        assert_eq!(node.node_type, ConceptNodeType::Concept);
+        assert!(node.description.is_none());
+
+        for (kind, expected) in [
+            ("property", ConceptNodeType::Property),
+            ("Instance", ConceptNodeType::Instance),
+            ("rule", ConceptNodeType::Rule),
+            ("AXIOM", ConceptNodeType::Axiom),
+        ] {
+            let node = ConceptNode::from_event_data(&node_added("n", kind, serde_json::json!({}))).unwrap();
+            assert_eq!(node.node_type, expected);
+        }
+    }
+
+    #[test]
+    fn test_concept_node_merge() {
+        let mut node = ConceptNode::concept("c", "Car")
+            .with_description("Vehicle")
+            .with_property("wheels", serde_json::json!(4));
+
+        node.merge_event_data(&serde_json::json!({
+            "name": "Automobile",
+            "description": null,
+            "properties": {"wheels": null, "doors": 4},
+        }));
+
+        assert_eq!(node.name, "Automobile");
+        assert!(node.description.is_none());
+        assert!(!node.properties.contains_key("wheels"));
+        assert_eq!(node.properties.get("doors"), Some(&serde_json::json!(4)));
+    }
+
+    #[test]
+    fn test_concept_edge_from_event_data() {
+        let edge = ConceptEdge::from_event_data(&edge_added(
+            "e",
+            "dog",
+            "animal",
+            "is_a",
+            serde_json::json!({"strength": 0.8, "source": "curated"}),
+        ))
+        .unwrap();
+
+        assert_eq!(edge.relation_type, RelationType::IsA);
+        assert!((edge.strength - 0.8).abs() < f32::EPSILON);
+        assert_eq!(edge.metadata.get("source"), Some(&serde_json::json!("curated")));
+        assert!(!edge.metadata.contains_key("strength"));
+
+        let custom = ConceptEdge::from_event_data(&edge_added("c", "a", "b", "mentors", serde_json::json!({}))).unwrap();
+        assert_eq!(custom.relation_type, RelationType::Custom("mentors".to_string()));
+        assert_eq!(custom.strength, 1.0);
+    }
+
+    #[test]
+    fn test_concept_edge_merge_clamps_strength() {
+        let mut edge = ConceptEdge::has_a("e", "car", "wheel");
+        edge.merge_event_data(&serde_json::json!({"strength": 3.0}));
+        assert_eq!(edge.strength, 1.0);
+    }
+
+    #[test]
+    fn test_concept_projection_built_by_engine() {
+        use crate::core::cim_graph::GraphEvent;
+        use crate::core::ProjectionEngine;
+
+        let aggregate_id = uuid::Uuid::new_v4();
+        let data = vec![
+            node_added("animal", "category", serde_json::json!({"name": "Animal"})),
+            node_added("dog", "concept", serde_json::json!({"name": "Dog"})),
+            node_added("rex", "instance", serde_json::json!({"name": "Rex"})),
+            edge_added("e1", "dog", "animal", "IsA", serde_json::json!({})),
+            edge_added("e2", "rex", "dog", "instance_of", serde_json::json!({})),
+        ];
+        let events = data
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| GraphEvent {
+                event_id: uuid::Uuid::new_v4(),
+                aggregate_id,
+                sequence: i as u64 + 1,
+                subject: "cim.graph.concept.evt".to_string(),
+                timestamp: chrono::Utc::now(),
+                correlation_id: uuid::Uuid::new_v4(),
+                causation_id: None,
+                data,
+            })
+            .collect();
+
+        let projection = ProjectionEngine::<ConceptProjection>::new().project(events);
+
+        assert_eq!(projection.get_parents("dog")[0].name, "Animal");
+        assert_eq!(projection.get_instances_of("dog")[0].id, "rex");
+    }
+
+    #[test]
+    fn test_concept_to_event_data_roundtrip() {
+        let mut node = ConceptNode::category("animals", "Animals")
+            .with_description("Living things")
+            .with_property("kingdom", serde_json::json!("Animalia"));
+        node.metadata.insert("source".to_string(), serde_json::json!("wiki"));
+
+        let rebuilt = ConceptNode::from_event_data(&node_added(&node.id, &node.event_type(), node.event_payload())).unwrap();
+        assert_eq!(rebuilt.node_type, node.node_type);
+        assert_eq!(rebuilt.description, node.description);
+        assert_eq!(rebuilt.properties, node.properties);
+        assert_eq!(rebuilt.metadata, node.metadata);
+
+        for relation in [RelationType::PartOf, RelationType::Custom("mentors".to_string())] {
+            let edge = ConceptEdge::new("e", "a", "b", relation).with_strength(0.25);
+            let event = edge_added(&edge.id, &edge.source, &edge.target, &edge.event_type(), edge.event_payload());
+            let rebuilt = ConceptEdge::from_event_data(&event).unwrap();
+            assert_eq!(rebuilt.relation_type, edge.relation_type);
+            assert_eq!(rebuilt.strength, edge.strength);
+        }
+    }
+}
@@ -3,7 +3,8 @@
 //! This is a read-only projection computed from events.
 
 use crate::core::{Node, Edge};
-use crate::core::projection_engine::GenericGraphProjection;
+use crate::core::cim_graph::EventData;
+use crate::core::projection_engine::{payload_str, type_key, FromEventData, GenericGraphProjection, ToEventData};
 // Projections are ephemeral - no serialization
 
 /// Context node types
@@ -44,6 +45,75 @@ impl Default for ContextNodeType {
     }
 }
 
+/// Builds context nodes from `NodeAdded` events
+///
+/// `node_type` names the DDD element (`bounded_context`, `Aggregate`,
+/// `value-object`, ...; unknown kinds are entities). `name` defaults to the
+/// node id and the whole payload is kept in `data`.
+impl FromEventData for ContextNode {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (node_id, node_type, payload) = match data {
+            EventData::NodeAdded { node_id, node_type, data } => (node_id, node_type, data),
+            _ => return None,
+        };
+        let node_type = match type_key(node_type).as_str() {
+            "boundedcontext" | "context" => ContextNodeType::BoundedContext,
+            "aggregate" | "aggregateroot" => ContextNodeType::Aggregate,
+            "valueobject" => ContextNodeType::ValueObject,
+            _ => ContextNodeType::Entity,
+        };
+        Some(ContextNode {
+            id: node_id.clone(),
+            node_type,
+            name: payload_str(payload, "name").unwrap_or(node_id).to_string(),
+            data: payload.clone(),
+        })
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        let patch = match data.as_object() {
+            Some(patch) => patch,
+            None => return,
+        };
+        if !self.data.is_object() {
+            self.data = serde_json::Value::Object(Default::default());
+        }
+        if let Some(fields) = self.data.as_object_mut() {
+            for (key, value) in patch {
+                if value.is_null() {
+                    fields.remove(key);
+                } else {
+                    fields.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        if let Some(name) = payload_str(data, "name") {
+            self.name = name.to_string();
+        }
+    }
+}
+
+/// The payload is `data` with `name` set on it (when `data` is an object)
+impl ToEventData for ContextNode {
+    fn event_type(&self) -> String {
+        match self.node_type {
+            ContextNodeType::BoundedContext => "bounded_context",
+            ContextNodeType::Aggregate => "aggregate",
+            ContextNodeType::Entity => "entity",
+            ContextNodeType::ValueObject => "value_object",
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let mut payload = self.data.clone();
+        if let Some(fields) = payload.as_object_mut() {
+            fields.insert("name".to_string(), self.name.clone().into());
+        }
+        payload
+    }
+}
+
 /// Context edge - relationships in DDD
 #[derive(Debug, Clone, Default)]
 pub struct ContextEdge {
@@ -71,6 +141,38 @@ impl Edge for ContextEdge {
     }
 }
 
+/// Builds context relationships from `EdgeAdded` events; `edge_type` is the
+/// relationship, which an update can change through a `relationship` field
+impl FromEventData for ContextEdge {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        match data {
+            EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, .. } => Some(ContextEdge {
+                id: edge_id.clone(),
+                source: source_id.clone(),
+                target: target_id.clone(),
+                relationship: edge_type.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        if let Some(relationship) = payload_str(data, "relationship") {
+            self.relationship = relationship.to_string();
+        }
+    }
+}
+
+impl ToEventData for ContextEdge {
+    fn event_type(&self) -> String {
+        self.relationship.clone()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
 /// Context graph projection
 pub type ContextProjection = GenericGraphProjection<ContextNode, ContextEdge>;
 
@@ -80,6 +182,7 @@ pub type ContextGraph = ContextProjection;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_support::node_added;
     use uuid::Uuid;
     use crate::core::GraphType;
     use crate::core::GraphProjection;
@@ -622,4 +725,135 @@ mod tests {
         let graph: ContextGraph = ContextProjection::new(Uuid::new_v4(), GraphType::ContextGraph);
         assert_eq!(GraphProjection::node_count(&graph), 0);
     }
-}
\ No newline at end of file
+
+    // ========== FromEventData Tests ==========
+
+    #[test]
+    fn test_context_node_from_event_data() {
+        let node = ContextNode::from_event_data(&node_added(
+            "orders",
+            "BoundedContext",
+            serde_json::json!({"name": "Order Management", "team": "sales"}),
+        ))
+        .unwrap();
+
+        assert_eq!(node.id, "orders");
+        assert!(matches!(node.node_type, ContextNodeType::BoundedContext));
+        assert_eq!(node.name, "Order Management");
+        assert_eq!(node.data["team"], "sales");
+    }
+
+    #[test]
+    fn test_context_node_type_names() {
+        let cases = [
+            ("bounded_context", "BoundedContext"),
+            ("aggregate_root", "Aggregate"),
+            ("value-object", "ValueObject"),
+            ("entity", "Entity"),
+            ("unknown", "Entity"),
+        ];
+        for (name, expected) in cases {
+            let node = ContextNode::from_event_data(&node_added("n", name, serde_json::json!({}))).unwrap();
+            assert_eq!(format!("{:?}", node.node_type), expected);
+            assert_eq!(node.name, "n");
+        }
+    }
+
+    #[test]
+    fn test_context_node_merge() {
+        let mut node = ContextNode {
+            id: "c".to_string(),
+            name: "Old".to_string(),
+            data: serde_json::json!({"team": "a", "legacy": true}),
+            ..Default::default()
+        };
+        node.merge_event_data(&serde_json::json!({"name": "New", "team": "b", "legacy": null}));
+
+        assert_eq!(node.name, "New");
+        assert_eq!(node.data, serde_json::json!({"name": "New", "team": "b"}));
+
+        // Non-object data is replaced by the patch object
+        let mut bare = ContextNode::default();
+        bare.merge_event_data(&serde_json::json!({"k": 1}));
+        assert_eq!(bare.data, serde_json::json!({"k": 1}));
+    }
+
+    #[test]
+    fn test_context_edge_from_event_data() {
+        let data = EventData::EdgeAdded {
+            edge_id: "r".to_string(),
+            source_id: "orders".to_string(),
+            target_id: "billing".to_string(),
+            edge_type: "upstream".to_string(),
+            data: serde_json::json!({}),
+        };
+        let mut edge = ContextEdge::from_event_data(&data).unwrap();
+        assert_eq!(edge.relationship, "upstream");
+        assert!(ContextNode::from_event_data(&data).is_none());
+
+        edge.merge_event_data(&serde_json::json!({"relationship": "partnership"}));
+        assert_eq!(edge.relationship, "partnership");
+    }
+
+    #[test]
+    fn test_context_projection_built_by_engine() {
+        use crate::core::cim_graph::GraphEvent;
+        use crate::core::ProjectionEngine;
+
+        let aggregate_id = Uuid::new_v4();
+        let event = |sequence: u64, data: EventData| GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            sequence,
+            subject: "cim.graph.context.evt".to_string(),
+            timestamp: chrono::Utc::now(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            data,
+        };
+
+        let projection = ProjectionEngine::<ContextProjection>::new().project(vec![
+            event(1, node_added("orders", "bounded_context", serde_json::json!({}))),
+            event(2, node_added("order", "aggregate", serde_json::json!({"name": "Order"}))),
+            event(3, EventData::NodeUpdated {
+                node_id: "order".to_string(),
+                data: serde_json::json!({"invariants": 3}),
+            }),
+        ]);
+
+        assert_eq!(GraphProjection::node_count(&projection), 2);
+        let order = projection.get_node("order").unwrap();
+        assert_eq!(order.name, "Order");
+        assert_eq!(order.data["invariants"], 3);
+    }
+
+    #[test]
+    fn test_context_to_event_data_roundtrip() {
+        let node = ContextNode {
+            id: "order".to_string(),
+            node_type: ContextNodeType::ValueObject,
+            name: "Order".to_string(),
+            data: serde_json::json!({"invariants": 3}),
+        };
+        let rebuilt = ContextNode::from_event_data(&node_added(&node.id, &node.event_type(), node.event_payload())).unwrap();
+        assert!(matches!(rebuilt.node_type, ContextNodeType::ValueObject));
+        assert_eq!(rebuilt.name, "Order");
+        assert_eq!(rebuilt.event_payload(), node.event_payload());
+
+        let edge = ContextEdge {
+            id: "r".to_string(),
+            source: "orders".to_string(),
+            target: "order".to_string(),
+            relationship: "contains".to_string(),
+        };
+        let rebuilt = ContextEdge::from_event_data(&EventData::EdgeAdded {
+            edge_id: edge.id.clone(),
+            source_id: edge.source.clone(),
+            target_id: edge.target.clone(),
+            edge_type: edge.event_type(),
+            data: edge.event_payload(),
+        })
+        .unwrap();
+        assert_eq!(rebuilt.relationship, "contains");
+    }
+}
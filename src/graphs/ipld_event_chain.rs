@@ -4,38 +4,19 @@
 //! These CIDs form Merkle DAGs that create CID chains for entire aggregate transactions.
 //! This enables referring to and retrieving entire event streams with a single CID.
 //!
-//! Integration with cim-ipld library handles the actual CID generation and DAG construction.
+//! [`DagCborCidGenerator`] produces real CIDv1 values so chains can be checked by
+//! any IPLD tooling; [`MockCidGenerator`] remains for lightweight tests.
 
+pub use crate::core::cid::Cid;
+use crate::core::cid::{Codec, HashAlgorithm};
 use crate::core::{Node, Edge};
 use crate::core::cim_graph::{GraphEvent, EventData};
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-/// CID (Content Identifier) - the hash of event payload data
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct Cid(pub String);
-
-impl Cid {
-    /// Create a new CID
-    pub fn new(hash: impl Into<String>) -> Self {
-        Cid(hash.into())
-    }
-    
-    /// Get the CID string
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
-
-impl std::fmt::Display for Cid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-
 /// Event payload with its CID - this is what gets stored in IPLD
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventPayload {
@@ -135,7 +116,7 @@ impl IpldEventNode {
 
 impl Node for IpldEventNode {
     fn id(&self) -> String {
-        self.cid.0.clone()
+        self.cid.to_string()
     }
 }
 
@@ -159,37 +140,64 @@ impl Edge for IpldChainEdge {
     }
     
     fn source(&self) -> String {
-        self.source.0.clone()
+        self.source.to_string()
     }
     
     fn target(&self) -> String {
-        self.target.0.clone()
+        self.target.to_string()
     }
 }
 
 /// Functions to integrate with cim-ipld for CID generation
 pub trait CidGenerator {
-    /// Generate a CID for an event payload
-    fn generate_cid(&self, data: &EventData) -> Cid;
+    /// Generate a CID for an event payload, failing if it cannot be serialized
+    fn generate_cid(&self, data: &EventData) -> Result<Cid>;
     
     /// Verify a CID matches its data
     fn verify_cid(&self, cid: &Cid, data: &EventData) -> bool;
 }
 
-/// Mock CID generator for testing (real implementation would use cim-ipld)
+/// CID generator producing CIDv1 over the DAG-CBOR encoding of the event data
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DagCborCidGenerator {
+    algorithm: HashAlgorithm,
+}
+
+impl DagCborCidGenerator {
+    /// Create a generator using the given multihash function
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self { algorithm }
+    }
+}
+
+impl CidGenerator for DagCborCidGenerator {
+    fn generate_cid(&self, data: &EventData) -> Result<Cid> {
+        let value = serde_json::to_value(data)?;
+        Ok(Cid::for_payload(&value, self.algorithm))
+    }
+
+    fn verify_cid(&self, cid: &Cid, data: &EventData) -> bool {
+        serde_json::to_value(data)
+            .map(|value| cid.matches_payload(&value))
+            .unwrap_or(false)
+    }
+}
+
+/// Mock CID generator for testing (use [`DagCborCidGenerator`] for real CIDs)
 #[derive(Debug)]
 pub struct MockCidGenerator;
 
 impl CidGenerator for MockCidGenerator {
-    fn generate_cid(&self, data: &EventData) -> Cid {
-        // In real implementation, this would use cim-ipld to generate proper CIDs
-        let json = serde_json::to_string(data).unwrap_or_default();
-        let hash = format!("Qm{}", &json.as_bytes().iter().take(8).map(|b| format!("{:02x}", b)).collect::<String>());
-        Cid::new(hash)
+    fn generate_cid(&self, data: &EventData) -> Result<Cid> {
+        // Raw CID over the plain JSON bytes - valid, but not canonical
+        let json = serde_json::to_string(data)?;
+        Ok(Cid::for_bytes(Codec::Raw, HashAlgorithm::Sha2_256, json.as_bytes()))
     }
     
     fn verify_cid(&self, cid: &Cid, data: &EventData) -> bool {
-        self.generate_cid(data) == *cid
+        self.generate_cid(data)
+            .map(|generated| generated == *cid)
+            .unwrap_or(false)
     }
 }
 
@@ -210,18 +218,21 @@ impl<G: CidGenerator> EventChainBuilder<G> {
     }
     
     /// Build a CID chain from a stream of graph events
-    pub fn build_chain(&mut self, events: &[GraphEvent]) -> CidChain {
+    ///
+    /// Fails if an event payload cannot be serialized for hashing.
+    pub fn build_chain(&mut self, events: &[GraphEvent]) -> Result<CidChain> {
         if events.is_empty() {
             panic!("Cannot build chain from empty event stream");
         }
         
         let aggregate_id = events[0].aggregate_id;
-        let mut chain = CidChain::new(Cid::new(""), aggregate_id);
+        let first_cid = self.generator.generate_cid(&events[0].data)?;
+        let mut chain = CidChain::new(first_cid, aggregate_id);
         let mut previous_cid: Option<Cid> = None;
         
         for event in events {
             // Generate CID for the event data payload
-            let cid = self.generator.generate_cid(&event.data);
+            let cid = self.generator.generate_cid(&event.data)?;
             
             // Event payload would be stored in IPLD with this structure
             // For now just store in metadata for debugging
@@ -241,12 +252,12 @@ impl<G: CidGenerator> EventChainBuilder<G> {
             previous_cid = Some(cid);
         }
         
-        chain
+        Ok(chain)
     }
     
     /// Retrieve an entire event stream using a single root CID
     /// (In real implementation, this would fetch from NATS JetStream)
-    pub fn retrieve_by_cid(&self, _root_cid: &Cid) -> Result<Vec<GraphEvent>, String> {
+    pub fn retrieve_by_cid(&self, _root_cid: &Cid) -> std::result::Result<Vec<GraphEvent>, String> {
         // This is where cim-ipld would retrieve the entire Merkle DAG
         // from NATS JetStream using the root CID
         Err("Not implemented - would use cim-ipld to fetch from JetStream".to_string())
@@ -278,28 +289,38 @@ pub enum IpldChainCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::cid::test_cid;
 
     // ========================================================================
     // Cid tests
     // ========================================================================
 
     #[test]
-    fn test_cid_new() {
-        let cid = Cid::new("QmTestCid123");
-        assert_eq!(cid.as_str(), "QmTestCid123");
+    fn test_cid_parse_roundtrip() {
+        let cid = test_cid("QmTestCid123");
+        assert_eq!(Cid::parse(cid.as_str()).unwrap(), cid);
+        assert!(Cid::parse("QmTestCid123").is_err());
     }
 
     #[test]
     fn test_cid_display() {
-        let cid = Cid::new("QmDisplayTest");
-        assert_eq!(format!("{}", cid), "QmDisplayTest");
+        let cid = test_cid("QmDisplayTest");
+        assert_eq!(format!("{}", cid), cid.as_str());
+    }
+
+    #[test]
+    fn test_cid_shared_with_core_chain() {
+        // The same type flows between the core chain and the event chain
+        let cid: crate::core::ipld_chain::Cid = test_cid("shared");
+        let chain = CidChain::new(cid.clone(), Uuid::new_v4());
+        assert_eq!(chain.root, cid);
     }
 
     #[test]
     fn test_cid_equality() {
-        let cid1 = Cid::new("QmSame");
-        let cid2 = Cid::new("QmSame");
-        let cid3 = Cid::new("QmDifferent");
+        let cid1 = test_cid("QmSame");
+        let cid2 = test_cid("QmSame");
+        let cid3 = test_cid("QmDifferent");
 
         assert_eq!(cid1, cid2);
         assert_ne!(cid1, cid3);
@@ -310,16 +331,16 @@ mod tests {
         use std::collections::HashSet;
 
         let mut set = HashSet::new();
-        set.insert(Cid::new("QmA"));
-        set.insert(Cid::new("QmB"));
-        set.insert(Cid::new("QmA")); // Duplicate
+        set.insert(test_cid("QmA"));
+        set.insert(test_cid("QmB"));
+        set.insert(test_cid("QmA")); // Duplicate
 
         assert_eq!(set.len(), 2);
     }
 
     #[test]
     fn test_cid_serialization() {
-        let cid = Cid::new("QmSerialize");
+        let cid = test_cid("QmSerialize");
         let json = serde_json::to_string(&cid).unwrap();
         let deserialized: Cid = serde_json::from_str(&json).unwrap();
         assert_eq!(cid, deserialized);
@@ -331,7 +352,7 @@ mod tests {
 
     #[test]
     fn test_cid_chain_new() {
-        let root = Cid::new("QmRoot");
+        let root = test_cid("QmRoot");
         let aggregate_id = Uuid::new_v4();
         let chain = CidChain::new(root.clone(), aggregate_id);
 
@@ -343,11 +364,11 @@ mod tests {
 
     #[test]
     fn test_cid_chain_add_event() {
-        let root = Cid::new("QmRoot");
+        let root = test_cid("QmRoot");
         let aggregate_id = Uuid::new_v4();
         let mut chain = CidChain::new(root, aggregate_id);
 
-        let cid1 = Cid::new("QmEvent1");
+        let cid1 = test_cid("QmEvent1");
         let timestamp1 = Utc::now();
         chain.add_event(0, cid1.clone(), timestamp1);
 
@@ -358,13 +379,13 @@ mod tests {
 
     #[test]
     fn test_cid_chain_add_multiple_events() {
-        let root = Cid::new("QmRoot");
+        let root = test_cid("QmRoot");
         let aggregate_id = Uuid::new_v4();
         let mut chain = CidChain::new(root, aggregate_id);
 
-        let cid1 = Cid::new("QmEvent1");
-        let cid2 = Cid::new("QmEvent2");
-        let cid3 = Cid::new("QmEvent3");
+        let cid1 = test_cid("QmEvent1");
+        let cid2 = test_cid("QmEvent2");
+        let cid3 = test_cid("QmEvent3");
 
         chain.add_event(0, cid1.clone(), Utc::now());
         chain.add_event(1, cid2.clone(), Utc::now());
@@ -377,12 +398,12 @@ mod tests {
 
     #[test]
     fn test_cid_chain_get_cid() {
-        let root = Cid::new("QmRoot");
+        let root = test_cid("QmRoot");
         let aggregate_id = Uuid::new_v4();
         let mut chain = CidChain::new(root, aggregate_id);
 
-        let cid1 = Cid::new("QmEvent1");
-        let cid2 = Cid::new("QmEvent2");
+        let cid1 = test_cid("QmEvent1");
+        let cid2 = test_cid("QmEvent2");
 
         chain.add_event(0, cid1.clone(), Utc::now());
         chain.add_event(1, cid2.clone(), Utc::now());
@@ -394,14 +415,14 @@ mod tests {
 
     #[test]
     fn test_cid_chain_get_ordered_cids() {
-        let root = Cid::new("QmRoot");
+        let root = test_cid("QmRoot");
         let aggregate_id = Uuid::new_v4();
         let mut chain = CidChain::new(root, aggregate_id);
 
         // Add out of order
-        let cid0 = Cid::new("QmEvent0");
-        let cid1 = Cid::new("QmEvent1");
-        let cid2 = Cid::new("QmEvent2");
+        let cid0 = test_cid("QmEvent0");
+        let cid1 = test_cid("QmEvent1");
+        let cid2 = test_cid("QmEvent2");
 
         chain.add_event(2, cid2.clone(), Utc::now());
         chain.add_event(0, cid0.clone(), Utc::now());
@@ -420,7 +441,7 @@ mod tests {
 
     #[test]
     fn test_event_payload_creation() {
-        let cid = Cid::new("QmPayload");
+        let cid = test_cid("QmPayload");
         let payload = EventPayload {
             cid: cid.clone(),
             data: EventData::NodeAdded {
@@ -440,8 +461,8 @@ mod tests {
 
     #[test]
     fn test_event_payload_with_previous() {
-        let prev_cid = Cid::new("QmPrevious");
-        let cid = Cid::new("QmCurrent");
+        let prev_cid = test_cid("QmPrevious");
+        let cid = test_cid("QmCurrent");
         let payload = EventPayload {
             cid: cid.clone(),
             data: EventData::NodeAdded {
@@ -463,7 +484,7 @@ mod tests {
 
     #[test]
     fn test_ipld_event_node_new() {
-        let cid = Cid::new("QmEventNode");
+        let cid = test_cid("QmEventNode");
         let payload = EventPayload {
             cid: cid.clone(),
             data: EventData::NodeAdded {
@@ -484,8 +505,8 @@ mod tests {
 
     #[test]
     fn test_event_node_linkage() {
-        let cid1 = Cid::new("QmFirst");
-        let cid2 = Cid::new("QmSecond");
+        let cid1 = test_cid("QmFirst");
+        let cid2 = test_cid("QmSecond");
 
         let payload = EventPayload {
             cid: cid2.clone(),
@@ -507,7 +528,7 @@ mod tests {
 
     #[test]
     fn test_ipld_event_node_implements_node_trait() {
-        let cid = Cid::new("QmNodeTrait");
+        let cid = test_cid("QmNodeTrait");
         let payload = EventPayload {
             cid: cid.clone(),
             data: EventData::NodeAdded {
@@ -521,7 +542,7 @@ mod tests {
         };
 
         let node = IpldEventNode::new(cid.clone(), payload);
-        assert_eq!(node.id(), "QmNodeTrait");
+        assert_eq!(node.id(), cid.as_str());
     }
 
     // ========================================================================
@@ -532,14 +553,14 @@ mod tests {
     fn test_ipld_chain_edge() {
         let edge = IpldChainEdge {
             id: "edge1".to_string(),
-            source: Cid::new("QmSource"),
-            target: Cid::new("QmTarget"),
+            source: test_cid("QmSource"),
+            target: test_cid("QmTarget"),
             link_type: "previous".to_string(),
         };
 
         assert_eq!(edge.id(), "edge1");
-        assert_eq!(edge.source(), "QmSource");
-        assert_eq!(edge.target(), "QmTarget");
+        assert_eq!(edge.source(), test_cid("QmSource").as_str());
+        assert_eq!(edge.target(), test_cid("QmTarget").as_str());
     }
 
     // ========================================================================
@@ -561,14 +582,14 @@ mod tests {
             node_id: "unique_node_2".to_string(),
         };
 
-        let cid1 = generator.generate_cid(&data1);
-        let cid2 = generator.generate_cid(&data2);
+        let cid1 = generator.generate_cid(&data1).unwrap();
+        let cid2 = generator.generate_cid(&data2).unwrap();
 
         // Different data should produce different CIDs
         assert_ne!(cid1, cid2);
-        // CIDs should start with "Qm"
-        assert!(cid1.as_str().starts_with("Qm"));
-        assert!(cid2.as_str().starts_with("Qm"));
+        // Mock CIDs are valid raw-codec CIDv1 values
+        assert_eq!(cid1.codec(), Codec::Raw);
+        assert!(Cid::parse(cid2.as_str()).is_ok());
     }
 
     #[test]
@@ -581,7 +602,7 @@ mod tests {
             data: serde_json::json!({}),
         };
 
-        let cid = generator.generate_cid(&data);
+        let cid = generator.generate_cid(&data).unwrap();
 
         // Same data should verify
         assert!(generator.verify_cid(&cid, &data));
@@ -593,6 +614,44 @@ mod tests {
         assert!(!generator.verify_cid(&cid, &different_data));
     }
 
+    // ========================================================================
+    // DagCborCidGenerator tests
+    // ========================================================================
+
+    #[test]
+    fn test_dag_cbor_cid_generator() {
+        let generator = DagCborCidGenerator::default();
+
+        let data = EventData::NodeAdded {
+            node_id: "node1".to_string(),
+            node_type: "test".to_string(),
+            data: serde_json::json!({"value": 42}),
+        };
+
+        let cid = generator.generate_cid(&data).unwrap();
+        assert!(cid.as_str().starts_with("bafyrei"));
+        assert_eq!(cid, generator.generate_cid(&data).unwrap());
+        assert!(generator.verify_cid(&cid, &data));
+
+        let other = EventData::NodeRemoved {
+            node_id: "node1".to_string(),
+        };
+        assert!(!generator.verify_cid(&cid, &other));
+        assert!(!generator.verify_cid(&test_cid("unrelated"), &data));
+    }
+
+    #[test]
+    fn test_dag_cbor_cid_generator_matches_ipld_chain() {
+        let data = EventData::NodeRemoved {
+            node_id: "n1".to_string(),
+        };
+        let value = serde_json::to_value(&data).unwrap();
+
+        let generator = DagCborCidGenerator::new(HashAlgorithm::Blake3);
+        let expected = crate::core::ipld_chain::generate_cid_with(&value, HashAlgorithm::Blake3).unwrap();
+        assert_eq!(generator.generate_cid(&data).unwrap(), expected);
+    }
+
     // ========================================================================
     // EventChainBuilder tests
     // ========================================================================
@@ -642,7 +701,7 @@ mod tests {
             },
         ];
 
-        let chain = builder.build_chain(&events);
+        let chain = builder.build_chain(&events).unwrap();
 
         assert_eq!(chain.aggregate_id, aggregate_id);
         // Length is the highest sequence + 1
@@ -660,7 +719,7 @@ mod tests {
         let generator = MockCidGenerator;
         let builder = EventChainBuilder::new(generator);
 
-        let cid = Cid::new("QmTest");
+        let cid = test_cid("QmTest");
         let result = builder.retrieve_by_cid(&cid);
 
         // Should return error (not implemented)
@@ -689,7 +748,7 @@ mod tests {
                     data: serde_json::json!({}),
                 },
             },
-            previous_cid: Some(Cid::new("QmPrev")),
+            previous_cid: Some(test_cid("QmPrev")),
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
@@ -699,18 +758,18 @@ mod tests {
     #[test]
     fn test_ipld_chain_command_retrieve_chain() {
         let cmd = IpldChainCommand::RetrieveChain {
-            root_cid: Cid::new("QmRoot"),
+            root_cid: test_cid("QmRoot"),
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
         assert!(json.contains("RetrieveChain"));
-        assert!(json.contains("QmRoot"));
+        assert!(json.contains(test_cid("QmRoot").as_str()));
     }
 
     #[test]
     fn test_ipld_chain_command_verify_chain() {
         let cmd = IpldChainCommand::VerifyChain {
-            root_cid: Cid::new("QmVerify"),
+            root_cid: test_cid("QmVerify"),
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
@@ -4,32 +4,19 @@
 //! This is a read-only projection computed from events.
 
 use crate::core::{Node, Edge};
-use crate::core::cim_graph::{GraphEvent, GraphCommand};
-use crate::core::projection_engine::{ProjectionEngine, GenericGraphProjection};
-// Projections are ephemeral - no serialization
-// Commands still need serialization for events
+use crate::core::cim_graph::{EventData, GraphEvent, GraphCommand};
+use crate::core::projection_engine::{
+    payload_str, FromEventData, GenericGraphProjection, ProjectionEngine, ToEventData,
+};
+// Projections are rebuilt from events; serde is only for snapshots and commands
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-/// Content Identifier (CID) - simplified version for this implementation
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Cid(String);
-
-impl Cid {
-    /// Create a new CID from a string
-    pub fn new(s: impl Into<String>) -> Self {
-        Cid(s.into())
-    }
-    
-    /// Get the CID as a string
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
+pub use crate::core::cid::Cid;
 
 /// IPLD node containing content-addressed data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IpldNode {
     /// Content identifier (hash of the data)
     cid: Cid,
@@ -67,13 +54,85 @@ impl IpldNode {
 
 impl Node for IpldNode {
     fn id(&self) -> String {
-        self.cid.0.clone()
+        self.cid.to_string()
+    }
+}
+
+/// Parse a `name -> CID` link map, skipping entries that are not valid CIDs
+fn parse_links(links: Option<&serde_json::Value>) -> HashMap<String, Cid> {
+    links
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(name, target)| {
+                    let cid = Cid::parse(target.as_str()?).ok()?;
+                    Some((name.clone(), cid))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds IPLD nodes from `NodeAdded` events
+///
+/// The node id must be a valid CID. Payloads shaped like
+/// [`IpldCommand::AddCid`] (`{"cid", "data", "links"}`) are unwrapped;
+/// anything else is taken as the node data. Content is immutable, so an
+/// update only merges its `links` (a `null` target removes a link).
+impl FromEventData for IpldNode {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (node_id, payload) = match data {
+            EventData::NodeAdded { node_id, data, .. } => (node_id, data),
+            _ => return None,
+        };
+        let cid = Cid::parse(node_id).ok()?;
+        let node = if payload.get("cid").is_some() && payload.get("data").is_some() {
+            IpldNode {
+                cid,
+                data: payload["data"].clone(),
+                links: parse_links(payload.get("links")),
+            }
+        } else {
+            IpldNode::new(cid, payload.clone())
+        };
+        Some(node)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        if let Some(links) = data.get("links").and_then(|v| v.as_object()) {
+            for (name, target) in links {
+                if target.is_null() {
+                    self.links.remove(name);
+                }
+            }
+        }
+        self.links.extend(parse_links(data.get("links")));
+    }
+}
+
+/// Rebuilt as an [`IpldCommand::AddCid`]-shaped payload
+impl ToEventData for IpldNode {
+    fn event_type(&self) -> String {
+        "ipld_node".to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let links: serde_json::Map<String, serde_json::Value> = self
+            .links
+            .iter()
+            .map(|(name, cid)| (name.clone(), cid.to_string().into()))
+            .collect();
+        serde_json::json!({
+            "cid": self.cid.to_string(),
+            "data": self.data,
+            "links": links,
+        })
     }
 }
 
 
 /// IPLD edge (link between content-addressed nodes)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IpldEdge {
     /// Edge identifier
     id: String,
@@ -103,11 +162,43 @@ impl Edge for IpldEdge {
     }
     
     fn source(&self) -> String {
-        self.source.0.clone()
+        self.source.to_string()
     }
     
     fn target(&self) -> String {
-        self.target.0.clone()
+        self.target.to_string()
+    }
+}
+
+/// Builds IPLD links from `EdgeAdded` events; both endpoints must be valid
+/// CIDs and the label comes from the payload's `label`, else the edge type
+impl FromEventData for IpldEdge {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        match data {
+            EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, data } => Some(IpldEdge::new(
+                edge_id.clone(),
+                Cid::parse(source_id).ok()?,
+                Cid::parse(target_id).ok()?,
+                payload_str(data, "label").unwrap_or(edge_type).to_string(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        if let Some(label) = payload_str(data, "label") {
+            self.label = label.to_string();
+        }
+    }
+}
+
+impl ToEventData for IpldEdge {
+    fn event_type(&self) -> String {
+        "ipld_link".to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        serde_json::json!({ "label": self.label })
     }
 }
 
@@ -205,10 +296,12 @@ impl IpldProjectionBuilder {
 }
 
 /// Helper functions for working with IPLD projections
+///
+/// Lookups accept either a [`Cid`] or its string form.
 impl IpldProjection {
     /// Get a node by CID
-    pub fn get_by_cid(&self, cid: &str) -> Option<&IpldNode> {
-        self.get_node(cid)
+    pub fn get_by_cid(&self, cid: impl AsRef<str>) -> Option<&IpldNode> {
+        self.get_node(cid.as_ref())
     }
     
     /// Get all CIDs in the graph
@@ -220,8 +313,8 @@ impl IpldProjection {
     }
     
     /// Get all links from a CID
-    pub fn get_links(&self, cid: &str) -> Vec<(&str, &Cid)> {
-        if let Some(node) = self.get_node(cid) {
+    pub fn get_links(&self, cid: impl AsRef<str>) -> Vec<(&str, &Cid)> {
+        if let Some(node) = self.get_node(cid.as_ref()) {
             node.links()
                 .iter()
                 .map(|(name, target)| (name.as_str(), target))
@@ -232,8 +325,8 @@ impl IpldProjection {
     }
     
     /// Check if a CID exists
-    pub fn has_cid(&self, cid: &str) -> bool {
-        self.get_node(cid).is_some()
+    pub fn has_cid(&self, cid: impl AsRef<str>) -> bool {
+        self.get_node(cid.as_ref()).is_some()
     }
 }
 
@@ -241,6 +334,7 @@ impl IpldProjection {
 mod tests {
     use super::*;
     use chrono::Utc;
+    use crate::core::cid::test_cid;
     use crate::core::cim_graph::{EventData, GraphProjection};
 
     // ========================================================================
@@ -248,20 +342,30 @@ mod tests {
     // ========================================================================
 
     #[test]
-    fn test_cid_new_from_string() {
-        let cid = Cid::new("QmTestHash123");
-        assert_eq!(cid.as_str(), "QmTestHash123");
+    fn test_cid_parse_validates() {
+        let cid = test_cid("QmTestHash123");
+        assert_eq!(Cid::parse(cid.as_str()).unwrap(), cid);
+        assert!(Cid::parse("QmTestHash123").is_err());
     }
 
     #[test]
-    fn test_cid_new_from_owned_string() {
-        let cid = Cid::new(String::from("QmOwnedHash456"));
-        assert_eq!(cid.as_str(), "QmOwnedHash456");
+    fn test_cid_from_str() {
+        let cid = test_cid("QmOwnedHash456");
+        let parsed: Cid = String::from(cid.as_str()).parse().unwrap();
+        assert_eq!(parsed, cid);
+    }
+
+    #[test]
+    fn test_cid_shared_with_event_chain() {
+        // Projection nodes and event chain nodes use the same CID type
+        let cid = test_cid("shared");
+        let chain_cid: crate::graphs::ipld_event_chain::Cid = cid.clone();
+        assert_eq!(IpldNode::new(chain_cid, serde_json::json!({})).cid(), &cid);
     }
 
     #[test]
     fn test_cid_clone() {
-        let cid1 = Cid::new("QmCloneTest");
+        let cid1 = test_cid("QmCloneTest");
         let cid2 = cid1.clone();
         assert_eq!(cid1, cid2);
         assert_eq!(cid1.as_str(), cid2.as_str());
@@ -269,9 +373,9 @@ mod tests {
 
     #[test]
     fn test_cid_equality() {
-        let cid1 = Cid::new("QmSame");
-        let cid2 = Cid::new("QmSame");
-        let cid3 = Cid::new("QmDifferent");
+        let cid1 = test_cid("QmSame");
+        let cid2 = test_cid("QmSame");
+        let cid3 = test_cid("QmDifferent");
 
         assert_eq!(cid1, cid2);
         assert_ne!(cid1, cid3);
@@ -282,13 +386,13 @@ mod tests {
         use std::collections::HashSet;
 
         let mut set = HashSet::new();
-        set.insert(Cid::new("QmHash1"));
-        set.insert(Cid::new("QmHash2"));
-        set.insert(Cid::new("QmHash1")); // Duplicate
+        set.insert(test_cid("QmHash1"));
+        set.insert(test_cid("QmHash2"));
+        set.insert(test_cid("QmHash1")); // Duplicate
 
         assert_eq!(set.len(), 2);
-        assert!(set.contains(&Cid::new("QmHash1")));
-        assert!(set.contains(&Cid::new("QmHash2")));
+        assert!(set.contains(&test_cid("QmHash1")));
+        assert!(set.contains(&test_cid("QmHash2")));
     }
 
     // ========================================================================
@@ -297,7 +401,7 @@ mod tests {
 
     #[test]
     fn test_ipld_node_new() {
-        let cid = Cid::new("QmTestNode");
+        let cid = test_cid("QmTestNode");
         let data = serde_json::json!({"key": "value"});
         let node = IpldNode::new(cid.clone(), data.clone());
 
@@ -308,15 +412,15 @@ mod tests {
 
     #[test]
     fn test_ipld_node_implements_node_trait() {
-        let cid = Cid::new("QmNodeTrait");
+        let cid = test_cid("QmNodeTrait");
         let node = IpldNode::new(cid.clone(), serde_json::json!({}));
 
-        assert_eq!(node.id(), "QmNodeTrait");
+        assert_eq!(node.id(), cid.as_str());
     }
 
     #[test]
     fn test_ipld_node_clone() {
-        let cid = Cid::new("QmCloneNode");
+        let cid = test_cid("QmCloneNode");
         let data = serde_json::json!({"nested": {"value": 42}});
         let node1 = IpldNode::new(cid.clone(), data.clone());
         let node2 = node1.clone();
@@ -331,13 +435,13 @@ mod tests {
 
     #[test]
     fn test_ipld_edge_new() {
-        let source = Cid::new("QmSource");
-        let target = Cid::new("QmTarget");
+        let source = test_cid("QmSource");
+        let target = test_cid("QmTarget");
         let edge = IpldEdge::new("edge1".to_string(), source.clone(), target.clone(), "link".to_string());
 
         assert_eq!(edge.id(), "edge1");
-        assert_eq!(edge.source(), "QmSource");
-        assert_eq!(edge.target(), "QmTarget");
+        assert_eq!(edge.source(), source.as_str());
+        assert_eq!(edge.target(), target.as_str());
         assert_eq!(edge.label(), "link");
     }
 
@@ -345,14 +449,14 @@ mod tests {
     fn test_ipld_edge_implements_edge_trait() {
         let edge = IpldEdge::new(
             "e1".to_string(),
-            Cid::new("QmA"),
-            Cid::new("QmB"),
+            test_cid("QmA"),
+            test_cid("QmB"),
             "contains".to_string(),
         );
 
         assert_eq!(edge.id(), "e1");
-        assert_eq!(edge.source(), "QmA");
-        assert_eq!(edge.target(), "QmB");
+        assert_eq!(edge.source(), test_cid("QmA").as_str());
+        assert_eq!(edge.target(), test_cid("QmB").as_str());
     }
 
     // ========================================================================
@@ -495,9 +599,173 @@ mod tests {
         // Build projection
         let projection = builder.from_events(events);
 
-        // The generic projection doesn't create nodes from the events
-        // It just tracks adjacency and metadata
+        // "QmHash123" is not a CID, so no node is materialized
         assert_eq!(projection.version(), 2);
+        assert_eq!(projection.node_count(), 0);
+    }
+
+    #[test]
+    fn test_ipld_projection_materializes_cid_nodes() {
+        let aggregate_id = Uuid::new_v4();
+        let builder = IpldProjectionBuilder::new();
+        let parent = test_cid("parent");
+        let child = test_cid("child");
+
+        let event = |sequence: u64, data: EventData| GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            sequence,
+            subject: "ipld.graph.events".to_string(),
+            timestamp: Utc::now(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            data,
+        };
+        let add_cid = |cid: &Cid, links: HashMap<String, String>| {
+            match ipld_command_to_graph_command(aggregate_id, IpldCommand::AddCid {
+                cid: cid.to_string(),
+                data: serde_json::json!({"name": cid.to_string()}),
+                links,
+            }) {
+                GraphCommand::AddNode { node_id, node_type, data, .. } => {
+                    EventData::NodeAdded { node_id, node_type, data }
+                }
+                _ => unreachable!(),
+            }
+        };
+
+        let mut links = HashMap::new();
+        links.insert("child".to_string(), child.to_string());
+        links.insert("broken".to_string(), "not-a-cid".to_string());
+
+        let mut projection = builder.from_events(vec![
+            event(1, add_cid(&child, HashMap::new())),
+            event(2, add_cid(&parent, links)),
+            event(3, EventData::EdgeAdded {
+                edge_id: format!("{}->{}:child", parent, child),
+                source_id: parent.to_string(),
+                target_id: child.to_string(),
+                edge_type: "ipld_link".to_string(),
+                data: serde_json::json!({"label": "child"}),
+            }),
+        ]);
+
+        assert!(projection.has_cid(&parent));
+        assert_eq!(projection.get_by_cid(&child).unwrap().data()["name"], child.to_string());
+        // Invalid link targets are dropped
+        assert_eq!(projection.get_links(&parent), vec![("child", &child)]);
+        assert_eq!(projection.edges_between(parent.as_str(), child.as_str())[0].label(), "child");
+
+        // Updates only touch links
+        builder.apply_event(&mut projection, &event(4, EventData::NodeUpdated {
+            node_id: parent.to_string(),
+            data: serde_json::json!({"data": "ignored", "links": {"child": null}}),
+        }));
+        let node = projection.get_by_cid(&parent).unwrap();
+        assert!(node.links().is_empty());
+        assert_eq!(node.data()["name"], parent.to_string());
+    }
+
+    #[test]
+    fn test_ipld_projection_as_of_snapshot() {
+        use crate::core::projection_engine::{AsOf, EventStore};
+        use crate::core::{GraphType, MemorySnapshotStore, SnapshotStore};
+
+        let aggregate_id = Uuid::new_v4();
+        let engine: ProjectionEngine<IpldProjection> = ProjectionEngine::new();
+        let cids = [test_cid("first"), test_cid("second"), test_cid("third")];
+
+        let mut store = EventStore::new();
+        store.append(aggregate_id, cids.iter().zip(1..).map(|(cid, sequence)| GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            sequence,
+            subject: "ipld.graph.events".to_string(),
+            timestamp: Utc::now(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            data: EventData::NodeAdded {
+                node_id: cid.to_string(),
+                node_type: "ipld_node".to_string(),
+                data: serde_json::json!({"name": cid.to_string()}),
+            },
+        }).collect());
+
+        let mut snapshots = MemorySnapshotStore::default();
+        let at_two = engine.project(store.get_events_until(&aggregate_id, 2));
+        snapshots.save(&at_two.to_snapshot().unwrap()).unwrap();
+
+        let projection = engine
+            .project_as_of(aggregate_id, GraphType::IpldGraph, AsOf::Sequence(2), &store, &snapshots)
+            .unwrap();
+        assert_eq!(projection.version(), 2);
+        assert!(projection.has_cid(&cids[1]));
+        assert!(!projection.has_cid(&cids[2]));
+        assert_eq!(projection.get_by_cid(&cids[0]).unwrap().data()["name"], cids[0].to_string());
+
+        let projection = engine
+            .project_as_of(aggregate_id, GraphType::IpldGraph, AsOf::Sequence(3), &store, &snapshots)
+            .unwrap();
+        assert!(projection.has_cid(&cids[2]));
+    }
+
+    #[test]
+    fn test_ipld_to_event_data_roundtrip() {
+        let child = test_cid("child");
+        let mut node = IpldNode::new(test_cid("parent"), serde_json::json!({"name": "parent"}));
+        node.links.insert("child".to_string(), child.clone());
+
+        let rebuilt = IpldNode::from_event_data(&EventData::NodeAdded {
+            node_id: node.id(),
+            node_type: node.event_type(),
+            data: node.event_payload(),
+        })
+        .unwrap();
+        assert_eq!(rebuilt.data(), node.data());
+        assert_eq!(rebuilt.links(), node.links());
+
+        let edge = IpldEdge::new("e".to_string(), node.cid().clone(), child, "child".to_string());
+        let rebuilt = IpldEdge::from_event_data(&EventData::EdgeAdded {
+            edge_id: edge.id(),
+            source_id: edge.source(),
+            target_id: edge.target(),
+            edge_type: edge.event_type(),
+            data: edge.event_payload(),
+        })
+        .unwrap();
+        assert_eq!(rebuilt.label(), "child");
+    }
+
+    #[test]
+    fn test_ipld_node_from_plain_payload() {
+        let cid = test_cid("plain");
+        let node = IpldNode::from_event_data(&EventData::NodeAdded {
+            node_id: cid.to_string(),
+            node_type: "ipld_node".to_string(),
+            data: serde_json::json!({"content": "Hello IPLD"}),
+        })
+        .unwrap();
+
+        assert_eq!(node.cid(), &cid);
+        assert_eq!(node.data(), &serde_json::json!({"content": "Hello IPLD"}));
+        assert!(node.links().is_empty());
+    }
+
+    #[test]
+    fn test_ipld_edge_rejects_invalid_cids() {
+        let cid = test_cid("ok");
+        let edge = |source: &str, target: &str| EventData::EdgeAdded {
+            edge_id: "e".to_string(),
+            source_id: source.to_string(),
+            target_id: target.to_string(),
+            edge_type: "ipld_link".to_string(),
+            data: serde_json::json!({}),
+        };
+
+        assert!(IpldEdge::from_event_data(&edge("QmBad", cid.as_str())).is_none());
+        assert!(IpldEdge::from_event_data(&edge(cid.as_str(), "QmBad")).is_none());
+        let ok = IpldEdge::from_event_data(&edge(cid.as_str(), cid.as_str())).unwrap();
+        assert_eq!(ok.label(), "ipld_link");
     }
 
     #[test]
@@ -520,16 +788,19 @@ mod tests {
 
         let mut projection: IpldProjection = GenericGraphProjection::new(Uuid::new_v4(), GraphType::Generic);
 
+        let cid = test_cid("QmTest");
+
         // Initially should not have any CIDs
-        assert!(!projection.has_cid("QmTest"));
+        assert!(!projection.has_cid(cid.as_str()));
 
         // Add a node
-        let node = IpldNode::new(Cid::new("QmTest"), serde_json::json!({}));
-        projection.nodes.insert("QmTest".to_string(), node);
+        let node = IpldNode::new(cid.clone(), serde_json::json!({}));
+        projection.nodes.insert(cid.to_string(), node);
 
         // Now should have the CID
-        assert!(projection.has_cid("QmTest"));
-        assert!(!projection.has_cid("QmOther"));
+        assert!(projection.has_cid(&cid));
+        assert!(projection.has_cid(cid.as_str()));
+        assert!(!projection.has_cid(test_cid("QmOther")));
     }
 
     #[test]
@@ -539,12 +810,13 @@ mod tests {
 
         let mut projection: IpldProjection = GenericGraphProjection::new(Uuid::new_v4(), GraphType::Generic);
 
-        let node = IpldNode::new(Cid::new("QmGetTest"), serde_json::json!({"data": "value"}));
-        projection.nodes.insert("QmGetTest".to_string(), node);
+        let cid = test_cid("QmGetTest");
+        let node = IpldNode::new(cid.clone(), serde_json::json!({"data": "value"}));
+        projection.nodes.insert(cid.to_string(), node);
 
-        let retrieved = projection.get_by_cid("QmGetTest");
+        let retrieved = projection.get_by_cid(&cid);
         assert!(retrieved.is_some());
-        assert_eq!(retrieved.unwrap().cid().as_str(), "QmGetTest");
+        assert_eq!(retrieved.unwrap().cid(), &cid);
 
         assert!(projection.get_by_cid("QmNotExist").is_none());
     }
@@ -556,21 +828,17 @@ mod tests {
 
         let mut projection: IpldProjection = GenericGraphProjection::new(Uuid::new_v4(), GraphType::Generic);
 
-        let node1 = IpldNode::new(Cid::new("QmCid1"), serde_json::json!({}));
-        let node2 = IpldNode::new(Cid::new("QmCid2"), serde_json::json!({}));
-        let node3 = IpldNode::new(Cid::new("QmCid3"), serde_json::json!({}));
-
-        projection.nodes.insert("QmCid1".to_string(), node1);
-        projection.nodes.insert("QmCid2".to_string(), node2);
-        projection.nodes.insert("QmCid3".to_string(), node3);
+        let cids = [test_cid("QmCid1"), test_cid("QmCid2"), test_cid("QmCid3")];
+        for cid in &cids {
+            let node = IpldNode::new(cid.clone(), serde_json::json!({}));
+            projection.nodes.insert(cid.to_string(), node);
+        }
 
         let all_cids = projection.all_cids();
         assert_eq!(all_cids.len(), 3);
-
-        let cid_strings: Vec<&str> = all_cids.iter().map(|c| c.as_str()).collect();
-        assert!(cid_strings.contains(&"QmCid1"));
-        assert!(cid_strings.contains(&"QmCid2"));
-        assert!(cid_strings.contains(&"QmCid3"));
+        for cid in &cids {
+            assert!(all_cids.contains(cid));
+        }
     }
 
     #[test]
@@ -580,10 +848,11 @@ mod tests {
 
         let mut projection: IpldProjection = GenericGraphProjection::new(Uuid::new_v4(), GraphType::Generic);
 
-        let node = IpldNode::new(Cid::new("QmNoLinks"), serde_json::json!({}));
-        projection.nodes.insert("QmNoLinks".to_string(), node);
+        let cid = test_cid("QmNoLinks");
+        let node = IpldNode::new(cid.clone(), serde_json::json!({}));
+        projection.nodes.insert(cid.to_string(), node);
 
-        let links = projection.get_links("QmNoLinks");
+        let links = projection.get_links(cid.as_str());
         assert!(links.is_empty());
     }
 
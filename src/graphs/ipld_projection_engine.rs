@@ -53,12 +53,21 @@ impl IpldGraphProjection {
         if let EventPayload::Ipld(ipld_payload) = &event.payload {
             match ipld_payload {
                 IpldPayload::CidAdded { cid, codec, size, data } => {
+                    // Events carry CIDs as strings; only valid CIDv1 values enter the graph
+                    let parsed = match Cid::parse(cid) {
+                        Ok(parsed) => parsed,
+                        Err(_) => {
+                            self.metadata.insert(format!("cid_{}_invalid", cid), serde_json::json!(true));
+                            return;
+                        }
+                    };
+
                     // Store codec and size in projection metadata
                     self.metadata.insert(format!("cid_{}_codec", cid), serde_json::json!(codec));
                     self.metadata.insert(format!("cid_{}_size", cid), serde_json::json!(size));
                     // Create the event payload that would be CID'd
                     let event_payload = crate::graphs::ipld_event_chain::EventPayload {
-                        cid: Cid::new(cid),
+                        cid: parsed.clone(),
                         data: crate::core::cim_graph::EventData::NodeAdded {
                             node_id: cid.clone(),
                             node_type: "ipld".to_string(),
@@ -70,18 +79,19 @@ impl IpldGraphProjection {
                     };
                     
                     // Create the node
-                    let node = IpldEventNode::new(Cid::new(cid), event_payload);
+                    let node = IpldEventNode::new(parsed.clone(), event_payload);
                     
                     // Add to projection
                     self.nodes.insert(cid.clone(), node);
-                    self.cid_chain.push(Cid::new(cid));
+                    self.cid_chain.push(parsed.clone());
                     
                     // If there was a previous CID, create an edge
-                    if let Some(prev_cid) = self.cid_chain.get(self.cid_chain.len() - 2) {
+                    if self.cid_chain.len() >= 2 {
+                        let prev_cid = &self.cid_chain[self.cid_chain.len() - 2];
                         let edge = IpldChainEdge {
-                            id: format!("{}->{}:chain", prev_cid.as_str(), cid),
+                            id: format!("{}->{}:chain", prev_cid, cid),
                             source: prev_cid.clone(),
-                            target: Cid::new(cid),
+                            target: parsed,
                             link_type: "previous".to_string(),
                         };
                         self.edges.insert(edge.id.clone(), edge);
@@ -89,18 +99,29 @@ impl IpldGraphProjection {
                 }
                 
                 IpldPayload::CidLinkAdded { cid, link_name, target_cid } => {
+                    let (source, target) = match (Cid::parse(cid), Cid::parse(target_cid)) {
+                        (Ok(source), Ok(target)) => (source, target),
+                        _ => {
+                            self.metadata.insert(
+                                format!("link_{}->{}:{}_invalid", cid, target_cid, link_name),
+                                serde_json::json!(true),
+                            );
+                            return;
+                        }
+                    };
+
                     // Add a named link between existing CIDs
                     let edge = IpldChainEdge {
                         id: format!("{}->{}:{}", cid, target_cid, link_name),
-                        source: Cid::new(cid),
-                        target: Cid::new(target_cid),
+                        source,
+                        target: target.clone(),
                         link_type: link_name.clone(),
                     };
                     self.edges.insert(edge.id.clone(), edge);
                     
                     // Update the node's links if it exists
                     if let Some(node) = self.nodes.get_mut(cid) {
-                        node.links.insert(link_name.clone(), Cid::new(target_cid));
+                        node.links.insert(link_name.clone(), target);
                     }
                 }
                 
@@ -217,6 +238,7 @@ pub fn build_ipld_projection(events: Vec<(GraphEvent, u64)>) -> IpldGraphProject
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::cid::test_cid;
     use crate::core::{Node, Edge};
 
     // ========== IpldGraphProjection Creation Tests ==========
@@ -254,12 +276,12 @@ mod tests {
     fn test_ipld_graph_projection_root_cid_with_chain() {
         let mut projection = IpldGraphProjection::new(Uuid::new_v4());
 
-        projection.cid_chain.push(Cid::new("cid1"));
-        projection.cid_chain.push(Cid::new("cid2"));
-        projection.cid_chain.push(Cid::new("cid3"));
+        projection.cid_chain.push(test_cid("cid1"));
+        projection.cid_chain.push(test_cid("cid2"));
+        projection.cid_chain.push(test_cid("cid3"));
 
         let root = projection.root_cid().unwrap();
-        assert_eq!(root.as_str(), "cid3");
+        assert_eq!(root, &test_cid("cid3"));
     }
 
     // ========== CID at Sequence Tests ==========
@@ -268,13 +290,13 @@ mod tests {
     fn test_ipld_graph_projection_cid_at_sequence() {
         let mut projection = IpldGraphProjection::new(Uuid::new_v4());
 
-        projection.cid_chain.push(Cid::new("first"));
-        projection.cid_chain.push(Cid::new("second"));
-        projection.cid_chain.push(Cid::new("third"));
+        projection.cid_chain.push(test_cid("first"));
+        projection.cid_chain.push(test_cid("second"));
+        projection.cid_chain.push(test_cid("third"));
 
-        assert_eq!(projection.cid_at_sequence(0).unwrap().as_str(), "first");
-        assert_eq!(projection.cid_at_sequence(1).unwrap().as_str(), "second");
-        assert_eq!(projection.cid_at_sequence(2).unwrap().as_str(), "third");
+        assert_eq!(projection.cid_at_sequence(0), Some(&test_cid("first")));
+        assert_eq!(projection.cid_at_sequence(1), Some(&test_cid("second")));
+        assert_eq!(projection.cid_at_sequence(2), Some(&test_cid("third")));
         assert!(projection.cid_at_sequence(3).is_none());
     }
 
@@ -308,7 +330,7 @@ mod tests {
         let mut projection = IpldGraphProjection::new(Uuid::new_v4());
 
         // Create a test node
-        let cid = Cid::new("test_cid");
+        let cid = test_cid("test_cid");
         let event_payload = crate::graphs::ipld_event_chain::EventPayload {
             cid: cid.clone(),
             data: crate::core::cim_graph::EventData::NodeAdded {
@@ -334,8 +356,8 @@ mod tests {
 
         let edge = IpldChainEdge {
             id: "test_edge".to_string(),
-            source: Cid::new("from"),
-            target: Cid::new("to"),
+            source: test_cid("from"),
+            target: test_cid("to"),
             link_type: "previous".to_string(),
         };
         projection.edges.insert("test_edge".to_string(), edge);
@@ -350,7 +372,7 @@ mod tests {
 
         // Add multiple nodes
         for i in 0..3 {
-            let cid = Cid::new(&format!("cid{}", i));
+            let cid = test_cid(&format!("cid{}", i));
             let event_payload = crate::graphs::ipld_event_chain::EventPayload {
                 cid: cid.clone(),
                 data: crate::core::cim_graph::EventData::NodeAdded {
@@ -377,8 +399,8 @@ mod tests {
         for i in 0..2 {
             let edge = IpldChainEdge {
                 id: format!("edge{}", i),
-                source: Cid::new(&format!("from{}", i)),
-                target: Cid::new(&format!("to{}", i)),
+                source: test_cid(&format!("from{}", i)),
+                target: test_cid(&format!("to{}", i)),
                 link_type: "link".to_string(),
             };
             projection.edges.insert(format!("edge{}", i), edge);
@@ -394,7 +416,7 @@ mod tests {
         assert_eq!(projection.node_count(), 0);
 
         for i in 0..5 {
-            let cid = Cid::new(&format!("cid{}", i));
+            let cid = test_cid(&format!("cid{}", i));
             let event_payload = crate::graphs::ipld_event_chain::EventPayload {
                 cid: cid.clone(),
                 data: crate::core::cim_graph::EventData::NodeAdded {
@@ -421,8 +443,8 @@ mod tests {
         for i in 0..3 {
             let edge = IpldChainEdge {
                 id: format!("edge{}", i),
-                source: Cid::new("from"),
-                target: Cid::new("to"),
+                source: test_cid("from"),
+                target: test_cid("to"),
                 link_type: "link".to_string(),
             };
             projection.edges.insert(format!("edge{}", i), edge);
@@ -440,8 +462,8 @@ mod tests {
             "e1".to_string(),
             IpldChainEdge {
                 id: "e1".to_string(),
-                source: Cid::new("A"),
-                target: Cid::new("B"),
+                source: test_cid("A"),
+                target: test_cid("B"),
                 link_type: "link".to_string(),
             },
         );
@@ -449,8 +471,8 @@ mod tests {
             "e2".to_string(),
             IpldChainEdge {
                 id: "e2".to_string(),
-                source: Cid::new("A"),
-                target: Cid::new("B"),
+                source: test_cid("A"),
+                target: test_cid("B"),
                 link_type: "another".to_string(),
             },
         );
@@ -458,19 +480,21 @@ mod tests {
             "e3".to_string(),
             IpldChainEdge {
                 id: "e3".to_string(),
-                source: Cid::new("B"),
-                target: Cid::new("C"),
+                source: test_cid("B"),
+                target: test_cid("C"),
                 link_type: "link".to_string(),
             },
         );
 
-        let edges_ab = projection.edges_between("A", "B");
+        let (a, b, c) = (test_cid("A"), test_cid("B"), test_cid("C"));
+
+        let edges_ab = projection.edges_between(a.as_str(), b.as_str());
         assert_eq!(edges_ab.len(), 2);
 
-        let edges_bc = projection.edges_between("B", "C");
+        let edges_bc = projection.edges_between(b.as_str(), c.as_str());
         assert_eq!(edges_bc.len(), 1);
 
-        let edges_ac = projection.edges_between("A", "C");
+        let edges_ac = projection.edges_between(a.as_str(), c.as_str());
         assert_eq!(edges_ac.len(), 0);
     }
 
@@ -483,8 +507,8 @@ mod tests {
             "e1".to_string(),
             IpldChainEdge {
                 id: "e1".to_string(),
-                source: Cid::new("A"),
-                target: Cid::new("B"),
+                source: test_cid("A"),
+                target: test_cid("B"),
                 link_type: "link".to_string(),
             },
         );
@@ -492,8 +516,8 @@ mod tests {
             "e2".to_string(),
             IpldChainEdge {
                 id: "e2".to_string(),
-                source: Cid::new("A"),
-                target: Cid::new("C"),
+                source: test_cid("A"),
+                target: test_cid("C"),
                 link_type: "link".to_string(),
             },
         );
@@ -501,22 +525,24 @@ mod tests {
             "e3".to_string(),
             IpldChainEdge {
                 id: "e3".to_string(),
-                source: Cid::new("B"),
-                target: Cid::new("D"),
+                source: test_cid("B"),
+                target: test_cid("D"),
                 link_type: "link".to_string(),
             },
         );
 
-        let neighbors_a = projection.neighbors("A");
+        let (a, b, c, d) = (test_cid("A"), test_cid("B"), test_cid("C"), test_cid("D"));
+
+        let neighbors_a = projection.neighbors(a.as_str());
         assert_eq!(neighbors_a.len(), 2);
-        assert!(neighbors_a.contains(&"B"));
-        assert!(neighbors_a.contains(&"C"));
+        assert!(neighbors_a.contains(&b.as_str()));
+        assert!(neighbors_a.contains(&c.as_str()));
 
-        let neighbors_b = projection.neighbors("B");
+        let neighbors_b = projection.neighbors(b.as_str());
         assert_eq!(neighbors_b.len(), 1);
-        assert!(neighbors_b.contains(&"D"));
+        assert!(neighbors_b.contains(&d.as_str()));
 
-        let neighbors_d = projection.neighbors("D");
+        let neighbors_d = projection.neighbors(d.as_str());
         assert_eq!(neighbors_d.len(), 0);
     }
 
@@ -540,14 +566,14 @@ mod tests {
     fn test_ipld_chain_edge_creation() {
         let edge = IpldChainEdge {
             id: "edge_id".to_string(),
-            source: Cid::new("source_cid"),
-            target: Cid::new("target_cid"),
+            source: test_cid("source_cid"),
+            target: test_cid("target_cid"),
             link_type: "previous".to_string(),
         };
 
         assert_eq!(edge.id, "edge_id");
-        assert_eq!(edge.source.as_str(), "source_cid");
-        assert_eq!(edge.target.as_str(), "target_cid");
+        assert_eq!(edge.source, test_cid("source_cid"));
+        assert_eq!(edge.target, test_cid("target_cid"));
         assert_eq!(edge.link_type, "previous");
     }
 
@@ -555,21 +581,21 @@ mod tests {
     fn test_ipld_chain_edge_implements_edge_trait() {
         let edge = IpldChainEdge {
             id: "test_edge".to_string(),
-            source: Cid::new("from"),
-            target: Cid::new("to"),
+            source: test_cid("from"),
+            target: test_cid("to"),
             link_type: "link".to_string(),
         };
 
         assert_eq!(Edge::id(&edge), "test_edge");
-        assert_eq!(Edge::source(&edge), "from");
-        assert_eq!(Edge::target(&edge), "to");
+        assert_eq!(Edge::source(&edge), test_cid("from").as_str());
+        assert_eq!(Edge::target(&edge), test_cid("to").as_str());
     }
 
     // ========== IpldEventNode Tests ==========
 
     #[test]
     fn test_ipld_event_node_creation() {
-        let cid = Cid::new("test_cid");
+        let cid = test_cid("test_cid");
         let aggregate_id = Uuid::new_v4();
         let event_payload = crate::graphs::ipld_event_chain::EventPayload {
             cid: cid.clone(),
@@ -585,13 +611,13 @@ mod tests {
 
         let node = IpldEventNode::new(cid.clone(), event_payload);
 
-        assert_eq!(node.cid.as_str(), "test_cid");
+        assert_eq!(node.cid, cid);
         assert!(node.links.is_empty());
     }
 
     #[test]
     fn test_ipld_event_node_implements_node_trait() {
-        let cid = Cid::new("node_cid");
+        let cid = test_cid("node_cid");
         let aggregate_id = Uuid::new_v4();
         let event_payload = crate::graphs::ipld_event_chain::EventPayload {
             cid: cid.clone(),
@@ -607,12 +633,12 @@ mod tests {
 
         let node = IpldEventNode::new(cid.clone(), event_payload);
 
-        assert_eq!(Node::id(&node), "node_cid");
+        assert_eq!(Node::id(&node), cid.as_str());
     }
 
     #[test]
     fn test_ipld_event_node_with_links() {
-        let cid = Cid::new("main_cid");
+        let cid = test_cid("main_cid");
         let aggregate_id = Uuid::new_v4();
         let event_payload = crate::graphs::ipld_event_chain::EventPayload {
             cid: cid.clone(),
@@ -627,12 +653,12 @@ mod tests {
         };
 
         let mut node = IpldEventNode::new(cid.clone(), event_payload);
-        node.links.insert("child".to_string(), Cid::new("child_cid"));
-        node.links.insert("sibling".to_string(), Cid::new("sibling_cid"));
+        node.links.insert("child".to_string(), test_cid("child_cid"));
+        node.links.insert("sibling".to_string(), test_cid("sibling_cid"));
 
         assert_eq!(node.links.len(), 2);
-        assert_eq!(node.links.get("child").unwrap().as_str(), "child_cid");
-        assert_eq!(node.links.get("sibling").unwrap().as_str(), "sibling_cid");
+        assert_eq!(node.links.get("child"), Some(&test_cid("child_cid")));
+        assert_eq!(node.links.get("sibling"), Some(&test_cid("sibling_cid")));
     }
 
     // ========== Verify Chain Tests ==========
@@ -648,7 +674,7 @@ mod tests {
     fn test_verify_chain_single_node() {
         let mut projection = IpldGraphProjection::new(Uuid::new_v4());
 
-        let cid = Cid::new("first_cid");
+        let cid = test_cid("first_cid");
         let event_payload = crate::graphs::ipld_event_chain::EventPayload {
             cid: cid.clone(),
             data: crate::core::cim_graph::EventData::NodeAdded {
@@ -669,6 +695,51 @@ mod tests {
         assert!(projection.verify_chain());
     }
 
+    // ========== Apply Tests ==========
+
+    fn cid_added(aggregate_id: Uuid, cid: &str) -> GraphEvent {
+        GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Ipld(IpldPayload::CidAdded {
+                cid: cid.to_string(),
+                codec: "dag-cbor".to_string(),
+                size: 0,
+                data: serde_json::json!({}),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_apply_builds_chain_from_valid_cids() {
+        let aggregate_id = Uuid::new_v4();
+        let first = test_cid("first");
+        let second = test_cid("second");
+
+        let projection = build_ipld_projection(vec![
+            (cid_added(aggregate_id, first.as_str()), 1),
+            (cid_added(aggregate_id, second.as_str()), 2),
+        ]);
+
+        assert_eq!(projection.cid_chain, vec![first.clone(), second.clone()]);
+        assert_eq!(projection.root_cid(), Some(&second));
+        assert_eq!(projection.edges_between(first.as_str(), second.as_str()).len(), 1);
+        assert_eq!(projection.nodes[second.as_str()].payload.previous, Some(first));
+        assert!(projection.verify_chain());
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_cids() {
+        let aggregate_id = Uuid::new_v4();
+        let projection = build_ipld_projection(vec![(cid_added(aggregate_id, "QmNotValid"), 1)]);
+
+        assert!(projection.nodes.is_empty());
+        assert!(projection.cid_chain.is_empty());
+        assert_eq!(projection.metadata.get("cid_QmNotValid_invalid"), Some(&serde_json::json!(true)));
+    }
+
     // ========== Debug Tests ==========
 
     #[test]
@@ -1,10 +1,15 @@
 //! Workflow graph - state machines (event-driven projection)
 
-// Projections are ephemeral - no serialization
+// Projections are rebuilt from events; serde is only for snapshots
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use crate::core::projection_engine::GenericGraphProjection;
 pub use crate::core::{Node, Edge};
+use crate::core::cim_graph::EventData;
+use crate::core::projection_engine::{
+    merge_fields, payload_fields, payload_str, payload_with, type_key, FromEventData, ToEventData,
+};
 
 /// Workflow graph projection
 pub type WorkflowGraph = GenericGraphProjection<WorkflowNode, WorkflowEdge>;
@@ -13,7 +18,7 @@ pub type WorkflowGraph = GenericGraphProjection<WorkflowNode, WorkflowEdge>;
 pub type WorkflowProjection = WorkflowGraph;
 
 /// Workflow state enumeration
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WorkflowState {
     /// Workflow is being designed
     Draft,
@@ -34,7 +39,7 @@ pub enum WorkflowState {
 }
 
 /// Type of workflow node
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WorkflowNodeType {
     /// Start state
     Start,
@@ -68,7 +73,7 @@ pub enum WorkflowNodeType {
 }
 
 /// Workflow node represents a state or action in a state machine
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowNode {
     /// Unique identifier for the node
     pub id: String,
@@ -133,8 +138,81 @@ impl Node for WorkflowNode {
     }
 }
 
+/// Builds workflow nodes from `NodeAdded` events
+///
+/// `node_type` selects the variant (`start`/`initial`, `end`/`final`,
+/// `decision`, `action`, `wait`, `error`, anything else is a `state`); the
+/// variant's field is read from `name`, `condition`, `operation`,
+/// `event_type` or `message` in the payload, and every payload field is kept
+/// in `metadata`.
+impl FromEventData for WorkflowNode {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (node_id, node_type, payload) = match data {
+            EventData::NodeAdded { node_id, node_type, data } => (node_id, node_type, data),
+            _ => return None,
+        };
+        let field = |key: &str| payload_str(payload, key).unwrap_or_default().to_string();
+        let kind = match type_key(node_type).as_str() {
+            "start" | "initial" => WorkflowNodeType::Start,
+            "end" | "final" => WorkflowNodeType::End,
+            "decision" => WorkflowNodeType::Decision { condition: field("condition") },
+            "action" => WorkflowNodeType::Action { operation: field("operation") },
+            "wait" => WorkflowNodeType::Wait { event_type: field("event_type") },
+            "error" => WorkflowNodeType::Error { message: field("message") },
+            _ => WorkflowNodeType::State {
+                name: payload_str(payload, "name").unwrap_or(node_id).to_string(),
+            },
+        };
+        let mut node = WorkflowNode::new(node_id.clone(), kind);
+        node.metadata = payload_fields(payload);
+        Some(node)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        merge_fields(&mut self.metadata, data);
+        let (slot, key) = match &mut self.node_type {
+            WorkflowNodeType::State { name } => (name, "name"),
+            WorkflowNodeType::Decision { condition } => (condition, "condition"),
+            WorkflowNodeType::Action { operation } => (operation, "operation"),
+            WorkflowNodeType::Wait { event_type } => (event_type, "event_type"),
+            WorkflowNodeType::Error { message } => (message, "message"),
+            WorkflowNodeType::Start | WorkflowNodeType::End => return,
+        };
+        if let Some(value) = payload_str(data, key) {
+            *slot = value.to_string();
+        }
+    }
+}
+
+impl ToEventData for WorkflowNode {
+    fn event_type(&self) -> String {
+        match self.node_type {
+            WorkflowNodeType::Start => "start",
+            WorkflowNodeType::End => "end",
+            WorkflowNodeType::State { .. } => "state",
+            WorkflowNodeType::Decision { .. } => "decision",
+            WorkflowNodeType::Action { .. } => "action",
+            WorkflowNodeType::Wait { .. } => "wait",
+            WorkflowNodeType::Error { .. } => "error",
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let field = match &self.node_type {
+            WorkflowNodeType::State { name } => Some(("name", name)),
+            WorkflowNodeType::Decision { condition } => Some(("condition", condition)),
+            WorkflowNodeType::Action { operation } => Some(("operation", operation)),
+            WorkflowNodeType::Wait { event_type } => Some(("event_type", event_type)),
+            WorkflowNodeType::Error { message } => Some(("message", message)),
+            WorkflowNodeType::Start | WorkflowNodeType::End => None,
+        };
+        payload_with(&self.metadata, field.map(|(key, value)| (key, value.clone().into())))
+    }
+}
+
 /// Type of workflow edge (transition)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WorkflowEdgeType {
     /// Normal transition
     Transition,
@@ -158,7 +236,7 @@ pub enum WorkflowEdgeType {
 }
 
 /// Workflow edge represents a transition between states
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowEdge {
     /// Unique identifier for the edge
     pub id: String,
@@ -254,6 +332,89 @@ impl Edge for WorkflowEdge {
     }
 }
 
+/// Builds workflow transitions from `EdgeAdded` events
+///
+/// `edge_type` selects the variant (`conditional`, `error`, `timeout`,
+/// `event`, with or without a `_transition` suffix; anything else is a plain
+/// transition). `condition`, `timeout_ms`, `event_type` and `trigger` are
+/// read from the payload, and every payload field is kept in `metadata`.
+impl FromEventData for WorkflowEdge {
+    fn from_event_data(data: &EventData) -> Option<Self> {
+        let (edge_id, source_id, target_id, edge_type, payload) = match data {
+            EventData::EdgeAdded { edge_id, source_id, target_id, edge_type, data } => {
+                (edge_id, source_id, target_id, edge_type, data)
+            }
+            _ => return None,
+        };
+        let field = |key: &str| payload_str(payload, key).unwrap_or_default().to_string();
+        let key = type_key(edge_type);
+        let kind = match key.strip_suffix("transition").unwrap_or(&key) {
+            "conditional" => WorkflowEdgeType::ConditionalTransition { condition: field("condition") },
+            "error" => WorkflowEdgeType::ErrorTransition,
+            "timeout" => WorkflowEdgeType::TimeoutTransition {
+                timeout_ms: payload.get("timeout_ms").and_then(|v| v.as_u64()).unwrap_or_default(),
+            },
+            "event" => WorkflowEdgeType::EventTransition { event_type: field("event_type") },
+            _ => WorkflowEdgeType::Transition,
+        };
+        let mut edge = WorkflowEdge::new(edge_id.clone(), source_id.clone(), target_id.clone(), kind);
+        edge.trigger = payload_str(payload, "trigger").map(str::to_string);
+        edge.metadata = payload_fields(payload);
+        Some(edge)
+    }
+
+    fn merge_event_data(&mut self, data: &serde_json::Value) {
+        merge_fields(&mut self.metadata, data);
+        match data.get("trigger") {
+            Some(serde_json::Value::String(trigger)) => self.trigger = Some(trigger.clone()),
+            Some(serde_json::Value::Null) => self.trigger = None,
+            _ => {}
+        }
+        match &mut self.edge_type {
+            WorkflowEdgeType::ConditionalTransition { condition } => {
+                if let Some(value) = payload_str(data, "condition") {
+                    *condition = value.to_string();
+                }
+            }
+            WorkflowEdgeType::TimeoutTransition { timeout_ms } => {
+                if let Some(value) = data.get("timeout_ms").and_then(|v| v.as_u64()) {
+                    *timeout_ms = value;
+                }
+            }
+            WorkflowEdgeType::EventTransition { event_type } => {
+                if let Some(value) = payload_str(data, "event_type") {
+                    *event_type = value.to_string();
+                }
+            }
+            WorkflowEdgeType::Transition | WorkflowEdgeType::ErrorTransition => {}
+        }
+    }
+}
+
+impl ToEventData for WorkflowEdge {
+    fn event_type(&self) -> String {
+        match self.edge_type {
+            WorkflowEdgeType::Transition => "transition",
+            WorkflowEdgeType::ConditionalTransition { .. } => "conditional",
+            WorkflowEdgeType::ErrorTransition => "error",
+            WorkflowEdgeType::TimeoutTransition { .. } => "timeout",
+            WorkflowEdgeType::EventTransition { .. } => "event",
+        }
+        .to_string()
+    }
+
+    fn event_payload(&self) -> serde_json::Value {
+        let field = match &self.edge_type {
+            WorkflowEdgeType::ConditionalTransition { condition } => Some(("condition", condition.clone().into())),
+            WorkflowEdgeType::TimeoutTransition { timeout_ms } => Some(("timeout_ms", (*timeout_ms).into())),
+            WorkflowEdgeType::EventTransition { event_type } => Some(("event_type", event_type.clone().into())),
+            WorkflowEdgeType::Transition | WorkflowEdgeType::ErrorTransition => None,
+        };
+        let trigger = self.trigger.clone().map(|trigger| ("trigger", trigger.into()));
+        payload_with(&self.metadata, field.into_iter().chain(trigger))
+    }
+}
+
 /// Extension methods for WorkflowProjection
 impl WorkflowProjection {
     /// Get all states in the workflow
@@ -367,9 +528,77 @@ impl WorkflowProjection {
             }
         }
 
+        // Check for loops that can never reach an end node; components come
+        // in topological order, so successors are settled first in reverse
+        let condensed = crate::algorithms::condensation(self);
+        let mut reaches_end = vec![false; condensed.components.len()];
+        for (index, members) in condensed.components.iter().enumerate().rev() {
+            reaches_end[index] = members.iter().any(|id| {
+                self.nodes.get(id).is_some_and(|node| matches!(node.node_type, WorkflowNodeType::End))
+            }) || condensed.successors(index).iter().any(|&next| reaches_end[next]);
+
+            let is_loop = members.len() > 1
+                || self.adjacency.get(&members[0]).is_some_and(|targets| targets.contains(&members[0]));
+            if is_loop && !reaches_end[index] {
+                return Err(format!(
+                    "States {} form a loop with no path to an end node",
+                    members.join(", ")
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// States that every path from the start node to an end node passes
+    /// through, in the order they are visited
+    ///
+    /// Includes the start node itself, and the end node when there is only
+    /// one reachable end. Empty if there is no start node or no end node is
+    /// reachable from it.
+    pub fn mandatory_states(&self) -> Vec<&WorkflowNode> {
+        let Some(tree) = self.start_dominator_tree() else {
+            return Vec::new();
+        };
+        let mut common: Option<Vec<&str>> = None;
+        for end in self.get_end_nodes().into_iter().filter(|end| tree.contains(&end.id)) {
+            let mut chain = tree.dominators(&end.id);
+            chain.reverse();
+            common = Some(match common {
+                None => chain,
+                Some(prefix) => prefix
+                    .into_iter()
+                    .zip(chain)
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+        common
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| self.nodes.get(id))
+            .collect()
+    }
+
+    /// Whether every path from the start node to `b` passes through `a`
+    ///
+    /// False if there is no start node or `b` is unreachable from it.
+    pub fn dominates(&self, a: &str, b: &str) -> bool {
+        self.start_dominator_tree().is_some_and(|tree| tree.dominates(a, b))
+    }
+
+    /// Whether every path from `b` to an end node passes through `a`
+    pub fn post_dominates(&self, a: &str, b: &str) -> bool {
+        let ends: Vec<&str> = self.get_end_nodes().iter().map(|end| end.id.as_str()).collect();
+        crate::algorithms::post_dominator_tree(self, &ends).is_ok_and(|tree| tree.dominates(a, b))
+    }
+
+    fn start_dominator_tree(&self) -> Option<crate::algorithms::DominatorTree> {
+        let start = self.get_start_node()?;
+        crate::algorithms::dominator_tree(self, &start.id).ok()
+    }
+
     /// Check if the workflow is in a running state
     pub fn is_running(&self) -> bool {
         self.nodes()
@@ -386,6 +615,7 @@ impl WorkflowProjection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::test_support::{edge_added, node_added};
     use crate::core::GraphType;
     use crate::core::projection_engine::GenericGraphProjection;
     use uuid::Uuid;
@@ -774,6 +1004,64 @@ mod tests {
         assert!(result.unwrap_err().contains("unreachable"));
     }
 
+    #[test]
+    fn test_validate_reports_trapped_loop() {
+        let mut projection = create_simple_workflow_projection();
+        // reject -> retry -> reject, with no way back out to the end
+        projection.nodes.insert("retry".to_string(), WorkflowNode::state("retry", "Retry"));
+        projection.edges.insert("e6".to_string(), WorkflowEdge::transition("e6", "reject", "retry"));
+        projection.edges.insert("e7".to_string(), WorkflowEdge::transition("e7", "retry", "reject"));
+        projection.adjacency.insert("reject".to_string(), vec!["retry".to_string()]);
+        projection.adjacency.insert("retry".to_string(), vec!["reject".to_string()]);
+
+        let result = projection.validate();
+        assert_eq!(
+            result.unwrap_err(),
+            "States reject, retry form a loop with no path to an end node"
+        );
+    }
+
+    #[test]
+    fn test_validate_allows_loop_with_exit() {
+        let mut projection = create_simple_workflow_projection();
+        // reject -> process sends the request around again
+        projection.edges.insert("e7".to_string(), WorkflowEdge::transition("e7", "reject", "process"));
+        projection.adjacency.get_mut("reject").unwrap().push("process".to_string());
+
+        assert!(projection.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mandatory_states() {
+        let projection = create_simple_workflow_projection();
+        let ids: Vec<&str> = projection.mandatory_states().iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(ids, vec!["start", "process", "check", "end"]);
+
+        // A shortcut from start straight to the end skips everything in between
+        let mut shortcut = create_simple_workflow_projection();
+        shortcut.edges.insert("e7".to_string(), WorkflowEdge::transition("e7", "start", "end"));
+        shortcut.adjacency.get_mut("start").unwrap().push("end".to_string());
+        let ids: Vec<&str> = shortcut.mandatory_states().iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(ids, vec!["start", "end"]);
+
+        let empty: WorkflowProjection = GenericGraphProjection::new(Uuid::new_v4(), GraphType::Generic);
+        assert!(empty.mandatory_states().is_empty());
+    }
+
+    #[test]
+    fn test_dominates() {
+        let projection = create_simple_workflow_projection();
+
+        assert!(projection.dominates("process", "approve"));
+        assert!(projection.dominates("check", "end"));
+        assert!(!projection.dominates("approve", "end"));
+        assert!(!projection.dominates("missing", "end"));
+
+        assert!(projection.post_dominates("end", "approve"));
+        assert!(projection.post_dominates("check", "process"));
+        assert!(!projection.post_dominates("approve", "check"));
+    }
+
     #[test]
     fn test_is_running() {
         let mut projection = create_simple_workflow_projection();
@@ -805,4 +1093,196 @@ mod tests {
         assert!(current.is_some());
         assert_eq!(current.unwrap().id, "approve");
     }
-}
\ No newline at end of file
+
+    // ========================================================================
+    // FromEventData tests
+    // ========================================================================
+
+    #[test]
+    fn test_workflow_node_from_event_data_variants() {
+        let cases = vec![
+            (node_added("s", "Start", serde_json::json!({})), WorkflowNodeType::Start),
+            (node_added("e", "final", serde_json::json!({})), WorkflowNodeType::End),
+            (
+                node_added("d", "decision", serde_json::json!({"condition": "amount > 100"})),
+                WorkflowNodeType::Decision { condition: "amount > 100".to_string() },
+            ),
+            (
+                node_added("a", "Action", serde_json::json!({"operation": "notify"})),
+                WorkflowNodeType::Action { operation: "notify".to_string() },
+            ),
+            (
+                node_added("w", "wait", serde_json::json!({"event_type": "PaymentReceived"})),
+                WorkflowNodeType::Wait { event_type: "PaymentReceived".to_string() },
+            ),
+            (
+                node_added("x", "error", serde_json::json!({"message": "boom"})),
+                WorkflowNodeType::Error { message: "boom".to_string() },
+            ),
+            (
+                node_added("p", "state", serde_json::json!({"name": "Processing"})),
+                WorkflowNodeType::State { name: "Processing".to_string() },
+            ),
+            // Unknown types become states named after the node
+            (
+                node_added("q", "custom", serde_json::json!({})),
+                WorkflowNodeType::State { name: "q".to_string() },
+            ),
+        ];
+
+        for (data, expected) in cases {
+            let node = WorkflowNode::from_event_data(&data).unwrap();
+            assert_eq!(node.node_type, expected);
+        }
+    }
+
+    #[test]
+    fn test_workflow_node_keeps_payload_as_metadata() {
+        let node = WorkflowNode::from_event_data(&node_added(
+            "p",
+            "state",
+            serde_json::json!({"name": "Processing", "sla_minutes": 30}),
+        ))
+        .unwrap();
+
+        assert_eq!(node.id, "p");
+        assert_eq!(node.metadata.get("sla_minutes"), Some(&serde_json::json!(30)));
+        assert_eq!(node.workflow_state, WorkflowState::Draft);
+    }
+
+    #[test]
+    fn test_workflow_node_from_edge_event_is_none() {
+        let data = edge_added("t", "a", "b", "transition", serde_json::json!({}));
+        assert!(WorkflowNode::from_event_data(&data).is_none());
+        assert!(WorkflowEdge::from_event_data(&node_added("a", "start", serde_json::json!({}))).is_none());
+    }
+
+    #[test]
+    fn test_workflow_node_merge_updates_typed_field() {
+        let mut node = WorkflowNode::decision("d", "x > 1");
+        node.merge_event_data(&serde_json::json!({"condition": "x > 2", "owner": "risk"}));
+
+        assert_eq!(node.node_type, WorkflowNodeType::Decision { condition: "x > 2".to_string() });
+        assert_eq!(node.metadata.get("owner"), Some(&serde_json::json!("risk")));
+
+        // Start/End have no typed field to update
+        let mut start = WorkflowNode::start("s");
+        start.merge_event_data(&serde_json::json!({"name": "ignored"}));
+        assert_eq!(start.node_type, WorkflowNodeType::Start);
+    }
+
+    #[test]
+    fn test_workflow_edge_from_event_data_variants() {
+        let cases = vec![
+            ("transition", serde_json::json!({}), WorkflowEdgeType::Transition),
+            (
+                "ConditionalTransition",
+                serde_json::json!({"condition": "approved"}),
+                WorkflowEdgeType::ConditionalTransition { condition: "approved".to_string() },
+            ),
+            ("error_transition", serde_json::json!({}), WorkflowEdgeType::ErrorTransition),
+            (
+                "timeout",
+                serde_json::json!({"timeout_ms": 5000}),
+                WorkflowEdgeType::TimeoutTransition { timeout_ms: 5000 },
+            ),
+            (
+                "event",
+                serde_json::json!({"event_type": "Paid"}),
+                WorkflowEdgeType::EventTransition { event_type: "Paid".to_string() },
+            ),
+            ("unknown", serde_json::json!({}), WorkflowEdgeType::Transition),
+        ];
+
+        for (edge_type, data, expected) in cases {
+            let edge = WorkflowEdge::from_event_data(&edge_added("t", "a", "b", edge_type, data)).unwrap();
+            assert_eq!(edge.edge_type, expected, "edge type {}", edge_type);
+            assert_eq!(edge.source, "a");
+            assert_eq!(edge.target, "b");
+        }
+    }
+
+    #[test]
+    fn test_workflow_edge_trigger_and_merge() {
+        let mut edge = WorkflowEdge::from_event_data(&edge_added(
+            "t",
+            "a",
+            "b",
+            "timeout",
+            serde_json::json!({"trigger": "expire", "timeout_ms": 10}),
+        ))
+        .unwrap();
+        assert_eq!(edge.trigger.as_deref(), Some("expire"));
+
+        edge.merge_event_data(&serde_json::json!({"timeout_ms": 20}));
+        assert_eq!(edge.edge_type, WorkflowEdgeType::TimeoutTransition { timeout_ms: 20 });
+        assert_eq!(edge.trigger.as_deref(), Some("expire"));
+
+        edge.merge_event_data(&serde_json::json!({"trigger": null}));
+        assert!(edge.trigger.is_none());
+        assert!(!edge.metadata.contains_key("trigger"));
+    }
+
+    #[test]
+    fn test_workflow_projection_built_by_engine() {
+        use crate::core::cim_graph::GraphEvent;
+        use crate::core::ProjectionEngine;
+
+        let aggregate_id = Uuid::new_v4();
+        let data = vec![
+            node_added("start", "start", serde_json::json!({})),
+            node_added("review", "state", serde_json::json!({"name": "Review"})),
+            node_added("done", "end", serde_json::json!({})),
+            edge_added("t1", "start", "review", "transition", serde_json::json!({"trigger": "submit"})),
+            edge_added("t2", "review", "done", "transition", serde_json::json!({"trigger": "approve"})),
+        ];
+        let events = data
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| GraphEvent {
+                event_id: Uuid::new_v4(),
+                aggregate_id,
+                sequence: i as u64 + 1,
+                subject: "cim.graph.workflow.evt".to_string(),
+                timestamp: chrono::Utc::now(),
+                correlation_id: Uuid::new_v4(),
+                causation_id: None,
+                data,
+            })
+            .collect();
+
+        let projection = ProjectionEngine::<WorkflowProjection>::new().project(events);
+
+        assert_eq!(projection.get_start_node().unwrap().id, "start");
+        assert_eq!(projection.get_states().len(), 1);
+        assert!(projection.validate().is_ok());
+        assert_eq!(
+            projection.find_path("start", "done"),
+            Some(vec!["start".to_string(), "review".to_string(), "done".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_workflow_to_event_data_roundtrip() {
+        let mut review = WorkflowNode::state("review", "Review");
+        review.metadata.insert("owner".to_string(), serde_json::json!("ann"));
+        for node in [WorkflowNode::start("s"), WorkflowNode::decision("d", "amount > 100"), review] {
+            let rebuilt = WorkflowNode::from_event_data(&node_added(&node.id, &node.event_type(), node.event_payload())).unwrap();
+            assert_eq!(rebuilt.node_type, node.node_type);
+            assert_eq!(rebuilt.event_payload(), node.event_payload());
+        }
+
+        let edges = [
+            WorkflowEdge::new("t", "a", "b", WorkflowEdgeType::TimeoutTransition { timeout_ms: 500 }).with_trigger("tick"),
+            WorkflowEdge::new("e", "a", "b", WorkflowEdgeType::EventTransition { event_type: "Paid".to_string() }),
+            WorkflowEdge::new("x", "a", "b", WorkflowEdgeType::ErrorTransition),
+        ];
+        for edge in edges {
+            let event = edge_added(&edge.id, &edge.source, &edge.target, &edge.event_type(), edge.event_payload());
+            let rebuilt = WorkflowEdge::from_event_data(&event).unwrap();
+            assert_eq!(rebuilt.edge_type, edge.edge_type);
+            assert_eq!(rebuilt.trigger, edge.trigger);
+            assert_eq!(rebuilt.event_payload(), edge.event_payload());
+        }
+    }
+}
@@ -78,6 +78,9 @@ pub mod graphs;
 /// Graph algorithms
 pub mod algorithms;
 
+/// Declarative graph queries
+pub mod query;
+
 /// Error types
 pub mod error;
 
@@ -8,11 +8,15 @@
 //! - [`SubjectSegment`] for individual subject tokens
 
 use crate::error::{GraphError, Result};
-use crate::events::{GraphEvent, EventPayload, GraphType as SubjectGraphType, EventType};
-use crate::core::ipld_chain::Cid;
+use crate::events::{GraphEvent, EventPayload, GraphType as SubjectGraphType, EventType, StreamMetadata};
+use crate::core::cid::Cid;
+use crate::core::CimGraphEvent;
 use async_nats::jetstream::{self, consumer::PullConsumer, stream::Stream};
+use async_nats::jetstream::context::PublishErrorKind;
+use async_nats::jetstream::stream::LastRawMessageErrorKind;
 use futures::StreamExt;
 use async_trait::async_trait;
+use chrono::DateTime;
 use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -86,14 +90,19 @@ impl Default for JetStreamConfig {
     }
 }
 
+/// Window in which a repeated `Nats-Msg-Id` is treated as a duplicate
+pub(super) const DUPLICATE_WINDOW_SECS: u64 = 120;
+
 /// Event envelope for JetStream storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct EventEnvelope {
-    /// Event data
+    /// Event data, upcast to the current schema version on read
+    #[serde(with = "crate::events::upcast::versioned")]
     event: GraphEvent,
     
     /// CID of the event (from IPLD chain)
-    cid: Option<String>,
+    #[serde(default)]
+    cid: Option<EnvelopeCid>,
     
     /// Sequence number in the aggregate
     sequence: u64,
@@ -102,6 +111,20 @@ struct EventEnvelope {
     headers: EventHeaders,
 }
 
+/// CID stored in an envelope
+///
+/// Envelopes written before CIDs were validated may hold strings that are not
+/// CIDs. Those are kept as written, so chain verification can tell a bad CID
+/// from a missing one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum EnvelopeCid {
+    /// A CID that parses
+    Valid(Cid),
+    /// A stored value that does not parse as a CID
+    Invalid(String),
+}
+
 /// Headers for event routing and correlation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct EventHeaders {
@@ -175,7 +198,7 @@ impl JetStreamEventStore {
         };
         
         if self.config.enable_dedup {
-            stream_config.duplicate_window = std::time::Duration::from_secs(120);
+            stream_config.duplicate_window = std::time::Duration::from_secs(DUPLICATE_WINDOW_SECS);
         }
         
         // Create or update stream
@@ -191,6 +214,41 @@ impl JetStreamEventStore {
     
     /// Publish an event to JetStream
     pub async fn publish_event(&self, event: GraphEvent, cid: Option<Cid>) -> Result<u64> {
+        self.publish_with_expectation(event, cid, None).await
+    }
+    
+    /// Publish events for an aggregate only if it is still at `expected_version`
+    ///
+    /// The version is the stream sequence of the aggregate's last event on its
+    /// subject (0 when it has none), as returned by a previous publish. Each
+    /// event is sent with a `Nats-Expected-Last-Subject-Sequence` header, so the
+    /// server rejects the append if another writer got there first and this
+    /// returns [`GraphError::ConcurrencyConflict`] with the current version.
+    /// All events must belong to the aggregate and share one graph type.
+    /// Events are published one at a time, so those sent before a conflict
+    /// stay in the stream. Returns the new version.
+    pub async fn append_expected(
+        &self,
+        aggregate_id: Uuid,
+        expected_version: u64,
+        events: Vec<GraphEvent>,
+    ) -> Result<u64> {
+        check_expected_batch(aggregate_id, &events)?;
+        
+        let mut version = expected_version;
+        for event in events {
+            version = self.publish_with_expectation(event, None, Some(version)).await?;
+        }
+        Ok(version)
+    }
+    
+    /// Publish an event, optionally requiring the last sequence on its subject
+    async fn publish_with_expectation(
+        &self,
+        event: GraphEvent,
+        cid: Option<Cid>,
+        expected_sequence: Option<u64>,
+    ) -> Result<u64> {
         // Determine event type and graph type from payload
         let (event_type, graph_type) = determine_event_type(&event.payload);
 
@@ -218,7 +276,7 @@ impl JetStreamEventStore {
         // Create envelope
         let envelope = EventEnvelope {
             event: event.clone(),
-            cid: cid.map(|c| c.to_string()),
+            cid: cid.map(EnvelopeCid::Valid),
             sequence: 0, // Will be set by JetStream
             headers,
         };
@@ -243,19 +301,67 @@ impl JetStreamEventStore {
             nats_headers.insert("Nats-Msg-Id", event.event_id.to_string());
         }
         
+        if let Some(expected) = expected_sequence {
+            nats_headers.insert("Nats-Expected-Last-Subject-Sequence", expected.to_string());
+        }
+        
         // Publish with headers
-        let ack = self.jetstream
-            .publish_with_headers(subject, nats_headers, payload.into())
-            .await
-            .map_err(|e| NatsError::JetStreamError(e.to_string()))?
+        let ack = match self.jetstream
+            .publish_with_headers(subject.clone(), nats_headers, payload.into())
             .await
-            .map_err(|e| NatsError::JetStreamError(e.to_string()))?;
+        {
+            Ok(ack) => ack.await,
+            Err(e) => Err(e),
+        };
+        
+        match ack {
+            Ok(ack) => Ok(ack.sequence),
+            Err(e) => match expected_sequence {
+                Some(expected) if e.kind() == PublishErrorKind::WrongLastSequence => {
+                    let actual = self.last_subject_sequence(&subject).await?;
+                    Err(GraphError::ConcurrencyConflict { expected, actual })
+                }
+                _ => Err(NatsError::JetStreamError(e.to_string()).into()),
+            },
+        }
+    }
+    
+    /// Stream sequence of the last message on a subject, 0 when there is none
+    async fn last_subject_sequence(&self, subject: &str) -> Result<u64> {
+        let stream = self.stream.read().await;
+        let stream = stream.as_ref()
+            .ok_or_else(|| NatsError::StreamNotFound(self.config.stream_name.clone()))?;
         
-        Ok(ack.sequence)
+        match stream.get_last_raw_message_by_subject(subject).await {
+            Ok(message) => Ok(message.sequence),
+            Err(e) if matches!(e.kind(), LastRawMessageErrorKind::NoMessageFound) => Ok(0),
+            Err(e) => Err(NatsError::JetStreamError(e.to_string()).into()),
+        }
     }
     
     /// Fetch events for an aggregate
     pub async fn fetch_events(&self, aggregate_id: Uuid) -> Result<Vec<GraphEvent>> {
+        let events = self.fetch_stored_events(aggregate_id).await?;
+        Ok(events.into_iter().map(|(event, _)| event).collect())
+    }
+    
+    /// Fetch events for an aggregate as projection events
+    ///
+    /// Sequence, subject and timestamp come from the JetStream message, so the
+    /// result can be passed straight to `ProjectionEngine::project`. Fails if
+    /// any event has no projection form (a generic payload that does not name
+    /// an `EventData` variant), since projecting the rest would silently give
+    /// the wrong graph.
+    pub async fn fetch_projection_events(&self, aggregate_id: Uuid) -> Result<Vec<CimGraphEvent>> {
+        let events = self.fetch_stored_events(aggregate_id).await?;
+        events
+            .into_iter()
+            .map(|(event, stream)| event.to_cim_event(&stream))
+            .collect()
+    }
+    
+    /// Fetch events for an aggregate along with their stream metadata
+    async fn fetch_stored_events(&self, aggregate_id: Uuid) -> Result<Vec<(GraphEvent, StreamMetadata)>> {
         let stream = self.stream.read().await;
         let stream = stream.as_ref()
             .ok_or_else(|| NatsError::StreamNotFound(self.config.stream_name.clone()))?;
@@ -288,9 +394,9 @@ impl JetStreamEventStore {
             .map_err(|e| NatsError::ConsumerError(e.to_string()))?;
         
         for message in messages {
-            // Deserialize envelope
+            // Deserialize envelope and read the stream position JetStream assigned
             if let Ok(envelope) = serde_json::from_slice::<EventEnvelope>(&message.payload) {
-                events.push(envelope.event);
+                events.push((envelope.event, stream_metadata(&message)?));
             }
             
             // Acknowledge message
@@ -533,10 +639,50 @@ impl EventStream for EventSubscription {
 // Subject Building Helpers using CIM Domain Subject Algebra
 // ============================================================================
 
+/// Check that a batch for an expected-version append targets one aggregate subject
+pub(super) fn check_expected_batch(aggregate_id: Uuid, events: &[GraphEvent]) -> Result<()> {
+    if let Some(event) = events.iter().find(|e| e.aggregate_id != aggregate_id) {
+        return Err(GraphError::InvalidOperation(format!(
+            "Event {} belongs to aggregate {}, not {}",
+            event.event_id, event.aggregate_id, aggregate_id
+        )));
+    }
+    let graph_types: Vec<_> = events
+        .iter()
+        .map(|event| determine_event_type(&event.payload).1)
+        .collect();
+    if graph_types.windows(2).any(|pair| pair[0] != pair[1]) {
+        return Err(GraphError::InvalidOperation(
+            "Events appended with an expected version must share one graph type".to_string()
+        ));
+    }
+    Ok(())
+}
+
+/// Read the stream sequence, subject and publish time of a JetStream message
+fn stream_metadata(message: &jetstream::Message) -> Result<StreamMetadata> {
+    let info = message.info().map_err(|e| {
+        NatsError::JetStreamError(format!("Message on {} has no stream info: {}", message.subject, e))
+    })?;
+    let published = info.published;
+    let timestamp = DateTime::from_timestamp(published.unix_timestamp(), published.nanosecond())
+        .ok_or_else(|| {
+            NatsError::JetStreamError(format!(
+                "Message {} on {} has an out-of-range publish time",
+                info.stream_sequence, message.subject
+            ))
+        })?;
+    Ok(StreamMetadata {
+        sequence: info.stream_sequence,
+        subject: message.subject.to_string(),
+        timestamp,
+    })
+}
+
 /// Build a concrete subject for entity events using Subject type.
 ///
 /// Creates a validated subject like `prefix.graph_type.entity_id`
-fn build_entity_subject(prefix: &str, graph_type: &str, entity_id: &str) -> std::result::Result<String, String> {
+pub(super) fn build_entity_subject(prefix: &str, graph_type: &str, entity_id: &str) -> std::result::Result<String, String> {
     let mut segments = Vec::new();
 
     // Parse prefix into segments
@@ -581,7 +727,7 @@ fn build_wildcard_pattern(prefix: &str, wildcard: &str) -> String {
 /// Build a filter pattern for aggregate events using SubjectPattern.
 ///
 /// Creates patterns like `prefix.*.aggregate_id` for single-level wildcards
-fn build_aggregate_filter_pattern(prefix: &str, aggregate_id: &str) -> String {
+pub(super) fn build_aggregate_filter_pattern(prefix: &str, aggregate_id: &str) -> String {
     let pattern_str = format!("{}.*.{}", prefix, aggregate_id);
     // Validate using SubjectPattern
     match SubjectPattern::parse(&pattern_str) {
@@ -593,7 +739,7 @@ fn build_aggregate_filter_pattern(prefix: &str, aggregate_id: &str) -> String {
 /// Build a filter pattern for graph type events using SubjectPattern.
 ///
 /// Creates patterns like `prefix.graph_type.*` for subscribing to all entities of a type
-fn build_graph_type_filter_pattern(prefix: &str, graph_type: &str) -> String {
+pub(super) fn build_graph_type_filter_pattern(prefix: &str, graph_type: &str) -> String {
     let pattern_str = format!("{}.{}.*", prefix, graph_type);
     // Validate using SubjectPattern
     match SubjectPattern::parse(&pattern_str) {
@@ -619,7 +765,7 @@ fn build_correlation_segments(correlation_id: Uuid) -> Vec<SubjectSegment> {
 // ============================================================================
 
 /// Determine event type and graph type from payload
-fn determine_event_type(payload: &EventPayload) -> (EventType, SubjectGraphType) {
+pub(super) fn determine_event_type(payload: &EventPayload) -> (EventType, SubjectGraphType) {
     match payload {
         EventPayload::Generic(_) => (EventType::Updated, SubjectGraphType::Composed),
         EventPayload::Ipld(p) => {
@@ -761,6 +907,85 @@ mod tests {
         assert!(subject.contains(&entity_id));
     }
 
+    // ========================================================================
+    // Envelope Decoding Tests
+    // ========================================================================
+
+    #[test]
+    fn test_envelope_decodes_unversioned_event() {
+        let aggregate_id = Uuid::new_v4();
+        let correlation_id = Uuid::new_v4();
+        let payload = serde_json::json!({
+            "event": {
+                "event_id": Uuid::new_v4(),
+                "aggregate_id": aggregate_id,
+                "correlation_id": correlation_id,
+                "causation_id": null,
+                "payload": {"Workflow": {"StateTransitioned": {
+                    "instance_id": aggregate_id,
+                    "from_state": "draft",
+                    "to_state": "review",
+                }}},
+            },
+            "cid": null,
+            "sequence": 1,
+            "headers": {
+                "aggregate_id": aggregate_id,
+                "event_type": "StateTransitioned",
+                "graph_type": "workflow",
+                "correlation_id": correlation_id,
+                "causation_id": null,
+            },
+        });
+
+        let envelope: EventEnvelope = serde_json::from_value(payload).unwrap();
+        assert_eq!(envelope.event.aggregate_id, aggregate_id);
+
+        let written = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(
+            written["event"]["schema_version"],
+            crate::events::upcast::CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_envelope_keeps_invalid_legacy_cid() {
+        let event = GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id: Uuid::new_v4(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Generic(crate::events::GenericPayload {
+                event_type: "Legacy".to_string(),
+                data: serde_json::json!({}),
+            }),
+        };
+        let mut payload = serde_json::json!({
+            "event": event,
+            "sequence": 1,
+            "headers": {
+                "aggregate_id": event.aggregate_id,
+                "event_type": "Legacy",
+                "graph_type": "generic",
+                "correlation_id": event.correlation_id,
+                "causation_id": null,
+            },
+        });
+
+        let envelope: EventEnvelope = serde_json::from_value(payload.clone()).unwrap();
+        assert_eq!(envelope.cid, None);
+
+        let cid = crate::core::cid::test_cid("legacy");
+        payload["cid"] = serde_json::json!(cid.to_string());
+        let envelope: EventEnvelope = serde_json::from_value(payload.clone()).unwrap();
+        assert_eq!(envelope.cid, Some(EnvelopeCid::Valid(cid)));
+
+        payload["cid"] = serde_json::json!("not-a-cid");
+        let envelope: EventEnvelope = serde_json::from_value(payload).unwrap();
+        assert_eq!(envelope.cid, Some(EnvelopeCid::Invalid("not-a-cid".to_string())));
+        assert_eq!(serde_json::to_value(&envelope).unwrap()["cid"], "not-a-cid");
+    }
+
     // ========================================================================
     // Integration Tests (Require NATS Server)
     // ========================================================================
@@ -816,6 +1041,85 @@ mod tests {
         assert_eq!(events[0].event_id, event.event_id);
     }
 
+    #[tokio::test]
+    #[ignore] // Requires NATS server
+    async fn test_fetch_projection_events() {
+        use crate::core::EventData;
+        use crate::events::WorkflowPayload;
+
+        let config = test_config();
+        let store = JetStreamEventStore::new(config).await.unwrap();
+
+        let aggregate_id = Uuid::new_v4();
+        let event = GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Workflow(WorkflowPayload::StateAdded {
+                workflow_id: aggregate_id,
+                state_id: "draft".to_string(),
+                state_type: "initial".to_string(),
+            }),
+        };
+
+        let seq = store.publish_event(event.clone(), None).await.unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let events = store.fetch_projection_events(aggregate_id).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_id, event.event_id);
+        assert_eq!(events[0].sequence, seq);
+        assert!(events[0].subject.ends_with(&aggregate_id.to_string()));
+        assert!(matches!(&events[0].data, EventData::NodeAdded { node_id, .. } if node_id == "draft"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NATS server
+    async fn test_append_expected_conflict() {
+        use crate::events::GenericPayload;
+
+        let config = test_config();
+        let store = JetStreamEventStore::new(config).await.unwrap();
+
+        let aggregate_id = Uuid::new_v4();
+        let event = |name: &str| GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Generic(GenericPayload {
+                event_type: name.to_string(),
+                data: serde_json::json!({}),
+            }),
+        };
+
+        let version = store
+            .append_expected(aggregate_id, 0, vec![event("First"), event("Second")])
+            .await
+            .unwrap();
+        assert!(version > 0);
+
+        // A writer that still believes the aggregate is empty must be rejected
+        let err = store
+            .append_expected(aggregate_id, 0, vec![event("Stale")])
+            .await
+            .unwrap_err();
+        match err {
+            GraphError::ConcurrencyConflict { expected, actual } => {
+                assert_eq!(expected, 0);
+                assert_eq!(actual, version);
+            }
+            other => panic!("expected a concurrency conflict, got {:?}", other),
+        }
+
+        let next = store
+            .append_expected(aggregate_id, version, vec![event("Retry")])
+            .await
+            .unwrap();
+        assert!(next > version);
+    }
+
     #[tokio::test]
     #[ignore] // Requires NATS server
     async fn test_subscribe_to_aggregate() {
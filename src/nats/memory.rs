@@ -0,0 +1,794 @@
+//! In-process JetStream-compatible event store
+//!
+//! [`InMemoryJetStream`] keeps events in memory but routes them the same way
+//! as [`JetStreamEventStore`](super::JetStreamEventStore): events land on
+//! `{prefix}.{graph_type}.{aggregate_id}` subjects, get stream and
+//! per-subject sequences, are deduplicated by `Nats-Msg-Id` (the event ID),
+//! honor expected-sequence appends and can be replayed through durable
+//! consumers. Subject filters use [`matches_pattern`].
+//!
+//! No server is involved, so tests and single-node deployments can swap it
+//! in through the [`AsyncEventStore`] trait.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
+
+use super::jetstream::{
+    build_aggregate_filter_pattern, build_entity_subject, build_graph_type_filter_pattern,
+    check_expected_batch, determine_event_type, AsyncEventStore, EventStream, JetStreamConfig,
+    NatsError, DUPLICATE_WINDOW_SECS,
+};
+use crate::channels::matches_pattern;
+use crate::core::cid::Cid;
+use crate::core::CimGraphEvent;
+use crate::error::{GraphError, Result};
+use crate::events::{GraphEvent, GraphType as SubjectGraphType, StreamMetadata};
+
+/// Messages buffered for live subscribers before slow ones start lagging
+const SUBSCRIPTION_BUFFER: usize = 1024;
+
+/// A message stored in the in-memory stream
+#[derive(Debug, Clone)]
+struct StoredMessage {
+    /// Stream sequence
+    sequence: u64,
+    /// Subject the event was published on
+    subject: String,
+    /// When the message was stored
+    published: DateTime<Utc>,
+    /// The event itself
+    event: GraphEvent,
+    /// CID of the event, if the publisher supplied one
+    cid: Option<Cid>,
+}
+
+impl StoredMessage {
+    fn stream_metadata(&self) -> StreamMetadata {
+        StreamMetadata {
+            sequence: self.sequence,
+            subject: self.subject.clone(),
+            timestamp: self.published,
+        }
+    }
+}
+
+/// Mutable stream state shared by the store and its consumers
+#[derive(Debug, Default)]
+struct StreamState {
+    /// Retained messages in sequence order
+    messages: VecDeque<StoredMessage>,
+    /// Last assigned stream sequence
+    last_sequence: u64,
+    /// Stream sequence of the last retained message per subject
+    subject_sequences: HashMap<String, u64>,
+    /// Message IDs seen inside the duplicate window, with their sequence
+    message_ids: HashMap<String, (u64, DateTime<Utc>)>,
+    /// Next sequence each durable consumer will deliver
+    consumers: HashMap<String, u64>,
+}
+
+/// In-memory event store with JetStream semantics
+#[derive(Debug, Clone)]
+pub struct InMemoryJetStream {
+    /// Configuration (prefix, dedup and retention)
+    config: JetStreamConfig,
+    /// Stream contents
+    state: Arc<RwLock<StreamState>>,
+    /// Live feed for subscriptions
+    notifier: broadcast::Sender<StoredMessage>,
+}
+
+impl InMemoryJetStream {
+    /// Create an empty store; `server_url` and `stream_name` are ignored
+    pub fn new(config: JetStreamConfig) -> Self {
+        let (notifier, _) = broadcast::channel(SUBSCRIPTION_BUFFER);
+        Self {
+            config,
+            state: Arc::new(RwLock::new(StreamState::default())),
+            notifier,
+        }
+    }
+
+    /// Get the store configuration
+    pub fn config(&self) -> &JetStreamConfig {
+        &self.config
+    }
+
+    /// Publish an event, returning its stream sequence
+    ///
+    /// A repeated event ID inside the duplicate window is not stored again;
+    /// the sequence of the original message is returned instead.
+    pub async fn publish_event(&self, event: GraphEvent, cid: Option<Cid>) -> Result<u64> {
+        let mut state = self.state.write().await;
+        self.store(&mut state, event, cid, None)
+    }
+
+    /// Publish events for an aggregate only if it is still at `expected_version`
+    ///
+    /// Same contract as [`JetStreamEventStore::append_expected`](super::JetStreamEventStore::append_expected):
+    /// the version is the sequence of the last event on the aggregate's subject
+    /// and a mismatch fails with [`GraphError::ConcurrencyConflict`]. Events
+    /// before a conflict stay in the stream.
+    pub async fn append_expected(
+        &self,
+        aggregate_id: Uuid,
+        expected_version: u64,
+        events: Vec<GraphEvent>,
+    ) -> Result<u64> {
+        check_expected_batch(aggregate_id, &events)?;
+
+        let mut state = self.state.write().await;
+        let mut version = expected_version;
+        for event in events {
+            version = self.store(&mut state, event, None, Some(version))?;
+        }
+        Ok(version)
+    }
+
+    /// Fetch the stored history of an aggregate
+    pub async fn fetch_events(&self, aggregate_id: Uuid) -> Result<Vec<GraphEvent>> {
+        let events = self.fetch_stored_events(aggregate_id).await;
+        Ok(events.into_iter().map(|message| message.event).collect())
+    }
+
+    /// Fetch the stored history of an aggregate as projection events
+    ///
+    /// Fails if any event has no projection form, as
+    /// [`JetStreamEventStore::fetch_projection_events`](super::JetStreamEventStore::fetch_projection_events) does.
+    pub async fn fetch_projection_events(&self, aggregate_id: Uuid) -> Result<Vec<CimGraphEvent>> {
+        let events = self.fetch_stored_events(aggregate_id).await;
+        events
+            .into_iter()
+            .map(|message| message.event.to_cim_event(&message.stream_metadata()))
+            .collect()
+    }
+
+    /// Fetch events with a correlation ID across all aggregates
+    pub async fn fetch_by_correlation(&self, correlation_id: Uuid) -> Result<Vec<GraphEvent>> {
+        let state = self.state.read().await;
+        Ok(state
+            .messages
+            .iter()
+            .filter(|message| message.event.correlation_id == correlation_id)
+            .map(|message| message.event.clone())
+            .collect())
+    }
+
+    /// Fetch the CID an event was published with
+    pub async fn event_cid(&self, event_id: Uuid) -> Option<Cid> {
+        let state = self.state.read().await;
+        state
+            .messages
+            .iter()
+            .find(|message| message.event.event_id == event_id)
+            .and_then(|message| message.cid.clone())
+    }
+
+    /// Subscribe to events published for an aggregate from now on
+    pub async fn subscribe_to_aggregate(&self, aggregate_id: Uuid) -> Result<InMemorySubscription> {
+        let filter = build_aggregate_filter_pattern(
+            &self.config.subject_prefix,
+            &aggregate_id.to_string(),
+        );
+        Ok(self.subscription(filter, aggregate_id))
+    }
+
+    /// Subscribe to events published for any aggregate of a graph type
+    pub async fn subscribe_to_graph_type(
+        &self,
+        graph_type: SubjectGraphType,
+        aggregate_id: Uuid,
+    ) -> Result<InMemorySubscription> {
+        let graph_type_str = format!("{:?}", graph_type).to_lowercase();
+        let filter = build_graph_type_filter_pattern(&self.config.subject_prefix, &graph_type_str);
+        Ok(self.subscription(filter, aggregate_id))
+    }
+
+    /// Create or resume a durable consumer for event replay
+    ///
+    /// A new consumer starts at `start_sequence` (the first message when
+    /// `None`); an existing one keeps its position.
+    pub async fn create_replay_consumer(
+        &self,
+        consumer_name: &str,
+        start_sequence: Option<u64>,
+    ) -> Result<InMemoryReplayConsumer> {
+        let mut state = self.state.write().await;
+        state
+            .consumers
+            .entry(consumer_name.to_string())
+            .or_insert_with(|| start_sequence.unwrap_or(1));
+        Ok(InMemoryReplayConsumer {
+            name: consumer_name.to_string(),
+            state: Arc::clone(&self.state),
+        })
+    }
+
+    /// Stored messages of an aggregate, oldest first
+    async fn fetch_stored_events(&self, aggregate_id: Uuid) -> Vec<StoredMessage> {
+        let filter = build_aggregate_filter_pattern(
+            &self.config.subject_prefix,
+            &aggregate_id.to_string(),
+        );
+        let state = self.state.read().await;
+        state
+            .messages
+            .iter()
+            .filter(|message| matches_pattern(&message.subject, &filter))
+            .cloned()
+            .collect()
+    }
+
+    fn subscription(&self, filter: String, aggregate_id: Uuid) -> InMemorySubscription {
+        InMemorySubscription {
+            receiver: self.notifier.subscribe(),
+            filter,
+            aggregate_id,
+        }
+    }
+
+    /// Store one message under the write lock, applying dedup and expectations
+    fn store(
+        &self,
+        state: &mut StreamState,
+        event: GraphEvent,
+        cid: Option<Cid>,
+        expected_sequence: Option<u64>,
+    ) -> Result<u64> {
+        let now = Utc::now();
+        self.expire(state, now);
+
+        let (_, graph_type) = determine_event_type(&event.payload);
+        let graph_type_str = format!("{:?}", graph_type).to_lowercase();
+        let subject = build_entity_subject(
+            &self.config.subject_prefix,
+            &graph_type_str,
+            &event.aggregate_id.to_string(),
+        ).map_err(NatsError::JetStreamError)?;
+
+        let message_id = event.event_id.to_string();
+        if self.config.enable_dedup {
+            if let Some((sequence, _)) = state.message_ids.get(&message_id) {
+                return Ok(*sequence);
+            }
+        }
+
+        if let Some(expected) = expected_sequence {
+            let actual = state.subject_sequences.get(&subject).copied().unwrap_or(0);
+            if actual != expected {
+                return Err(GraphError::ConcurrencyConflict { expected, actual });
+            }
+        }
+
+        state.last_sequence += 1;
+        let sequence = state.last_sequence;
+        let message = StoredMessage {
+            sequence,
+            subject: subject.clone(),
+            published: now,
+            event,
+            cid,
+        };
+        state.subject_sequences.insert(subject, sequence);
+        if self.config.enable_dedup {
+            state.message_ids.insert(message_id, (sequence, now));
+        }
+        state.messages.push_back(message.clone());
+
+        // Nobody listening is not an error, just like core NATS
+        let _ = self.notifier.send(message);
+        Ok(sequence)
+    }
+
+    /// Drop messages past `max_age_secs` (0 keeps everything) and stale message IDs
+    fn expire(&self, state: &mut StreamState, now: DateTime<Utc>) {
+        if let Some(max_age) = retention(self.config.max_age_secs) {
+            let cutoff = now - max_age;
+            while state.messages.front().is_some_and(|m| m.published <= cutoff) {
+                if let Some(expired) = state.messages.pop_front() {
+                    if state.subject_sequences.get(&expired.subject) == Some(&expired.sequence) {
+                        state.subject_sequences.remove(&expired.subject);
+                    }
+                }
+            }
+        }
+
+        if let Some(window) = retention(DUPLICATE_WINDOW_SECS) {
+            let cutoff = now - window;
+            state.message_ids.retain(|_, (_, seen)| *seen > cutoff);
+        }
+    }
+}
+
+impl Default for InMemoryJetStream {
+    fn default() -> Self {
+        Self::new(JetStreamConfig::default())
+    }
+}
+
+/// Convert a limit in seconds to a duration, `None` meaning unlimited
+fn retention(secs: u64) -> Option<Duration> {
+    if secs == 0 {
+        return None;
+    }
+    i64::try_from(secs).ok().and_then(Duration::try_seconds)
+}
+
+/// Live subscription to an [`InMemoryJetStream`]
+///
+/// Like a core NATS subscription it only sees events published after it was
+/// created, and a subscriber that falls more than the buffer behind skips
+/// the messages it missed.
+#[derive(Debug)]
+pub struct InMemorySubscription {
+    receiver: broadcast::Receiver<StoredMessage>,
+    filter: String,
+    aggregate_id: Uuid,
+}
+
+impl InMemorySubscription {
+    /// Get the aggregate ID this subscription is for
+    pub fn aggregate_id(&self) -> Uuid {
+        self.aggregate_id
+    }
+
+    /// Get the next matching event, `None` once the store is gone
+    pub async fn next(&mut self) -> Result<Option<GraphEvent>> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(message) if matches_pattern(&message.subject, &self.filter) => {
+                    return Ok(Some(message.event));
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Durable consumer replaying an [`InMemoryJetStream`]
+#[derive(Debug)]
+pub struct InMemoryReplayConsumer {
+    name: String,
+    state: Arc<RwLock<StreamState>>,
+}
+
+impl InMemoryReplayConsumer {
+    /// Fetch the next batch of events and acknowledge them
+    pub async fn fetch_batch(&self, max_messages: usize) -> Result<Vec<GraphEvent>> {
+        let mut state = self.state.write().await;
+        let next = state.consumers.get(&self.name).copied().unwrap_or(1);
+        let batch: Vec<StoredMessage> = state
+            .messages
+            .iter()
+            .filter(|message| message.sequence >= next)
+            .take(max_messages)
+            .cloned()
+            .collect();
+        if let Some(last) = batch.last() {
+            state.consumers.insert(self.name.clone(), last.sequence + 1);
+        }
+        Ok(batch.into_iter().map(|message| message.event).collect())
+    }
+}
+
+#[async_trait]
+impl AsyncEventStore for InMemoryJetStream {
+    async fn publish(&self, event: GraphEvent) -> Result<u64> {
+        self.publish_event(event, None).await
+    }
+
+    async fn fetch_aggregate_events(&self, aggregate_id: Uuid) -> Result<Vec<GraphEvent>> {
+        self.fetch_events(aggregate_id).await
+    }
+
+    async fn subscribe(&self, aggregate_id: Uuid) -> Result<Box<dyn EventStream>> {
+        let subscription = self.subscribe_to_aggregate(aggregate_id).await?;
+        Ok(Box::new(subscription))
+    }
+}
+
+#[async_trait]
+impl EventStream for InMemorySubscription {
+    async fn next(&mut self) -> Result<Option<GraphEvent>> {
+        self.next().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cid::HashAlgorithm;
+    use crate::events::{EventPayload, GenericPayload, IpldPayload, WorkflowPayload};
+
+    fn test_store() -> InMemoryJetStream {
+        InMemoryJetStream::new(JetStreamConfig {
+            subject_prefix: "test.cim.graph".to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn generic_event(aggregate_id: Uuid, name: &str) -> GraphEvent {
+        GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Generic(GenericPayload {
+                event_type: name.to_string(),
+                data: serde_json::json!({ "name": name }),
+            }),
+        }
+    }
+
+    fn ids(events: &[GraphEvent]) -> Vec<Uuid> {
+        events.iter().map(|event| event.event_id).collect()
+    }
+
+    fn ipld_event(aggregate_id: Uuid, cid: &str) -> GraphEvent {
+        GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Ipld(IpldPayload::CidPinned {
+                cid: cid.to_string(),
+                recursive: false,
+            }),
+        }
+    }
+
+    // ========== Publish and Fetch Tests ==========
+
+    #[tokio::test]
+    async fn test_publish_assigns_stream_sequences() {
+        let store = test_store();
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+
+        assert_eq!(store.publish_event(generic_event(a, "one"), None).await.unwrap(), 1);
+        assert_eq!(store.publish_event(generic_event(b, "two"), None).await.unwrap(), 2);
+        assert_eq!(store.publish_event(generic_event(a, "three"), None).await.unwrap(), 3);
+
+        let events = store.fetch_events(a).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.aggregate_id == a));
+        assert_eq!(store.fetch_events(b).await.unwrap().len(), 1);
+        assert!(store.fetch_events(Uuid::new_v4()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_keeps_publish_order() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        let published: Vec<_> = (0..5)
+            .map(|i| generic_event(aggregate_id, &format!("event{}", i)))
+            .collect();
+
+        for event in &published {
+            store.publish_event(event.clone(), None).await.unwrap();
+        }
+
+        let fetched = store.fetch_events(aggregate_id).await.unwrap();
+        assert_eq!(ids(&fetched), ids(&published));
+    }
+
+    #[tokio::test]
+    async fn test_publish_keeps_cid() {
+        let store = test_store();
+        let event = generic_event(Uuid::new_v4(), "with-cid");
+        let cid = Cid::for_payload(&serde_json::json!({ "name": "with-cid" }), HashAlgorithm::Sha2_256);
+
+        store.publish_event(event.clone(), Some(cid.clone())).await.unwrap();
+
+        assert_eq!(store.event_cid(event.event_id).await, Some(cid));
+        assert_eq!(store.event_cid(Uuid::new_v4()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_by_correlation_across_aggregates() {
+        let store = test_store();
+        let correlation_id = Uuid::new_v4();
+
+        for name in ["one", "two", "three"] {
+            let mut event = generic_event(Uuid::new_v4(), name);
+            event.correlation_id = correlation_id;
+            store.publish_event(event, None).await.unwrap();
+        }
+        store.publish_event(generic_event(Uuid::new_v4(), "other"), None).await.unwrap();
+
+        let fetched = store.fetch_by_correlation(correlation_id).await.unwrap();
+        assert_eq!(fetched.len(), 3);
+        assert!(fetched.iter().all(|event| event.correlation_id == correlation_id));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_projection_events() {
+        use crate::core::EventData;
+
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        let event = GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Workflow(WorkflowPayload::StateAdded {
+                workflow_id: aggregate_id,
+                state_id: "draft".to_string(),
+                state_type: "initial".to_string(),
+            }),
+        };
+
+        let seq = store.publish_event(event.clone(), None).await.unwrap();
+
+        let events = store.fetch_projection_events(aggregate_id).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_id, event.event_id);
+        assert_eq!(events[0].sequence, seq);
+        assert_eq!(events[0].subject, format!("test.cim.graph.workflow.{}", aggregate_id));
+        assert!(matches!(&events[0].data, EventData::NodeAdded { node_id, .. } if node_id == "draft"));
+
+        // An event the projection cannot represent fails the fetch instead of vanishing
+        store.publish_event(generic_event(aggregate_id, "opaque"), None).await.unwrap();
+        let err = store.fetch_projection_events(aggregate_id).await.unwrap_err();
+        assert!(matches!(err, GraphError::InvalidOperation(_)));
+    }
+
+    // ========== Deduplication Tests ==========
+
+    #[tokio::test]
+    async fn test_duplicate_message_id_is_not_stored_twice() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        let event = generic_event(aggregate_id, "once");
+
+        let first = store.publish_event(event.clone(), None).await.unwrap();
+        store.publish_event(generic_event(aggregate_id, "between"), None).await.unwrap();
+        let again = store.publish_event(event, None).await.unwrap();
+
+        assert_eq!(first, again);
+        assert_eq!(store.fetch_events(aggregate_id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_disabled_stores_duplicates() {
+        let store = InMemoryJetStream::new(JetStreamConfig {
+            enable_dedup: false,
+            ..Default::default()
+        });
+        let aggregate_id = Uuid::new_v4();
+        let event = generic_event(aggregate_id, "twice");
+
+        let first = store.publish_event(event.clone(), None).await.unwrap();
+        let second = store.publish_event(event, None).await.unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(store.fetch_events(aggregate_id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_window_expires() {
+        let store = test_store();
+        let event = generic_event(Uuid::new_v4(), "late-retry");
+
+        let first = store.publish_event(event.clone(), None).await.unwrap();
+        {
+            let mut state = store.state.write().await;
+            for (_, seen) in state.message_ids.values_mut() {
+                *seen -= Duration::seconds(DUPLICATE_WINDOW_SECS as i64 + 1);
+            }
+        }
+        let second = store.publish_event(event, None).await.unwrap();
+
+        assert_eq!(second, first + 1);
+    }
+
+    // ========== Expected Version Tests ==========
+
+    #[tokio::test]
+    async fn test_append_expected_chains_subject_sequences() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let version = store
+            .append_expected(aggregate_id, 0, vec![generic_event(aggregate_id, "a")])
+            .await
+            .unwrap();
+        assert_eq!(version, 1);
+
+        // Another aggregate moves the stream but not this subject
+        store.publish_event(generic_event(other, "x"), None).await.unwrap();
+
+        let version = store
+            .append_expected(
+                aggregate_id,
+                version,
+                vec![generic_event(aggregate_id, "b"), generic_event(aggregate_id, "c")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(version, 4);
+        assert_eq!(store.fetch_events(aggregate_id).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_append_expected_conflict() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+
+        store.publish_event(generic_event(aggregate_id, "a"), None).await.unwrap();
+        store.publish_event(generic_event(aggregate_id, "b"), None).await.unwrap();
+
+        let result = store
+            .append_expected(aggregate_id, 1, vec![generic_event(aggregate_id, "stale")])
+            .await;
+        assert!(matches!(
+            result,
+            Err(GraphError::ConcurrencyConflict { expected: 1, actual: 2 })
+        ));
+        assert_eq!(store.fetch_events(aggregate_id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_append_expected_rejects_foreign_events() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+
+        let result = store
+            .append_expected(aggregate_id, 0, vec![generic_event(Uuid::new_v4(), "foreign")])
+            .await;
+        assert!(matches!(result, Err(GraphError::InvalidOperation(_))));
+
+        let result = store
+            .append_expected(
+                aggregate_id,
+                0,
+                vec![generic_event(aggregate_id, "a"), ipld_event(aggregate_id, "bafy")],
+            )
+            .await;
+        assert!(matches!(result, Err(GraphError::InvalidOperation(_))));
+        assert!(store.fetch_events(aggregate_id).await.unwrap().is_empty());
+    }
+
+    // ========== Subscription Tests ==========
+
+    #[tokio::test]
+    async fn test_subscribe_to_aggregate_filters_subjects() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        let mut subscription = store.subscribe_to_aggregate(aggregate_id).await.unwrap();
+        assert_eq!(subscription.aggregate_id(), aggregate_id);
+
+        store.publish_event(generic_event(Uuid::new_v4(), "noise"), None).await.unwrap();
+        let event = generic_event(aggregate_id, "wanted");
+        store.publish_event(event.clone(), None).await.unwrap();
+
+        let received = subscription.next().await.unwrap().unwrap();
+        assert_eq!(received.event_id, event.event_id);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_to_graph_type() {
+        let store = test_store();
+        let mut subscription = store
+            .subscribe_to_graph_type(SubjectGraphType::Ipld, Uuid::nil())
+            .await
+            .unwrap();
+
+        store.publish_event(generic_event(Uuid::new_v4(), "generic"), None).await.unwrap();
+        let event = ipld_event(Uuid::new_v4(), "bafy");
+        store.publish_event(event.clone(), None).await.unwrap();
+
+        let received = subscription.next().await.unwrap().unwrap();
+        assert_eq!(received.event_id, event.event_id);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_ends_when_store_dropped() {
+        let store = test_store();
+        let mut subscription = store.subscribe_to_aggregate(Uuid::new_v4()).await.unwrap();
+        drop(store);
+
+        assert!(subscription.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_trait_object_roundtrip() {
+        let store: Box<dyn AsyncEventStore> = Box::new(test_store());
+        let aggregate_id = Uuid::new_v4();
+        let mut stream = store.subscribe(aggregate_id).await.unwrap();
+
+        let event = generic_event(aggregate_id, "via-trait");
+        let seq = store.publish(event.clone()).await.unwrap();
+        assert_eq!(seq, 1);
+
+        assert_eq!(stream.next().await.unwrap().unwrap().event_id, event.event_id);
+        let fetched = store.fetch_aggregate_events(aggregate_id).await.unwrap();
+        assert_eq!(ids(&fetched), vec![event.event_id]);
+    }
+
+    // ========== Replay Consumer Tests ==========
+
+    #[tokio::test]
+    async fn test_replay_consumer_resumes() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        for i in 0..5 {
+            store
+                .publish_event(generic_event(aggregate_id, &format!("event{}", i)), None)
+                .await
+                .unwrap();
+        }
+
+        let consumer = store.create_replay_consumer("replay", None).await.unwrap();
+        assert_eq!(consumer.fetch_batch(3).await.unwrap().len(), 3);
+
+        // Recreating a durable consumer keeps its position
+        let resumed = store.create_replay_consumer("replay", Some(1)).await.unwrap();
+        assert_eq!(resumed.fetch_batch(10).await.unwrap().len(), 2);
+        assert!(resumed.fetch_batch(10).await.unwrap().is_empty());
+
+        store.publish_event(generic_event(aggregate_id, "late"), None).await.unwrap();
+        assert_eq!(consumer.fetch_batch(10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replay_consumer_start_sequence() {
+        let store = test_store();
+        let aggregate_id = Uuid::new_v4();
+        let events: Vec<_> = (0..4)
+            .map(|i| generic_event(aggregate_id, &format!("event{}", i)))
+            .collect();
+        for event in &events {
+            store.publish_event(event.clone(), None).await.unwrap();
+        }
+
+        let consumer = store.create_replay_consumer("from-3", Some(3)).await.unwrap();
+        let batch = consumer.fetch_batch(10).await.unwrap();
+        assert_eq!(ids(&batch), ids(&events[2..]));
+    }
+
+    // ========== Retention Tests ==========
+
+    #[tokio::test]
+    async fn test_max_age_expires_messages() {
+        let store = InMemoryJetStream::new(JetStreamConfig {
+            max_age_secs: 60,
+            ..Default::default()
+        });
+        let aggregate_id = Uuid::new_v4();
+
+        store.publish_event(generic_event(aggregate_id, "old"), None).await.unwrap();
+        {
+            let mut state = store.state.write().await;
+            for message in state.messages.iter_mut() {
+                message.published -= Duration::seconds(61);
+            }
+        }
+
+        // The expired message was the subject's last, so its version resets
+        let version = store
+            .append_expected(aggregate_id, 0, vec![generic_event(aggregate_id, "new")])
+            .await
+            .unwrap();
+        assert_eq!(version, 2);
+
+        let events = store.fetch_events(aggregate_id).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(store.state.read().await.messages.front().unwrap().sequence, 2);
+    }
+
+    #[test]
+    fn test_zero_max_age_keeps_everything() {
+        assert!(retention(0).is_none());
+        assert_eq!(retention(60), Some(Duration::seconds(60)));
+        assert!(retention(u64::MAX).is_none());
+    }
+}
@@ -4,4 +4,16 @@
 pub mod jetstream;
 
 #[cfg(feature = "nats")]
-pub use jetstream::{JetStreamEventStore, JetStreamConfig, NatsError};
\ No newline at end of file
+pub mod memory;
+
+#[cfg(feature = "nats")]
+pub mod snapshots;
+
+#[cfg(feature = "nats")]
+pub use jetstream::{JetStreamEventStore, JetStreamConfig, NatsError};
+
+#[cfg(feature = "nats")]
+pub use memory::{InMemoryJetStream, InMemoryReplayConsumer, InMemorySubscription};
+
+#[cfg(feature = "nats")]
+pub use snapshots::{AsyncSnapshotStore, JetStreamSnapshotStore};
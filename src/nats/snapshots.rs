@@ -0,0 +1,209 @@
+//! Projection snapshots in a JetStream key-value bucket
+//!
+//! [`JetStreamSnapshotStore`] keeps the same compressed, hash-checked
+//! snapshots as the [`SnapshotStore`](crate::core::SnapshotStore) backends,
+//! keyed `{aggregate_id}.{version:020}` in a KV bucket, and prunes them with
+//! the same [`SnapshotRetention`].
+
+use async_nats::jetstream::{self, kv};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use super::jetstream::NatsError;
+use crate::core::projection_engine::ProjectionSnapshot;
+use crate::core::SnapshotRetention;
+use crate::error::Result;
+use crate::optimization::EventCompressor;
+
+/// Async snapshot backend, the counterpart of [`SnapshotStore`](crate::core::SnapshotStore)
+#[async_trait]
+pub trait AsyncSnapshotStore: Send + Sync {
+    /// Store a snapshot and prune the aggregate's older snapshots
+    async fn save(&self, snapshot: &ProjectionSnapshot) -> Result<()>;
+
+    /// Load the snapshot of an aggregate at exactly `version`
+    async fn load(&self, aggregate_id: Uuid, version: u64) -> Result<Option<ProjectionSnapshot>>;
+
+    /// Versions stored for an aggregate, ascending
+    async fn versions(&self, aggregate_id: Uuid) -> Result<Vec<u64>>;
+
+    /// Delete one snapshot; deleting a missing snapshot is not an error
+    async fn delete(&self, aggregate_id: Uuid, version: u64) -> Result<()>;
+
+    /// Newest snapshot at or below `max_version`
+    async fn latest_at(&self, aggregate_id: Uuid, max_version: u64) -> Result<Option<ProjectionSnapshot>> {
+        let version = self
+            .versions(aggregate_id)
+            .await?
+            .into_iter()
+            .rev()
+            .find(|version| *version <= max_version);
+        match version {
+            Some(version) => self.load(aggregate_id, version).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Newest snapshot of an aggregate
+    async fn latest(&self, aggregate_id: Uuid) -> Result<Option<ProjectionSnapshot>> {
+        self.latest_at(aggregate_id, u64::MAX).await
+    }
+}
+
+/// Snapshot store backed by a JetStream key-value bucket
+#[derive(Debug)]
+pub struct JetStreamSnapshotStore {
+    /// Key-value bucket
+    store: kv::Store,
+    /// Retention policy
+    retention: SnapshotRetention,
+    /// Compressor for stored snapshots
+    compressor: EventCompressor,
+}
+
+impl JetStreamSnapshotStore {
+    /// Connect to NATS and open (or create) the snapshot bucket
+    pub async fn new(server_url: &str, bucket: &str, retention: SnapshotRetention) -> Result<Self> {
+        let client = async_nats::connect(server_url)
+            .await
+            .map_err(|e| NatsError::ConnectionError(e.to_string()))?;
+        Self::from_context(&jetstream::new(client), bucket, retention).await
+    }
+
+    /// Open (or create) the snapshot bucket on an existing JetStream context
+    pub async fn from_context(
+        jetstream: &jetstream::Context,
+        bucket: &str,
+        retention: SnapshotRetention,
+    ) -> Result<Self> {
+        let store = match jetstream.get_key_value(bucket).await {
+            Ok(store) => store,
+            Err(_) => jetstream
+                .create_key_value(kv::Config {
+                    bucket: bucket.to_string(),
+                    description: "CIM graph projection snapshots".to_string(),
+                    history: 1,
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| NatsError::JetStreamError(e.to_string()))?,
+        };
+
+        Ok(Self {
+            store,
+            retention,
+            compressor: EventCompressor::default(),
+        })
+    }
+
+    /// Get the retention policy
+    pub fn retention(&self) -> &SnapshotRetention {
+        &self.retention
+    }
+}
+
+#[async_trait]
+impl AsyncSnapshotStore for JetStreamSnapshotStore {
+    async fn save(&self, snapshot: &ProjectionSnapshot) -> Result<()> {
+        let encoded = snapshot.encode(&self.compressor)?;
+        self.store
+            .put(snapshot_key(snapshot.aggregate_id, snapshot.version), encoded.into())
+            .await
+            .map_err(|e| NatsError::JetStreamError(e.to_string()))?;
+
+        let versions = self.versions(snapshot.aggregate_id).await?;
+        for version in self.retention.expired(&versions) {
+            self.delete(snapshot.aggregate_id, version).await?;
+        }
+        Ok(())
+    }
+
+    async fn load(&self, aggregate_id: Uuid, version: u64) -> Result<Option<ProjectionSnapshot>> {
+        let encoded = self
+            .store
+            .get(snapshot_key(aggregate_id, version))
+            .await
+            .map_err(|e| NatsError::JetStreamError(e.to_string()))?;
+        match encoded {
+            Some(encoded) => ProjectionSnapshot::decode(&encoded).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn versions(&self, aggregate_id: Uuid) -> Result<Vec<u64>> {
+        let keys: Vec<String> = self
+            .store
+            .keys()
+            .await
+            .map_err(|e| NatsError::JetStreamError(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| NatsError::JetStreamError(e.to_string()))?;
+
+        let mut versions: Vec<u64> = keys
+            .iter()
+            .filter_map(|key| parse_snapshot_key(key, aggregate_id))
+            .collect();
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    async fn delete(&self, aggregate_id: Uuid, version: u64) -> Result<()> {
+        self.store
+            .purge(snapshot_key(aggregate_id, version))
+            .await
+            .map_err(|e| NatsError::JetStreamError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Bucket key of a snapshot
+fn snapshot_key(aggregate_id: Uuid, version: u64) -> String {
+    format!("{}.{:020}", aggregate_id, version)
+}
+
+/// Version of a snapshot key belonging to `aggregate_id`
+fn parse_snapshot_key(key: &str, aggregate_id: Uuid) -> Option<u64> {
+    let (id, version) = key.split_once('.')?;
+    if id != aggregate_id.to_string() {
+        return None;
+    }
+    version.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_key_roundtrip() {
+        let aggregate_id = Uuid::new_v4();
+        let key = snapshot_key(aggregate_id, 42);
+
+        assert_eq!(key, format!("{}.00000000000000000042", aggregate_id));
+        assert_eq!(parse_snapshot_key(&key, aggregate_id), Some(42));
+        assert_eq!(parse_snapshot_key(&key, Uuid::new_v4()), None);
+        assert_eq!(parse_snapshot_key("no-separator", aggregate_id), None);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires NATS server
+    async fn test_jetstream_snapshot_store() {
+        let bucket = format!("CIM_SNAPSHOTS_TEST_{}", &Uuid::new_v4().to_string()[..8]);
+        let store = JetStreamSnapshotStore::new("localhost:4222", &bucket, SnapshotRetention::keep_last(2))
+            .await
+            .unwrap();
+
+        let aggregate_id = Uuid::new_v4();
+        for version in [10, 20, 30] {
+            let snapshot = ProjectionSnapshot::new(aggregate_id, version, serde_json::json!({ "v": version }));
+            store.save(&snapshot).await.unwrap();
+        }
+
+        assert_eq!(store.versions(aggregate_id).await.unwrap(), vec![20, 30]);
+        assert_eq!(store.latest(aggregate_id).await.unwrap().unwrap().version, 30);
+        assert_eq!(store.latest_at(aggregate_id, 25).await.unwrap().unwrap().version, 20);
+        assert!(store.load(aggregate_id, 10).await.unwrap().is_none());
+    }
+}
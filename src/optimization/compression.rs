@@ -1,9 +1,53 @@
 //! Event stream compression for efficient storage and transmission
+//!
+//! Events are compressed as schema-versioned JSON so that old streams can be
+//! upcast with [`UpcasterRegistry`] when they are read back. Streams written
+//! by earlier releases as bincode are still decoded, without upcasting.
 
+use crate::events::upcast::UpcasterRegistry;
 use crate::events::GraphEvent;
 use crate::error::{GraphError, Result};
+use serde_json::Value;
 use std::io::{Read, Write, BufRead, BufReader};
 
+/// Serialize a batch of events as versioned JSON
+fn encode_events(events: &[GraphEvent]) -> Result<Vec<u8>> {
+    let registry = UpcasterRegistry::standard();
+    let encoded = events
+        .iter()
+        .map(|event| registry.encode(event))
+        .collect::<Result<Vec<Value>>>()?;
+    serde_json::to_vec(&encoded)
+        .map_err(|e| GraphError::SerializationError(format!("Failed to serialize events: {}", e)))
+}
+
+/// Deserialize a batch of events, upcasting JSON and falling back to legacy bincode
+fn decode_events(data: &[u8]) -> Result<Vec<GraphEvent>> {
+    match serde_json::from_slice::<Vec<Value>>(data) {
+        Ok(encoded) => {
+            let registry = UpcasterRegistry::standard();
+            encoded.into_iter().map(|event| registry.decode(event)).collect()
+        }
+        Err(_) => bincode::deserialize(data)
+            .map_err(|e| GraphError::SerializationError(e.to_string())),
+    }
+}
+
+/// Serialize a single event as versioned JSON
+fn encode_event(event: &GraphEvent) -> Result<Vec<u8>> {
+    serde_json::to_vec(&UpcasterRegistry::standard().encode(event)?)
+        .map_err(|e| GraphError::SerializationError(format!("Failed to serialize event: {}", e)))
+}
+
+/// Deserialize a single event, upcasting JSON and falling back to legacy bincode
+fn decode_event(data: &[u8]) -> Result<GraphEvent> {
+    match serde_json::from_slice::<Value>(data) {
+        Ok(encoded) => UpcasterRegistry::standard().decode(encoded),
+        Err(_) => bincode::deserialize(data)
+            .map_err(|e| GraphError::SerializationError(e.to_string())),
+    }
+}
+
 /// Compressed event stream using zstd compression
 #[derive(Debug, Clone)]
 pub struct CompressedEventStream {
@@ -18,13 +62,8 @@ pub struct CompressedEventStream {
 impl CompressedEventStream {
     /// Decompress events
     pub fn decompress(&self) -> Result<Vec<GraphEvent>> {
-        let decompressed = zstd::decode_all(&self.data[..])
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
-        
-        let events: Vec<GraphEvent> = bincode::deserialize(&decompressed)
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
-        
-        Ok(events)
+        let decompressed = EventCompressor::decompress_bytes(&self.data)?;
+        decode_events(&decompressed)
     }
     
     /// Get compression ratio (compressed size / original size)
@@ -68,15 +107,12 @@ impl EventCompressor {
     
     /// Compress a batch of events
     pub fn compress(&self, events: &[GraphEvent]) -> Result<CompressedEventStream> {
-        // Serialize events to binary
-        let serialized = bincode::serialize(events)
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
+        let serialized = encode_events(events)?;
         
         let original_size = serialized.len();
         
         // Compress with zstd
-        let compressed = zstd::encode_all(&serialized[..], self.compression_level)
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
+        let compressed = self.compress_bytes(&serialized)?;
         
         let compressed_size = compressed.len();
         let compression_ratio = compressed_size as f64 / original_size as f64;
@@ -88,10 +124,21 @@ impl EventCompressor {
         })
     }
     
+    /// Compress arbitrary bytes with the configured zstd level
+    pub fn compress_bytes(&self, data: &[u8]) -> Result<Vec<u8>> {
+        zstd::encode_all(data, self.compression_level)
+            .map_err(|e| GraphError::SerializationError(e.to_string()))
+    }
+    
+    /// Decompress bytes produced by [`compress_bytes`](Self::compress_bytes)
+    pub fn decompress_bytes(data: &[u8]) -> Result<Vec<u8>> {
+        zstd::decode_all(data)
+            .map_err(|e| GraphError::SerializationError(e.to_string()))
+    }
+    
     /// Compress and write to a writer
     pub fn compress_to<W: Write>(&self, events: &[GraphEvent], writer: W) -> Result<()> {
-        let serialized = bincode::serialize(events)
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
+        let serialized = encode_events(events)?;
         
         let mut encoder = zstd::Encoder::new(writer, self.compression_level)
             .map_err(|e| GraphError::SerializationError(e.to_string()))?;
@@ -114,10 +161,7 @@ impl EventCompressor {
         decoder.read_to_end(&mut decompressed)
             .map_err(|e| GraphError::SerializationError(e.to_string()))?;
         
-        let events: Vec<GraphEvent> = bincode::deserialize(&decompressed)
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
-        
-        Ok(events)
+        decode_events(&decompressed)
     }
 }
 
@@ -149,8 +193,7 @@ impl<W: Write> StreamingCompressor<W> {
     
     /// Add an event to the stream
     pub fn add_event(&mut self, event: &GraphEvent) -> Result<()> {
-        let serialized = bincode::serialize(event)
-            .map_err(|e| GraphError::SerializationError(e.to_string()))?;
+        let serialized = encode_event(event)?;
         
         // Write length prefix for framing
         let len = serialized.len() as u32;
@@ -220,10 +263,7 @@ impl<R: Read> StreamingDecompressor<R> {
             decoder.read_exact(&mut event_data)
                 .map_err(|e| GraphError::SerializationError(e.to_string()))?;
             
-            let event: GraphEvent = bincode::deserialize(&event_data)
-                .map_err(|e| GraphError::SerializationError(e.to_string()))?;
-            
-            events.push(event);
+            events.push(decode_event(&event_data)?);
         }
         
         Ok(events)
@@ -295,6 +335,17 @@ mod tests {
 
     // ========== EventCompressor Tests ==========
 
+    #[test]
+    fn test_compress_bytes_roundtrip() {
+        let compressor = EventCompressor::default();
+        let data = b"snapshot snapshot snapshot snapshot snapshot".repeat(20);
+
+        let compressed = compressor.compress_bytes(&data).unwrap();
+        assert!(compressed.len() < data.len());
+        assert_eq!(EventCompressor::decompress_bytes(&compressed).unwrap(), data);
+        assert!(EventCompressor::decompress_bytes(b"not zstd").is_err());
+    }
+
     #[test]
     fn test_event_compressor_default() {
         let compressor = EventCompressor::default();
@@ -609,8 +660,6 @@ mod tests {
         let compressor = EventCompressor::default();
         let aggregate_id = Uuid::new_v4();
 
-        // Note: Using payloads without serde_json::Value fields because bincode
-        // doesn't support deserialize_any required by serde_json::Value
         let events = vec![
             GraphEvent {
                 event_id: Uuid::new_v4(),
@@ -770,8 +819,7 @@ mod tests {
     fn test_compress_events_with_large_data() {
         let compressor = EventCompressor::default();
 
-        // Create many events with repetitive data (large payload with bincode-compatible types)
-        // Note: We can't use serde_json::Value with bincode, so we use repetitive string data
+        // Create many events with repetitive data
         let large_cid = "Qm".to_string() + &"x".repeat(1000);
         let large_link_name = "link_".to_string() + &"a".repeat(500);
 
@@ -823,4 +871,67 @@ mod tests {
                 "Level {} failed to decompress correctly", level);
         }
     }
-}
\ No newline at end of file
+
+    // ========== Schema Version Tests ==========
+
+    #[test]
+    fn test_compress_generic_json_payloads() {
+        let event = GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id: Uuid::new_v4(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Generic(crate::events::GenericPayload {
+                event_type: "NodeAdded".to_string(),
+                data: serde_json::json!({ "node_id": "a", "tags": ["x", "y"], "weight": 1.5 }),
+            }),
+        };
+
+        let compressed = EventCompressor::default().compress(std::slice::from_ref(&event)).unwrap();
+        let decompressed = compressed.decompress().unwrap();
+
+        match &decompressed[0].payload {
+            EventPayload::Generic(generic) => assert_eq!(generic.data["tags"][1], "y"),
+            _ => panic!("Wrong payload type"),
+        }
+    }
+
+    #[test]
+    fn test_decompress_unversioned_json() {
+        let events = create_test_events(3);
+        let unversioned = serde_json::to_vec(&events).unwrap();
+        let compressor = EventCompressor::default();
+
+        let stream = CompressedEventStream {
+            data: compressor.compress_bytes(&unversioned).unwrap(),
+            event_count: 3,
+            compression_ratio: 1.0,
+        };
+
+        let decompressed = stream.decompress().unwrap();
+        assert_eq!(decompressed[2].event_id, events[2].event_id);
+    }
+
+    #[test]
+    fn test_decompress_legacy_bincode() {
+        let events = create_test_events(5);
+        let compressor = EventCompressor::default();
+
+        let legacy = bincode::serialize(&events).unwrap();
+        let mut buffer = Vec::new();
+        let mut encoder = zstd::Encoder::new(&mut buffer, 3).unwrap();
+        encoder.write_all(&legacy).unwrap();
+        encoder.finish().unwrap();
+
+        let decompressed = EventCompressor::decompress_from(BufReader::new(&buffer[..])).unwrap();
+        assert_eq!(decompressed.len(), 5);
+        assert_eq!(decompressed[4].event_id, events[4].event_id);
+
+        let stream = CompressedEventStream {
+            data: compressor.compress_bytes(&legacy).unwrap(),
+            event_count: 5,
+            compression_ratio: 1.0,
+        };
+        assert_eq!(stream.decompress().unwrap()[0].event_id, events[0].event_id);
+    }
+}
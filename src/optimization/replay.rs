@@ -2,11 +2,14 @@
 
 use crate::events::GraphEvent;
 use crate::core::{build_projection, GraphAggregateProjection};
+use crate::core::snapshot_store::{MemorySnapshotStore, SnapshotRetention, SnapshotStore};
 use crate::error::Result;
 use std::collections::{HashMap, BTreeMap};
 use uuid::Uuid;
 use std::sync::{Arc, RwLock};
 
+pub use crate::core::projection_engine::ProjectionSnapshot;
+
 /// Strategy for replaying events
 #[derive(Debug, Clone, Copy)]
 pub enum ReplayStrategy {
@@ -29,24 +32,11 @@ pub enum ReplayStrategy {
     },
 }
 
-/// Snapshot of projection state at a point in time
-#[derive(Debug, Clone)]
-pub struct ProjectionSnapshot {
-    /// Sequence number of last event in snapshot
-    pub sequence: u64,
-    /// Aggregate ID
-    pub aggregate_id: Uuid,
-    /// Serialized projection state
-    pub state: Vec<u8>,
-    /// When snapshot was taken
-    pub timestamp: std::time::SystemTime,
-}
-
 /// Replay optimizer that manages snapshots and efficient replay
 #[derive(Debug)]
 pub struct ReplayOptimizer {
-    /// Snapshots by aggregate ID
-    snapshots: Arc<RwLock<HashMap<Uuid, Vec<ProjectionSnapshot>>>>,
+    /// Snapshot backend
+    snapshots: Arc<RwLock<Box<dyn SnapshotStore>>>,
     /// Configuration
     config: ReplayConfig,
 }
@@ -73,34 +63,39 @@ impl Default for ReplayConfig {
 }
 
 impl ReplayOptimizer {
-    /// Create a new replay optimizer
+    /// Create a new replay optimizer keeping `max_snapshots` in memory
     pub fn new(config: ReplayConfig) -> Self {
+        let store = MemorySnapshotStore::new(SnapshotRetention::keep_last(config.max_snapshots));
+        Self::with_store(config, Box::new(store))
+    }
+    
+    /// Create a replay optimizer on a snapshot backend
+    ///
+    /// The backend's own retention policy applies; `max_snapshots` is ignored.
+    pub fn with_store(config: ReplayConfig, store: Box<dyn SnapshotStore>) -> Self {
         Self {
-            snapshots: Arc::new(RwLock::new(HashMap::new())),
+            snapshots: Arc::new(RwLock::new(store)),
             config,
         }
     }
     
     /// Determine optimal replay strategy for an aggregate
+    ///
+    /// Any stored snapshot at or before `current_sequence` is used, since
+    /// replaying from it never touches more events than a full replay.
     pub fn determine_strategy(
         &self,
         aggregate_id: Uuid,
         current_sequence: u64,
         total_events: usize,
     ) -> ReplayStrategy {
-        let snapshots = self.snapshots.read().unwrap();
+        // An unreadable store just means replaying from events
+        let latest = self.snapshots.read().unwrap()
+            .latest_version_at(aggregate_id, current_sequence)
+            .unwrap_or(None);
         
-        if let Some(aggregate_snapshots) = snapshots.get(&aggregate_id) {
-            if let Some(latest_snapshot) = aggregate_snapshots.last() {
-                let events_since_snapshot = current_sequence - latest_snapshot.sequence;
-                
-                // If we have a recent snapshot and few events since, use it
-                if events_since_snapshot < self.config.snapshot_interval * 2 {
-                    return ReplayStrategy::FromSnapshot {
-                        sequence: latest_snapshot.sequence,
-                    };
-                }
-            }
+        if let Some(sequence) = latest {
+            return ReplayStrategy::FromSnapshot { sequence };
         }
         
         // For small event counts, just replay all
@@ -115,6 +110,9 @@ impl ReplayOptimizer {
     }
     
     /// Replay events with optimal strategy
+    ///
+    /// `FromSnapshot` restores the snapshot and applies only later events; if
+    /// the snapshot is gone, all events are replayed instead.
     pub fn replay_events(
         &self,
         events: Vec<(GraphEvent, u64)>,
@@ -124,9 +122,19 @@ impl ReplayOptimizer {
             ReplayStrategy::Full => events,
             
             ReplayStrategy::FromSnapshot { sequence } => {
-                events.into_iter()
-                    .filter(|(_, seq)| *seq > sequence)
-                    .collect()
+                let restored = match events.first() {
+                    Some((event, _)) => self.load_snapshot(event.aggregate_id, sequence)?,
+                    None => None,
+                };
+                match restored {
+                    Some(mut projection) => {
+                        for (event, seq) in events.iter().filter(|(_, seq)| *seq > sequence) {
+                            projection.apply(event, *seq);
+                        }
+                        return Ok(projection);
+                    }
+                    None => events,
+                }
             }
             
             ReplayStrategy::Recent { max_events } => {
@@ -149,28 +157,26 @@ impl ReplayOptimizer {
         projection: &GraphAggregateProjection,
         sequence: u64,
     ) -> Result<()> {
-        let snapshot = ProjectionSnapshot {
+        let snapshot = ProjectionSnapshot::new(
+            projection.aggregate_id,
             sequence,
-            aggregate_id: projection.aggregate_id,
-            state: bincode::serialize(projection)
-                .map_err(|e| crate::error::GraphError::SerializationError(e.to_string()))?,
-            timestamp: std::time::SystemTime::now(),
-        };
-        
-        let mut snapshots = self.snapshots.write().unwrap();
-        let aggregate_snapshots = snapshots
-            .entry(projection.aggregate_id)
-            .or_insert_with(Vec::new);
-        
-        aggregate_snapshots.push(snapshot);
+            serde_json::to_value(projection)?,
+        );
         
-        // Keep only max_snapshots most recent
-        if aggregate_snapshots.len() > self.config.max_snapshots {
-            let remove_count = aggregate_snapshots.len() - self.config.max_snapshots;
-            aggregate_snapshots.drain(0..remove_count);
+        self.snapshots.write().unwrap().save(&snapshot)
+    }
+    
+    /// Load the projection stored in the snapshot at `sequence`
+    pub fn load_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        sequence: u64,
+    ) -> Result<Option<GraphAggregateProjection>> {
+        let snapshot = self.snapshots.read().unwrap().load(aggregate_id, sequence)?;
+        match snapshot {
+            Some(snapshot) => Ok(Some(serde_json::from_value(snapshot.data)?)),
+            None => Ok(None),
         }
-        
-        Ok(())
     }
     
     /// Check if a snapshot should be created
@@ -179,12 +185,12 @@ impl ReplayOptimizer {
             return false;
         }
         
-        let snapshots = self.snapshots.read().unwrap();
-        if let Some(aggregate_snapshots) = snapshots.get(&aggregate_id) {
-            if let Some(latest) = aggregate_snapshots.last() {
-                // Don't snapshot if we recently did
-                return current_sequence > latest.sequence + self.config.snapshot_interval / 2;
-            }
+        let latest = self.snapshots.read().unwrap()
+            .latest_version_at(aggregate_id, u64::MAX)
+            .unwrap_or(None);
+        if let Some(latest) = latest {
+            // Don't snapshot if we recently did
+            return current_sequence > latest + self.config.snapshot_interval / 2;
         }
         
         true
@@ -192,16 +198,25 @@ impl ReplayOptimizer {
     
     /// Get snapshot statistics
     pub fn snapshot_stats(&self) -> SnapshotStats {
-        let snapshots = self.snapshots.read().unwrap();
+        let store = self.snapshots.read().unwrap();
         
-        let total_snapshots: usize = snapshots.values().map(|v| v.len()).sum();
-        let total_size: usize = snapshots.values()
-            .flat_map(|v| v.iter())
-            .map(|s| s.state.len())
-            .sum();
+        let mut aggregate_count = 0;
+        let mut total_snapshots = 0;
+        let mut total_size = 0;
+        for aggregate_id in store.aggregate_ids().unwrap_or_default() {
+            let versions = store.versions(aggregate_id).unwrap_or_default();
+            if versions.is_empty() {
+                continue;
+            }
+            aggregate_count += 1;
+            total_snapshots += versions.len();
+            total_size += versions.iter()
+                .filter_map(|version| store.stored_size(aggregate_id, *version).ok().flatten())
+                .sum::<u64>() as usize;
+        }
         
         SnapshotStats {
-            aggregate_count: snapshots.len(),
+            aggregate_count,
             total_snapshots,
             total_size_bytes: total_size,
             avg_snapshot_size: if total_snapshots > 0 {
@@ -743,12 +758,7 @@ mod tests {
 
     #[test]
     fn test_projection_snapshot_debug() {
-        let snapshot = ProjectionSnapshot {
-            sequence: 100,
-            aggregate_id: Uuid::new_v4(),
-            state: vec![1, 2, 3, 4],
-            timestamp: std::time::SystemTime::now(),
-        };
+        let snapshot = ProjectionSnapshot::new(Uuid::new_v4(), 100, serde_json::json!([1, 2, 3, 4]));
 
         let debug_str = format!("{:?}", snapshot);
         assert!(debug_str.contains("ProjectionSnapshot"));
@@ -804,4 +814,80 @@ mod tests {
         // Should use FromSnapshot since we have a recent snapshot
         assert!(matches!(strategy, ReplayStrategy::FromSnapshot { sequence: 50 }));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_determine_strategy_ignores_future_snapshots() {
+        let optimizer = ReplayOptimizer::new(ReplayConfig::default());
+        let aggregate_id = Uuid::new_v4();
+
+        let projection = build_projection(create_test_events(aggregate_id, 50));
+        optimizer.create_snapshot(&projection, 50).unwrap();
+
+        // A snapshot past the requested sequence cannot seed the replay
+        let strategy = optimizer.determine_strategy(aggregate_id, 40, 40);
+        assert!(matches!(strategy, ReplayStrategy::Full));
+
+        // An old snapshot is still cheaper than replaying everything
+        let strategy = optimizer.determine_strategy(aggregate_id, 5000, 5000);
+        assert!(matches!(strategy, ReplayStrategy::FromSnapshot { sequence: 50 }));
+    }
+
+    #[test]
+    fn test_replay_from_snapshot_matches_full_replay() {
+        let optimizer = ReplayOptimizer::new(ReplayConfig::default());
+        let aggregate_id = Uuid::new_v4();
+        let events = create_test_events(aggregate_id, 20);
+
+        let snapshot_projection = build_projection(events[..12].to_vec());
+        optimizer.create_snapshot(&snapshot_projection, 12).unwrap();
+
+        let strategy = optimizer.determine_strategy(aggregate_id, 20, 20);
+        assert!(matches!(strategy, ReplayStrategy::FromSnapshot { sequence: 12 }));
+
+        let from_snapshot = optimizer.replay_events(events.clone(), strategy).unwrap();
+        let full = optimizer.replay_events(events, ReplayStrategy::Full).unwrap();
+        assert_eq!(from_snapshot.version, 20);
+        assert_eq!(from_snapshot.components, full.components);
+        assert_eq!(from_snapshot.relationships, full.relationships);
+    }
+
+    #[test]
+    fn test_replay_from_missing_snapshot_replays_everything() {
+        let optimizer = ReplayOptimizer::new(ReplayConfig::default());
+        let aggregate_id = Uuid::new_v4();
+        let events = create_test_events(aggregate_id, 10);
+
+        let projection = optimizer.replay_events(
+            events.clone(),
+            ReplayStrategy::FromSnapshot { sequence: 5 },
+        ).unwrap();
+        let full = build_projection(events);
+
+        assert_eq!(projection.components, full.components);
+    }
+
+    #[test]
+    fn test_optimizer_with_file_store() {
+        use crate::core::snapshot_store::FileSnapshotStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let aggregate_id = Uuid::new_v4();
+        let projection = build_projection(create_test_events(aggregate_id, 10));
+
+        {
+            let store = FileSnapshotStore::open(dir.path(), SnapshotRetention::default()).unwrap();
+            let optimizer = ReplayOptimizer::with_store(ReplayConfig::default(), Box::new(store));
+            optimizer.create_snapshot(&projection, 10).unwrap();
+        }
+
+        // A new optimizer on the same directory picks the snapshot up
+        let store = FileSnapshotStore::open(dir.path(), SnapshotRetention::default()).unwrap();
+        let optimizer = ReplayOptimizer::with_store(ReplayConfig::default(), Box::new(store));
+        assert!(matches!(
+            optimizer.determine_strategy(aggregate_id, 15, 15),
+            ReplayStrategy::FromSnapshot { sequence: 10 }
+        ));
+        let restored = optimizer.load_snapshot(aggregate_id, 10).unwrap().unwrap();
+        assert_eq!(restored.components, projection.components);
+    }
+}
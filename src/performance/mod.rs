@@ -6,10 +6,12 @@
 //! - Memory pooling
 //! - Parallel operations
 
+use crate::algorithms::LandmarkIndex;
 use crate::core::{Node, Edge};
-use crate::error::Result;
+use crate::error::{GraphError, Result};
 use std::collections::{HashMap, BTreeMap};
 use std::sync::{Arc, RwLock};
+use uuid::Uuid;
 use rayon::prelude::*;
 
 /// Index for fast node lookups by various properties
@@ -142,6 +144,9 @@ pub struct GraphCache {
     /// Cache for connected components
     components: RwLock<Option<Vec<Vec<String>>>>,
     
+    /// Cache for ALT landmark indexes, one per aggregate and weight function
+    landmarks: RwLock<HashMap<(Uuid, String), Arc<LandmarkIndex>>>,
+    
     /// Cache generation number - increment to invalidate all caches
     generation: RwLock<u64>,
 }
@@ -153,6 +158,7 @@ impl GraphCache {
             shortest_paths: RwLock::new(HashMap::new()),
             degrees: RwLock::new(HashMap::new()),
             components: RwLock::new(None),
+            landmarks: RwLock::new(HashMap::new()),
             generation: RwLock::new(0),
         }
     }
@@ -166,6 +172,7 @@ impl GraphCache {
         self.shortest_paths.write().unwrap().clear();
         self.degrees.write().unwrap().clear();
         *self.components.write().unwrap() = None;
+        self.landmarks.write().unwrap().clear();
     }
     
     /// Get or compute shortest path
@@ -190,6 +197,47 @@ impl GraphCache {
         self.shortest_paths.write().unwrap().insert(key, path.clone());
         Ok(path)
     }
+    
+    /// Get or build the landmark index for a projection version
+    ///
+    /// An index bakes in the edge weights it was built with, so `weights`
+    /// names the weight function and indexes built with different weights
+    /// are cached apart. Only the latest version is kept for each aggregate
+    /// and weight function; an index built for an older version is replaced
+    /// rather than returned. Fails if `compute` builds an index for another
+    /// aggregate or version.
+    pub fn get_landmarks<F>(
+        &self,
+        aggregate_id: Uuid,
+        version: u64,
+        weights: &str,
+        compute: F,
+    ) -> Result<Arc<LandmarkIndex>>
+    where
+        F: FnOnce() -> LandmarkIndex,
+    {
+        let key = (aggregate_id, weights.to_string());
+        let matches = |index: &LandmarkIndex| index.aggregate_id() == aggregate_id && index.version() == version;
+        
+        if let Some(index) = self.landmarks.read().unwrap().get(&key) {
+            if matches(index) {
+                return Ok(index.clone());
+            }
+        }
+        
+        let index = Arc::new(compute());
+        if !matches(&index) {
+            return Err(GraphError::InvalidOperation(format!(
+                "Landmark index for aggregate {} version {} cannot be cached as aggregate {} version {}",
+                index.aggregate_id(),
+                index.version(),
+                aggregate_id,
+                version
+            )));
+        }
+        self.landmarks.write().unwrap().insert(key, index.clone());
+        Ok(index)
+    }
 }
 
 /// Memory pool for node allocations
@@ -402,6 +450,82 @@ mod tests {
         assert!(cached.is_empty());
     }
     
+    #[test]
+    fn test_cache_landmarks_per_version() {
+        use crate::core::projection_engine::GenericGraphProjection;
+        use crate::graphs::workflow::{WorkflowEdge, WorkflowNode};
+        
+        let mut projection: GenericGraphProjection<WorkflowNode, WorkflowEdge> =
+            GenericGraphProjection::new(Uuid::new_v4(), crate::core::GraphType::Generic);
+        for id in ["a", "b"] {
+            projection.nodes.insert(id.to_string(), WorkflowNode::state(id, id));
+        }
+        projection.adjacency.insert("a".to_string(), vec!["b".to_string()]);
+        
+        let cache = GraphCache::new();
+        let builds = std::cell::Cell::new(0);
+        let build = |projection: &GenericGraphProjection<WorkflowNode, WorkflowEdge>| {
+            builds.set(builds.get() + 1);
+            LandmarkIndex::build(projection, 2, |_, _| 1)
+        };
+        let (id, version) = (projection.aggregate_id, projection.version);
+        
+        let first = cache.get_landmarks(id, version, "hops", || build(&projection)).unwrap();
+        let again = cache.get_landmarks(id, version, "hops", || build(&projection)).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(builds.get(), 1);
+        
+        // A new version rebuilds and replaces the old index
+        projection.version += 1;
+        let newer = cache.get_landmarks(id, projection.version, "hops", || build(&projection)).unwrap();
+        assert_eq!(newer.version(), projection.version);
+        assert_eq!(builds.get(), 2);
+        
+        // An index built for another version is refused
+        let stale = cache.get_landmarks(id, projection.version + 1, "hops", || build(&projection));
+        assert!(stale.is_err());
+        let newest = cache.get_landmarks(id, projection.version, "hops", || build(&projection)).unwrap();
+        assert!(Arc::ptr_eq(&newer, &newest));
+        
+        cache.invalidate();
+        assert!(cache.landmarks.read().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_cache_landmarks_per_weight_function() {
+        use crate::core::projection_engine::GenericGraphProjection;
+        use crate::graphs::workflow::{WorkflowEdge, WorkflowNode};
+        
+        // a -> b -> c, plus a direct a -> c
+        let mut projection: GenericGraphProjection<WorkflowNode, WorkflowEdge> =
+            GenericGraphProjection::new(Uuid::new_v4(), crate::core::GraphType::Generic);
+        for id in ["a", "b", "c"] {
+            projection.nodes.insert(id.to_string(), WorkflowNode::state(id, id));
+        }
+        projection.adjacency.insert("a".to_string(), vec!["b".to_string(), "c".to_string()]);
+        projection.adjacency.insert("b".to_string(), vec!["c".to_string()]);
+        let direct_is_slow = |from: &str, to: &str| if (from, to) == ("a", "c") { 10 } else { 1 };
+        
+        let cache = GraphCache::new();
+        let (id, version) = (projection.aggregate_id, projection.version);
+        let hops = cache
+            .get_landmarks(id, version, "hops", || LandmarkIndex::build(&projection, 2, |_, _| 1))
+            .unwrap();
+        let weighted = cache
+            .get_landmarks(id, version, "direct-is-slow", || LandmarkIndex::build(&projection, 2, direct_is_slow))
+            .unwrap();
+        
+        assert!(!Arc::ptr_eq(&hops, &weighted));
+        assert_eq!(hops.shortest_path("a", "c").unwrap().map(|(_, cost)| cost), Some(1));
+        assert_eq!(weighted.shortest_path("a", "c").unwrap().map(|(_, cost)| cost), Some(2));
+        
+        // Both stay cached under their own keys
+        let again = cache
+            .get_landmarks(id, version, "direct-is-slow", || unreachable!("index should be cached"))
+            .unwrap();
+        assert!(Arc::ptr_eq(&weighted, &again));
+    }
+    
     #[test]
     fn test_node_pool() {
         #[derive(Default, Debug, PartialEq)]
@@ -0,0 +1,632 @@
+//! Plan execution against a projection
+
+use super::parser::{BinaryOp, Expr, Function, QueryAst, RelDirection};
+use super::planner::{Hop, Plan, Statistics, Step};
+use super::QueryResult;
+use crate::core::projection_engine::type_key;
+use crate::core::{Edge, GraphProjection, Node, ToEventData};
+use crate::error::Result;
+use serde_json::Value;
+use std::cell::OnceCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// Projection nodes and edges by position, sorted by ID, with adjacency
+pub(crate) struct GraphIndex<'a, N, E> {
+    nodes: Vec<&'a N>,
+    node_ids: Vec<String>,
+    node_types: Vec<String>,
+    node_properties: Vec<OnceCell<Value>>,
+    edges: Vec<&'a E>,
+    edge_ids: Vec<String>,
+    edge_types: Vec<String>,
+    edge_properties: Vec<OnceCell<Value>>,
+    /// (source, target) of each edge
+    edge_ends: Vec<(usize, usize)>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl<'a, N, E> GraphIndex<'a, N, E>
+where
+    N: Node + ToEventData,
+    E: Edge + ToEventData,
+{
+    pub(crate) fn new<P>(projection: &'a P) -> Self
+    where
+        P: GraphProjection<Node = N, Edge = E>,
+    {
+        let mut nodes = projection.nodes();
+        nodes.sort_by_key(|node| node.id());
+        let node_ids: Vec<String> = nodes.iter().map(|node| node.id()).collect();
+        let position: HashMap<&str, usize> = node_ids.iter().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+
+        let mut edges: Vec<&E> = projection.edges();
+        edges.sort_by_key(|edge| edge.id());
+        let mut edge_ends = Vec::with_capacity(edges.len());
+        edges.retain(|edge| {
+            match (position.get(edge.source().as_str()), position.get(edge.target().as_str())) {
+                (Some(&source), Some(&target)) => {
+                    edge_ends.push((source, target));
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        let mut outgoing = vec![Vec::new(); nodes.len()];
+        let mut incoming = vec![Vec::new(); nodes.len()];
+        for (edge, &(source, target)) in edge_ends.iter().enumerate() {
+            outgoing[source].push(edge);
+            incoming[target].push(edge);
+        }
+
+        Self {
+            node_types: nodes.iter().map(|node| node.event_type()).collect(),
+            node_properties: nodes.iter().map(|_| OnceCell::new()).collect(),
+            edge_ids: edges.iter().map(|edge| edge.id()).collect(),
+            edge_types: edges.iter().map(|edge| edge.event_type()).collect(),
+            edge_properties: edges.iter().map(|_| OnceCell::new()).collect(),
+            nodes,
+            node_ids,
+            edges,
+            edge_ends,
+            outgoing,
+            incoming,
+        }
+    }
+
+    pub(crate) fn statistics(&self) -> Statistics {
+        let mut by_type = HashMap::new();
+        for node_type in &self.node_types {
+            *by_type.entry(type_key(node_type)).or_insert(0) += 1;
+        }
+        Statistics {
+            total: self.nodes.len(),
+            by_type,
+        }
+    }
+
+    fn node_properties(&self, node: usize) -> &Value {
+        self.node_properties[node].get_or_init(|| self.nodes[node].event_payload())
+    }
+
+    fn edge_properties(&self, edge: usize) -> &Value {
+        self.edge_properties[edge].get_or_init(|| self.edges[edge].event_payload())
+    }
+
+    fn node_value(&self, node: usize) -> Value {
+        serde_json::json!({
+            "id": self.node_ids[node],
+            "type": self.node_types[node],
+            "properties": self.node_properties(node),
+        })
+    }
+
+    fn edge_value(&self, edge: usize) -> Value {
+        let (source, target) = self.edge_ends[edge];
+        serde_json::json!({
+            "id": self.edge_ids[edge],
+            "type": self.edge_types[edge],
+            "source": self.node_ids[source],
+            "target": self.node_ids[target],
+            "properties": self.edge_properties(edge),
+        })
+    }
+
+    /// Edges leaving `node` in `direction`, with the node at their other end
+    fn adjacent(&self, node: usize, direction: RelDirection) -> Vec<(usize, usize)> {
+        let outgoing = self.outgoing[node].iter().map(|&edge| (edge, self.edge_ends[edge].1));
+        let incoming = self.incoming[node].iter().map(|&edge| (edge, self.edge_ends[edge].0));
+        match direction {
+            RelDirection::Outgoing => outgoing.collect(),
+            RelDirection::Incoming => incoming.collect(),
+            // A self-loop appears in both lists but is one relationship
+            RelDirection::Either => outgoing.chain(incoming.filter(|&(_, other)| other != node)).collect(),
+        }
+    }
+}
+
+/// What a slot is bound to in the current row
+#[derive(Debug, Clone)]
+enum Bound {
+    Node(usize),
+    Edge(usize),
+    /// Edges of a variable-length relationship, in pattern order
+    Path(Vec<usize>),
+}
+
+/// A partial match
+struct Row {
+    bindings: Vec<Option<Bound>>,
+    /// Edges already matched; a relationship is used at most once per row
+    used: HashSet<usize>,
+}
+
+/// Where a sort key comes from
+enum SortKey<'q> {
+    Column(usize),
+    Expr(&'q Expr),
+}
+
+/// Evaluates a plan and collects the result rows
+struct Executor<'q, 'a, N, E> {
+    index: &'q GraphIndex<'a, N, E>,
+    plan: &'q Plan,
+    parameters: &'q HashMap<String, Value>,
+}
+
+/// Run `plan` for `query` over `index`
+pub(crate) fn execute<N, E>(
+    query: &QueryAst,
+    plan: &Plan,
+    index: &GraphIndex<'_, N, E>,
+    parameters: &HashMap<String, Value>,
+) -> Result<QueryResult>
+where
+    N: Node + ToEventData,
+    E: Edge + ToEventData,
+{
+    let executor = Executor {
+        index,
+        plan,
+        parameters,
+    };
+
+    let aliases: HashMap<&str, usize> = query
+        .returns
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| item.alias.as_deref().map(|alias| (alias, i)))
+        .collect();
+    let sort_keys: Vec<(SortKey<'_>, bool)> = query
+        .order_by
+        .iter()
+        .map(|item| {
+            let key = match &item.expr {
+                Expr::Variable(name) if aliases.contains_key(name.as_str()) => SortKey::Column(aliases[name.as_str()]),
+                expr => SortKey::Expr(expr),
+            };
+            (key, item.descending)
+        })
+        .collect();
+
+    // Without sorting or de-duplication the first rows are the final ones
+    let wanted = match (query.order_by.is_empty() && !query.distinct, query.limit) {
+        (true, Some(limit)) => query.skip.unwrap_or(0).saturating_add(limit),
+        _ => usize::MAX,
+    };
+
+    let mut rows: Vec<(Vec<Value>, Vec<Value>)> = Vec::new();
+    let mut row = Row {
+        bindings: vec![None; plan.slots.len()],
+        used: HashSet::new(),
+    };
+    if wanted > 0 {
+        executor.run(0, &mut row, &mut |row| {
+            let values = query
+                .returns
+                .iter()
+                .map(|item| executor.eval(&item.expr, row))
+                .collect::<Result<Vec<_>>>()?;
+            let keys = sort_keys
+                .iter()
+                .map(|(key, _)| match key {
+                    SortKey::Column(column) => Ok(values[*column].clone()),
+                    SortKey::Expr(expr) => executor.eval(expr, row),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            rows.push((values, keys));
+            Ok(rows.len() < wanted)
+        })?;
+    }
+
+    if !sort_keys.is_empty() {
+        rows.sort_by(|(_, a), (_, b)| {
+            a.iter()
+                .zip(b)
+                .zip(&sort_keys)
+                .map(|((a, b), (_, descending))| {
+                    let order = sort_order(a, b);
+                    if *descending {
+                        order.reverse()
+                    } else {
+                        order
+                    }
+                })
+                .find(|order| order.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    let mut rows: Vec<Vec<Value>> = rows.into_iter().map(|(values, _)| values).collect();
+    if query.distinct {
+        let mut seen = HashSet::new();
+        rows.retain(|values| seen.insert(Value::Array(values.clone()).to_string()));
+    }
+    let rows = rows
+        .into_iter()
+        .skip(query.skip.unwrap_or(0))
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(QueryResult {
+        columns: query.returns.iter().map(|item| item.column()).collect(),
+        rows,
+    })
+}
+
+/// Row callback; returns whether to keep producing rows
+type Emit<'e> = dyn FnMut(&Row) -> Result<bool> + 'e;
+
+impl<N, E> Executor<'_, '_, N, E>
+where
+    N: Node + ToEventData,
+    E: Edge + ToEventData,
+{
+    /// Run the steps from `step` on, emitting complete rows
+    fn run(&self, step: usize, row: &mut Row, emit: &mut Emit<'_>) -> Result<bool> {
+        let current = match self.plan.steps.get(step) {
+            Some(current) => current,
+            None => return emit(row),
+        };
+
+        match current {
+            Step::Scan { node: slot } => {
+                for node in 0..self.index.nodes.len() {
+                    if self.node_matches(*slot, node)? {
+                        row.bindings[*slot] = Some(Bound::Node(node));
+                        let more = self.run(step + 1, row, emit)?;
+                        row.bindings[*slot] = None;
+                        if !more {
+                            return Ok(false);
+                        }
+                    }
+                }
+                Ok(true)
+            }
+            Step::Filter(expr) => {
+                if self.eval(expr, row)? == Value::Bool(true) {
+                    self.run(step + 1, row, emit)
+                } else {
+                    Ok(true)
+                }
+            }
+            Step::Expand {
+                from,
+                to,
+                hop,
+                direction,
+                reversed,
+            } => {
+                let start = match row.bindings[*from] {
+                    Some(Bound::Node(node)) => node,
+                    _ => return Ok(true),
+                };
+                let expansion = Expansion {
+                    next: step + 1,
+                    to: *to,
+                    target: match row.bindings[*to] {
+                        Some(Bound::Node(node)) => Some(node),
+                        _ => None,
+                    },
+                    hop,
+                    direction: *direction,
+                    reversed: *reversed,
+                };
+                match hop.length {
+                    None => self.expand_once(&expansion, start, row, emit),
+                    Some(_) => self.expand_path(&expansion, start, row, emit),
+                }
+            }
+        }
+    }
+
+    fn expand_once(&self, expansion: &Expansion<'_>, start: usize, row: &mut Row, emit: &mut Emit<'_>) -> Result<bool> {
+        for (edge, other) in self.index.adjacent(start, expansion.direction) {
+            if row.used.contains(&edge) || !self.edge_matches(expansion.hop, edge)? {
+                continue;
+            }
+            row.used.insert(edge);
+            let more = self.arrive(expansion, other, || Bound::Edge(edge), row, emit);
+            row.used.remove(&edge);
+            if !more? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Depth-first walk over unused edges, emitting every endpoint within the hop range
+    fn expand_path(&self, expansion: &Expansion<'_>, start: usize, row: &mut Row, emit: &mut Emit<'_>) -> Result<bool> {
+        let mut path = Vec::new();
+        let more = self.walk_paths(expansion, start, &mut path, row, emit);
+        // Release the edges of the path the walk stopped on
+        for edge in path {
+            row.used.remove(&edge);
+        }
+        more
+    }
+
+    /// The walk behind [`Self::expand_path`], on an explicit stack so that
+    /// unbounded lengths over long chains cannot overflow the call stack
+    fn walk_paths(
+        &self,
+        expansion: &Expansion<'_>,
+        start: usize,
+        path: &mut Vec<usize>,
+        row: &mut Row,
+        emit: &mut Emit<'_>,
+    ) -> Result<bool> {
+        let (min, max) = expansion.hop.length.unwrap_or((1, Some(1)));
+        // Edges still to try from the node at the end of each path prefix
+        let mut pending: Vec<std::vec::IntoIter<(usize, usize)>> = Vec::new();
+        let mut reached = Some(start);
+        loop {
+            if let Some(node) = reached.take() {
+                if path.len() >= min {
+                    let relationship = || {
+                        let mut edges = path.clone();
+                        if expansion.reversed {
+                            edges.reverse();
+                        }
+                        Bound::Path(edges)
+                    };
+                    if !self.arrive(expansion, node, relationship, row, emit)? {
+                        return Ok(false);
+                    }
+                }
+                let next = if max.is_some_and(|max| path.len() >= max) {
+                    Vec::new()
+                } else {
+                    self.index.adjacent(node, expansion.direction)
+                };
+                pending.push(next.into_iter());
+            }
+
+            let Some(edges) = pending.last_mut() else {
+                return Ok(true);
+            };
+            match edges.next() {
+                Some((edge, other)) => {
+                    if !row.used.contains(&edge) && self.edge_matches(expansion.hop, edge)? {
+                        row.used.insert(edge);
+                        path.push(edge);
+                        reached = Some(other);
+                    }
+                }
+                None => {
+                    // Done with this node; step back over the edge that reached it
+                    pending.pop();
+                    if let Some(edge) = path.pop() {
+                        row.used.remove(&edge);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Bind the relationship and the far node, then continue with the next step
+    ///
+    /// `relationship` is only built once the far node matches.
+    fn arrive(
+        &self,
+        expansion: &Expansion<'_>,
+        node: usize,
+        relationship: impl FnOnce() -> Bound,
+        row: &mut Row,
+        emit: &mut Emit<'_>,
+    ) -> Result<bool> {
+        match expansion.target {
+            Some(target) if target != node => return Ok(true),
+            Some(_) => {}
+            None if !self.node_matches(expansion.to, node)? => return Ok(true),
+            None => row.bindings[expansion.to] = Some(Bound::Node(node)),
+        }
+        row.bindings[expansion.hop.relationship] = Some(relationship());
+        let more = self.run(expansion.next, row, emit);
+        row.bindings[expansion.hop.relationship] = None;
+        if expansion.target.is_none() {
+            row.bindings[expansion.to] = None;
+        }
+        more
+    }
+
+    fn node_matches(&self, slot: usize, node: usize) -> Result<bool> {
+        let slot = &self.plan.slots[slot];
+        let node_type = type_key(&self.index.node_types[node]);
+        if !slot.labels.iter().all(|alternatives| alternatives.contains(&node_type)) {
+            return Ok(false);
+        }
+        self.properties_match(&slot.properties, self.index.node_properties(node))
+    }
+
+    fn edge_matches(&self, hop: &Hop, edge: usize) -> Result<bool> {
+        if !hop.types.is_empty() && !hop.types.contains(&type_key(&self.index.edge_types[edge])) {
+            return Ok(false);
+        }
+        self.properties_match(&hop.properties, self.index.edge_properties(edge))
+    }
+
+    fn properties_match(&self, expected: &[(String, Expr)], properties: &Value) -> Result<bool> {
+        let empty = Row {
+            bindings: Vec::new(),
+            used: HashSet::new(),
+        };
+        for (key, expr) in expected {
+            let wanted = self.eval(expr, &empty)?;
+            let actual = lookup(properties, std::slice::from_ref(key));
+            if values_equal(&actual, &wanted) != Some(true) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn binding(&self, row: &Row, name: &str) -> Option<Bound> {
+        self.plan.names.get(name).and_then(|&slot| row.bindings.get(slot).cloned().flatten())
+    }
+
+    fn eval(&self, expr: &Expr, row: &Row) -> Result<Value> {
+        Ok(match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Parameter(name) => self.parameters.get(name).cloned().unwrap_or(Value::Null),
+            Expr::Variable(name) => match self.binding(row, name) {
+                Some(Bound::Node(node)) => self.index.node_value(node),
+                Some(Bound::Edge(edge)) => self.index.edge_value(edge),
+                Some(Bound::Path(edges)) => Value::Array(edges.into_iter().map(|edge| self.index.edge_value(edge)).collect()),
+                None => Value::Null,
+            },
+            Expr::Property(name, path) => match self.binding(row, name) {
+                Some(Bound::Node(node)) => lookup(self.index.node_properties(node), path),
+                Some(Bound::Edge(edge)) => lookup(self.index.edge_properties(edge), path),
+                _ => Value::Null,
+            },
+            Expr::Function(function, name) => match (function, self.binding(row, name)) {
+                (Function::Id, Some(Bound::Node(node))) => self.index.node_ids[node].clone().into(),
+                (Function::Id, Some(Bound::Edge(edge))) => self.index.edge_ids[edge].clone().into(),
+                (Function::Type, Some(Bound::Node(node))) => self.index.node_types[node].clone().into(),
+                (Function::Type, Some(Bound::Edge(edge))) => self.index.edge_types[edge].clone().into(),
+                (Function::Length, Some(Bound::Edge(_))) => 1.into(),
+                (Function::Length, Some(Bound::Path(edges))) => edges.len().into(),
+                _ => Value::Null,
+            },
+            Expr::List(items) => Value::Array(items.iter().map(|item| self.eval(item, row)).collect::<Result<_>>()?),
+            Expr::Not(inner) => match self.eval(inner, row)? {
+                Value::Bool(value) => Value::Bool(!value),
+                _ => Value::Null,
+            },
+            Expr::Negate(inner) => match self.eval(inner, row)? {
+                Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                    (Some(i), _) => Value::from(-i),
+                    (None, Some(f)) => Value::from(-f),
+                    _ => Value::Null,
+                },
+                _ => Value::Null,
+            },
+            Expr::IsNull(inner, negated) => Value::Bool(self.eval(inner, row)?.is_null() != *negated),
+            Expr::Binary(BinaryOp::And, left, right) => {
+                logic(self.eval(left, row)?, self.eval(right, row)?, false)
+            }
+            Expr::Binary(BinaryOp::Or, left, right) => logic(self.eval(left, row)?, self.eval(right, row)?, true),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (self.eval(left, row)?, self.eval(right, row)?);
+                compare(*op, &left, &right).map(Value::Bool).unwrap_or(Value::Null)
+            }
+        })
+    }
+}
+
+/// A relationship being followed by an `Expand` step
+struct Expansion<'h> {
+    /// Step to run once the far node is bound
+    next: usize,
+    /// Slot of the far node
+    to: usize,
+    /// Node the far end must be, when already bound
+    target: Option<usize>,
+    hop: &'h Hop,
+    direction: RelDirection,
+    reversed: bool,
+}
+
+/// Follow a property path; keys missing at the top level are also looked up
+/// in a nested `properties` object, where concept nodes keep theirs
+fn lookup(properties: &Value, path: &[String]) -> Value {
+    let (first, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return properties.clone(),
+    };
+    let mut current = match properties.get(first) {
+        Some(value) => value,
+        None => match properties.get("properties").and_then(|nested| nested.get(first)) {
+            Some(value) => value,
+            None => return Value::Null,
+        },
+    };
+    for key in rest {
+        current = match current.get(key) {
+            Some(value) => value,
+            None => return Value::Null,
+        };
+    }
+    current.clone()
+}
+
+/// Three-valued AND (`any` false) or OR (`any` true)
+fn logic(left: Value, right: Value, any: bool) -> Value {
+    match (left.as_bool(), right.as_bool()) {
+        (Some(l), _) if l == any => Value::Bool(any),
+        (_, Some(r)) if r == any => Value::Bool(any),
+        (Some(_), Some(_)) => Value::Bool(!any),
+        _ => Value::Null,
+    }
+}
+
+/// Equality with numbers compared by value; `None` when either side is null
+fn values_equal(left: &Value, right: &Value) -> Option<bool> {
+    match (left, right) {
+        (Value::Null, _) | (_, Value::Null) => None,
+        (Value::Number(l), Value::Number(r)) => Some(l.as_f64() == r.as_f64()),
+        _ => Some(left == right),
+    }
+}
+
+/// Ordering between values of the same kind
+fn partial_order(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64()?.partial_cmp(&r.as_f64()?),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+/// Evaluate a comparison operator; `None` is a null result
+fn compare(op: BinaryOp, left: &Value, right: &Value) -> Option<bool> {
+    match op {
+        BinaryOp::Eq => values_equal(left, right),
+        BinaryOp::Ne => values_equal(left, right).map(|equal| !equal),
+        BinaryOp::Lt => partial_order(left, right).map(Ordering::is_lt),
+        BinaryOp::Le => partial_order(left, right).map(Ordering::is_le),
+        BinaryOp::Gt => partial_order(left, right).map(Ordering::is_gt),
+        BinaryOp::Ge => partial_order(left, right).map(Ordering::is_ge),
+        BinaryOp::Contains => match (left, right) {
+            (Value::String(l), Value::String(r)) => Some(l.contains(r.as_str())),
+            (Value::Array(items), value) => Some(items.iter().any(|item| values_equal(item, value) == Some(true))),
+            _ => None,
+        },
+        BinaryOp::StartsWith => Some(left.as_str()?.starts_with(right.as_str()?)),
+        BinaryOp::EndsWith => Some(left.as_str()?.ends_with(right.as_str()?)),
+        BinaryOp::In => match right {
+            Value::Array(items) => {
+                let results: Vec<Option<bool>> = items.iter().map(|item| values_equal(left, item)).collect();
+                if results.contains(&Some(true)) {
+                    Some(true)
+                } else if results.contains(&None) {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            _ => None,
+        },
+        BinaryOp::And | BinaryOp::Or => None,
+    }
+}
+
+/// Total order for `ORDER BY`: booleans, numbers, strings, other values, then nulls
+fn sort_order(left: &Value, right: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Bool(_) => 0,
+            Value::Number(_) => 1,
+            Value::String(_) => 2,
+            Value::Array(_) | Value::Object(_) => 3,
+            Value::Null => 4,
+        }
+    }
+    rank(left)
+        .cmp(&rank(right))
+        .then_with(|| partial_order(left, right).unwrap_or_else(|| left.to_string().cmp(&right.to_string())))
+}
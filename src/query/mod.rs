@@ -0,0 +1,431 @@
+//! Declarative graph queries over projections
+//!
+//! A small Cypher-like language for reading projections without hand-written
+//! loops over `nodes()`, `edges()` and `neighbors()`:
+//!
+//! ```text
+//! MATCH (a:Concept)-[:IsA*1..3]->(b) WHERE b.name = 'Animal' RETURN a.name
+//! ```
+//!
+//! Queries run against any [`GraphProjection`] whose nodes and edges
+//! implement [`ToEventData`]: a node's or edge's type is its
+//! [`event_type`](ToEventData::event_type), compared the way event types are
+//! (`:IsA`, `:is_a` and `:is-a` are the same), and its properties are the
+//! fields of its [`event_payload`](ToEventData::event_payload).
+//!
+//! # Supported syntax
+//!
+//! - `MATCH` one or more comma-separated paths. Nodes are written
+//!   `(var:Type|Other {key: value})` and relationships `-[var:TYPE]->`,
+//!   `<-[...]-` or `-[...]-`, with `*`, `*n`, `*min..max` for variable
+//!   length. A relationship is matched at most once per result row.
+//! - `WHERE` with `AND`, `OR`, `NOT`, `=`, `<>`, `<`, `<=`, `>`, `>=`,
+//!   `CONTAINS`, `STARTS WITH`, `ENDS WITH`, `IN [...]`, `IS [NOT] NULL`,
+//!   `$parameters` and the functions `id()`, `type()` and `length()`.
+//! - `RETURN [DISTINCT] expr [AS name], ...`, then `ORDER BY ... [DESC]`,
+//!   `SKIP n` and `LIMIT n`.
+//!
+//! `var.key` reads a payload field, falling back to the nested `properties`
+//! object concept nodes use. Returning a bare variable yields an object with
+//! the element's `id`, `type` and `properties`.
+//!
+//! [`QueryResult`] serializes to JSON, so a query service can answer requests
+//! on a [`channel_for_query`](crate::channels::channel_for_query) subject.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use cim_graph::query::Query;
+//!
+//! let query = Query::parse("MATCH (a:Concept)-[:IsA*1..3]->(b) WHERE b.name = $root RETURN a.name")?;
+//! let parameters = HashMap::from([("root".to_string(), json!("Animal"))]);
+//! let result = query.execute_with(&concepts, &parameters)?;
+//! for row in &result.rows {
+//!     println!("{}", row[0]);
+//! }
+//! ```
+
+mod executor;
+mod parser;
+mod planner;
+
+use crate::core::{Edge, GraphProjection, Node, ToEventData};
+use crate::error::{GraphError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+
+use executor::GraphIndex;
+use parser::QueryAst;
+
+/// A parsed query, reusable across projections
+#[derive(Debug, Clone)]
+pub struct Query {
+    text: String,
+    ast: QueryAst,
+}
+
+impl Query {
+    /// Parse query text
+    ///
+    /// Fails with [`GraphError::QuerySyntax`] pointing at the offending byte.
+    pub fn parse(text: &str) -> Result<Self> {
+        Ok(Self {
+            text: text.to_string(),
+            ast: parser::parse(text)?,
+        })
+    }
+
+    /// The query text as given
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Names of the result columns
+    pub fn columns(&self) -> Vec<String> {
+        self.ast.returns.iter().map(|item| item.column()).collect()
+    }
+
+    /// Names of the `$parameters` the query reads, sorted
+    pub fn parameters(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        let ast = &self.ast;
+        for path in &ast.patterns {
+            let inline = path
+                .hops
+                .iter()
+                .flat_map(|(relationship, node)| relationship.properties.iter().chain(&node.properties))
+                .chain(&path.start.properties);
+            for (_, expr) in inline {
+                expr.parameters(&mut names);
+            }
+        }
+        let expressions = ast
+            .filter
+            .iter()
+            .chain(ast.returns.iter().map(|item| &item.expr))
+            .chain(ast.order_by.iter().map(|item| &item.expr));
+        for expr in expressions {
+            expr.parameters(&mut names);
+        }
+        names.into_iter().collect::<BTreeSet<_>>().into_iter().collect()
+    }
+
+    /// Run the query against a projection
+    pub fn execute<P>(&self, projection: &P) -> Result<QueryResult>
+    where
+        P: GraphProjection,
+        P::Node: Node + ToEventData,
+        P::Edge: Edge + ToEventData,
+    {
+        self.execute_with(projection, &HashMap::new())
+    }
+
+    /// Run the query with values for its `$parameters`
+    ///
+    /// Fails with [`GraphError::InvalidOperation`] if a parameter is missing
+    /// or the query uses a variable that `MATCH` does not bind.
+    pub fn execute_with<P>(&self, projection: &P, parameters: &HashMap<String, Value>) -> Result<QueryResult>
+    where
+        P: GraphProjection,
+        P::Node: Node + ToEventData,
+        P::Edge: Edge + ToEventData,
+    {
+        if let Some(missing) = self.parameters().into_iter().find(|name| !parameters.contains_key(name)) {
+            return Err(GraphError::InvalidOperation(format!("Missing query parameter ${}", missing)));
+        }
+        let index = GraphIndex::new(projection);
+        let plan = planner::plan(&self.ast, &index.statistics())?;
+        executor::execute(&self.ast, &plan, &index, parameters)
+    }
+
+    /// The plan the query would run with against a projection, one step per line
+    pub fn explain<P>(&self, projection: &P) -> Result<String>
+    where
+        P: GraphProjection,
+        P::Node: Node + ToEventData,
+        P::Edge: Edge + ToEventData,
+    {
+        let index = GraphIndex::new(projection);
+        Ok(planner::plan(&self.ast, &index.statistics())?.to_string())
+    }
+}
+
+impl FromStr for Query {
+    type Err = GraphError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+/// Parse and run a query in one call
+pub fn execute<P>(projection: &P, text: &str) -> Result<QueryResult>
+where
+    P: GraphProjection,
+    P::Node: Node + ToEventData,
+    P::Edge: Edge + ToEventData,
+{
+    Query::parse(text)?.execute(projection)
+}
+
+/// Rows produced by a query
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryResult {
+    /// Column names, from `AS` aliases or the expressions as written
+    pub columns: Vec<String>,
+    /// One value per column in each row
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl QueryResult {
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the query matched nothing
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Every row's value in the named column
+    pub fn column(&self, name: &str) -> Option<Vec<&Value>> {
+        let index = self.columns.iter().position(|column| column == name)?;
+        Some(self.rows.iter().map(|row| &row[index]).collect())
+    }
+
+    /// Rows as JSON objects keyed by column name
+    pub fn records(&self) -> Vec<serde_json::Map<String, Value>> {
+        self.rows
+            .iter()
+            .map(|row| self.columns.iter().cloned().zip(row.iter().cloned()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GraphType;
+    use crate::graphs::composed::{ComposedEdge, ComposedEdgeType, ComposedGraph, ComposedNode, ComposedNodeType};
+    use crate::graphs::concept::{ConceptEdge, ConceptNode, ConceptNodeType, ConceptProjection};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    /// Dog, Cat -> Mammal -> Animal <- Bird <- Sparrow, plus Dog has-a Tail
+    fn create_taxonomy() -> ConceptProjection {
+        let mut projection = ConceptProjection::new(Uuid::new_v4(), GraphType::ConceptGraph);
+        let nodes = [
+            ConceptNode::new("animal", "Animal", ConceptNodeType::Category),
+            ConceptNode::new("mammal", "Mammal", ConceptNodeType::Concept),
+            ConceptNode::new("bird", "Bird", ConceptNodeType::Concept),
+            ConceptNode::new("dog", "Dog", ConceptNodeType::Concept).with_property("legs", json!(4)),
+            ConceptNode::new("cat", "Cat", ConceptNodeType::Concept).with_property("legs", json!(4)),
+            ConceptNode::new("sparrow", "Sparrow", ConceptNodeType::Concept).with_property("legs", json!(2)),
+            ConceptNode::new("tail", "Tail", ConceptNodeType::Property),
+        ];
+        for node in nodes {
+            projection.adjacency.insert(node.id.clone(), vec![]);
+            projection.nodes.insert(node.id.clone(), node);
+        }
+        let edges = [
+            ConceptEdge::is_a("e1", "mammal", "animal"),
+            ConceptEdge::is_a("e2", "bird", "animal"),
+            ConceptEdge::is_a("e3", "dog", "mammal"),
+            ConceptEdge::is_a("e4", "cat", "mammal"),
+            ConceptEdge::is_a("e5", "sparrow", "bird").with_strength(0.5),
+            ConceptEdge::has_a("e6", "dog", "tail"),
+        ];
+        for edge in edges {
+            projection.adjacency.get_mut(&edge.source).unwrap().push(edge.target.clone());
+            projection.edges.insert(edge.id.clone(), edge);
+        }
+        projection
+    }
+
+    fn strings(result: &QueryResult, column: &str) -> Vec<String> {
+        result
+            .column(column)
+            .unwrap()
+            .into_iter()
+            .map(|value| value.as_str().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    // ========== Query Execution Tests ==========
+
+    #[test]
+    fn test_variable_length_query() {
+        let projection = create_taxonomy();
+        let result = execute(
+            &projection,
+            "MATCH (a:Concept)-[:IsA*1..3]->(b) WHERE b.name = 'Animal' RETURN a.name ORDER BY a.name",
+        )
+        .unwrap();
+
+        assert_eq!(result.columns, vec!["a.name"]);
+        assert_eq!(strings(&result, "a.name"), vec!["Bird", "Cat", "Dog", "Mammal", "Sparrow"]);
+
+        let result = execute(
+            &projection,
+            "MATCH (a)-[p:is_a*2]->(b {name: 'Animal'}) RETURN id(a) AS id, length(p) AS hops ORDER BY id",
+        )
+        .unwrap();
+        assert_eq!(strings(&result, "id"), vec!["cat", "dog", "sparrow"]);
+        assert!(result.column("hops").unwrap().iter().all(|hops| **hops == json!(2)));
+    }
+
+    #[test]
+    fn test_directions_and_joined_patterns() {
+        let projection = create_taxonomy();
+
+        let children = execute(&projection, "MATCH (m {name: 'Mammal'})<-[:IsA]-(c) RETURN c.name ORDER BY c.name").unwrap();
+        assert_eq!(strings(&children, "c.name"), vec!["Cat", "Dog"]);
+
+        let neighbours = execute(&projection, "MATCH (m {name: 'Mammal'})--(n) RETURN n.name ORDER BY n.name").unwrap();
+        assert_eq!(strings(&neighbours, "n.name"), vec!["Animal", "Cat", "Dog"]);
+
+        // Siblings: two concepts sharing a parent, joined through the shared variable
+        let siblings = execute(
+            &projection,
+            "MATCH (a)-[:IsA]->(p), (b)-[:IsA]->(p) WHERE id(a) < id(b) RETURN a.name, b.name",
+        )
+        .unwrap();
+        assert_eq!(siblings.rows, vec![vec![json!("Bird"), json!("Mammal")], vec![json!("Cat"), json!("Dog")]]);
+    }
+
+    #[test]
+    fn test_filters_properties_and_functions() {
+        let projection = create_taxonomy();
+
+        let four_legged = execute(&projection, "MATCH (a) WHERE a.legs = 4 RETURN a.name ORDER BY a.name").unwrap();
+        assert_eq!(strings(&four_legged, "a.name"), vec!["Cat", "Dog"]);
+
+        let weak = execute(&projection, "MATCH (a)-[r]->(b) WHERE r.strength < 1 RETURN type(r), b.name").unwrap();
+        assert_eq!(weak.rows, vec![vec![json!("is_a"), json!("Bird")]]);
+
+        let result = execute(
+            &projection,
+            "MATCH (a) WHERE a.name STARTS WITH 'S' OR a.name IN ['Tail', 'Cat'] AND NOT a.legs IS NULL \
+             RETURN a.name ORDER BY a.name DESC",
+        )
+        .unwrap();
+        assert_eq!(strings(&result, "a.name"), vec!["Sparrow", "Cat"]);
+
+        let types = execute(&projection, "MATCH (a:Property|Category) RETURN DISTINCT type(a) AS t ORDER BY t").unwrap();
+        assert_eq!(strings(&types, "t"), vec!["category", "property"]);
+    }
+
+    #[test]
+    fn test_return_elements_skip_and_limit() {
+        let projection = create_taxonomy();
+        let result = execute(&projection, "MATCH (a:Concept)-[r:HasA]->(b) RETURN a, r, b.name").unwrap();
+
+        assert_eq!(result.len(), 1);
+        let record = &result.records()[0];
+        assert_eq!(record["a"]["id"], "dog");
+        assert_eq!(record["a"]["type"], "concept");
+        assert_eq!(record["a"]["properties"]["name"], "Dog");
+        assert_eq!(record["r"]["source"], "dog");
+        assert_eq!(record["r"]["target"], "tail");
+        assert_eq!(record["b.name"], "Tail");
+
+        let page = execute(&projection, "MATCH (a) RETURN id(a) AS id ORDER BY id SKIP 2 LIMIT 2").unwrap();
+        assert_eq!(strings(&page, "id"), vec!["cat", "dog"]);
+
+        let first = execute(&projection, "MATCH (a) RETURN id(a) LIMIT 3").unwrap();
+        assert_eq!(first.len(), 3);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let projection = create_taxonomy();
+        let query = Query::parse("MATCH (a)-[:IsA]->(b {name: $parent}) WHERE a.legs >= $legs RETURN a.name").unwrap();
+        assert_eq!(query.parameters(), vec!["legs", "parent"]);
+
+        let parameters = HashMap::from([("parent".to_string(), json!("Mammal")), ("legs".to_string(), json!(4))]);
+        let result = query.execute_with(&projection, &parameters).unwrap();
+        assert_eq!(result.len(), 2);
+
+        assert!(matches!(query.execute(&projection), Err(GraphError::InvalidOperation(_))));
+    }
+
+    #[test]
+    fn test_relationships_are_not_reused() {
+        let projection = create_taxonomy();
+        // Undirected two-hop walks may not come back over the same edge
+        let result = execute(&projection, "MATCH (a {name: 'Dog'})-[*2]-(b) RETURN b.name ORDER BY b.name").unwrap();
+        assert_eq!(strings(&result, "b.name"), vec!["Animal", "Cat"]);
+    }
+
+    #[test]
+    fn test_unbounded_length_over_long_chain() {
+        let mut projection = ConceptProjection::new(Uuid::new_v4(), GraphType::ConceptGraph);
+        let count = 100_000;
+        for i in 0..count {
+            let node = ConceptNode::new(format!("c{}", i), format!("C{}", i), ConceptNodeType::Concept);
+            projection.adjacency.insert(node.id.clone(), vec![]);
+            projection.nodes.insert(node.id.clone(), node);
+        }
+        for i in 1..count {
+            let edge = ConceptEdge::is_a(format!("e{}", i), format!("c{}", i - 1), format!("c{}", i));
+            projection.adjacency.get_mut(&edge.source).unwrap().push(edge.target.clone());
+            projection.edges.insert(edge.id.clone(), edge);
+        }
+
+        // One hop per node; the walk must not use one stack frame per hop
+        let result = execute(&projection, "MATCH (a {name: 'C0'})-[p*]->(b {name: 'C99999'}) RETURN length(p)").unwrap();
+        assert_eq!(result.rows, vec![vec![json!(count - 1)]]);
+    }
+
+    #[test]
+    fn test_composed_projection_query() {
+        let mut projection = ComposedGraph::new(Uuid::new_v4(), GraphType::ComposedGraph);
+        for id in ["ingest", "parse", "store"] {
+            let node = ComposedNode::new(id, ComposedNodeType::Transform { operation: id.to_string() });
+            projection.nodes.insert(id.to_string(), node);
+        }
+        let edges = [
+            ComposedEdge::new("f1", "ingest", "parse", ComposedEdgeType::DataFlow { flow_type: "stream".to_string() }),
+            ComposedEdge::new("f2", "parse", "store", ComposedEdgeType::DataFlow { flow_type: "batch".to_string() }),
+            ComposedEdge::new("c1", "ingest", "store", ComposedEdgeType::ControlFlow),
+        ];
+        for edge in edges {
+            projection.edges.insert(edge.id.clone(), edge);
+        }
+
+        let result = execute(
+            &projection,
+            "MATCH (s:Transform)-[f:DataFlow {flow_type: 'stream'}]->(t) RETURN s.operation, id(t), id(f)",
+        )
+        .unwrap();
+        assert_eq!(result.rows, vec![vec![json!("ingest"), json!("parse"), json!("f1")]]);
+    }
+
+    #[test]
+    fn test_explain_and_errors() {
+        let projection = create_taxonomy();
+        let query = Query::parse("MATCH (a:Concept)-[:IsA]->(b:Category) RETURN a").unwrap();
+        assert_eq!(query.explain(&projection).unwrap(), "Scan (b:category)\nExpand (b)<-[_1:IsA]-(a:concept)\n");
+        assert_eq!(query.columns(), vec!["a"]);
+
+        assert!(matches!("MATCH (a".parse::<Query>(), Err(GraphError::QuerySyntax { .. })));
+        assert!(matches!(
+            execute(&projection, "MATCH (a) RETURN b"),
+            Err(GraphError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_result_serialization() {
+        let projection = create_taxonomy();
+        let result = execute(&projection, "MATCH (a:Category) RETURN a.name AS name").unwrap();
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json, json!({"columns": ["name"], "rows": [["Animal"]]}));
+        let restored: QueryResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, result);
+    }
+}
@@ -0,0 +1,1023 @@
+//! Lexer, syntax tree and recursive-descent parser for graph queries
+
+use crate::error::{GraphError, Result};
+use serde_json::Value;
+use std::fmt;
+
+// ============================================================================
+// Syntax tree
+// ============================================================================
+
+/// A parsed `MATCH ... WHERE ... RETURN ...` query
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct QueryAst {
+    pub(crate) patterns: Vec<PathPattern>,
+    pub(crate) filter: Option<Expr>,
+    pub(crate) distinct: bool,
+    pub(crate) returns: Vec<ReturnItem>,
+    pub(crate) order_by: Vec<SortItem>,
+    pub(crate) skip: Option<usize>,
+    pub(crate) limit: Option<usize>,
+}
+
+/// `(a)-[r]->(b)<-[s]-(c)`: a start node and the hops after it
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PathPattern {
+    pub(crate) start: NodePattern,
+    pub(crate) hops: Vec<(RelPattern, NodePattern)>,
+}
+
+/// `(name:Label|Other {key: value})`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct NodePattern {
+    pub(crate) variable: Option<String>,
+    pub(crate) labels: Vec<String>,
+    pub(crate) properties: Vec<(String, Expr)>,
+}
+
+/// Which way a relationship pattern points, read left to right
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RelDirection {
+    /// `-[]->`
+    Outgoing,
+    /// `<-[]-`
+    Incoming,
+    /// `-[]-`
+    Either,
+}
+
+impl RelDirection {
+    pub(crate) fn reversed(self) -> Self {
+        match self {
+            RelDirection::Outgoing => RelDirection::Incoming,
+            RelDirection::Incoming => RelDirection::Outgoing,
+            RelDirection::Either => RelDirection::Either,
+        }
+    }
+}
+
+/// `-[name:TYPE|OTHER*min..max {key: value}]->`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RelPattern {
+    pub(crate) variable: Option<String>,
+    pub(crate) types: Vec<String>,
+    pub(crate) direction: RelDirection,
+    /// Hop range of a variable-length relationship; `None` for a single hop
+    pub(crate) length: Option<(usize, Option<usize>)>,
+    pub(crate) properties: Vec<(String, Expr)>,
+}
+
+/// A `RETURN` column
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReturnItem {
+    pub(crate) expr: Expr,
+    pub(crate) alias: Option<String>,
+}
+
+impl ReturnItem {
+    /// Column name: the alias, or the expression as written
+    pub(crate) fn column(&self) -> String {
+        self.alias.clone().unwrap_or_else(|| self.expr.to_string())
+    }
+}
+
+/// An `ORDER BY` key
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SortItem {
+    pub(crate) expr: Expr,
+    pub(crate) descending: bool,
+}
+
+/// Binary operators in `WHERE` expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+    EndsWith,
+    In,
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Or => "OR",
+            BinaryOp::And => "AND",
+            BinaryOp::Eq => "=",
+            BinaryOp::Ne => "<>",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Contains => "CONTAINS",
+            BinaryOp::StartsWith => "STARTS WITH",
+            BinaryOp::EndsWith => "ENDS WITH",
+            BinaryOp::In => "IN",
+        }
+    }
+}
+
+/// Built-in functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Function {
+    /// `id(x)`: node or relationship ID
+    Id,
+    /// `type(x)`: node or relationship type name
+    Type,
+    /// `length(p)`: hops in a variable-length relationship
+    Length,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "id" => Some(Function::Id),
+            "type" | "labels" => Some(Function::Type),
+            "length" => Some(Function::Length),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Function::Id => "id",
+            Function::Type => "type",
+            Function::Length => "length",
+        }
+    }
+}
+
+/// Expressions in `WHERE`, `RETURN`, `ORDER BY` and inline property maps
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Literal(Value),
+    Parameter(String),
+    Variable(String),
+    /// `var.key.nested`
+    Property(String, Vec<String>),
+    Function(Function, String),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    Negate(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// `expr IS NULL`, or `IS NOT NULL` when the flag is set
+    IsNull(Box<Expr>, bool),
+}
+
+impl Expr {
+    /// Variables the expression reads
+    pub(crate) fn variables(&self, out: &mut Vec<String>) {
+        match self {
+            Expr::Variable(name) | Expr::Property(name, _) | Expr::Function(_, name) => out.push(name.clone()),
+            Expr::List(items) => items.iter().for_each(|item| item.variables(out)),
+            Expr::Not(inner) | Expr::Negate(inner) | Expr::IsNull(inner, _) => inner.variables(out),
+            Expr::Binary(_, left, right) => {
+                left.variables(out);
+                right.variables(out);
+            }
+            Expr::Literal(_) | Expr::Parameter(_) => {}
+        }
+    }
+
+    /// Parameters the expression reads
+    pub(crate) fn parameters(&self, out: &mut Vec<String>) {
+        match self {
+            Expr::Parameter(name) => out.push(name.clone()),
+            Expr::List(items) => items.iter().for_each(|item| item.parameters(out)),
+            Expr::Not(inner) | Expr::Negate(inner) | Expr::IsNull(inner, _) => inner.parameters(out),
+            Expr::Binary(_, left, right) => {
+                left.parameters(out);
+                right.parameters(out);
+            }
+            Expr::Literal(_) | Expr::Variable(_) | Expr::Property(..) | Expr::Function(..) => {}
+        }
+    }
+
+    /// Split a conjunction into its parts
+    pub(crate) fn conjuncts(self) -> Vec<Expr> {
+        match self {
+            Expr::Binary(BinaryOp::And, left, right) => {
+                let mut parts = left.conjuncts();
+                parts.extend(right.conjuncts());
+                parts
+            }
+            other => vec![other],
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(Value::String(s)) => write!(f, "'{}'", s.replace('\'', "\\'")),
+            Expr::Literal(value) => write!(f, "{}", value),
+            Expr::Parameter(name) => write!(f, "${}", name),
+            Expr::Variable(name) => write!(f, "{}", name),
+            Expr::Property(name, path) => write!(f, "{}.{}", name, path.join(".")),
+            Expr::Function(function, name) => write!(f, "{}({})", function.name(), name),
+            Expr::List(items) => {
+                let items: Vec<String> = items.iter().map(Expr::to_string).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Expr::Not(inner) => write!(f, "NOT {}", inner),
+            Expr::Negate(inner) => write!(f, "-{}", inner),
+            Expr::Binary(op @ (BinaryOp::And | BinaryOp::Or), left, right) => {
+                write!(f, "({} {} {})", left, op.symbol(), right)
+            }
+            Expr::Binary(op, left, right) => write!(f, "{} {} {}", left, op.symbol(), right),
+            Expr::IsNull(inner, false) => write!(f, "{} IS NULL", inner),
+            Expr::IsNull(inner, true) => write!(f, "{} IS NOT NULL", inner),
+        }
+    }
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Param(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Colon,
+    Comma,
+    Dot,
+    DotDot,
+    Pipe,
+    Star,
+    Dash,
+    /// `->`
+    Arrow,
+    /// `<-`
+    LeftArrow,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eof,
+}
+
+fn syntax_error(position: usize, message: impl Into<String>) -> GraphError {
+    GraphError::QuerySyntax {
+        position,
+        message: message.into(),
+    }
+}
+
+/// Split query text into tokens paired with their byte offsets
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let peek = |i: usize| chars.get(i).map(|&(_, c)| c);
+
+    while let Some(&(start, c)) = chars.get(i) {
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if peek(i + 1) == Some('/') => {
+                while peek(i).is_some_and(|c| c != '\n') {
+                    i += 1;
+                }
+                continue;
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '*' => Token::Star,
+            '=' => Token::Eq,
+            '.' if peek(i + 1) == Some('.') => {
+                i += 1;
+                Token::DotDot
+            }
+            '.' => Token::Dot,
+            '-' if peek(i + 1) == Some('>') => {
+                i += 1;
+                Token::Arrow
+            }
+            '-' => Token::Dash,
+            '<' => match peek(i + 1) {
+                Some('-') => {
+                    i += 1;
+                    Token::LeftArrow
+                }
+                Some('=') => {
+                    i += 1;
+                    Token::Le
+                }
+                Some('>') => {
+                    i += 1;
+                    Token::Ne
+                }
+                _ => Token::Lt,
+            },
+            '>' if peek(i + 1) == Some('=') => {
+                i += 1;
+                Token::Ge
+            }
+            '>' => Token::Gt,
+            '!' if peek(i + 1) == Some('=') => {
+                i += 1;
+                Token::Ne
+            }
+            '\'' | '"' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match peek(i) {
+                        None => return Err(syntax_error(start, "unterminated string")),
+                        Some('\\') => {
+                            let escaped = match peek(i + 1) {
+                                Some('n') => '\n',
+                                Some('t') => '\t',
+                                Some(other) => other,
+                                None => return Err(syntax_error(start, "unterminated string")),
+                            };
+                            value.push(escaped);
+                            i += 2;
+                        }
+                        Some(q) if q == c => break,
+                        Some(other) => {
+                            value.push(other);
+                            i += 1;
+                        }
+                    }
+                }
+                Token::Str(value)
+            }
+            '`' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match peek(i) {
+                        None => return Err(syntax_error(start, "unterminated quoted name")),
+                        Some('`') => break,
+                        Some(other) => {
+                            value.push(other);
+                            i += 1;
+                        }
+                    }
+                }
+                Token::Ident(value)
+            }
+            '$' => {
+                let mut name = String::new();
+                while peek(i + 1).is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    i += 1;
+                    name.push(chars[i].1);
+                }
+                if name.is_empty() {
+                    return Err(syntax_error(start, "expected parameter name after $"));
+                }
+                Token::Param(name)
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = i;
+                while peek(end + 1).is_some_and(|c| c.is_ascii_digit()) {
+                    end += 1;
+                }
+                // A dot starts a fraction only when a digit follows, so `1..3` stays a range
+                let fraction = peek(end + 1) == Some('.') && peek(end + 2).is_some_and(|c| c.is_ascii_digit());
+                if fraction {
+                    end += 2;
+                    while peek(end + 1).is_some_and(|c| c.is_ascii_digit()) {
+                        end += 1;
+                    }
+                }
+                let literal: String = chars[i..=end].iter().map(|&(_, c)| c).collect();
+                i = end;
+                if fraction {
+                    Token::Float(literal.parse().map_err(|_| syntax_error(start, "invalid number"))?)
+                } else {
+                    Token::Int(literal.parse().map_err(|_| syntax_error(start, "integer out of range"))?)
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while peek(i + 1).is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    i += 1;
+                    name.push(chars[i].1);
+                }
+                Token::Ident(name)
+            }
+            other => return Err(syntax_error(start, format!("unexpected character '{}'", other))),
+        };
+        tokens.push((token, start));
+        i += 1;
+    }
+
+    tokens.push((Token::Eof, text.len()));
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// Parse query text into a syntax tree
+pub(crate) fn parse(text: &str) -> Result<QueryAst> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+    };
+    let query = parser.query()?;
+    parser.expect(&Token::Eof, "end of query")?;
+    Ok(query)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens[self.position].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].0.clone();
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error(&self, expected: &str) -> GraphError {
+        let found = match self.peek() {
+            Token::Eof => "end of query".to_string(),
+            Token::Ident(name) => format!("'{}'", name),
+            other => format!("{:?}", other),
+        };
+        syntax_error(self.offset(), format!("expected {}, found {}", expected, found))
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == token {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, expected: &str) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Ident(name) if name.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.at_keyword(keyword) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(keyword))
+        }
+    }
+
+    fn identifier(&mut self, expected: &str) -> Result<String> {
+        match self.peek() {
+            Token::Ident(name) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn count(&mut self, expected: &str) -> Result<usize> {
+        match self.peek() {
+            Token::Int(value) if *value >= 0 => {
+                let value = *value as usize;
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn query(&mut self) -> Result<QueryAst> {
+        self.expect_keyword("MATCH")?;
+        let mut patterns = vec![self.path()?];
+        while self.eat(&Token::Comma) {
+            patterns.push(self.path()?);
+        }
+
+        let filter = if self.eat_keyword("WHERE") {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+
+        self.expect_keyword("RETURN")?;
+        let distinct = self.eat_keyword("DISTINCT");
+        let mut returns = vec![self.return_item()?];
+        while self.eat(&Token::Comma) {
+            returns.push(self.return_item()?);
+        }
+
+        let mut order_by = Vec::new();
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let expr = self.expression()?;
+                let descending = if self.eat_keyword("DESC") || self.eat_keyword("DESCENDING") {
+                    true
+                } else {
+                    let _ = self.eat_keyword("ASC") || self.eat_keyword("ASCENDING");
+                    false
+                };
+                order_by.push(SortItem { expr, descending });
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let skip = if self.eat_keyword("SKIP") {
+            Some(self.count("row count after SKIP")?)
+        } else {
+            None
+        };
+        let limit = if self.eat_keyword("LIMIT") {
+            Some(self.count("row count after LIMIT")?)
+        } else {
+            None
+        };
+
+        Ok(QueryAst {
+            patterns,
+            filter,
+            distinct,
+            returns,
+            order_by,
+            skip,
+            limit,
+        })
+    }
+
+    fn return_item(&mut self) -> Result<ReturnItem> {
+        let expr = self.expression()?;
+        let alias = if self.eat_keyword("AS") {
+            Some(self.identifier("column name after AS")?)
+        } else {
+            None
+        };
+        Ok(ReturnItem { expr, alias })
+    }
+
+    fn path(&mut self) -> Result<PathPattern> {
+        let start = self.node()?;
+        let mut hops = Vec::new();
+        while matches!(self.peek(), Token::Dash | Token::LeftArrow | Token::Arrow) {
+            let relationship = self.relationship()?;
+            hops.push((relationship, self.node()?));
+        }
+        Ok(PathPattern { start, hops })
+    }
+
+    fn node(&mut self) -> Result<NodePattern> {
+        self.expect(&Token::LParen, "'(' to start a node pattern")?;
+        let variable = match self.peek() {
+            Token::Ident(_) => Some(self.identifier("variable")?),
+            _ => None,
+        };
+        let labels = self.labels()?;
+        let properties = self.property_map()?;
+        self.expect(&Token::RParen, "')' to close the node pattern")?;
+        Ok(NodePattern {
+            variable,
+            labels,
+            properties,
+        })
+    }
+
+    /// `:A|B` or `:A|:B`
+    fn labels(&mut self) -> Result<Vec<String>> {
+        let mut labels = Vec::new();
+        if self.eat(&Token::Colon) {
+            labels.push(self.identifier("type name after ':'")?);
+            while self.eat(&Token::Pipe) {
+                self.eat(&Token::Colon);
+                labels.push(self.identifier("type name after '|'")?);
+            }
+        }
+        Ok(labels)
+    }
+
+    fn property_map(&mut self) -> Result<Vec<(String, Expr)>> {
+        let mut properties = Vec::new();
+        if self.eat(&Token::LBrace) && !self.eat(&Token::RBrace) {
+            loop {
+                let key = self.identifier("property name")?;
+                self.expect(&Token::Colon, "':' after the property name")?;
+                properties.push((key, self.unary()?));
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect(&Token::RBrace, "'}' to close the property map")?;
+        }
+        Ok(properties)
+    }
+
+    /// `-[...]->`, `<-[...]-`, `-[...]-`, `-->`, `<--` or `--`
+    fn relationship(&mut self) -> Result<RelPattern> {
+        let incoming = match self.advance() {
+            Token::LeftArrow => true,
+            Token::Dash => false,
+            // `-->` lexes as `-` `->`; a bare `->` here means the dash was missing
+            _ => return Err(syntax_error(self.tokens[self.position - 1].1, "expected '-' or '<-'")),
+        };
+
+        let mut relationship = RelPattern {
+            variable: None,
+            types: Vec::new(),
+            direction: RelDirection::Either,
+            length: None,
+            properties: Vec::new(),
+        };
+
+        if self.eat(&Token::LBracket) {
+            if let Token::Ident(_) = self.peek() {
+                relationship.variable = Some(self.identifier("variable")?);
+            }
+            relationship.types = self.labels()?;
+            if self.eat(&Token::Star) {
+                relationship.length = Some(self.hop_range()?);
+            }
+            relationship.properties = self.property_map()?;
+            self.expect(&Token::RBracket, "']' to close the relationship pattern")?;
+        }
+
+        let outgoing = match self.advance() {
+            Token::Arrow => true,
+            Token::Dash => false,
+            _ => return Err(syntax_error(self.tokens[self.position - 1].1, "expected '-' or '->'")),
+        };
+
+        relationship.direction = match (incoming, outgoing) {
+            (true, true) => RelDirection::Either,
+            (true, false) => RelDirection::Incoming,
+            (false, true) => RelDirection::Outgoing,
+            (false, false) => RelDirection::Either,
+        };
+        Ok(relationship)
+    }
+
+    /// After `*`: nothing, `n`, `min..`, `..max` or `min..max`
+    fn hop_range(&mut self) -> Result<(usize, Option<usize>)> {
+        let min = match self.peek() {
+            Token::Int(_) => Some(self.count("hop count")?),
+            _ => None,
+        };
+        if !self.eat(&Token::DotDot) {
+            return Ok(match min {
+                Some(exact) => (exact, Some(exact)),
+                None => (1, None),
+            });
+        }
+        let max = match self.peek() {
+            Token::Int(_) => Some(self.count("maximum hop count")?),
+            _ => None,
+        };
+        let min = min.unwrap_or(1);
+        if max.is_some_and(|max| max < min) {
+            return Err(syntax_error(self.offset(), "maximum hop count is below the minimum"));
+        }
+        Ok((min, max))
+    }
+
+    fn expression(&mut self) -> Result<Expr> {
+        let mut left = self.conjunction()?;
+        while self.eat_keyword("OR") {
+            let right = self.conjunction()?;
+            left = Expr::Binary(BinaryOp::Or, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn conjunction(&mut self) -> Result<Expr> {
+        let mut left = self.negation()?;
+        while self.eat_keyword("AND") {
+            let right = self.negation()?;
+            left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn negation(&mut self) -> Result<Expr> {
+        if self.eat_keyword("NOT") {
+            Ok(Expr::Not(Box::new(self.negation()?)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let left = self.unary()?;
+        let op = match self.peek() {
+            Token::Eq => BinaryOp::Eq,
+            Token::Ne => BinaryOp::Ne,
+            Token::Lt => BinaryOp::Lt,
+            Token::Le => BinaryOp::Le,
+            Token::Gt => BinaryOp::Gt,
+            Token::Ge => BinaryOp::Ge,
+            _ if self.at_keyword("CONTAINS") => BinaryOp::Contains,
+            _ if self.at_keyword("IN") => BinaryOp::In,
+            _ if self.at_keyword("STARTS") || self.at_keyword("ENDS") => {
+                let op = if self.at_keyword("STARTS") {
+                    BinaryOp::StartsWith
+                } else {
+                    BinaryOp::EndsWith
+                };
+                self.advance();
+                if !self.at_keyword("WITH") {
+                    return Err(self.error("WITH"));
+                }
+                op
+            }
+            _ if self.at_keyword("IS") => {
+                self.advance();
+                let negated = self.eat_keyword("NOT");
+                self.expect_keyword("NULL")?;
+                return Ok(Expr::IsNull(Box::new(left), negated));
+            }
+            _ => return Ok(left),
+        };
+        self.advance();
+        let right = self.unary()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat(&Token::Dash) {
+            return Ok(match self.unary()? {
+                Expr::Literal(Value::Number(n)) => match (n.as_i64(), n.as_f64()) {
+                    (Some(i), _) => Expr::Literal(Value::from(-i)),
+                    (None, Some(f)) => Expr::Literal(Value::from(-f)),
+                    _ => Expr::Negate(Box::new(Expr::Literal(Value::Number(n)))),
+                },
+                other => Expr::Negate(Box::new(other)),
+            });
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let offset = self.offset();
+        match self.peek().clone() {
+            Token::Str(value) => {
+                self.advance();
+                Ok(Expr::Literal(Value::String(value)))
+            }
+            Token::Int(value) => {
+                self.advance();
+                Ok(Expr::Literal(Value::from(value)))
+            }
+            Token::Float(value) => {
+                self.advance();
+                Ok(Expr::Literal(Value::from(value)))
+            }
+            Token::Param(name) => {
+                self.advance();
+                Ok(Expr::Parameter(name))
+            }
+            Token::LParen => {
+                self.advance();
+                let inner = self.expression()?;
+                self.expect(&Token::RParen, "')'")?;
+                Ok(inner)
+            }
+            Token::LBracket => {
+                self.advance();
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.expression()?);
+                        if !self.eat(&Token::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(&Token::RBracket, "']' to close the list")?;
+                }
+                Ok(Expr::List(items))
+            }
+            Token::Ident(name) => {
+                self.advance();
+                if name.eq_ignore_ascii_case("true") {
+                    return Ok(Expr::Literal(Value::Bool(true)));
+                }
+                if name.eq_ignore_ascii_case("false") {
+                    return Ok(Expr::Literal(Value::Bool(false)));
+                }
+                if name.eq_ignore_ascii_case("null") {
+                    return Ok(Expr::Literal(Value::Null));
+                }
+                if self.eat(&Token::LParen) {
+                    let function = Function::from_name(&name)
+                        .ok_or_else(|| syntax_error(offset, format!("unknown function {}", name)))?;
+                    let argument = self.identifier("variable as the function argument")?;
+                    self.expect(&Token::RParen, "')' after the function argument")?;
+                    return Ok(Expr::Function(function, argument));
+                }
+                let mut path = Vec::new();
+                while self.peek() == &Token::Dot {
+                    self.advance();
+                    path.push(self.identifier("property name after '.'")?);
+                }
+                Ok(if path.is_empty() {
+                    Expr::Variable(name)
+                } else {
+                    Expr::Property(name, path)
+                })
+            }
+            _ => Err(self.error("an expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property(variable: &str, key: &str) -> Expr {
+        Expr::Property(variable.to_string(), vec![key.to_string()])
+    }
+
+    // ========== Lexer Tests ==========
+
+    #[test]
+    fn test_tokenize_ranges_and_arrows() {
+        let tokens: Vec<Token> = tokenize("-[*1..3]-> <-- 2.5 $p <> 'it\\'s'")
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Dash,
+                Token::LBracket,
+                Token::Star,
+                Token::Int(1),
+                Token::DotDot,
+                Token::Int(3),
+                Token::RBracket,
+                Token::Arrow,
+                Token::LeftArrow,
+                Token::Dash,
+                Token::Float(2.5),
+                Token::Param("p".to_string()),
+                Token::Ne,
+                Token::Str("it's".to_string()),
+                Token::Eof,
+            ]
+        );
+    }
+
+    // ========== Parser Tests ==========
+
+    #[test]
+    fn test_parse_variable_length_query() {
+        let query = parse("MATCH (a:Concept)-[:IsA*1..3]->(b) WHERE b.name = 'Animal' RETURN a").unwrap();
+
+        assert_eq!(query.patterns.len(), 1);
+        let path = &query.patterns[0];
+        assert_eq!(path.start.variable.as_deref(), Some("a"));
+        assert_eq!(path.start.labels, vec!["Concept"]);
+
+        let (relationship, target) = &path.hops[0];
+        assert_eq!(relationship.types, vec!["IsA"]);
+        assert_eq!(relationship.direction, RelDirection::Outgoing);
+        assert_eq!(relationship.length, Some((1, Some(3))));
+        assert_eq!(target.variable.as_deref(), Some("b"));
+
+        assert_eq!(
+            query.filter,
+            Some(Expr::Binary(
+                BinaryOp::Eq,
+                Box::new(property("b", "name")),
+                Box::new(Expr::Literal(Value::from("Animal")))
+            ))
+        );
+        assert_eq!(query.returns[0].column(), "a");
+    }
+
+    #[test]
+    fn test_parse_directions_and_hop_ranges() {
+        let query = parse("MATCH (a)<-[r]-(b)-[:X|Y*]-(c)--(d)-->(e)-[*2]->(f)-[*..4]->(g) RETURN a").unwrap();
+        let hops: Vec<_> = query.patterns[0]
+            .hops
+            .iter()
+            .map(|(relationship, _)| (relationship.direction, relationship.length))
+            .collect();
+
+        assert_eq!(
+            hops,
+            vec![
+                (RelDirection::Incoming, None),
+                (RelDirection::Either, Some((1, None))),
+                (RelDirection::Either, None),
+                (RelDirection::Outgoing, None),
+                (RelDirection::Outgoing, Some((2, Some(2)))),
+                (RelDirection::Outgoing, Some((1, Some(4)))),
+            ]
+        );
+        assert_eq!(query.patterns[0].hops[0].0.variable.as_deref(), Some("r"));
+        assert_eq!(query.patterns[0].hops[1].0.types, vec!["X", "Y"]);
+    }
+
+    #[test]
+    fn test_parse_clauses_and_precedence() {
+        let query = parse(
+            "match (a {name: 'x', rank: -2}), (b) \
+             where not a.rank > 1 or b.tags contains 'y' and b.x is not null \
+             return distinct a.name as name, id(b) order by name desc, b.rank skip 1 limit 5",
+        )
+        .unwrap();
+
+        assert_eq!(query.patterns.len(), 2);
+        assert_eq!(query.patterns[0].start.properties[1], ("rank".to_string(), Expr::Literal(Value::from(-2))));
+        assert!(query.distinct);
+        assert_eq!(query.returns[0].column(), "name");
+        assert_eq!(query.returns[1].column(), "id(b)");
+        assert!(query.order_by[0].descending);
+        assert!(!query.order_by[1].descending);
+        assert_eq!((query.skip, query.limit), (Some(1), Some(5)));
+
+        // OR binds loosest, NOT tighter than AND
+        assert_eq!(
+            query.filter.unwrap().to_string(),
+            "(NOT a.rank > 1 OR (b.tags CONTAINS 'y' AND b.x IS NOT NULL))"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let error = parse("MATCH (a RETURN a").unwrap_err();
+        assert!(matches!(error, GraphError::QuerySyntax { position: 9, .. }));
+
+        for text in [
+            "RETURN a",
+            "MATCH (a) RETURN",
+            "MATCH (a)-[*3..1]->(b) RETURN a",
+            "MATCH (a) RETURN nope(a)",
+            "MATCH (a) WHERE a.name = 'open RETURN a",
+            "MATCH (a) RETURN a LIMIT -1",
+            "MATCH (a) RETURN a extra",
+        ] {
+            assert!(
+                matches!(parse(text), Err(GraphError::QuerySyntax { .. })),
+                "accepted {}",
+                text
+            );
+        }
+    }
+}
@@ -0,0 +1,438 @@
+//! Query planning: variable slots, expansion order and filter placement
+//!
+//! The planner binds one node with a scan, then grows the match along
+//! relationships, always taking the hop that reaches the most selective
+//! unbound node next. `WHERE` conjuncts run as soon as every variable they
+//! read is bound, and disconnected patterns join as a cross product.
+
+use super::parser::{BinaryOp, Expr, PathPattern, QueryAst, RelDirection};
+use crate::core::projection_engine::type_key;
+use crate::error::{GraphError, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Node counts used to estimate how selective a scan is
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Statistics {
+    pub(crate) total: usize,
+    /// Node count per normalized type name
+    pub(crate) by_type: HashMap<String, usize>,
+}
+
+/// What a variable slot holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SlotKind {
+    Node,
+    Relationship,
+}
+
+/// A query variable, named or anonymous, with the node constraints on it
+#[derive(Debug, Clone)]
+pub(crate) struct Slot {
+    pub(crate) name: String,
+    pub(crate) kind: SlotKind,
+    /// Every occurrence's type alternatives, normalized; all must match
+    pub(crate) labels: Vec<Vec<String>>,
+    /// Inline property equalities from every occurrence
+    pub(crate) properties: Vec<(String, Expr)>,
+}
+
+/// One relationship pattern between two node slots
+#[derive(Debug, Clone)]
+pub(crate) struct Hop {
+    pub(crate) relationship: usize,
+    /// Relationship type alternatives, normalized
+    pub(crate) types: Vec<String>,
+    pub(crate) properties: Vec<(String, Expr)>,
+    pub(crate) length: Option<(usize, Option<usize>)>,
+    /// Written type names, for plan output
+    written_types: Vec<String>,
+}
+
+/// A plan step
+#[derive(Debug, Clone)]
+pub(crate) enum Step {
+    /// Bind a node slot to every matching node
+    Scan { node: usize },
+    /// Follow a hop from a bound node, binding or checking the other end
+    Expand {
+        from: usize,
+        to: usize,
+        hop: Hop,
+        direction: RelDirection,
+        /// Whether the hop is walked against the way it was written
+        reversed: bool,
+    },
+    /// Drop rows for which the expression is not true
+    Filter(Expr),
+}
+
+/// An executable plan
+#[derive(Debug, Clone)]
+pub(crate) struct Plan {
+    pub(crate) slots: Vec<Slot>,
+    pub(crate) steps: Vec<Step>,
+    /// Slot index by variable name
+    pub(crate) names: HashMap<String, usize>,
+}
+
+impl Plan {
+    fn node_text(&self, slot: usize) -> String {
+        let slot = &self.slots[slot];
+        let labels: Vec<String> = slot.labels.iter().map(|alternatives| format!(":{}", alternatives.join("|"))).collect();
+        format!("({}{})", slot.name, labels.concat())
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            match step {
+                Step::Scan { node } => writeln!(f, "Scan {}", self.node_text(*node))?,
+                Step::Expand {
+                    from,
+                    to,
+                    hop,
+                    direction,
+                    ..
+                } => {
+                    let mut body = self.slots[hop.relationship].name.clone();
+                    if !hop.written_types.is_empty() {
+                        body.push(':');
+                        body.push_str(&hop.written_types.join("|"));
+                    }
+                    if let Some((min, max)) = hop.length {
+                        body.push_str(&format!("*{}..{}", min, max.map(|m| m.to_string()).unwrap_or_default()));
+                    }
+                    let (left, right) = match direction {
+                        RelDirection::Outgoing => ("-", "->"),
+                        RelDirection::Incoming => ("<-", "-"),
+                        RelDirection::Either => ("-", "-"),
+                    };
+                    writeln!(f, "Expand ({}){}[{}]{}{}", self.slots[*from].name, left, body, right, self.node_text(*to))?
+                }
+                Step::Filter(expr) => writeln!(f, "Filter {}", expr)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build a plan for a parsed query
+pub(crate) fn plan(query: &QueryAst, statistics: &Statistics) -> Result<Plan> {
+    let mut builder = SlotBuilder::default();
+    let mut hops: Vec<(usize, usize, Hop, RelDirection)> = Vec::new();
+    let mut isolated = Vec::new();
+    for path in &query.patterns {
+        builder.path(path, &mut hops, &mut isolated)?;
+    }
+    let SlotBuilder { slots, names, .. } = builder;
+
+    // Conjuncts with the slots they read
+    let mut filters: Vec<(Expr, HashSet<usize>)> = Vec::new();
+    if let Some(filter) = &query.filter {
+        for conjunct in filter.clone().conjuncts() {
+            let reads = resolve(&conjunct, &names)?;
+            filters.push((conjunct, reads));
+        }
+    }
+    for item in &query.returns {
+        resolve(&item.expr, &names)?;
+    }
+    let aliases: HashSet<String> = query.returns.iter().filter_map(|item| item.alias.clone()).collect();
+    for item in &query.order_by {
+        match &item.expr {
+            Expr::Variable(name) if aliases.contains(name) => {}
+            expr => {
+                resolve(expr, &names)?;
+            }
+        }
+    }
+
+    let estimates: Vec<usize> = (0..slots.len())
+        .map(|slot| estimate(&slots[slot], slot, &filters, statistics))
+        .collect();
+
+    let mut bound = vec![false; slots.len()];
+    let mut steps = Vec::new();
+    let mut pending = hops;
+    place_filters(&mut filters, &bound, &mut steps);
+
+    loop {
+        // Prefer hops closing a cycle, then the one reaching the smallest estimate
+        let next = pending
+            .iter()
+            .enumerate()
+            .filter(|(_, (left, right, _, _))| bound[*left] || bound[*right])
+            .map(|(index, (left, right, _, _))| {
+                let cost = match (bound[*left], bound[*right]) {
+                    (true, true) => 0,
+                    (true, false) => estimates[*right].saturating_add(1),
+                    _ => estimates[*left].saturating_add(1),
+                };
+                (cost, index)
+            })
+            .min();
+
+        if let Some((_, index)) = next {
+            let (left, right, hop, direction) = pending.remove(index);
+            let (from, to, direction, reversed) = if bound[left] {
+                (left, right, direction, false)
+            } else {
+                (right, left, direction.reversed(), true)
+            };
+            bound[to] = true;
+            bound[hop.relationship] = true;
+            steps.push(Step::Expand {
+                from,
+                to,
+                hop,
+                direction,
+                reversed,
+            });
+        } else {
+            let start = pending
+                .iter()
+                .flat_map(|(left, right, _, _)| [*left, *right])
+                .chain(isolated.iter().copied())
+                .filter(|&slot| !bound[slot])
+                .min_by_key(|&slot| (estimates[slot], slot));
+            match start {
+                Some(node) => {
+                    bound[node] = true;
+                    steps.push(Step::Scan { node });
+                }
+                None => break,
+            }
+        }
+        place_filters(&mut filters, &bound, &mut steps);
+    }
+
+    Ok(Plan { slots, steps, names })
+}
+
+/// Append every filter whose slots are all bound
+fn place_filters(filters: &mut Vec<(Expr, HashSet<usize>)>, bound: &[bool], steps: &mut Vec<Step>) {
+    let mut index = 0;
+    while index < filters.len() {
+        if filters[index].1.iter().all(|&slot| bound[slot]) {
+            steps.push(Step::Filter(filters.remove(index).0));
+        } else {
+            index += 1;
+        }
+    }
+}
+
+/// Slots an expression reads, failing on unknown variables
+fn resolve(expr: &Expr, names: &HashMap<String, usize>) -> Result<HashSet<usize>> {
+    let mut variables = Vec::new();
+    expr.variables(&mut variables);
+    variables
+        .into_iter()
+        .map(|name| {
+            names
+                .get(&name)
+                .copied()
+                .ok_or_else(|| GraphError::InvalidOperation(format!("Variable {} is not defined in MATCH", name)))
+        })
+        .collect()
+}
+
+/// Rough number of nodes a scan of `slot` yields
+fn estimate(slot: &Slot, index: usize, filters: &[(Expr, HashSet<usize>)], statistics: &Statistics) -> usize {
+    if slot.kind == SlotKind::Relationship {
+        return usize::MAX;
+    }
+    let mut estimate = slot
+        .labels
+        .iter()
+        .map(|alternatives| {
+            alternatives
+                .iter()
+                .map(|label| statistics.by_type.get(label).copied().unwrap_or(0))
+                .sum()
+        })
+        .min()
+        .unwrap_or(statistics.total);
+
+    // Each equality on the node alone is assumed to keep a tenth of it
+    let equalities = slot.properties.len()
+        + filters
+            .iter()
+            .filter(|(expr, reads)| {
+                reads.len() == 1 && reads.contains(&index) && matches!(expr, Expr::Binary(BinaryOp::Eq, ..))
+            })
+            .count();
+    for _ in 0..equalities {
+        estimate = estimate.div_ceil(10);
+    }
+    estimate
+}
+
+/// Assigns slots to pattern variables while walking the patterns
+#[derive(Default)]
+struct SlotBuilder {
+    slots: Vec<Slot>,
+    names: HashMap<String, usize>,
+    anonymous: usize,
+}
+
+impl SlotBuilder {
+    fn path(
+        &mut self,
+        path: &PathPattern,
+        hops: &mut Vec<(usize, usize, Hop, RelDirection)>,
+        isolated: &mut Vec<usize>,
+    ) -> Result<()> {
+        let mut left = self.node(&path.start.variable, &path.start.labels, &path.start.properties)?;
+        if path.hops.is_empty() {
+            isolated.push(left);
+        }
+        for (relationship, node) in &path.hops {
+            let slot = self.slot(&relationship.variable, SlotKind::Relationship)?;
+            check_inline(&relationship.properties)?;
+            let right = self.node(&node.variable, &node.labels, &node.properties)?;
+            let hop = Hop {
+                relationship: slot,
+                types: relationship.types.iter().map(|t| type_key(t)).collect(),
+                properties: relationship.properties.clone(),
+                length: relationship.length,
+                written_types: relationship.types.clone(),
+            };
+            hops.push((left, right, hop, relationship.direction));
+            left = right;
+        }
+        Ok(())
+    }
+
+    fn node(&mut self, variable: &Option<String>, labels: &[String], properties: &[(String, Expr)]) -> Result<usize> {
+        check_inline(properties)?;
+        let slot = self.slot(variable, SlotKind::Node)?;
+        if !labels.is_empty() {
+            self.slots[slot].labels.push(labels.iter().map(|l| type_key(l)).collect());
+        }
+        self.slots[slot].properties.extend(properties.iter().cloned());
+        Ok(slot)
+    }
+
+    fn slot(&mut self, variable: &Option<String>, kind: SlotKind) -> Result<usize> {
+        let name = match variable {
+            Some(name) => name.clone(),
+            None => {
+                self.anonymous += 1;
+                format!("_{}", self.anonymous)
+            }
+        };
+
+        if let Some(&existing) = self.names.get(&name) {
+            return match (self.slots[existing].kind, kind) {
+                (SlotKind::Node, SlotKind::Node) => Ok(existing),
+                (SlotKind::Relationship, SlotKind::Relationship) => Err(GraphError::InvalidOperation(format!(
+                    "Relationship variable {} is used more than once",
+                    name
+                ))),
+                _ => Err(GraphError::InvalidOperation(format!(
+                    "Variable {} is used for both a node and a relationship",
+                    name
+                ))),
+            };
+        }
+
+        self.slots.push(Slot {
+            name: name.clone(),
+            kind,
+            labels: Vec::new(),
+            properties: Vec::new(),
+        });
+        self.names.insert(name, self.slots.len() - 1);
+        Ok(self.slots.len() - 1)
+    }
+}
+
+/// Inline property values are evaluated before the row exists, so they may
+/// not read variables
+fn check_inline(properties: &[(String, Expr)]) -> Result<()> {
+    for (key, expr) in properties {
+        let mut variables = Vec::new();
+        expr.variables(&mut variables);
+        if !variables.is_empty() {
+            return Err(GraphError::InvalidOperation(format!(
+                "Inline property {} must be a literal or parameter",
+                key
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::parser::parse;
+
+    fn statistics(counts: &[(&str, usize)]) -> Statistics {
+        Statistics {
+            total: counts.iter().map(|(_, count)| count).sum(),
+            by_type: counts.iter().map(|(name, count)| (type_key(name), *count)).collect(),
+        }
+    }
+
+    fn explain(text: &str, counts: &[(&str, usize)]) -> String {
+        plan(&parse(text).unwrap(), &statistics(counts)).unwrap().to_string()
+    }
+
+    // ========== Planner Tests ==========
+
+    #[test]
+    fn test_plan_starts_from_most_selective_node() {
+        let counts = [("concept", 1000), ("category", 5)];
+
+        let plan = explain("MATCH (a:Concept)-[:IsA]->(b:Category) RETURN a", &counts);
+        assert_eq!(plan, "Scan (b:category)\nExpand (b)<-[_1:IsA]-(a:concept)\n");
+
+        let plan = explain("MATCH (a:Concept {name: 'Dog'})-[:IsA]->(b) RETURN a", &counts);
+        assert!(plan.starts_with("Scan (a:concept)"), "{}", plan);
+    }
+
+    #[test]
+    fn test_plan_pushes_filters_down() {
+        let plan = explain(
+            "MATCH (a)-->(b), (c) WHERE a.x = 1 AND b.y = c.y AND true RETURN a",
+            &[("concept", 10)],
+        );
+        let lines: Vec<&str> = plan.lines().collect();
+
+        assert_eq!(lines[0], "Filter true");
+        assert_eq!(lines[1], "Scan (a)");
+        assert_eq!(lines[2], "Filter a.x = 1");
+        assert!(lines[3].starts_with("Expand (a)"));
+        assert_eq!(lines[4], "Scan (c)");
+        assert_eq!(lines[5], "Filter b.y = c.y");
+    }
+
+    #[test]
+    fn test_plan_checks_shared_variables_as_cycles() {
+        let plan = explain("MATCH (a)-->(b)-->(c), (c)-->(a) RETURN a", &[("concept", 10)]);
+        assert_eq!(plan.lines().filter(|line| line.starts_with("Scan")).count(), 1);
+        assert_eq!(plan.lines().filter(|line| line.starts_with("Expand")).count(), 3);
+    }
+
+    #[test]
+    fn test_plan_rejects_invalid_variables() {
+        let stats = statistics(&[]);
+        for text in [
+            "MATCH (a) WHERE b.x = 1 RETURN a",
+            "MATCH (a) RETURN b",
+            "MATCH (a)-[r]->(b)-[r]->(c) RETURN a",
+            "MATCH (a)-[a]->(b) RETURN a",
+            "MATCH (a), (b {name: a.name}) RETURN a",
+        ] {
+            assert!(
+                matches!(plan(&parse(text).unwrap(), &stats), Err(GraphError::InvalidOperation(_))),
+                "accepted {}",
+                text
+            );
+        }
+        assert!(plan(&parse("MATCH (a) RETURN a.name AS n ORDER BY n").unwrap(), &stats).is_ok());
+    }
+}
@@ -10,22 +10,33 @@
 //!
 //! The old graph serialization has been removed as it contradicts
 //! the event-sourcing pattern.
+//!
+//! Events are written with a `schema_version` and upcast through
+//! [`UpcasterRegistry::standard`] when read back.
 
+use crate::events::upcast::{self, UpcasterRegistry};
 use crate::events::{GraphEvent, GraphCommand};
 use crate::error::{GraphError, Result};
 use serde::{Serialize, Deserialize};
 use std::path::Path;
 
-/// Serializes events to JSON
+/// Serializes events to JSON, tagged with the current schema version
 pub fn serialize_events(events: &[GraphEvent]) -> Result<String> {
-    serde_json::to_string_pretty(events)
+    let registry = UpcasterRegistry::standard();
+    let encoded = events
+        .iter()
+        .map(|event| registry.encode(event))
+        .collect::<Result<Vec<_>>>()?;
+    serde_json::to_string_pretty(&encoded)
         .map_err(|e| GraphError::SerializationError(format!("Failed to serialize events: {}", e)))
 }
 
-/// Deserializes events from JSON
+/// Deserializes events from JSON, upcasting older schema versions
 pub fn deserialize_events(json: &str) -> Result<Vec<GraphEvent>> {
-    serde_json::from_str(json)
-        .map_err(|e| GraphError::SerializationError(format!("Failed to deserialize events: {}", e)))
+    let encoded: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| GraphError::SerializationError(format!("Failed to deserialize events: {}", e)))?;
+    let registry = UpcasterRegistry::standard();
+    encoded.into_iter().map(|event| registry.decode(event)).collect()
 }
 
 /// Saves events to a file
@@ -94,6 +105,7 @@ pub struct EventJournal {
     /// Metadata about the journal
     pub metadata: EventStorageMetadata,
     /// The events in order
+    #[serde(with = "upcast::versioned_list")]
     pub events: Vec<GraphEvent>,
 }
 
@@ -629,6 +641,31 @@ mod tests {
         assert_eq!(deserialized.events.len(), 1);
     }
 
+    #[test]
+    fn test_journal_accepts_unversioned_events() {
+        let event = GraphEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id: Uuid::new_v4(),
+            correlation_id: Uuid::new_v4(),
+            causation_id: None,
+            payload: EventPayload::Workflow(WorkflowPayload::StateTransitioned {
+                instance_id: Uuid::new_v4(),
+                from_state: "draft".to_string(),
+                to_state: "review".to_string(),
+            }),
+        };
+        let journal = serde_json::json!({
+            "metadata": EventStorageMetadata::from_events(std::slice::from_ref(&event)),
+            "events": [event],
+        });
+
+        let loaded: EventJournal = serde_json::from_value(journal).unwrap();
+        assert_eq!(loaded.events[0].event_id, event.event_id);
+
+        let saved = serde_json::to_value(&loaded).unwrap();
+        assert_eq!(saved["events"][0]["schema_version"], upcast::CURRENT_SCHEMA_VERSION);
+    }
+
     // ========== File I/O Tests ==========
 
     #[test]
@@ -1,13 +1,15 @@
 //! Integration tests for NATS JetStream event store
 //!
-//! These tests require a running NATS server with JetStream enabled.
+//! The JetStream tests require a running NATS server with JetStream enabled.
 //! Run with: cargo test --features nats nats_integration -- --ignored
+//!
+//! The in-memory tests run the same scenarios against `InMemoryJetStream`.
 
 #![cfg(feature = "nats")]
 
 use cim_graph::{
     events::{GraphEvent, EventPayload, GenericPayload},
-    nats::{JetStreamEventStore, JetStreamConfig},
+    nats::{InMemoryJetStream, JetStreamEventStore, JetStreamConfig},
 };
 use uuid::Uuid;
 
@@ -159,6 +161,77 @@ async fn test_replay_consumer() {
     assert!(!batch.is_empty());
 }
 
+#[tokio::test]
+async fn test_in_memory_publish_and_fetch_events() {
+    let store = InMemoryJetStream::default();
+    let aggregate_id = Uuid::new_v4();
+    
+    let events = vec![
+        create_test_event(aggregate_id, "Event1"),
+        create_test_event(aggregate_id, "Event2"),
+        create_test_event(aggregate_id, "Event3"),
+    ];
+    
+    for event in &events {
+        let seq = store.publish_event(event.clone(), None).await.unwrap();
+        assert!(seq > 0);
+    }
+    
+    let fetched = store.fetch_events(aggregate_id).await.unwrap();
+    let event_ids: Vec<_> = fetched.iter().map(|e| e.event_id).collect();
+    let expected: Vec<_> = events.iter().map(|e| e.event_id).collect();
+    assert_eq!(event_ids, expected);
+}
+
+#[tokio::test]
+async fn test_in_memory_event_subscription() {
+    let store = InMemoryJetStream::default();
+    let aggregate_id = Uuid::new_v4();
+    
+    let mut subscription = store.subscribe_to_aggregate(aggregate_id).await.unwrap();
+    
+    let event = create_test_event(aggregate_id, "SubscriptionTest");
+    store.publish_event(event.clone(), None).await.unwrap();
+    
+    let received = tokio::time::timeout(
+        tokio::time::Duration::from_secs(5),
+        subscription.next()
+    ).await;
+    
+    assert!(received.is_ok(), "Timeout waiting for event");
+    let received_event = received.unwrap().unwrap().unwrap();
+    assert_eq!(received_event.event_id, event.event_id);
+}
+
+#[tokio::test]
+async fn test_in_memory_correlation_id_fetch() {
+    let store = InMemoryJetStream::default();
+    let correlation_id = Uuid::new_v4();
+    
+    for data in ["Event1", "Event2", "Event3"] {
+        let event = create_correlated_event(Uuid::new_v4(), correlation_id, data);
+        store.publish_event(event, None).await.unwrap();
+    }
+    
+    let fetched = store.fetch_by_correlation(correlation_id).await.unwrap();
+    assert_eq!(fetched.len(), 3);
+}
+
+#[tokio::test]
+async fn test_in_memory_replay_consumer() {
+    let store = InMemoryJetStream::default();
+    let aggregate_id = Uuid::new_v4();
+    
+    for i in 0..5 {
+        let event = create_test_event(aggregate_id, &format!("Event{}", i));
+        store.publish_event(event, None).await.unwrap();
+    }
+    
+    let replay_consumer = store.create_replay_consumer("test-replay", None).await.unwrap();
+    let batch = replay_consumer.fetch_batch(10).await.unwrap();
+    assert_eq!(batch.len(), 5);
+}
+
 // Helper functions
 fn create_test_event(aggregate_id: Uuid, data: &str) -> GraphEvent {
     GraphEvent {
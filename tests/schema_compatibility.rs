@@ -0,0 +1,98 @@
+//! Schema compatibility tests
+//!
+//! `tests/fixtures/events` holds events exactly as earlier releases wrote
+//! them, one file per schema version. Every file must keep decoding into the
+//! current event types; when a payload changes shape, add an upcaster rather
+//! than editing the fixtures.
+
+use cim_graph::{
+    events::{
+        upcast::{payload_kind, schema_version, UNVERSIONED_SCHEMA_VERSION},
+        EventPayload, GraphEvent, UpcasterRegistry, WorkflowPayload, CURRENT_SCHEMA_VERSION,
+    },
+    optimization::EventCompressor,
+    serde_support::{deserialize_events, serialize_events, EventJournal, EventStorageMetadata},
+};
+use std::collections::HashSet;
+use std::io::BufReader;
+
+/// Events written before `schema_version` existed
+const V1_EVENTS: &str = include_str!("fixtures/events/v1.json");
+
+fn v1_corpus() -> Vec<serde_json::Value> {
+    serde_json::from_str(V1_EVENTS).unwrap()
+}
+
+#[test]
+fn test_v1_corpus_is_unversioned_and_covers_every_payload() {
+    let corpus = v1_corpus();
+    let kinds: HashSet<String> = corpus.iter().filter_map(payload_kind).collect();
+
+    assert!(corpus
+        .iter()
+        .all(|event| schema_version(event).unwrap() == UNVERSIONED_SCHEMA_VERSION));
+    // Generic plus every Ipld, Context, Workflow, Concept and Composed variant
+    assert_eq!(kinds.len(), 21);
+}
+
+#[test]
+fn test_v1_corpus_decodes() {
+    let events = deserialize_events(V1_EVENTS).unwrap();
+    assert_eq!(events.len(), v1_corpus().len());
+
+    let transitioned = events
+        .iter()
+        .find_map(|event| match &event.payload {
+            EventPayload::Workflow(WorkflowPayload::StateTransitioned { from_state, to_state, .. }) => {
+                Some((from_state.clone(), to_state.clone()))
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(transitioned, ("draft".to_string(), "review".to_string()));
+}
+
+#[test]
+fn test_v1_corpus_roundtrips_at_current_version() {
+    let events = deserialize_events(V1_EVENTS).unwrap();
+    let json = serialize_events(&events).unwrap();
+
+    let written: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert!(written
+        .iter()
+        .all(|event| schema_version(event).unwrap() == CURRENT_SCHEMA_VERSION));
+
+    let reread = deserialize_events(&json).unwrap();
+    for (original, restored) in events.iter().zip(&reread) {
+        assert_eq!(original.event_id, restored.event_id);
+        assert_eq!(original.causation_id, restored.causation_id);
+    }
+}
+
+#[test]
+fn test_v1_corpus_loads_in_journal() {
+    let events: Vec<GraphEvent> = deserialize_events(V1_EVENTS).unwrap();
+    let journal = serde_json::json!({
+        "metadata": EventStorageMetadata::from_events(&events),
+        "events": v1_corpus(),
+    });
+
+    let journal: EventJournal = serde_json::from_value(journal).unwrap();
+    assert_eq!(journal.events.len(), events.len());
+}
+
+#[test]
+fn test_v1_corpus_decompresses() {
+    let compressed = EventCompressor::default().compress_bytes(V1_EVENTS.as_bytes()).unwrap();
+    let events = EventCompressor::decompress_from(BufReader::new(&compressed[..])).unwrap();
+    assert_eq!(events.len(), v1_corpus().len());
+}
+
+#[test]
+fn test_standard_registry_decodes_every_corpus_event() {
+    let registry = UpcasterRegistry::standard();
+    for event in v1_corpus() {
+        let kind = payload_kind(&event).unwrap();
+        assert!(registry.decode(event).is_ok(), "failed to decode {}", kind);
+    }
+}